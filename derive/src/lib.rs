@@ -0,0 +1,151 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse::Parse, parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, Ident, LitInt, Member, Token};
+
+/// Implements `nanotekspice::components::Component` for a struct holding a `PinContainer`.
+///
+/// ```ignore
+/// #[derive(Component)]
+/// #[component(pins = 3, inputs(1, 2), outputs(3), simulate = update)]
+/// struct MyAnd {
+///     pins: PinContainer,
+/// }
+/// ```
+///
+/// The container field is either marked with `#[pins]` or named `pins`.
+/// `simulate` names a method `fn(&self, &HashMap<PinNumber, &Cell<Tristate>>)` called once per tick
+/// with the output cells to fill. A `build_pin_container()` associated function is generated.
+#[proc_macro_derive(Component, attributes(component, pins))]
+pub fn derive_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct ComponentArgs {
+    nb_pins: Option<usize>,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+    bidirectional: Vec<usize>,
+    simulate: Option<Ident>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let args = parse_component_args(&input)?;
+    let container = find_pins_field(&input)?;
+
+    let nb_pins = args.nb_pins.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `pins = N` in #[component(...)]"))?;
+
+    let mut specs: Vec<TokenStream2> = Vec::new();
+    for pin in args.inputs.iter() {
+        specs.push(quote! { (#pin, ::nanotekspice::pin::PinSpecification::UnidirectionalInput()) });
+    }
+    for pin in args.outputs.iter() {
+        specs.push(quote! { (#pin, ::nanotekspice::pin::PinSpecification::UnidirectionalOutput()) });
+    }
+    for pin in args.bidirectional.iter() {
+        specs.push(quote! {
+            (#pin, ::nanotekspice::pin::PinSpecification::Bidirectional(::nanotekspice::pin::PinMode::Input))
+        });
+    }
+
+    let simulate_body = match args.simulate {
+        Some(method) => quote! { self.#container.simulate(tick, |outputs| self.#method(outputs)) },
+        None => quote! { self.#container.simulate(tick, |_| ()) },
+    };
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn build_pin_container() -> ::nanotekspice::pin::PinContainer {
+                ::nanotekspice::pin::PinContainer::new(#nb_pins, ::std::collections::HashMap::from([#(#specs),*]))
+            }
+        }
+
+        impl #impl_generics ::nanotekspice::components::Component for #name #ty_generics #where_clause {
+            fn simulate(&self, tick: ::nanotekspice::components::Tick) {
+                #simulate_body
+            }
+
+            fn compute(
+                &self,
+                pin: ::nanotekspice::components::PinNumber,
+            ) -> ::std::result::Result<
+                ::nanotekspice::components::tristate::Tristate,
+                ::nanotekspice::components::InvalidPin,
+            > {
+                self.#container.compute_for_external(pin)
+            }
+
+            fn set_link(
+                &self,
+                pin: ::nanotekspice::components::PinNumber,
+                other_component: ::std::rc::Weak<dyn ::nanotekspice::components::Component>,
+                other_pin: ::nanotekspice::components::PinNumber,
+            ) -> ::std::result::Result<(), ::nanotekspice::components::InvalidPin> {
+                self.#container.set_link_to_external_component(pin, other_component, other_pin)
+            }
+
+            fn is_driving(&self, pin: ::nanotekspice::components::PinNumber) -> bool {
+                self.#container.is_driving(pin).unwrap_or(false)
+            }
+        }
+    })
+}
+
+fn parse_component_args(input: &DeriveInput) -> syn::Result<ComponentArgs> {
+    let mut args = ComponentArgs::default();
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("component")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("pins") {
+                args.nb_pins = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("simulate") {
+                args.simulate = Some(meta.value()?.parse::<Ident>()?);
+            } else if meta.path.is_ident("inputs") {
+                args.inputs.extend(parse_pin_list(&meta)?);
+            } else if meta.path.is_ident("outputs") {
+                args.outputs.extend(parse_pin_list(&meta)?);
+            } else if meta.path.is_ident("bidirectional") {
+                args.bidirectional.extend(parse_pin_list(&meta)?);
+            } else {
+                return Err(meta.error("unknown component attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(args)
+}
+
+fn parse_pin_list(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Vec<usize>> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+
+    content.parse_terminated(LitInt::parse, Token![,])?.iter().map(|lit| lit.base10_parse()).collect()
+}
+
+fn find_pins_field(input: &DeriveInput) -> syn::Result<Member> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(input.span(), "#[derive(Component)] only supports structs"));
+    };
+
+    let fields: Vec<(Member, &syn::Field)> = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().map(|f| (Member::Named(f.ident.clone().unwrap()), f)).collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().enumerate().map(|(idx, f)| (Member::from(idx), f)).collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    fields
+        .iter()
+        .find(|(_, field)| field.attrs.iter().any(|attr| attr.path().is_ident("pins")))
+        .or_else(|| fields.iter().find(|(member, _)| matches!(member, Member::Named(ident) if ident == "pins")))
+        .map(|(member, _)| member.clone())
+        .ok_or_else(|| syn::Error::new(input.span(), "no `PinContainer` field found (mark it with #[pins] or name it `pins`)"))
+}
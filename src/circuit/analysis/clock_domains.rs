@@ -0,0 +1,294 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::circuit::diagnostics::{Diagnostic, Severity};
+use crate::circuit::netlist::{Netlist, PinRef};
+use crate::components::info::PinDirection;
+
+/// Components whose value is traced back from a pin through combinational logic.
+#[derive(Debug, Default)]
+struct Trace<'a> {
+    clocks: BTreeSet<&'a str>,
+    inputs: BTreeSet<&'a str>,
+    registers: BTreeSet<&'a str>,
+    through_logic: bool,
+}
+
+fn trace<'a>(netlist: &'a Netlist, pin: PinRef) -> Trace<'a> {
+    let mut result = Trace::default();
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut pending: Vec<PinRef> = vec![pin];
+
+    while let Some(pin) = pending.pop() {
+        for driver in netlist.drivers_of(&pin) {
+            let name = driver.component.as_str();
+            let Some(component) = netlist.component(name) else {
+                continue;
+            };
+
+            if component.component_type == "clock" {
+                result.clocks.insert(name);
+            } else if component.component_type == "input" {
+                result.inputs.insert(name);
+            } else if component.info.as_ref().is_some_and(|info| info.sequential) {
+                result.registers.insert(name);
+            } else if visited.insert(name) {
+                result.through_logic = true;
+                pending.extend(netlist.input_pins(name).into_iter().map(|pin| PinRef::new(name, pin)));
+            }
+        }
+    }
+
+    result
+}
+
+fn is_clock_pin(netlist: &Netlist, pin: &PinRef) -> bool {
+    netlist
+        .component(&pin.component)
+        .and_then(|component| component.info.as_ref())
+        .and_then(|info| info.pin(pin.pin))
+        .is_some_and(|pin| pin.clock)
+}
+
+/// Assignment of sequential components to the clock driving them.
+///
+/// A component clocked by the output of another sequential component (a divided clock, for instance)
+/// belongs to the domain of that component. `input` components only count as clocks when no `clock`
+/// component reaches the same clock pin.
+#[derive(Debug, Default, Clone)]
+pub struct ClockDomains {
+    domains: BTreeMap<String, String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ClockDomains {
+    pub const MULTIPLE_CLOCKS: &'static str = "multiple-clocks";
+    pub const CLOCK_DOMAIN_CROSSING: &'static str = "clock-domain-crossing";
+
+    pub fn analyze(netlist: &Netlist) -> Self {
+        let mut analysis = Self::default();
+        let sequential: Vec<&str> = netlist
+            .component_names()
+            .into_iter()
+            .filter(|&name| netlist.component(name).unwrap().info.as_ref().is_some_and(|info| info.sequential))
+            .collect();
+
+        for &name in sequential.iter() {
+            analysis.resolve_domain(netlist, name, &mut HashSet::new());
+        }
+        for &name in sequential.iter() {
+            analysis.check_crossings(netlist, name);
+        }
+
+        analysis
+    }
+
+    /// Clock driving `component`, if it could be determined.
+    pub fn domain_of(&self, component: &str) -> Option<&str> {
+        self.domains.get(component).map(String::as_str)
+    }
+
+    /// Clock sources driving at least one sequential component, sorted.
+    pub fn clocks(&self) -> Vec<&str> {
+        let clocks: BTreeSet<&str> = self.domains.values().map(String::as_str).collect();
+
+        clocks.into_iter().collect()
+    }
+
+    /// Sequential components driven by `clock`, sorted.
+    pub fn components_in(&self, clock: &str) -> Vec<&str> {
+        self.domains.iter().filter(|(_, domain)| *domain == clock).map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn resolve_domain<'a>(&mut self, netlist: &'a Netlist, name: &'a str, resolving: &mut HashSet<&'a str>) -> Option<String> {
+        if let Some(domain) = self.domains.get(name) {
+            return Some(domain.clone());
+        }
+        if !resolving.insert(name) {
+            return None;
+        }
+
+        let info = netlist.component(name)?.info.as_ref()?;
+        let mut clocks: BTreeSet<String> = BTreeSet::new();
+
+        for pin in info.clock_pins() {
+            let trace = trace(netlist, PinRef::new(name, pin.number));
+
+            // Inputs reaching a clock pin along with a clock are gating it.
+            let sources = if trace.clocks.is_empty() { trace.inputs } else { trace.clocks };
+
+            clocks.extend(sources.into_iter().map(str::to_owned));
+            for register in trace.registers {
+                clocks.extend(self.resolve_domain(netlist, register, resolving));
+            }
+        }
+
+        let mut clocks = clocks.into_iter();
+        let domain = clocks.next()?;
+        let others: Vec<String> = clocks.collect();
+
+        if !others.is_empty() {
+            let message = format!("'{name}' is clocked by several clocks: {domain}, {}", others.join(", "));
+            self.diagnostics.push(Diagnostic::new(Severity::Warning, Self::MULTIPLE_CLOCKS, message).with_component(name));
+        }
+
+        self.domains.insert(name.to_owned(), domain.clone());
+        Some(domain)
+    }
+
+    fn check_crossings(&mut self, netlist: &Netlist, name: &str) {
+        let Some(domain) = self.domains.get(name).cloned() else {
+            return;
+        };
+        for pin in netlist.input_pins(name) {
+            let pin_ref = PinRef::new(name, pin);
+            if is_clock_pin(netlist, &pin_ref) {
+                continue;
+            }
+
+            let net = match netlist.label_of(&pin_ref) {
+                Some(label) => format!(" (net {label})"),
+                None => String::new(),
+            };
+            let trace = trace(netlist, pin_ref);
+            for register in trace.registers {
+                let Some(source_domain) = self.domains.get(register).cloned() else {
+                    continue;
+                };
+                if source_domain == domain || (!trace.through_logic && self.is_synchronizer(netlist, name)) {
+                    continue;
+                }
+
+                let message = format!(
+                    "Signal from '{register}' (clock {source_domain}) reaches '{name}' pin {pin}{net} (clock {domain}) without synchronization"
+                );
+                self.diagnostics.push(
+                    Diagnostic::new(Severity::Warning, Self::CLOCK_DOMAIN_CROSSING, message)
+                        .with_component(name)
+                        .with_component(register),
+                );
+            }
+        }
+    }
+
+    /// First stage of a two-register synchronizer: one of its outputs is directly read by another register
+    /// of the same domain.
+    fn is_synchronizer(&self, netlist: &Netlist, name: &str) -> bool {
+        let domain = self.domain_of(name);
+
+        netlist.output_pins(name).into_iter().any(|pin| {
+            netlist.linked_pins(&PinRef::new(name, pin)).into_iter().any(|other| {
+                other.component != name
+                    && self.domain_of(&other.component) == domain
+                    && netlist.pin_direction(other) == Some(PinDirection::Input)
+                    && !is_clock_pin(netlist, other)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClockDomains;
+    use crate::circuit::Circuit;
+    use crate::components::{fsm::FsmDefinition, registry::ComponentRegistry};
+
+    // Registers its input on each rising edge.
+    const REGISTER: &str = "
+        inputs: d
+        outputs: q
+        initial: low
+        state low: q=0
+        state high: q=1
+        low -> high: d=1
+        high -> low: d=0
+    ";
+
+    fn analyze(links: &str) -> ClockDomains {
+        let mut registry = ComponentRegistry::default();
+        REGISTER.parse::<FsmDefinition>().unwrap().register(&mut registry, "register");
+
+        let content = format!(
+            "
+            .chipsets:
+            clock clk_a
+            clock clk_b
+            input data
+            4081 gate
+            register ra
+            register rb
+            register rc
+            output out
+            .links:
+            {links}
+            "
+        );
+
+        ClockDomains::analyze(Circuit::parse_with_factory(&content, registry).unwrap().netlist())
+    }
+
+    #[test]
+    fn test_domains() {
+        // rc is clocked by clk_b gated with data.
+        let analysis = analyze("clk_a:1 ra:1\nclk_b:1 rb:1\nclk_b:1 gate:1\ndata:1 gate:2\ngate:3 rc:1\ndata:1 ra:2");
+
+        assert_eq!(analysis.domain_of("ra"), Some("clk_a"));
+        assert_eq!(analysis.domain_of("rb"), Some("clk_b"));
+        assert_eq!(analysis.domain_of("rc"), Some("clk_b"));
+        assert_eq!(analysis.domain_of("gate"), None);
+        assert_eq!(analysis.clocks(), vec!["clk_a", "clk_b"]);
+        assert_eq!(analysis.components_in("clk_b"), vec!["rb", "rc"]);
+        assert!(analysis.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_derived_clock() {
+        // rb is clocked by the output of ra.
+        let analysis = analyze("clk_a:1 ra:1\nra:3 rb:1");
+
+        assert_eq!(analysis.domain_of("rb"), Some("clk_a"));
+    }
+
+    #[test]
+    fn test_multiple_clocks() {
+        let analysis = analyze("clk_a:1 gate:1\nclk_b:1 gate:2\ngate:3 ra:1");
+
+        assert_eq!(analysis.diagnostics().len(), 1);
+        assert_eq!(analysis.diagnostics()[0].code, ClockDomains::MULTIPLE_CLOCKS);
+        assert_eq!(analysis.diagnostics()[0].components, vec!["ra"]);
+    }
+
+    #[test]
+    fn test_unsynchronized_crossing() {
+        let analysis = analyze("clk_a:1 ra:1\nclk_b:1 rb:1\nra:3 rb:2\nrb:3 out:1");
+
+        assert_eq!(analysis.diagnostics().len(), 1);
+        let diagnostic = &analysis.diagnostics()[0];
+        assert_eq!(diagnostic.code, ClockDomains::CLOCK_DOMAIN_CROSSING);
+        assert_eq!(diagnostic.components, vec!["rb", "ra"]);
+        assert_eq!(
+            diagnostic.to_string(),
+            "warning[clock-domain-crossing]: Signal from 'ra' (clock clk_a) reaches 'rb' pin 2 (clock clk_b) without synchronization"
+        );
+    }
+
+    #[test]
+    fn test_synchronized_crossing() {
+        // ra -> rb -> rc, rb and rc both on clk_b.
+        let analysis = analyze("clk_a:1 ra:1\nclk_b:1 rb:1\nclk_b:1 rc:1\nra:3 rb:2\nrb:3 rc:2");
+
+        assert!(analysis.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_crossing_through_logic() {
+        // Combinational logic before the first stage defeats the synchronizer.
+        let analysis = analyze("clk_a:1 ra:1\nclk_b:1 rb:1\nclk_b:1 rc:1\nra:3 gate:1\ndata:1 gate:2\ngate:3 rb:2\nrb:3 rc:2");
+
+        assert_eq!(analysis.diagnostics().len(), 1);
+        assert_eq!(analysis.diagnostics()[0].code, ClockDomains::CLOCK_DOMAIN_CROSSING);
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::circuit::netlist::{Netlist, PinRef};
+use crate::components::info::ComponentInfo;
+
+enum OutputDepth {
+    Computing,
+    Done { depth: usize, through: Option<PinRef> },
+}
+
+struct DepthAnalysis<'a> {
+    netlist: &'a Netlist,
+    outputs: HashMap<PinRef, OutputDepth>,
+}
+
+impl<'a> DepthAnalysis<'a> {
+    fn new(netlist: &'a Netlist) -> Self {
+        Self { netlist, outputs: HashMap::new() }
+    }
+
+    fn info(&self, component: &str) -> Option<&'a ComponentInfo> {
+        self.netlist.component(component)?.info.as_ref()
+    }
+
+    /// Number of gate levels between a start point and the output `pin`, with the input on the deepest path.
+    fn output_depth(&mut self, pin: &PinRef) -> (usize, Option<PinRef>) {
+        match self.outputs.get(pin) {
+            Some(OutputDepth::Done { depth, through }) => return (*depth, through.clone()),
+            // Combinational loop: cut it here.
+            Some(OutputDepth::Computing) => return (0, None),
+            None => (),
+        }
+
+        let inputs = match self.info(&pin.component) {
+            Some(info) if !info.sequential => info.dependencies(pin.pin),
+            _ => Vec::new(),
+        };
+        if inputs.is_empty() {
+            self.outputs.insert(pin.clone(), OutputDepth::Done { depth: 0, through: None });
+            return (0, None);
+        }
+
+        self.outputs.insert(pin.clone(), OutputDepth::Computing);
+        let mut deepest: (usize, Option<PinRef>) = (0, None);
+        for input in inputs {
+            let input = PinRef::new(&pin.component, input);
+            let (depth, _) = self.input_arrival(&input);
+
+            if deepest.1.is_none() || depth > deepest.0 {
+                deepest = (depth, Some(input));
+            }
+        }
+
+        let (depth, through) = (deepest.0 + 1, deepest.1);
+        self.outputs.insert(pin.clone(), OutputDepth::Done { depth, through: through.clone() });
+        (depth, through)
+    }
+
+    /// Depth of the deepest driver of the input `pin`, with that driver.
+    fn input_arrival(&mut self, pin: &PinRef) -> (usize, Option<PinRef>) {
+        let drivers: Vec<PinRef> = self.netlist.drivers_of(pin).into_iter().cloned().collect();
+        let mut deepest: (usize, Option<PinRef>) = (0, None);
+
+        for driver in drivers {
+            let (depth, _) = self.output_depth(&driver);
+
+            if deepest.1.is_none() || depth > deepest.0 {
+                deepest = (depth, Some(driver));
+            }
+        }
+
+        deepest
+    }
+
+    fn path_to(&mut self, endpoint: PinRef) -> Vec<PinRef> {
+        let mut path = vec![endpoint];
+
+        while let Some(driver) = self.input_arrival(path.last().unwrap()).1 {
+            if path.contains(&driver) {
+                break;
+            }
+            path.push(driver.clone());
+            match self.output_depth(&driver).1 {
+                Some(input) if !path.contains(&input) => path.push(input),
+                _ => break,
+            }
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Longest chain of combinational components between a start point (input, clock, constant or
+/// sequential component output) and an end point (output or sequential component input).
+///
+/// Each traversal of a combinational component counts as one level, using the pin dependencies
+/// declared by its info: going through two gates of the same chip counts twice.
+#[derive(Debug, Clone)]
+pub struct CriticalPath {
+    depth: usize,
+    pins: Vec<PinRef>,
+    /// Pin names as displayed: net labels when the pins are labelled.
+    names: Vec<String>,
+}
+
+impl CriticalPath {
+    /// Returns `None` when the circuit has no end point.
+    pub fn analyze(netlist: &Netlist) -> Option<Self> {
+        let mut analysis = DepthAnalysis::new(netlist);
+        let mut deepest: Option<(usize, PinRef)> = None;
+
+        for name in netlist.component_names() {
+            let Some(info) = analysis.info(name) else {
+                continue;
+            };
+            if !info.sequential && !netlist.output_pins(name).is_empty() {
+                continue;
+            }
+
+            for pin in netlist.input_pins(name) {
+                let endpoint = PinRef::new(name, pin);
+                let (depth, _) = analysis.input_arrival(&endpoint);
+
+                if deepest.as_ref().is_none_or(|(deepest, _)| depth > *deepest) {
+                    deepest = Some((depth, endpoint));
+                }
+            }
+        }
+
+        let (depth, endpoint) = deepest?;
+        let pins = analysis.path_to(endpoint);
+        let names = pins.iter().map(|pin| netlist.pin_name(pin)).collect();
+
+        Some(Self { depth, pins, names })
+    }
+
+    /// Number of combinational components along the path.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Pins along the path, from the start point to the end point.
+    pub fn pins(&self) -> &[PinRef] {
+        &self.pins
+    }
+
+    /// Components along the path, from the start point to the end point.
+    pub fn components(&self) -> Vec<&str> {
+        let mut components: Vec<&str> = self.pins.iter().map(|pin| pin.component.as_str()).collect();
+
+        components.dedup();
+        components
+    }
+}
+
+impl fmt::Display for CriticalPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&str> = self.names.iter().map(String::as_str).collect();
+
+        // Both ends of a labelled link are the same net.
+        names.dedup();
+        write!(f, "{} levels: {}", self.depth, names.join(" -> "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CriticalPath;
+    use crate::circuit::synthesize::from_expression;
+    use crate::circuit::Circuit;
+
+    #[test]
+    fn test_single_gate() {
+        let content = "
+            .chipsets:
+            input a
+            input b
+            4081 gate
+            output out
+            .links:
+            a:1 gate:1
+            b:1 gate:2
+            gate:3 out:1
+        ";
+        let circuit: Circuit = content.parse().unwrap();
+        let path = CriticalPath::analyze(circuit.netlist()).unwrap();
+
+        assert_eq!(path.depth(), 1);
+        assert_eq!(path.to_string(), "1 levels: a:1 -> gate:1 -> gate:3 -> out:1");
+        assert_eq!(path.components(), vec!["a", "gate", "out"]);
+    }
+
+    #[test]
+    fn test_net_labels() {
+        let content = "
+            .chipsets:
+            input a
+            input b
+            4081 gate
+            output out
+            .nets:
+            result: gate:3 out:1
+            .links:
+            a:1 gate:1
+            b:1 gate:2
+        ";
+        let circuit: Circuit = content.parse().unwrap();
+        let path = CriticalPath::analyze(circuit.netlist()).unwrap();
+
+        assert_eq!(path.to_string(), "1 levels: a:1 -> gate:1 -> result");
+        assert_eq!(path.pins().len(), 4);
+    }
+
+    #[test]
+    fn test_chained_gates_in_same_chip() {
+        // ((a & b) & c) & d: three AND gates of the same chip in series.
+        let circuit = from_expression("out = a & b & c & d").unwrap();
+        let path = CriticalPath::analyze(circuit.netlist()).unwrap();
+
+        assert_eq!(path.depth(), 3);
+        assert_eq!(path.components(), vec!["a", "and.1", "out"]);
+        assert_eq!(path.pins().len(), 8);
+    }
+
+    #[test]
+    fn test_deepest_output() {
+        let circuit = from_expression("x = a\ny = !(a | b) ^ c").unwrap();
+        let path = CriticalPath::analyze(circuit.netlist()).unwrap();
+
+        assert_eq!(path.depth(), 3);
+        assert_eq!(path.pins().last().unwrap().component, "y");
+    }
+
+    #[test]
+    fn test_combinational_loop() {
+        // SR latch made of two NOR gates.
+        let content = "
+            .chipsets:
+            input s
+            input r
+            4001 nor
+            output q
+            .links:
+            r:1 nor:1
+            nor:3 nor:5
+            nor:4 nor:2
+            s:1 nor:6
+            nor:3 q:1
+        ";
+        let circuit: Circuit = content.parse().unwrap();
+        let path = CriticalPath::analyze(circuit.netlist()).unwrap();
+
+        // The loop is cut when reached again: q <- nor:3 <- nor:4 <- (nor:3).
+        assert_eq!(path.depth(), 2);
+        assert_eq!(path.components(), vec!["nor", "q"]);
+        assert_eq!(path.pins().last().unwrap().to_string(), "q:1");
+    }
+
+    #[test]
+    fn test_no_endpoint() {
+        let circuit: Circuit = ".chipsets:\ninput a\n".parse().unwrap();
+
+        assert!(CriticalPath::analyze(circuit.netlist()).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use crate::circuit::diagnostics::{Diagnostic, Severity};
+use crate::circuit::netlist::{Netlist, PinRef};
+use crate::components::info::PinDirection;
+
+fn is_constant(netlist: &Netlist, name: &str) -> bool {
+    netlist.component(name).is_some_and(|component| matches!(component.component_type.as_str(), "true" | "false"))
+}
+
+/// Electrical rule checks: outputs driving more inputs than they can, and outputs shorted to a constant.
+///
+/// Outputs without a fan-out limit in their component info are checked against the default limit, if any.
+#[derive(Debug, Default, Clone)]
+pub struct ElectricalRules {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ElectricalRules {
+    pub const FAN_OUT_EXCEEDED: &'static str = "fan-out-exceeded";
+    pub const SHORTED_TO_CONSTANT: &'static str = "shorted-to-constant";
+
+    pub fn analyze(netlist: &Netlist, default_fan_out: Option<usize>) -> Self {
+        let mut analysis = Self::default();
+
+        for name in netlist.component_names() {
+            let Some(info) = netlist.component(name).unwrap().info.as_ref() else {
+                continue;
+            };
+            for pin in info.pins_with_direction(PinDirection::Output) {
+                let pin_ref = PinRef::new(name, pin.number);
+
+                if let Some(max) = pin.max_fan_out.or(default_fan_out) {
+                    analysis.check_fan_out(netlist, &pin_ref, max);
+                }
+                analysis.check_constant_drivers(netlist, &pin_ref);
+            }
+        }
+
+        analysis
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn check_fan_out(&mut self, netlist: &Netlist, pin: &PinRef, max: usize) {
+        let loads: HashSet<&PinRef> = netlist
+            .linked_pins(pin)
+            .into_iter()
+            .filter(|other| matches!(netlist.pin_direction(other), Some(PinDirection::Input | PinDirection::Bidirectional)))
+            .collect();
+
+        if loads.len() > max {
+            let message = format!("'{}' drives {} inputs, more than its fan-out of {max}", netlist.pin_name(pin), loads.len());
+            self.diagnostics
+                .push(Diagnostic::new(Severity::Warning, Self::FAN_OUT_EXCEEDED, message).with_component(&pin.component));
+        }
+    }
+
+    fn check_constant_drivers(&mut self, netlist: &Netlist, pin: &PinRef) {
+        let pin_is_constant = is_constant(netlist, &pin.component);
+
+        for other in netlist.drivers_of(pin) {
+            if other.component == pin.component || !is_constant(netlist, &other.component) {
+                continue;
+            }
+            // Between two constants, only report conflicting ones, once.
+            if pin_is_constant {
+                let same_value = netlist.component(&pin.component).map(|component| &component.component_type)
+                    == netlist.component(&other.component).map(|component| &component.component_type);
+                if same_value || pin.component > other.component {
+                    continue;
+                }
+            }
+
+            let message = format!("'{}' is shorted to the constant '{}'", netlist.pin_name(pin), other.component);
+            self.diagnostics.push(
+                Diagnostic::new(Severity::Error, Self::SHORTED_TO_CONSTANT, message)
+                    .with_component(&pin.component)
+                    .with_component(&other.component),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ElectricalRules;
+    use crate::circuit::Circuit;
+    use crate::components::info::{ComponentInfo, PinDirection};
+    use crate::components::registry::ComponentRegistry;
+    use crate::components::single_pin::input_component::InputComponent;
+
+    fn analyze(chipsets: &str, links: &str, default_fan_out: Option<usize>) -> ElectricalRules {
+        let mut registry = ComponentRegistry::default();
+        let info = ComponentInfo::new("weak", "Weak source", 1).with_pin(1, "out", PinDirection::Output).with_fan_out(1, 1);
+        registry.register_with_info("weak", info, || Box::new(InputComponent::default()));
+
+        let content = format!(".chipsets:\n{chipsets}\n.links:\n{links}\n");
+        let circuit = Circuit::parse_with_factory(&content, registry).unwrap();
+
+        ElectricalRules::analyze(circuit.netlist(), default_fan_out)
+    }
+
+    #[test]
+    fn test_fan_out() {
+        let chipsets = "input a\nweak w\n4081 gate\noutput out";
+        let links = "a:1 gate:1\na:1 gate:2\na:1 gate:5\nw:1 gate:6\ngate:3 out:1";
+
+        assert!(analyze(chipsets, links, None).diagnostics().is_empty());
+        assert!(analyze(chipsets, links, Some(3)).diagnostics().is_empty());
+
+        let analysis = analyze(chipsets, &format!("{links}\nw:1 gate:8"), Some(2));
+        let codes: Vec<_> = analysis.diagnostics().iter().map(|diagnostic| diagnostic.to_string()).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "warning[fan-out-exceeded]: 'a:1' drives 3 inputs, more than its fan-out of 2",
+                "warning[fan-out-exceeded]: 'w:1' drives 2 inputs, more than its fan-out of 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shorted_to_constant() {
+        let chipsets = "input a\ntrue high\ntrue high2\nfalse low\n4081 gate\noutput out";
+        let analysis =
+            analyze(chipsets, "a:1 gate:1\nhigh:1 gate:2\ngate:3 high:1\nhigh:1 high2:1\nlow:1 high2:1\ngate:3 out:1", None);
+        let diagnostics = analysis.diagnostics();
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].to_string(), "error[shorted-to-constant]: 'gate:3' is shorted to the constant 'high'");
+        assert_eq!(diagnostics[0].components, vec!["gate", "high"]);
+        assert_eq!(diagnostics[1].code, ElectricalRules::SHORTED_TO_CONSTANT);
+        assert_eq!(diagnostics[1].components, vec!["high2", "low"]);
+    }
+}
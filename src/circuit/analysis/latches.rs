@@ -0,0 +1,244 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::circuit::diagnostics::{Diagnostic, Severity};
+use crate::circuit::netlist::{Netlist, PinRef};
+use crate::components::info::PinDirection;
+
+/// Outputs of combinational components, with the outputs each one reads through its dependencies.
+struct OutputGraph {
+    outputs: Vec<PinRef>,
+    predecessors: Vec<Vec<usize>>,
+    successors: Vec<Vec<usize>>,
+}
+
+impl OutputGraph {
+    fn new(netlist: &Netlist) -> Self {
+        let mut outputs: Vec<PinRef> = Vec::new();
+
+        for name in netlist.component_names() {
+            match netlist.component(name).unwrap().info.as_ref() {
+                Some(info) if !info.sequential => {
+                    outputs.extend(info.pins_with_direction(PinDirection::Output).map(|pin| PinRef::new(name, pin.number)))
+                }
+                _ => (),
+            }
+        }
+
+        let indices: HashMap<&PinRef, usize> = outputs.iter().enumerate().map(|(index, pin)| (pin, index)).collect();
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); outputs.len()];
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); outputs.len()];
+
+        for (index, output) in outputs.iter().enumerate() {
+            let info = netlist.component(&output.component).unwrap().info.as_ref().unwrap();
+
+            for input in info.dependencies(output.pin) {
+                for driver in netlist.drivers_of(&PinRef::new(&output.component, input)) {
+                    if let Some(&driver) = indices.get(driver) {
+                        predecessors[index].push(driver);
+                        successors[driver].push(index);
+                    }
+                }
+            }
+        }
+
+        Self { outputs, predecessors, successors }
+    }
+
+    /// Nodes in increasing order of DFS finish time over the successors.
+    fn finish_order(&self) -> Vec<usize> {
+        let mut visited: Vec<bool> = vec![false; self.outputs.len()];
+        let mut order: Vec<usize> = Vec::with_capacity(self.outputs.len());
+
+        for root in 0..self.outputs.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+
+            // (node, index of the next successor to visit)
+            let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+            while let Some((node, next)) = stack.last_mut() {
+                if let Some(&successor) = self.successors[*node].get(*next) {
+                    *next += 1;
+                    if !visited[successor] {
+                        visited[successor] = true;
+                        stack.push((successor, 0));
+                    }
+                } else {
+                    order.push(*node);
+                    stack.pop();
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Strongly connected components (Kosaraju), each as a list of nodes.
+    fn strongly_connected(&self) -> Vec<Vec<usize>> {
+        let mut assigned: Vec<bool> = vec![false; self.outputs.len()];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+
+        for root in self.finish_order().into_iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+            assigned[root] = true;
+
+            let mut component: Vec<usize> = Vec::new();
+            let mut pending: Vec<usize> = vec![root];
+            while let Some(node) = pending.pop() {
+                component.push(node);
+                for &predecessor in self.predecessors[node].iter() {
+                    if !assigned[predecessor] {
+                        assigned[predecessor] = true;
+                        pending.push(predecessor);
+                    }
+                }
+            }
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+/// Feedback paths through combinational components only, which hold a state like a latch does.
+///
+/// Simulating them is well defined, feedback being read one tick late, but they are rarely intended
+/// outside of hand-built latches such as cross-coupled NOR gates.
+#[derive(Debug, Default, Clone)]
+pub struct InferredLatches {
+    loops: Vec<Vec<PinRef>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl InferredLatches {
+    pub const INFERRED_LATCH: &'static str = "inferred-latch";
+
+    pub fn analyze(netlist: &Netlist) -> Self {
+        let graph = OutputGraph::new(netlist);
+        let mut analysis = Self::default();
+
+        for nodes in graph.strongly_connected() {
+            let node = nodes[0];
+            if nodes.len() == 1 && !graph.predecessors[node].contains(&node) {
+                continue;
+            }
+
+            let mut pins: Vec<PinRef> = nodes.into_iter().map(|node| graph.outputs[node].clone()).collect();
+            pins.sort_by(|left, right| (&left.component, left.pin).cmp(&(&right.component, right.pin)));
+            analysis.loops.push(pins);
+        }
+        analysis.loops.sort_by(|left, right| (&left[0].component, left[0].pin).cmp(&(&right[0].component, right[0].pin)));
+
+        for pins in analysis.loops.iter() {
+            let components: BTreeSet<&str> = pins.iter().map(|pin| pin.component.as_str()).collect();
+            let names: Vec<String> = pins.iter().map(|pin| format!("'{}'", netlist.pin_name(pin))).collect();
+            let message = format!("{} feed back through combinational logic, forming a latch", names.join(", "));
+
+            analysis.diagnostics.push(
+                components
+                    .into_iter()
+                    .fold(Diagnostic::new(Severity::Warning, Self::INFERRED_LATCH, message), Diagnostic::with_component),
+            );
+        }
+
+        analysis
+    }
+
+    /// Outputs taking part in each feedback path, sorted.
+    pub fn loops(&self) -> &[Vec<PinRef>] {
+        &self.loops
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InferredLatches;
+    use crate::circuit::Circuit;
+
+    fn analyze(content: &str) -> InferredLatches {
+        InferredLatches::analyze(content.parse::<Circuit>().unwrap().netlist())
+    }
+
+    fn loops(analysis: &InferredLatches) -> Vec<Vec<String>> {
+        analysis.loops().iter().map(|pins| pins.iter().map(ToString::to_string).collect()).collect()
+    }
+
+    #[test]
+    fn test_cross_coupled_gates() {
+        let analysis = analyze(
+            "
+            .chipsets:
+            input s
+            input r
+            4001 top
+            4001 bottom
+            output q
+            .links:
+            r:1 top:1
+            bottom:3 top:2
+            s:1 bottom:1
+            top:3 bottom:2
+            top:3 q:1
+            ",
+        );
+
+        assert_eq!(loops(&analysis), vec![vec!["bottom:3", "top:3"]]);
+        let diagnostic = &analysis.diagnostics()[0];
+        assert_eq!(diagnostic.components, vec!["bottom", "top"]);
+        assert_eq!(
+            diagnostic.to_string(),
+            "warning[inferred-latch]: 'bottom:3', 'top:3' feed back through combinational logic, forming a latch"
+        );
+    }
+
+    #[test]
+    fn test_loops_inside_a_chip() {
+        // Two separate loops on the gates of a single chip, the first one being a gate reading its own output.
+        let analysis = analyze(
+            "
+            .chipsets:
+            input a
+            4011 nand
+            output out
+            .links:
+            a:1 nand:1
+            nand:3 nand:2
+            nand:4 nand:8
+            nand:10 nand:5
+            nand:10 nand:6
+            nand:4 out:1
+            ",
+        );
+
+        assert_eq!(loops(&analysis), vec![vec!["nand:3"], vec!["nand:4", "nand:10"]]);
+        assert_eq!(analysis.diagnostics().len(), 2);
+    }
+
+    #[test]
+    fn test_feedback_through_register() {
+        let analysis = analyze(
+            "
+            .chipsets:
+            clock clk
+            4013 flipflop
+            4069 not
+            output q
+            .links:
+            clk:1 flipflop:3
+            flipflop:1 not:1
+            not:2 flipflop:5
+            flipflop:1 q:1
+            ",
+        );
+
+        assert!(analysis.loops().is_empty());
+        assert!(analysis.diagnostics().is_empty());
+    }
+}
@@ -0,0 +1,11 @@
+//! Static checks run on the netlist of a circuit, without simulating it.
+
+mod clock_domains;
+mod critical_path;
+mod electrical_rules;
+mod latches;
+
+pub use clock_domains::ClockDomains;
+pub use critical_path::CriticalPath;
+pub use electrical_rules::ElectricalRules;
+pub use latches::InferredLatches;
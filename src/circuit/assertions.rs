@@ -0,0 +1,90 @@
+use std::fmt;
+
+use crate::components::{tristate::Tristate, Component, Tick};
+
+use super::Circuit;
+
+/// Tick at which the input of an `assert` component did not hold its expected value.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AssertionFailure {
+    pub component: String,
+    pub tick: Tick,
+    pub expected: Tristate,
+    pub got: Tristate,
+}
+
+impl fmt::Display for AssertionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tick {}: {} expected {}, got {}", self.tick, self.component, self.expected, self.got)
+    }
+}
+
+impl Circuit {
+    /// Failures recorded so far by the monitors of the circuit, sorted by tick then by component name.
+    pub fn assertion_failures(&self) -> Vec<AssertionFailure> {
+        let mut failures: Vec<AssertionFailure> = self
+            .components
+            .iter()
+            .filter_map(|(name, component)| Some((name, component.as_monitor()?)))
+            .flat_map(|(name, monitor)| {
+                let expected = monitor.expected();
+
+                monitor.failures().into_iter().map(move |(tick, got)| AssertionFailure {
+                    component: name.clone(),
+                    tick,
+                    expected,
+                    got,
+                })
+            })
+            .collect();
+
+        failures.sort_by(|left, right| (left.tick, &left.component).cmp(&(right.tick, &right.component)));
+        failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    // The AND of 'a' and 'b' is asserted high, its NAND low.
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 and
+        4011 nand
+        assert high
+        assert low expect=0
+        .links:
+        a:1 and:1
+        b:1 and:2
+        a:1 nand:1
+        b:1 nand:2
+        and:3 high:1
+        nand:3 low:1
+    ";
+
+    #[test]
+    fn test_assertion_failures() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        for (a, b) in [("1", "1"), ("1", "0"), ("1", "1"), ("U", "1")] {
+            circuit.set_value("a", a).unwrap();
+            circuit.set_value("b", b).unwrap();
+            circuit.simulate();
+        }
+
+        let failures: Vec<String> = circuit.assertion_failures().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            failures,
+            vec![
+                "tick 2: high expected 1, got 0",
+                "tick 2: low expected 0, got 1",
+                "tick 4: high expected 1, got U",
+                "tick 4: low expected 0, got U",
+            ]
+        );
+        assert!(CONTENT.replace("expect=0", "expect=2").parse::<Circuit>().is_err());
+    }
+}
@@ -1,9 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::rc::{Rc, Weak};
 
 use crate::components::factory::ComponentFactory;
-use crate::components::{Component, InvalidPin, PinNumber, Tick};
-
+use crate::components::info::PinDirection;
+use crate::components::params::{CreateError, Params};
+use crate::components::{tristate::Tristate, Component, InvalidPin, PinNumber, Tick};
+
+use super::fault::FaultableComponent;
+use super::netlist::{Netlist, PinRef};
+use super::parser::{Endpoint, Instruction};
+use super::subcircuit::{NotExposable, SubcircuitComponent};
 use super::Circuit;
 
 #[derive(Debug, Clone)]
@@ -12,49 +19,106 @@ pub enum CircuitBuildError<'a, Type: std::fmt::Debug + Clone> {
     ComponentNameOverride(&'a str),
     ComponentNameUnknown(&'a str),
     ComponentTypeUnknown(&'a str),
+    NotAnInput(&'a str),
+    /// The component of an embedded circuit given for a pin is missing, or neither an input nor an output.
+    NotExposable(&'a str),
     ComponentLinkIssue(&'a str, Type, PinNumber),
+    SubcircuitLinkIssue(&'a str, PinNumber),
+    ComponentCreation(&'a str, CreateError),
 }
 
+/// Type of a component, `None` for an embedded circuit.
+type ComponentEntry<Type> = (Option<Type>, Rc<FaultableComponent>);
+
 pub struct CircuitBuilder<Factory: ComponentFactory> {
-    components: HashMap<String, (Factory::Type, Rc<dyn Component>)>,
+    components: HashMap<String, ComponentEntry<Factory::Type>>,
+    input_sequences: HashMap<String, VecDeque<Tristate>>,
+    netlist: Netlist,
+    subcircuits: HashMap<String, Rc<SubcircuitComponent>>,
     factory: Factory,
+    base_path: Option<PathBuf>,
 }
 
 impl<Factory> CircuitBuilder<Factory>
 where
     Factory: ComponentFactory,
-    Factory::Type: std::str::FromStr + std::fmt::Debug + Copy,
+    Factory::Type: std::fmt::Debug + Clone,
 {
     pub fn new(factory: Factory) -> Self {
-        Self { components: HashMap::new(), factory }
+        Self {
+            components: HashMap::new(),
+            input_sequences: HashMap::new(),
+            netlist: Netlist::default(),
+            subcircuits: HashMap::new(),
+            factory,
+            base_path: None,
+        }
+    }
+
+    /// Directory the file parameters of the components are relative to, unless their params have their own.
+    pub fn base_path<P: AsRef<Path>>(mut self, base_path: P) -> Self {
+        self.base_path = Some(base_path.as_ref().to_owned());
+        self
     }
 
     pub fn build(self) -> Result<Circuit, CircuitBuildError<'static, Factory::Type>> {
-        let components: HashMap<String, Rc<dyn Component>> =
+        let components: HashMap<String, Rc<FaultableComponent>> =
             self.components.into_iter().map(|(name, (_, component))| (name, component)).collect();
 
         if components.is_empty() {
             return Err(CircuitBuildError::NoChipset);
         }
 
-        let current_tick: Tick = 0;
-
-        for (_, component) in components.iter() {
+        // Simulating drivers first keeps each component's inputs already computed, so the
+        // recursion through the links stays shallow whatever the depth of the circuit.
+        let schedule: Vec<Rc<FaultableComponent>> =
+            self.netlist.evaluation_order().into_iter().map(|name| components[name].clone()).collect();
+        let mut signals: Vec<(String, Rc<FaultableComponent>)> = components
+            .iter()
+            .filter(|(_, component)| component.as_input().is_some() || component.as_output().is_some())
+            .map(|(name, component)| (name.clone(), component.clone()))
+            .collect();
+        signals.sort_by(|(left, _), (right, _)| left.cmp(right));
+        let current_tick = Tick::ZERO;
+
+        for component in schedule.iter() {
             component.simulate(current_tick);
         }
 
-        Ok(Circuit { current_tick, components })
+        let mut circuit = Circuit {
+            current_tick,
+            components,
+            schedule,
+            input_sequences: self.input_sequences,
+            netlist: self.netlist,
+            conflict_policy: Default::default(),
+            undefined_watch: None,
+            first_defined: HashMap::new(),
+            embedded_checks: Vec::new(),
+            user_data: Default::default(),
+            subcircuits: self.subcircuits,
+            signals,
+        };
+        circuit.record_first_defined();
+
+        Ok(circuit)
     }
 
-    pub fn add_component<'a>(
+    pub fn add_component<'a>(self, component_type: &'a str, name: &'a str) -> Result<Self, CircuitBuildError<'a, Factory::Type>> {
+        self.add_component_with_params(component_type, name, &Params::new())
+    }
+
+    pub fn add_component_with_params<'a>(
         mut self,
         component_type: &'a str,
         name: &'a str,
+        params: &Params,
     ) -> Result<Self, CircuitBuildError<'a, Factory::Type>> {
-        let component_type: Factory::Type = match component_type.parse() {
-            Ok(t) => t,
-            Err(_) => {
-                return Err(CircuitBuildError::ComponentTypeUnknown(component_type));
+        let type_name = component_type;
+        let component_type: Factory::Type = match self.factory.parse_type(type_name) {
+            Some(t) => t,
+            None => {
+                return Err(CircuitBuildError::ComponentTypeUnknown(type_name));
             }
         };
 
@@ -62,16 +126,55 @@ where
 
         match self.components.entry(name.to_owned()) {
             Entry::Vacant(v) => {
-                let component = self.factory.create_component(component_type);
-                v.insert((component_type, component.into()));
+                let params = match (&self.base_path, params.base_path()) {
+                    (Some(base_path), None) => params.clone().with_base_path(base_path),
+                    _ => params.clone(),
+                };
+                let component = self
+                    .factory
+                    .create_component(component_type.clone(), &params)
+                    .map_err(|error| CircuitBuildError::ComponentCreation(name, error))?;
+                let info = self.factory.component_info_with_params(&component_type, &params);
+                let output_pins = info.as_ref().map(|info| {
+                    info.pins()
+                        .iter()
+                        .filter(|pin| matches!(pin.direction, PinDirection::Output | PinDirection::Bidirectional))
+                        .map(|pin| pin.number)
+                        .collect()
+                });
+                v.insert((Some(component_type), Rc::new(FaultableComponent::new(component.into(), output_pins))));
+                self.netlist.add_component(name, type_name, info);
                 Ok(self)
             }
             Entry::Occupied(_) => Err(CircuitBuildError::ComponentNameOverride(name)),
         }
     }
 
+    /// Embeds `circuit` as the component `name`, each `(pin, inner name)` of `pin_map` exposing an input or
+    /// an output of `circuit` as a pin of the component. This is the programmatic counterpart of a subcircuit.
+    pub fn add_subcircuit<'a>(
+        mut self,
+        name: &'a str,
+        circuit: Circuit,
+        pin_map: &[(PinNumber, &'a str)],
+    ) -> Result<Self, CircuitBuildError<'a, Factory::Type>> {
+        if self.components.contains_key(name) {
+            return Err(CircuitBuildError::ComponentNameOverride(name));
+        }
+
+        let subcircuit =
+            SubcircuitComponent::new(circuit, pin_map).map_err(|NotExposable(inner)| CircuitBuildError::NotExposable(inner))?;
+        let subcircuit = Rc::new(subcircuit);
+        let output_pins = pin_map.iter().map(|&(pin, _)| pin).filter(|&pin| !subcircuit.is_input(pin)).collect();
+
+        self.netlist.add_component(name, "subcircuit", Some(subcircuit.info(name, pin_map)));
+        self.components.insert(name.to_owned(), (None, Rc::new(FaultableComponent::new(subcircuit.clone(), Some(output_pins)))));
+        self.subcircuits.insert(name.to_owned(), subcircuit);
+        Ok(self)
+    }
+
     pub fn link_components<'a>(
-        self,
+        mut self,
         left_component_name: &'a str,
         left_component_pin: PinNumber,
         right_component_name: &'a str,
@@ -85,7 +188,7 @@ where
             left_component.as_ref(),
             left_component_type,
             left_component_pin,
-            Rc::downgrade(&right_component),
+            Rc::downgrade(&right_component) as Weak<dyn Component>,
             right_component_pin,
         )?;
         Self::unidirectional_link(
@@ -93,19 +196,133 @@ where
             right_component.as_ref(),
             right_component_type,
             right_component_pin,
-            Rc::downgrade(&left_component),
+            Rc::downgrade(&left_component) as Weak<dyn Component>,
             left_component_pin,
         )?;
+        let (left_pin, right_pin) =
+            (PinRef::new(left_component_name, left_component_pin), PinRef::new(right_component_name, right_component_pin));
+        // Without info, the direction of a pin is only known from the component itself.
+        for (pin, component) in [(&left_pin, &left_component), (&right_pin, &right_component)] {
+            if self.netlist.component(&pin.component).is_some_and(|component| component.info.is_none()) {
+                self.netlist.probe_pin(pin, component.is_driving(pin.pin));
+            }
+        }
+        self.netlist.add_link(left_pin, right_pin);
+        Ok(self)
+    }
+
+    /// Applies parsed instructions: components are added first, then nets, then links, whatever their order.
+    /// On failure, returns the index in `instructions` of the faulty one with the error.
+    pub fn add_instructions<'a>(
+        mut self,
+        instructions: &'a [Instruction],
+    ) -> Result<Self, (usize, CircuitBuildError<'a, Factory::Type>)> {
+        // Nets are built before the links, so that their declared pins come first.
+        let mut order: Vec<usize> = (0..instructions.len()).collect();
+        order.sort_by_key(|&index| match instructions[index] {
+            Instruction::AddComponent { .. } => 0,
+            Instruction::DeclareNet { .. } => 1,
+            Instruction::LinkComponents { .. } => 2,
+        });
+
+        // A label stands for the first pin of its net.
+        let net_drivers: HashMap<&str, (&str, PinNumber)> = instructions
+            .iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::DeclareNet { label, pins } => {
+                    pins.first().map(|(name, pin)| (label.as_str(), (name.as_str(), *pin)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        for index in order {
+            self = self.add_instruction(&instructions[index], &net_drivers).map_err(|err| (index, err))?;
+        }
+
         Ok(self)
     }
 
-    fn get_component<'a>(
-        &self,
+    fn add_instruction<'a>(
+        self,
+        instruction: &'a Instruction,
+        net_drivers: &HashMap<&'a str, (&'a str, PinNumber)>,
+    ) -> Result<Self, CircuitBuildError<'a, Factory::Type>> {
+        match instruction {
+            Instruction::AddComponent { name, component_type, values, params } => {
+                let builder = self.add_component_with_params(component_type, name, params)?;
+
+                match values {
+                    Some(values) => builder.input_sequence(name, values.clone()),
+                    None => Ok(builder),
+                }
+            }
+            Instruction::LinkComponents { left, right } => {
+                let resolve = |endpoint: &'a Endpoint| match endpoint {
+                    Endpoint::Pin(name, pin) => Ok((name.as_str(), *pin)),
+                    Endpoint::Label(label) => {
+                        net_drivers.get(label.as_str()).copied().ok_or(CircuitBuildError::ComponentNameUnknown(label))
+                    }
+                };
+                let (left_name, left_pin) = resolve(left)?;
+                let (right_name, right_pin) = resolve(right)?;
+                let builder = self.link_components(left_name, left_pin, right_name, right_pin)?;
+
+                match (left, right) {
+                    (Endpoint::Label(label), _) => builder.label_pin(label, right_name, right_pin),
+                    (_, Endpoint::Label(label)) => builder.label_pin(label, left_name, left_pin),
+                    _ => Ok(builder),
+                }
+            }
+            Instruction::DeclareNet { label, pins } => {
+                let Some((driver_name, driver_pin)) = pins.first() else {
+                    return Ok(self);
+                };
+
+                pins.iter().try_fold(self, |builder, (name, pin)| {
+                    let builder = builder.label_pin(label, name, *pin)?;
+
+                    if (name, pin) == (driver_name, driver_pin) {
+                        Ok(builder)
+                    } else {
+                        builder.link_components(driver_name, *driver_pin, name, *pin)
+                    }
+                })
+            }
+        }
+    }
+
+    /// Feeds the input `name` with `values`, one per tick from tick 1, before leaving it to `Circuit::set_value()`.
+    pub fn input_sequence<'a>(
+        mut self,
         name: &'a str,
-    ) -> Result<(Factory::Type, Rc<dyn Component>), CircuitBuildError<'a, Factory::Type>> {
+        values: Vec<Tristate>,
+    ) -> Result<Self, CircuitBuildError<'a, Factory::Type>> {
+        let (_, component) = self.get_component(name)?;
+
+        if component.as_input().is_none() {
+            return Err(CircuitBuildError::NotAnInput(name));
+        }
+        self.input_sequences.insert(name.to_owned(), values.into());
+        Ok(self)
+    }
+
+    /// Adds the pin `name:pin` to the net called `label`.
+    pub fn label_pin<'a>(
+        mut self,
+        label: &str,
+        name: &'a str,
+        pin: PinNumber,
+    ) -> Result<Self, CircuitBuildError<'a, Factory::Type>> {
+        self.get_component(name)?;
+        self.netlist.add_net_pin(label, PinRef::new(name, pin));
+        Ok(self)
+    }
+
+    fn get_component<'a>(&self, name: &'a str) -> Result<ComponentEntry<Factory::Type>, CircuitBuildError<'a, Factory::Type>> {
         let component_pair = self.components.get(name).ok_or(CircuitBuildError::ComponentNameUnknown(name))?;
 
-        let component_type = component_pair.0;
+        let component_type = component_pair.0.clone();
         let component = component_pair.1.clone();
 
         Ok((component_type, component))
@@ -115,14 +332,17 @@ where
     fn unidirectional_link<'a>(
         name: &'a str,
         component: &dyn Component,
-        component_type: Factory::Type,
+        component_type: Option<Factory::Type>,
         component_pin: PinNumber,
         other_component: Weak<dyn Component>,
         other_component_pin: PinNumber,
     ) -> Result<(), CircuitBuildError<'a, Factory::Type>> {
         match component.set_link(component_pin, other_component, other_component_pin) {
             Ok(_) => Ok(()),
-            Err(InvalidPin(pin)) => Err(CircuitBuildError::ComponentLinkIssue(name, component_type, pin)),
+            Err(InvalidPin(pin)) => match component_type {
+                Some(component_type) => Err(CircuitBuildError::ComponentLinkIssue(name, component_type, pin)),
+                None => Err(CircuitBuildError::SubcircuitLinkIssue(name, pin)),
+            },
         }
     }
 }
@@ -193,6 +413,19 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_label_pin() {
+        let builder = CircuitBuilder::new(MockComponentFactory);
+        let builder = builder.add_component("one", "dummy").unwrap();
+
+        assert!(matches!(builder.label_pin("net", "unknown", 1), Err(CircuitBuildError::ComponentNameUnknown("unknown"))));
+
+        let builder = CircuitBuilder::new(MockComponentFactory).add_component("one", "dummy").unwrap();
+        let circuit = builder.label_pin("net", "dummy", 1).unwrap().build().unwrap();
+
+        assert_eq!(circuit.netlist().label_of(&crate::circuit::PinRef::new("dummy", 1)), Some("net"));
+    }
+
     #[test]
     fn test_link_components_error_invalid_pin_for_left_component() {
         let builder = CircuitBuilder::new(MockComponentFactory);
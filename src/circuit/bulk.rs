@@ -0,0 +1,131 @@
+use crate::components::{tristate::Tristate, Component, Tick};
+
+use super::fault::FaultableComponent;
+use super::{Circuit, SimulationError};
+
+/// Input or output of a circuit, looked up once by `Circuit::signal_id()` instead of by name at each tick.
+///
+/// An identifier is only meaningful for the circuit which gave it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignalId(usize);
+
+/// Outcome of `Circuit::run()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunSummary {
+    /// Ticks simulated, one per item of the stimulus.
+    pub ticks: usize,
+    /// Values given to a signal which is not an input of the circuit, left out.
+    pub rejected: usize,
+    /// Errors reported by `Circuit::try_simulate()`, the ticks being completed anyway but for a tick overflow.
+    pub errors: Vec<(Tick, SimulationError)>,
+}
+
+/// Current state of an input, or value of an output.
+pub(super) fn signal_value(component: &FaultableComponent) -> Tristate {
+    match (component.as_input(), component.as_output()) {
+        (Some(input), _) => input.get_current_state(),
+        (None, Some(output)) => output.get_value(),
+        (None, None) => unreachable!("Signals are inputs or outputs"),
+    }
+}
+
+impl Circuit {
+    pub fn signal_id(&self, name: &str) -> Option<SignalId> {
+        self.signals.binary_search_by(|(other, _)| other.as_str().cmp(name)).ok().map(SignalId)
+    }
+
+    pub fn signal_name(&self, id: SignalId) -> Option<&str> {
+        self.signals.get(id.0).map(|(name, _)| name.as_str())
+    }
+
+    pub fn signal_value(&self, id: SignalId) -> Option<Tristate> {
+        self.signals.get(id.0).map(|(_, component)| signal_value(component))
+    }
+
+    /// Simulates one tick per item of `stimulus` until it is exhausted, the values of an item being given to
+    /// their inputs before its tick.
+    ///
+    /// Signals are not looked up by name nor values parsed: bindings paying for each call should prefer it to
+    /// `set_value()` and `simulate()`. The run stops early if the tick counter overflows.
+    pub fn run(&mut self, stimulus: impl Iterator<Item = Vec<(SignalId, Tristate)>>) -> RunSummary {
+        let mut summary = RunSummary::default();
+
+        for values in stimulus {
+            for (id, value) in values {
+                match self.signals.get(id.0).and_then(|(_, component)| component.as_input()) {
+                    Some(input) => input.set_state_for_next_tick(value),
+                    None => summary.rejected += 1,
+                }
+            }
+
+            match self.try_simulate() {
+                Ok(()) => (),
+                Err(SimulationError::TickOverflow) => {
+                    summary.errors.push((self.current_tick, SimulationError::TickOverflow));
+                    break;
+                }
+                Err(error) => summary.errors.push((self.current_tick, error)),
+            }
+            summary.ticks += 1;
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RunSummary, SignalId};
+    use crate::circuit::{Circuit, ConflictPolicy, SimulationError};
+    use crate::components::{tristate::Tristate, Tick};
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 gate
+        output out
+        .links:
+        a:1 gate:1
+        b:1 gate:2
+        gate:3 out:1
+    ";
+
+    #[test]
+    fn test_signal_ids() {
+        let circuit: Circuit = CONTENT.parse().unwrap();
+        let ids: Vec<SignalId> = ["a", "b", "out"].map(|name| circuit.signal_id(name).unwrap()).into();
+
+        assert_eq!(circuit.signal_id("gate"), None);
+        assert_eq!(circuit.signal_id("c"), None);
+        assert_eq!(ids.iter().map(|&id| circuit.signal_name(id).unwrap()).collect::<Vec<_>>(), vec!["a", "b", "out"]);
+        assert_eq!(circuit.signal_value(ids[2]), Some(Tristate::Undefined));
+        assert_eq!(circuit.signal_value(SignalId(3)), None);
+    }
+
+    #[test]
+    fn test_run() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        let [a, b, out] = ["a", "b", "out"].map(|name| circuit.signal_id(name).unwrap());
+        let (low, high) = (Tristate::State(false), Tristate::State(true));
+
+        let summary = circuit.run([vec![(a, high), (b, high)], vec![(b, low), (out, high)], vec![], vec![(b, high)]].into_iter());
+        assert_eq!(summary, RunSummary { ticks: 4, rejected: 1, errors: Vec::new() });
+        assert_eq!(circuit.current_tick().get(), 4);
+        assert_eq!(circuit.signal_value(out), Some(high));
+        assert_eq!(circuit.run(std::iter::empty()), RunSummary::default());
+    }
+
+    #[test]
+    fn test_run_errors() {
+        let mut circuit: Circuit = ".chipsets:\ninput a\ninput b\noutput out\n.links:\na:1 out:1\nb:1 out:1\n".parse().unwrap();
+        let [a, b] = ["a", "b"].map(|name| circuit.signal_id(name).unwrap());
+        let (low, high) = (Tristate::State(false), Tristate::State(true));
+
+        circuit.set_conflict_policy(ConflictPolicy::Error);
+        let summary = circuit.run([vec![(a, high), (b, high)], vec![(b, low)], vec![(a, low)]].into_iter());
+        assert_eq!(summary.ticks, 3);
+        assert_eq!(summary.errors, vec![(Tick::from(2), SimulationError::DriverConflicts(1))]);
+        assert_eq!(circuit.signal_value(circuit.signal_id("out").unwrap()), Some(low));
+    }
+}
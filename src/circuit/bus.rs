@@ -0,0 +1,188 @@
+//! Groups of single-bit inputs or outputs read and written as integers.
+//!
+//! A bus named `addr` is either a `businput`/`busoutput` component named `addr`, or made of the components
+//! `addr0`, `addr1`, ... (bit 0 being the least significant), numbered without gaps from 0.
+
+use std::fmt;
+
+use crate::components::{tristate::Tristate, Component};
+
+use super::Circuit;
+
+/// Maximum number of bits of a bus.
+pub const MAX_BUS_WIDTH: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone)]
+pub enum BusError<'a> {
+    /// There is no `{name}0` component of the expected kind.
+    UnknownBus(&'a str),
+    /// `value` does not fit on the bus.
+    ValueTooWide { name: &'a str, value: u64, width: usize },
+}
+
+impl fmt::Display for BusError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownBus(name) => write!(f, "Unknown bus \"{name}\""),
+            Self::ValueTooWide { name, value, width } => write!(f, "{value} does not fit on the {width} bits of \"{name}\""),
+        }
+    }
+}
+
+impl Circuit {
+    /// Sets the bus input `name`, or the inputs `{name}0`, `{name}1`, ..., to the bits of `value` for the next tick.
+    pub fn set_bus<'a>(&self, name: &'a str, value: u64) -> Result<(), BusError<'a>> {
+        if let Some(input) = self.components.get(name).and_then(|component| component.as_bus_input()) {
+            let width = input.width();
+
+            if width < MAX_BUS_WIDTH && value >> width != 0 {
+                return Err(BusError::ValueTooWide { name, value, width });
+            }
+            input.set_bus_value(value);
+            return Ok(());
+        }
+
+        let bits = self.bus_bits(name, |component| component.as_input().is_some());
+        let width = bits.len();
+
+        if width == 0 {
+            return Err(BusError::UnknownBus(name));
+        }
+        if width < MAX_BUS_WIDTH && value >> width != 0 {
+            return Err(BusError::ValueTooWide { name, value, width });
+        }
+
+        for (index, bit) in bits.into_iter().enumerate() {
+            let input = self.components[&bit].as_input().unwrap();
+
+            input.set_state_for_next_tick(Tristate::State((value >> index) & 1 == 1));
+        }
+        Ok(())
+    }
+
+    /// Value of the bus output `name`, or of the outputs `{name}0`, `{name}1`, ..., read as an integer.
+    /// `None` if there is no such output or if one of them is undefined.
+    pub fn get_bus(&self, name: &str) -> Option<u64> {
+        if let Some(output) = self.components.get(name).and_then(|component| component.as_bus_output()) {
+            return output.get_bus_value();
+        }
+
+        let bits = self.bus_bits(name, |component| component.as_output().is_some());
+
+        if bits.is_empty() {
+            return None;
+        }
+
+        bits.iter().enumerate().try_fold(0, |value, (index, bit)| match self.components[bit].as_output()?.get_value() {
+            Tristate::State(true) => Some(value | (1 << index)),
+            Tristate::State(false) => Some(value),
+            Tristate::Undefined => None,
+        })
+    }
+
+    /// Names of the bits of the bus `name`, from the least significant one.
+    fn bus_bits<F>(&self, name: &str, predicate: F) -> Vec<String>
+    where
+        F: Fn(&dyn Component) -> bool,
+    {
+        (0..MAX_BUS_WIDTH)
+            .map(|index| format!("{name}{index}"))
+            .take_while(|bit| self.components.get(bit).is_some_and(|component| predicate(component.as_ref())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BusError;
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a0
+        input a1
+        input a2
+        input a3
+        4069 not
+        output s0
+        output s1
+        output s2
+        output s3
+        .links:
+        a0:1 not:1
+        a1:1 not:3
+        a2:1 not:5
+        a3:1 not:9
+        not:2 s0:1
+        not:4 s1:1
+        not:6 s2:1
+        not:8 s3:1
+    ";
+
+    #[test]
+    fn test_inverted_bus() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        assert_eq!(circuit.get_bus("s"), None);
+
+        circuit.set_bus("a", 0b1011).unwrap();
+        circuit.simulate();
+
+        assert_eq!(circuit.get_bus("s"), Some(0b0100));
+        assert_eq!(circuit.get_input("a3").unwrap(), "1");
+        assert_eq!(circuit.get_input("a2").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_errors() {
+        let circuit: Circuit = CONTENT.parse().unwrap();
+
+        assert!(matches!(circuit.set_bus("a", 16), Err(BusError::ValueTooWide { name: "a", value: 16, width: 4 })));
+        assert!(matches!(circuit.set_bus("s", 1), Err(BusError::UnknownBus("s"))));
+        assert!(matches!(circuit.set_bus("nope", 1), Err(BusError::UnknownBus("nope"))));
+        assert_eq!(circuit.get_bus("a"), None);
+    }
+
+    #[test]
+    fn test_bus_components() {
+        let content = "
+            .chipsets:
+            businput a width=4
+            businput b width=4
+            false carry_in
+            4008 adder
+            busoutput sum width=5
+            .links:
+            a:1 adder:7
+            a:2 adder:5
+            a:3 adder:3
+            a:4 adder:1
+            b:1 adder:6
+            b:2 adder:4
+            b:3 adder:2
+            b:4 adder:15
+            carry_in:1 adder:9
+            adder:10 sum:1
+            adder:11 sum:2
+            adder:12 sum:3
+            adder:13 sum:4
+            adder:14 sum:5
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        assert_eq!(circuit.get_bus("sum"), None);
+        for (a, b) in [(9, 12), (15, 15), (0, 3)] {
+            circuit.set_bus("a", a).unwrap();
+            circuit.set_bus("b", b).unwrap();
+            circuit.simulate();
+            assert_eq!(circuit.get_bus("sum"), Some(a + b), "{a} + {b}");
+        }
+
+        assert!(matches!(circuit.set_bus("a", 16), Err(BusError::ValueTooWide { name: "a", value: 16, width: 4 })));
+        assert!(matches!(circuit.set_bus("sum", 1), Err(BusError::UnknownBus("sum"))));
+        assert_eq!(circuit.get_bus("a"), None);
+        for width in ["0", "65", "x"] {
+            assert!(content.replace("width=5", &format!("width={width}")).parse::<Circuit>().is_err(), "{width}");
+        }
+    }
+}
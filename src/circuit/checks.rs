@@ -0,0 +1,179 @@
+//! Expected outputs written in `.nts` files as `#! check tick=5 out=1 carry=0` pragmas.
+//!
+//! Other `#!` lines are comments. Inputs are driven by their `values=` sequences.
+
+use crate::components::{tristate::Tristate, Tick};
+
+use super::parser::SyntaxErrorKind;
+use super::{Circuit, Mismatch};
+
+static CHECK_PRAGMA: &str = "check";
+
+/// Outputs expected once the circuit reached `tick`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EmbeddedCheck {
+    pub line: usize,
+    pub tick: Tick,
+    pub expected: Vec<(String, Tristate)>,
+}
+
+/// Collects the check pragmas of `.nts` content, in source order.
+pub(super) fn parse_checks(input: &str) -> Result<Vec<EmbeddedCheck>, (usize, SyntaxErrorKind)> {
+    let mut checks: Vec<EmbeddedCheck> = Vec::new();
+
+    for (index, content) in input.lines().enumerate() {
+        let line = index + 1;
+        let Some(pragma) = content.trim().strip_prefix("#!") else {
+            continue;
+        };
+        let pragma = match pragma.find('#') {
+            Some(comment_idx) => &pragma[..comment_idx],
+            None => pragma,
+        };
+        let mut words = pragma.split_whitespace();
+        if words.next() != Some(CHECK_PRAGMA) {
+            continue;
+        }
+
+        let mut tick: Option<Tick> = None;
+        let mut expected: Vec<(String, Tristate)> = Vec::new();
+        for word in words {
+            let (name, value) = word.split_once('=').ok_or((line, SyntaxErrorKind::InvalidCheckFormat))?;
+
+            if name == "tick" && tick.is_none() {
+                tick = Some(value.parse::<u64>().map_err(|_| (line, SyntaxErrorKind::InvalidCheckFormat))?.into());
+            } else {
+                let value = value.parse().map_err(|_| (line, SyntaxErrorKind::InvalidInputValue { value: value.to_owned() }))?;
+                expected.push((name.to_owned(), value));
+            }
+        }
+
+        match tick {
+            Some(tick) if !expected.is_empty() => checks.push(EmbeddedCheck { line, tick, expected }),
+            _ => return Err((line, SyntaxErrorKind::InvalidCheckFormat)),
+        }
+    }
+
+    Ok(checks)
+}
+
+impl Circuit {
+    /// Check pragmas of the content the circuit was parsed from.
+    pub fn embedded_checks(&self) -> &[EmbeddedCheck] {
+        &self.embedded_checks
+    }
+
+    /// Simulates up to the tick of each embedded check, in tick order, returning the failed expectations.
+    /// Checks of ticks already past are reported as `Mismatch::Tick`.
+    pub fn run_embedded_checks(&mut self) -> Vec<Mismatch> {
+        let mut checks: Vec<EmbeddedCheck> = self.embedded_checks.clone();
+        let mut mismatches: Vec<Mismatch> = Vec::new();
+
+        checks.sort_by_key(|check| (check.tick, check.line));
+        for check in checks {
+            if check.tick < self.current_tick {
+                mismatches.push(Mismatch::Tick { line: check.line, tick: check.tick });
+                continue;
+            }
+            while self.current_tick < check.tick {
+                self.simulate();
+            }
+            mismatches.extend(self.check_outputs(&check));
+        }
+
+        mismatches
+    }
+
+    /// Failed expectations of the embedded checks of the current tick, in source order.
+    pub(super) fn current_check_mismatches(&self) -> Vec<Mismatch> {
+        self.embedded_checks
+            .iter()
+            .filter(|check| check.tick == self.current_tick)
+            .flat_map(|check| self.check_outputs(check))
+            .collect()
+    }
+
+    fn check_outputs(&self, check: &EmbeddedCheck) -> Vec<Mismatch> {
+        check
+            .expected
+            .iter()
+            .filter_map(|(name, expected)| {
+                let got = self.get_output(name).map(|value| value.parse().unwrap());
+
+                (got != Some(*expected)).then(|| Mismatch::Output {
+                    line: check.line,
+                    name: name.clone(),
+                    expected: *expected,
+                    got,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_checks, EmbeddedCheck};
+    use crate::circuit::{Circuit, Mismatch, ParseCircuitError, SyntaxErrorKind};
+    use crate::components::{tristate::Tristate, Tick};
+
+    const CONTENT: &str = "
+        #! check tick=2 out=1
+        #! check tick=1 out=1 # a first check
+        #!/ignored pragma
+        .chipsets:
+        input a values=0,1
+        4069 gate
+        output out
+        .links:
+        a:1 gate:1
+        gate:2 out:1
+        #! check tick=3 out=0 gate=0
+    ";
+
+    #[test]
+    fn test_parse_checks() {
+        let checks = parse_checks(CONTENT).unwrap();
+
+        assert_eq!(checks.len(), 3);
+        assert_eq!(
+            checks[1],
+            EmbeddedCheck { line: 3, tick: Tick::new(1), expected: vec![("out".to_owned(), Tristate::State(true))] }
+        );
+
+        for (pragma, line) in
+            [("#! check out=1", 1), ("\n#! check tick=1", 2), ("#! check tick=x out=1", 1), ("#! check tick=1 out", 1)]
+        {
+            assert!(matches!(parse_checks(pragma), Err((l, SyntaxErrorKind::InvalidCheckFormat)) if l == line), "{pragma}");
+        }
+        assert!(matches!(parse_checks("#! check tick=1 out=2"), Err((1, SyntaxErrorKind::InvalidInputValue { .. }))));
+    }
+
+    #[test]
+    fn test_run_embedded_checks() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        assert_eq!(
+            circuit.run_embedded_checks(),
+            vec![
+                Mismatch::Output {
+                    line: 2,
+                    name: "out".to_owned(),
+                    expected: Tristate::State(true),
+                    got: Some(Tristate::State(false))
+                },
+                Mismatch::Output { line: 12, name: "gate".to_owned(), expected: Tristate::State(false), got: None },
+            ]
+        );
+        assert_eq!(circuit.current_tick().get(), 3);
+        assert_eq!(circuit.run_embedded_checks()[0], Mismatch::Tick { line: 3, tick: Tick::new(1) });
+        assert_eq!(Mismatch::Tick { line: 3, tick: Tick::new(1) }.to_string(), "line 3: tick 1 is already past");
+    }
+
+    #[test]
+    fn test_invalid_pragma_in_circuit() {
+        let error = "#! check tick=1\n.chipsets:\ninput a\n".parse::<Circuit>().err().unwrap();
+
+        assert!(matches!(error, ParseCircuitError::Syntax { line: 1, kind: SyntaxErrorKind::InvalidCheckFormat }));
+    }
+}
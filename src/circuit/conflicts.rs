@@ -0,0 +1,129 @@
+use crate::components::{tristate::Tristate, Component};
+use crate::pin::ConflictPolicy;
+
+use super::diagnostics::{Diagnostic, Severity};
+use super::netlist::PinRef;
+use super::Circuit;
+
+impl Circuit {
+    pub const DRIVER_CONFLICT: &'static str = "driver-conflict";
+
+    /// Policy applied by the input pins whose drivers disagree, from the next tick on.
+    pub fn set_conflict_policy(&mut self, policy: ConflictPolicy) {
+        self.conflict_policy = policy;
+    }
+
+    pub fn conflict_policy(&self) -> ConflictPolicy {
+        self.conflict_policy
+    }
+
+    /// Input pins whose drivers currently disagree, sorted by pin.
+    ///
+    /// They are errors under `ConflictPolicy::Error`, which fails the tick, and warnings otherwise.
+    pub fn driver_conflicts(&self) -> Vec<Diagnostic> {
+        let severity = match self.conflict_policy {
+            ConflictPolicy::Error => Severity::Error,
+            ConflictPolicy::PreferDefined | ConflictPolicy::ForceUndefined => Severity::Warning,
+        };
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+        for name in self.netlist.component_names() {
+            for pin in self.netlist.input_pins(name) {
+                let pin = PinRef::new(name, pin);
+                let drivers: Vec<(&PinRef, Tristate)> = self
+                    .netlist
+                    .drivers_of(&pin)
+                    .into_iter()
+                    .filter(|driver| {
+                        self.components.get(&driver.component).is_some_and(|component| component.is_driving(driver.pin))
+                    })
+                    .map(|driver| (driver, self.pin_value(&driver.component, driver.pin)))
+                    .collect();
+                let values: Vec<Tristate> = drivers.iter().map(|(_, value)| *value).collect();
+                let (value, true) = self.conflict_policy.resolve(&values) else {
+                    continue;
+                };
+
+                let driven_by: Vec<String> = drivers.iter().map(|(driver, value)| format!("{driver} = {value}")).collect();
+                let message = format!(
+                    "'{}' is driven by {}, read as {value} ({} policy)",
+                    self.netlist.pin_name(&pin),
+                    driven_by.join(", "),
+                    self.conflict_policy
+                );
+                diagnostics.push(drivers.iter().fold(
+                    Diagnostic::new(severity, Self::DRIVER_CONFLICT, message).with_component(name),
+                    |diagnostic, (driver, _)| diagnostic.with_component(&driver.component),
+                ));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::{Circuit, SimulationError};
+    use crate::pin::ConflictPolicy;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        output out
+        .links:
+        a:1 out:1
+        b:1 out:1
+    ";
+
+    fn read(circuit: &mut Circuit, a: &str, b: &str) -> (Result<(), SimulationError>, String) {
+        circuit.set_value("a", a).unwrap();
+        circuit.set_value("b", b).unwrap();
+
+        let result = circuit.try_simulate();
+        (result, circuit.get_output("out").unwrap())
+    }
+
+    #[test]
+    fn test_policies() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        assert_eq!(circuit.conflict_policy(), ConflictPolicy::PreferDefined);
+
+        assert_eq!(read(&mut circuit, "1", "1"), (Ok(()), "1".to_owned()));
+        assert_eq!(read(&mut circuit, "1", "U"), (Ok(()), "1".to_owned()));
+        assert_eq!(read(&mut circuit, "1", "0"), (Ok(()), "U".to_owned()));
+
+        circuit.set_conflict_policy(ConflictPolicy::ForceUndefined);
+        assert_eq!(read(&mut circuit, "0", "U"), (Ok(()), "U".to_owned()));
+        assert_eq!(read(&mut circuit, "0", "0"), (Ok(()), "0".to_owned()));
+
+        circuit.set_conflict_policy(ConflictPolicy::Error);
+        assert_eq!(read(&mut circuit, "0", "1"), (Err(SimulationError::DriverConflicts(1)), "U".to_owned()));
+        assert_eq!(read(&mut circuit, "1", "1"), (Ok(()), "1".to_owned()));
+
+        assert_eq!("force-undefined".parse(), Ok(ConflictPolicy::ForceUndefined));
+        assert_eq!(ConflictPolicy::Error.to_string(), "error");
+        assert!("or".parse::<ConflictPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        read(&mut circuit, "1", "1").0.unwrap();
+        assert!(circuit.driver_conflicts().is_empty());
+
+        read(&mut circuit, "1", "U").0.unwrap();
+        let diagnostics: Vec<String> = circuit.driver_conflicts().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            diagnostics,
+            vec!["warning[driver-conflict]: 'out:1' is driven by a:1 = 1, b:1 = U, read as 1 (prefer-defined policy)"]
+        );
+
+        circuit.set_conflict_policy(ConflictPolicy::Error);
+        let diagnostic = circuit.driver_conflicts().remove(0);
+        assert!(diagnostic.to_string().starts_with("error[driver-conflict]"));
+        assert_eq!(diagnostic.components, vec!["out", "a", "b"]);
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+
+use crate::components::{tristate::Tristate, Component};
+
+use super::{Circuit, Mismatch};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvError {
+    pub line: usize,
+    pub kind: CsvErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvErrorKind {
+    Io { message: String },
+    MissingHeader,
+    UnknownColumn { name: String },
+    DuplicateColumn { name: String },
+    FieldCount { expected: usize, got: usize },
+    InvalidValue { column: String, value: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for CsvErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { message } => write!(f, "{message}"),
+            Self::MissingHeader => write!(f, "Missing header row"),
+            Self::UnknownColumn { name } => write!(f, "\"{name}\" is neither an input nor an output"),
+            Self::DuplicateColumn { name } => write!(f, "Duplicate column \"{name}\""),
+            Self::FieldCount { expected, got } => write!(f, "Expected {expected} fields, got {got}"),
+            Self::InvalidValue { column, value } => write!(f, "Invalid value \"{value}\" for \"{column}\""),
+        }
+    }
+}
+
+/// Outcome of `Circuit::verify_against_csv()`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CsvReport {
+    /// Number of rows checked.
+    pub rows: usize,
+    /// Expected outputs which were not met, with the line of their row.
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl CsvReport {
+    pub fn is_success(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl fmt::Display for CsvReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for mismatch in self.mismatches.iter() {
+            writeln!(f, "{mismatch}")?;
+        }
+        writeln!(f, "{} row(s), {} mismatch(es)", self.rows, self.mismatches.len())
+    }
+}
+
+enum Column {
+    Input(String),
+    Output(String),
+}
+
+impl Circuit {
+    /// Checks the circuit against a table of expected values: the header row names inputs and outputs,
+    /// each following row giving input values and the outputs expected one tick later.
+    ///
+    /// Values are `0`, `1` or `U`; an empty input cell leaves the input undefined and an empty output cell (or `?`)
+    /// is not checked. Before each row, every input is reset to undefined then set from the row. Components keep
+    /// their state from one row to the next, so the rows of sequential circuits follow one another.
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn verify_against_csv<R: Read>(&mut self, reader: R) -> Result<CsvReport, CsvError> {
+        let mut lines = BufReader::new(reader)
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| line.as_ref().map_or(true, |line| !line.trim().is_empty() && !line.starts_with('#')));
+        let read = |(line, content): (usize, std::io::Result<String>)| {
+            content
+                .map(|content| (line, content))
+                .map_err(|error| CsvError { line, kind: CsvErrorKind::Io { message: error.to_string() } })
+        };
+
+        let (header_line, header) = read(lines.next().ok_or(CsvError { line: 1, kind: CsvErrorKind::MissingHeader })?)?;
+        let columns = self.csv_columns(header_line, &header)?;
+        let mut report = CsvReport::default();
+
+        for line in lines {
+            let (line, content) = read(line)?;
+            let fields: Vec<&str> = content.split(',').map(str::trim).collect();
+            let error = |kind: CsvErrorKind| CsvError { line, kind };
+
+            if fields.len() != columns.len() {
+                return Err(error(CsvErrorKind::FieldCount { expected: columns.len(), got: fields.len() }));
+            }
+
+            for input in self.components.values().filter_map(|component| component.as_input()) {
+                input.set_state_for_next_tick(Tristate::Undefined);
+            }
+            for (column, &value) in columns.iter().zip(fields.iter()) {
+                if let Column::Input(name) = column {
+                    if !value.is_empty() && self.set_value(name, value).is_err() {
+                        return Err(error(CsvErrorKind::InvalidValue { column: name.clone(), value: value.to_owned() }));
+                    }
+                }
+            }
+            self.simulate();
+
+            for (column, &value) in columns.iter().zip(fields.iter()) {
+                let Column::Output(name) = column else {
+                    continue;
+                };
+                if value.is_empty() || value == "?" {
+                    continue;
+                }
+
+                let expected: Tristate = value
+                    .parse()
+                    .map_err(|_| error(CsvErrorKind::InvalidValue { column: name.clone(), value: value.to_owned() }))?;
+                let got = self.get_output(name).map(|value| value.parse().unwrap());
+                if got != Some(expected) {
+                    report.mismatches.push(Mismatch::Output { line, name: name.clone(), expected, got });
+                }
+            }
+            report.rows += 1;
+        }
+
+        Ok(report)
+    }
+
+    fn csv_columns(&self, line: usize, header: &str) -> Result<Vec<Column>, CsvError> {
+        let mut columns: Vec<Column> = Vec::new();
+
+        for name in header.split(',').map(str::trim) {
+            let error = |kind: CsvErrorKind| CsvError { line, kind };
+
+            if columns.iter().any(|(Column::Input(other) | Column::Output(other))| other == name) {
+                return Err(error(CsvErrorKind::DuplicateColumn { name: name.to_owned() }));
+            }
+            columns.push(match (self.get_input(name), self.get_output(name)) {
+                (Some(_), _) => Column::Input(name.to_owned()),
+                (None, Some(_)) => Column::Output(name.to_owned()),
+                (None, None) => return Err(error(CsvErrorKind::UnknownColumn { name: name.to_owned() })),
+            });
+        }
+
+        Ok(columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CsvError, CsvErrorKind};
+    use crate::circuit::{Circuit, Mismatch};
+    use crate::components::tristate::Tristate;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 gate
+        output out
+        .links:
+        a:1 gate:1
+        b:1 gate:2
+        gate:3 out:1
+    ";
+
+    #[test]
+    fn test_verify() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        let table = "a, b, out\n0, 0, 0\n\n# b is reset to U\n0, , 0\n1, 0, 1\n1, 1, 1\n1, 1, ?\n";
+        let report = circuit.verify_against_csv(table.as_bytes()).unwrap();
+
+        assert_eq!(report.rows, 5);
+        assert_eq!(
+            report.mismatches,
+            vec![Mismatch::Output {
+                line: 6,
+                name: "out".to_owned(),
+                expected: Tristate::State(true),
+                got: Some(Tristate::State(false))
+            }]
+        );
+        assert_eq!(report.to_string(), "line 6: expected out=1, got 0\n5 row(s), 1 mismatch(es)\n");
+        assert!(!report.is_success());
+        assert_eq!(circuit.current_tick().get(), 5);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        let mut error = |table: &str| circuit.verify_against_csv(table.as_bytes()).unwrap_err();
+
+        assert_eq!(error(""), CsvError { line: 1, kind: CsvErrorKind::MissingHeader });
+        assert_eq!(error("a,gate").kind, CsvErrorKind::UnknownColumn { name: "gate".to_owned() });
+        assert_eq!(error("a,a").kind, CsvErrorKind::DuplicateColumn { name: "a".to_owned() });
+        assert_eq!(error("a,out\n1").to_string(), "line 2: Expected 2 fields, got 1");
+        assert_eq!(error("a,out\n2,1").kind, CsvErrorKind::InvalidValue { column: "a".to_owned(), value: "2".to_owned() });
+        assert_eq!(error("a,out\n1,x").kind, CsvErrorKind::InvalidValue { column: "out".to_owned(), value: "x".to_owned() });
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+
+use crate::components::{tristate::Tristate, Tick};
+
+use super::fault::mix;
+use super::{Circuit, ParseCircuitError, Stimulus};
+
+/// Output which took another value once the components were simulated in a shuffled order.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Divergence {
+    pub tick: Tick,
+    pub output: String,
+    pub reference: Tristate,
+    pub shuffled: Tristate,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tick {}: {} is {} in order, {} shuffled", self.tick, self.output, self.reference, self.shuffled)
+    }
+}
+
+/// Result of a determinism audit: the outputs which depend on the order the components are simulated in.
+#[derive(Debug, Clone)]
+pub struct DeterminismReport {
+    pub seed: u64,
+    /// Sorted by tick then output.
+    pub divergences: Vec<Divergence>,
+}
+
+impl DeterminismReport {
+    pub fn is_deterministic(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+impl fmt::Display for DeterminismReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_deterministic() {
+            return writeln!(f, "No divergence with seed {}", self.seed);
+        }
+
+        writeln!(f, "{} divergence(s) with seed {}", self.divergences.len(), self.seed)?;
+        for divergence in self.divergences.iter() {
+            writeln!(f, "  {divergence}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Circuit {
+    /// Shuffles the order the components are simulated in, the same seed always giving the same order.
+    fn shuffle_schedule(&mut self, seed: u64) {
+        let mut state = seed;
+
+        // Fisher-Yates.
+        for index in (1..self.schedule.len()).rev() {
+            state = mix(state);
+            self.schedule.swap(index, (state % (index as u64 + 1)) as usize);
+        }
+    }
+}
+
+/// Replays `stimulus` on two instances of the circuit described by `content`, the second one simulating its
+/// components in an order shuffled from `seed`, and compares their outputs after every tick.
+///
+/// Links are followed whatever the order, so only feedback loops, where a component reads a value computed
+/// at the previous tick, should make the outputs diverge. Expectations of the stimulus are not checked.
+pub fn audit_determinism(content: &str, stimulus: &Stimulus, seed: u64) -> Result<DeterminismReport, ParseCircuitError> {
+    let mut reference: Circuit = content.parse()?;
+    // Same content as the reference circuit: cannot fail.
+    let mut shuffled: Circuit = content.parse().unwrap();
+    let mut divergences: Vec<Divergence> = Vec::new();
+
+    shuffled.shuffle_schedule(seed);
+    let mut compared: Tick = reference.current_tick();
+    for index in 0..stimulus.nb_steps() {
+        // Expectations are checked by `Stimulus::run()`: the mismatches are left out.
+        stimulus.run_step(index, &mut reference, &mut Vec::new());
+        stimulus.run_step(index, &mut shuffled, &mut Vec::new());
+
+        let tick = reference.current_tick();
+        if tick == compared {
+            continue;
+        }
+        compared = tick;
+
+        // Inputs take the values of the stimulus in both circuits: only outputs can diverge.
+        divergences.extend(
+            reference
+                .signals()
+                .zip(shuffled.signals())
+                .filter(|((_, left), (_, right))| left != right)
+                .map(|((output, reference), (_, shuffled))| Divergence { tick, output: output.to_owned(), reference, shuffled }),
+        );
+    }
+
+    Ok(DeterminismReport { seed, divergences })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::audit_determinism;
+    use crate::circuit::Stimulus;
+
+    /// NOR latch whose gates are two components, released from the forbidden state.
+    const LATCH: &str = "
+        .chipsets:
+        input s
+        input r
+        4001 top
+        4001 bottom
+        output q
+        output nq
+        .links:
+        r:1 top:1
+        bottom:3 top:2
+        s:1 bottom:1
+        top:3 bottom:2
+        top:3 q:1
+        bottom:3 nq:1
+    ";
+
+    const ADDER: &str = "
+        .chipsets:
+        input a
+        input b
+        4030 xor
+        4081 and
+        4069 not
+        output sum
+        output carry
+        .links:
+        a:1 xor:1
+        b:1 xor:2
+        a:1 and:1
+        b:1 and:2
+        xor:3 not:1
+        not:2 sum:1
+        and:3 carry:1
+    ";
+
+    fn stimulus() -> Stimulus {
+        "s=1\nr=1\na=1\nb=0\nsimulate\ns=0\nr=0\nb=1\nsimulate\nsimulate".parse().unwrap()
+    }
+
+    #[test]
+    fn test_order_independent() {
+        for seed in 0..8 {
+            let report = audit_determinism(ADDER, &stimulus(), seed).unwrap();
+
+            assert!(report.is_deterministic());
+            assert_eq!(report.to_string(), format!("No divergence with seed {seed}\n"));
+        }
+    }
+
+    #[test]
+    fn test_feedback_loop() {
+        // The latch settles to the gate simulated first: some orders do not match the reference.
+        let report = (0..8)
+            .map(|seed| audit_determinism(LATCH, &stimulus(), seed).unwrap())
+            .find(|report| !report.is_deterministic())
+            .unwrap();
+        let divergences: Vec<String> = report.divergences.iter().map(ToString::to_string).collect();
+
+        assert_eq!(divergences.len(), 4);
+        assert!(divergences[0].starts_with("tick 2: nq is "));
+        assert!(divergences[1].starts_with("tick 2: q is "));
+        assert!(report.divergences.iter().all(|divergence| divergence.reference != divergence.shuffled));
+        assert!(report.to_string().starts_with(&format!("4 divergence(s) with seed {}\n  tick 2", report.seed)));
+        assert!(audit_determinism("", &stimulus(), 0).is_err());
+    }
+}
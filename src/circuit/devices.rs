@@ -0,0 +1,212 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::components::image::{ImageError, ImageFormat, MemoryImage};
+use crate::components::Component;
+
+use super::Circuit;
+
+#[derive(Debug, Clone)]
+pub enum DeviceError<'a> {
+    UnknownName(&'a str),
+    NotAKeyboard(&'a str),
+    NotATerminal(&'a str),
+    NotAFileDevice(&'a str),
+    NotAMemory(&'a str),
+    ImageTooLarge { name: &'a str, size: usize, capacity: usize },
+    Io { name: &'a str, message: String },
+    Image { name: &'a str, error: ImageError },
+}
+
+impl fmt::Display for DeviceError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownName(name) => write!(f, "Unknown component \"{name}\""),
+            Self::NotAKeyboard(name) => write!(f, "\"{name}\" is not a keyboard"),
+            Self::NotATerminal(name) => write!(f, "\"{name}\" is not a terminal"),
+            Self::NotAFileDevice(name) => write!(f, "\"{name}\" does not use a file"),
+            Self::NotAMemory(name) => write!(f, "\"{name}\" is not a memory"),
+            Self::ImageTooLarge { name, size, capacity } => {
+                write!(f, "\"{name}\" holds {capacity} bytes, the image has {size} bytes")
+            }
+            Self::Io { name, message } => write!(f, "\"{name}\": {message}"),
+            Self::Image { name, error } => write!(f, "\"{name}\": {error}"),
+        }
+    }
+}
+
+impl Circuit {
+    /// Queues `key` on the keyboard `name`. Keys are exposed one per tick.
+    pub fn push_key<'a>(&self, name: &'a str, key: u8) -> Result<(), DeviceError<'a>> {
+        self.components
+            .get(name)
+            .ok_or(DeviceError::UnknownName(name))?
+            .as_keyboard()
+            .ok_or(DeviceError::NotAKeyboard(name))?
+            .push_key(key);
+
+        Ok(())
+    }
+
+    /// Queues the bytes of `text` on the keyboard `name`.
+    pub fn type_text<'a>(&self, name: &'a str, text: &str) -> Result<(), DeviceError<'a>> {
+        text.bytes().try_for_each(|key| self.push_key(name, key))
+    }
+
+    /// Text received so far by the terminal `name`.
+    pub fn terminal_text<'a>(&self, name: &'a str) -> Result<String, DeviceError<'a>> {
+        Ok(self
+            .components
+            .get(name)
+            .ok_or(DeviceError::UnknownName(name))?
+            .as_terminal()
+            .ok_or(DeviceError::NotATerminal(name))?
+            .text())
+    }
+
+    /// Attaches `path` to the `filein` or `fileout` component `name`.
+    pub fn attach_file<'a, P: AsRef<Path>>(&self, name: &'a str, path: P) -> Result<(), DeviceError<'a>> {
+        self.components
+            .get(name)
+            .ok_or(DeviceError::UnknownName(name))?
+            .as_file_device()
+            .ok_or(DeviceError::NotAFileDevice(name))?
+            .attach(path.as_ref())
+            .map_err(|error| DeviceError::Io { name, message: error.to_string() })
+    }
+
+    /// Byte stored at `address` by the memory `name`, `None` if it is undefined or out of range.
+    pub fn read_memory<'a>(&self, name: &'a str, address: usize) -> Result<Option<u8>, DeviceError<'a>> {
+        Ok(self
+            .components
+            .get(name)
+            .ok_or(DeviceError::UnknownName(name))?
+            .as_memory()
+            .ok_or(DeviceError::NotAMemory(name))?
+            .read(address))
+    }
+
+    /// Every byte stored by the memory `name`, from address 0, `None` for the undefined ones.
+    pub fn dump_memory<'a>(&self, name: &'a str) -> Result<Vec<Option<u8>>, DeviceError<'a>> {
+        Ok(self
+            .components
+            .get(name)
+            .ok_or(DeviceError::UnknownName(name))?
+            .as_memory()
+            .ok_or(DeviceError::NotAMemory(name))?
+            .dump())
+    }
+
+    /// Writes the content of the memory `name` to `path`, in the format given by its extension as for
+    /// `load_memory()`. Undefined bytes are left out of Intel HEX files, and written as zeros in binary ones.
+    pub fn save_memory<'a, P: AsRef<Path>>(&self, name: &'a str, path: P) -> Result<(), DeviceError<'a>> {
+        let path = path.as_ref();
+        let image = MemoryImage::from_dump(&self.dump_memory(name)?);
+
+        std::fs::write(path, image.encode(ImageFormat::from_path(path)))
+            .map_err(|error| DeviceError::Io { name, message: error.to_string() })
+    }
+
+    /// Stores `bytes` from address 0 in the memory `name`, e.g. the image of a ROM.
+    pub fn load_rom<'a>(&self, name: &'a str, bytes: &[u8]) -> Result<(), DeviceError<'a>> {
+        let memory =
+            self.components.get(name).ok_or(DeviceError::UnknownName(name))?.as_memory().ok_or(DeviceError::NotAMemory(name))?;
+
+        if bytes.len() > memory.size() {
+            return Err(DeviceError::ImageTooLarge { name, size: bytes.len(), capacity: memory.size() });
+        }
+
+        memory.load(bytes);
+        Ok(())
+    }
+
+    /// Stores `image` in the memory `name`, failing if it does not fit.
+    pub fn load_image<'a>(&self, name: &'a str, image: &MemoryImage) -> Result<(), DeviceError<'a>> {
+        let memory =
+            self.components.get(name).ok_or(DeviceError::UnknownName(name))?.as_memory().ok_or(DeviceError::NotAMemory(name))?;
+
+        image.load_into(memory).map_err(|error| DeviceError::Image { name, error })
+    }
+
+    /// Loads the image file `path` in the memory `name`, in Intel HEX format for the `.hex` extension
+    /// and as raw bytes otherwise (see `ImageFormat::from_path()`).
+    pub fn load_memory<'a, P: AsRef<Path>>(&self, name: &'a str, path: P) -> Result<(), DeviceError<'a>> {
+        let path = path.as_ref();
+        let content = std::fs::read(path).map_err(|error| DeviceError::Io { name, message: error.to_string() })?;
+        let image =
+            MemoryImage::parse(&content, ImageFormat::from_path(path)).map_err(|error| DeviceError::Image { name, error })?;
+
+        self.load_image(name, &image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeviceError;
+    use crate::circuit::Circuit;
+
+    // Echoes the keyboard on the terminal, latching each key on the rising edge of 'ready'.
+    const CONTENT: &str = "
+        .chipsets:
+        keyboard kbd
+        terminal term
+        .links:
+        kbd:1 term:1
+        kbd:2 term:2
+        kbd:3 term:3
+        kbd:4 term:4
+        kbd:5 term:5
+        kbd:6 term:6
+        kbd:7 term:7
+        kbd:8 term:8
+        kbd:9 term:9
+    ";
+
+    #[test]
+    fn test_echo() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        circuit.type_text("kbd", "hi!").unwrap();
+        for _ in 0..6 {
+            circuit.simulate();
+        }
+
+        assert_eq!(circuit.terminal_text("term").unwrap(), "hi!");
+        assert!(circuit.to_string().ends_with("terminal(s):\n  term: \"hi!\"\n"));
+    }
+
+    #[test]
+    fn test_file_copy() {
+        let content = format!(
+            ".chipsets:\nclock clk\nfilein in\nfileout out\n.links:\nclk:1 in:9\nclk:1 out:9\n{}",
+            (1..=8).map(|pin| format!("in:{pin} out:{pin}\n")).collect::<String>()
+        );
+        let directory = std::env::temp_dir();
+        let (source, destination) = (directory.join("nts_file_copy.in"), directory.join("nts_file_copy.out"));
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        std::fs::write(&source, "copy\n").unwrap();
+        circuit.attach_file("in", &source).unwrap();
+        circuit.attach_file("out", &destination).unwrap();
+        circuit.set_value("clk", "0").unwrap();
+        for _ in 0..12 {
+            circuit.simulate();
+        }
+
+        assert_eq!(std::fs::read_to_string(&destination).unwrap(), "copy\n");
+        assert!(matches!(circuit.attach_file("in", directory.join("nts_missing_file")), Err(DeviceError::Io { name: "in", .. })));
+        assert!(matches!(circuit.attach_file("clk", &source), Err(DeviceError::NotAFileDevice("clk"))));
+        std::fs::remove_file(source).unwrap();
+        std::fs::remove_file(destination).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let circuit: Circuit = CONTENT.parse().unwrap();
+
+        assert!(matches!(circuit.push_key("nope", b'a'), Err(DeviceError::UnknownName("nope"))));
+        assert!(matches!(circuit.push_key("term", b'a'), Err(DeviceError::NotAKeyboard("term"))));
+        assert!(matches!(circuit.terminal_text("kbd"), Err(DeviceError::NotATerminal("kbd"))));
+        assert!(matches!(circuit.read_memory("kbd", 0), Err(DeviceError::NotAMemory("kbd"))));
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Note => write!(f, "note"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Finding reported by an analysis of a circuit.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable identifier of the check which produced this diagnostic.
+    pub code: &'static str,
+    pub message: String,
+    /// Components involved, the most relevant first.
+    pub components: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: &'static str, message: String) -> Self {
+        Self { severity, code, message, components: Vec::new() }
+    }
+
+    pub fn with_component(mut self, name: &str) -> Self {
+        self.components.push(name.to_owned());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, Severity};
+
+    #[test]
+    fn test_display() {
+        let diagnostic = Diagnostic::new(Severity::Warning, "some-check", "Something is odd".to_owned())
+            .with_component("a")
+            .with_component("b");
+
+        assert_eq!(diagnostic.to_string(), "warning[some-check]: Something is odd");
+        assert_eq!(diagnostic.components, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_severity_order() {
+        assert!(Severity::Note < Severity::Warning);
+        assert!(Severity::Warning < Severity::Error);
+    }
+}
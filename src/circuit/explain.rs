@@ -0,0 +1,189 @@
+use std::fmt;
+
+use crate::components::{tristate::Tristate, Component, PinNumber, Tick};
+
+use super::netlist::PinRef;
+use super::Circuit;
+
+#[derive(Debug, Clone)]
+pub struct InputRead {
+    pub pin: PinNumber,
+    pub value: Tristate,
+    /// Linked pins driving the input during the tick, with their values.
+    pub sources: Vec<(PinRef, Tristate)>,
+    /// Linked pins only reading, such as other inputs or tri-stated outputs.
+    pub readers: Vec<PinRef>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExplanationStep {
+    pub component: String,
+    pub component_type: String,
+    pub inputs: Vec<InputRead>,
+    pub outputs: Vec<(PinNumber, Tristate)>,
+}
+
+/// Readable trace of one tick: components in evaluation order with the values they read and produced.
+#[derive(Debug, Clone)]
+pub struct TickExplanation {
+    pub tick: Tick,
+    pub steps: Vec<ExplanationStep>,
+}
+
+impl Circuit {
+    /// Runs one tick and explains how every component was evaluated.
+    pub fn simulate_explained(&mut self) -> TickExplanation {
+        self.simulate();
+        self.explain_current_tick()
+    }
+
+    pub fn explain_current_tick(&self) -> TickExplanation {
+        let steps = self
+            .netlist
+            .evaluation_order()
+            .into_iter()
+            .map(|name| ExplanationStep {
+                component: name.to_owned(),
+                component_type: self.netlist.component(name).unwrap().component_type.clone(),
+                inputs: self.netlist.input_pins(name).into_iter().map(|pin| self.explain_input(name, pin)).collect(),
+                outputs: self.netlist.output_pins(name).into_iter().map(|pin| (pin, self.pin_value(name, pin))).collect(),
+            })
+            .collect();
+
+        TickExplanation { tick: self.current_tick, steps }
+    }
+
+    pub(super) fn explain_input(&self, name: &str, pin: PinNumber) -> InputRead {
+        let this = PinRef::new(name, pin);
+        let (drivers, readers): (Vec<&PinRef>, Vec<&PinRef>) =
+            self.netlist.linked_pins(&this).into_iter().filter(|other| **other != this).partition(|other| {
+                self.components.get(&other.component).is_some_and(|component| component.is_driving(other.pin))
+            });
+        let sources: Vec<(PinRef, Tristate)> =
+            drivers.into_iter().map(|driver| (driver.clone(), self.pin_value(&driver.component, driver.pin))).collect();
+
+        // Same aggregation as an input pin: no link leaves it undefined, links to pins which only read it
+        // make it read 0, several drivers go through the policy.
+        let value = match sources[..] {
+            [] if !readers.is_empty() => Tristate::State(false),
+            [] => Tristate::Undefined,
+            [(_, value)] => value,
+            _ => self.conflict_policy.resolve(&sources.iter().map(|(_, value)| *value).collect::<Vec<_>>()).0,
+        };
+
+        InputRead { pin, value, sources, readers: readers.into_iter().cloned().collect() }
+    }
+
+    pub(super) fn pin_value(&self, name: &str, pin: PinNumber) -> Tristate {
+        self.components.get(name).and_then(|component| component.compute(pin).ok()).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for TickExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "tick: {}", self.tick)?;
+
+        for (idx, step) in self.steps.iter().enumerate() {
+            writeln!(f, "{}. {} ({})", idx + 1, step.component, step.component_type)?;
+            for input in step.inputs.iter() {
+                write!(f, "    read  pin {} = {}", input.pin, input.value)?;
+                if input.sources.is_empty() && input.readers.is_empty() {
+                    writeln!(f, " (not linked)")?;
+                } else if input.sources.is_empty() {
+                    let readers: Vec<String> = input.readers.iter().map(PinRef::to_string).collect();
+                    writeln!(f, " (no driver, linked to {})", readers.join(", "))?;
+                } else {
+                    let sources: Vec<String> =
+                        input.sources.iter().map(|(source, value)| format!("{source} = {value}")).collect();
+                    writeln!(f, " <- {}", sources.join(", "))?;
+                }
+            }
+            for (pin, value) in step.outputs.iter() {
+                writeln!(f, "    wrote pin {pin} = {value}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::builder::CircuitBuilder;
+    use crate::circuit::netlist::PinRef;
+    use crate::circuit::Circuit;
+    use crate::components::tristate::Tristate;
+
+    #[test]
+    fn test_explain_and_gate() {
+        let mut circuit = CircuitBuilder::default()
+            .add_component("input", "a")
+            .unwrap()
+            .add_component("input", "b")
+            .unwrap()
+            .add_component("4081", "gate")
+            .unwrap()
+            .add_component("output", "out")
+            .unwrap()
+            .link_components("a", 1, "gate", 1)
+            .unwrap()
+            .link_components("b", 1, "gate", 2)
+            .unwrap()
+            .link_components("gate", 3, "out", 1)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        circuit.set_value("a", "1").unwrap();
+        let explanation = circuit.simulate_explained();
+
+        assert_eq!(explanation.tick.get(), 1);
+        let order: Vec<&str> = explanation.steps.iter().map(|step| step.component.as_str()).collect();
+        assert_eq!(order, vec!["a", "b", "gate", "out"]);
+
+        let gate = &explanation.steps[2];
+        assert_eq!(gate.component_type, "4081");
+        assert_eq!(gate.inputs.len(), 8);
+        assert_eq!(gate.inputs[0].value, Tristate::State(true));
+        assert_eq!(gate.inputs[1].value, Tristate::Undefined);
+        assert_eq!(gate.inputs[2].value, Tristate::Undefined);
+        assert!(gate.inputs[2].sources.is_empty());
+        assert_eq!(gate.outputs[0], (3, Tristate::Undefined));
+
+        let text = explanation.to_string();
+        assert!(text.starts_with("tick: 1\n1. a (input)\n    wrote pin 1 = 1\n"));
+        assert!(text.contains("3. gate (4081)\n    read  pin 1 = 1 <- a:1 = 1\n    read  pin 2 = U <- b:1 = U\n"));
+        assert!(text.contains("    read  pin 5 = U (not linked)\n"));
+        assert!(text.ends_with("4. out (output)\n    read  pin 1 = U <- gate:3 = U\n"));
+    }
+
+    #[test]
+    fn test_explain_matches_pin_reads() {
+        // The data pin of the RAM is not driven while the RAM is not read, and both inputs of the gate only read.
+        let mut circuit: Circuit = "
+            .chipsets:
+            input i
+            4801 ram
+            4081 gate
+            output out
+            .links:
+            i:1 out:1
+            ram:9 out:1
+            gate:1 gate:2
+            gate:3 ram:1
+        "
+        .parse()
+        .unwrap();
+
+        circuit.set_value("i", "1").unwrap();
+        let explanation = circuit.simulate_explained();
+        let step = |name: &str| explanation.steps.iter().find(|step| step.component == name).unwrap();
+
+        let out = &step("out").inputs[0];
+        assert_eq!((out.value, out.readers.clone()), (Tristate::State(true), vec![PinRef::new("ram", 9)]));
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+        assert_eq!(step("gate").inputs[0].value, Tristate::State(false));
+        assert_eq!(step("gate").outputs[0], (3, Tristate::State(false)));
+        assert!(explanation.to_string().contains("    read  pin 1 = 0 (no driver, linked to gate:2)\n"));
+    }
+}
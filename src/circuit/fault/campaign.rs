@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::components::{info::PinDirection, tristate::Tristate};
+
+use crate::circuit::netlist::PinRef;
+use crate::circuit::{Circuit, Mismatch, ParseCircuitError, Stimulus};
+
+#[derive(Debug, Clone)]
+pub enum CampaignError {
+    Circuit(ParseCircuitError),
+    /// The stimulus fails on the circuit without any fault.
+    Reference(Vec<Mismatch>),
+}
+
+impl fmt::Display for CampaignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Circuit(error) => write!(f, "{error}"),
+            Self::Reference(mismatches) => {
+                write!(f, "The stimulus fails without fault:")?;
+                for mismatch in mismatches {
+                    write!(f, "\n  {mismatch}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A pin forced to a constant value.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StuckAt {
+    pub pin: PinRef,
+    pub value: bool,
+}
+
+impl fmt::Display for StuckAt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} stuck-at-{}", self.pin, self.value as u8)
+    }
+}
+
+/// Result of a stuck-at fault campaign: every fault, and whether the stimulus detected it.
+#[derive(Debug, Clone)]
+pub struct CampaignReport {
+    pub results: Vec<(StuckAt, bool)>,
+}
+
+impl CampaignReport {
+    pub fn detected(&self) -> impl Iterator<Item = &StuckAt> {
+        self.results.iter().filter(|(_, detected)| *detected).map(|(fault, _)| fault)
+    }
+
+    pub fn undetected(&self) -> impl Iterator<Item = &StuckAt> {
+        self.results.iter().filter(|(_, detected)| !*detected).map(|(fault, _)| fault)
+    }
+
+    /// Ratio of detected faults, between 0 and 1 (1 if there is no fault to detect).
+    pub fn coverage(&self) -> f64 {
+        if self.results.is_empty() {
+            return 1.0;
+        }
+
+        self.detected().count() as f64 / self.results.len() as f64
+    }
+}
+
+impl fmt::Display for CampaignReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}/{} faults detected ({:.1}%)", self.detected().count(), self.results.len(), self.coverage() * 100.0)?;
+        for fault in self.undetected() {
+            writeln!(f, "  undetected: {fault}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Replays `stimulus` on the circuit described by `content` once per stuck-at-0/1 fault on every used pin,
+/// a fault being detected when at least one expectation of the stimulus fails.
+pub fn campaign(content: &str, stimulus: &Stimulus) -> Result<CampaignReport, CampaignError> {
+    let mut reference: Circuit = content.parse().map_err(CampaignError::Circuit)?;
+    let mismatches = stimulus.run(&mut reference);
+
+    if !mismatches.is_empty() {
+        return Err(CampaignError::Reference(mismatches));
+    }
+
+    let netlist = reference.netlist();
+    let faults = netlist.component_names().into_iter().flat_map(|name| {
+        let pins: Vec<PinRef> = match netlist.component(name).and_then(|component| component.info.as_ref()) {
+            Some(info) => info
+                .pins()
+                .iter()
+                .filter(|pin| pin.direction != PinDirection::Unused)
+                .map(|pin| PinRef::new(name, pin.number))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        pins.into_iter().flat_map(|pin| [false, true].map(|value| StuckAt { pin: pin.clone(), value }))
+    });
+
+    let results = faults
+        .map(|fault| {
+            // Same content as the reference circuit: cannot fail.
+            let mut circuit: Circuit = content.parse().unwrap();
+
+            circuit.stick_pin(&fault.pin.component, fault.pin.pin, Tristate::State(fault.value)).unwrap();
+            let detected = !stimulus.run(&mut circuit).is_empty();
+            (fault, detected)
+        })
+        .collect();
+
+    Ok(CampaignReport { results })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{campaign, CampaignError};
+    use crate::circuit::Stimulus;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 gate
+        output out
+        .links:
+        a:1 gate:1
+        b:1 gate:2
+        gate:3 out:1
+    ";
+
+    #[test]
+    fn test_exhaustive_stimulus_detects_everything_observable() {
+        let stimulus: Stimulus = "
+            a=0\nb=1\nsimulate\n# expect: out=0
+            a=1\nb=0\nsimulate\n# expect: out=0
+            a=1\nb=1\nsimulate\n# expect: out=1
+        "
+        .parse()
+        .unwrap();
+        let report = campaign(CONTENT, &stimulus).unwrap();
+
+        // a, b, out: 1 pin each. gate: 12 pins.
+        assert_eq!(report.results.len(), 2 * 15);
+        let undetected: Vec<String> = report.undetected().map(|fault| fault.to_string()).collect();
+        // Only faults on the unused gates of the chip go unnoticed.
+        assert_eq!(undetected.len(), 18);
+        assert!(undetected.iter().all(|fault| fault.starts_with("gate:")
+            && !fault.starts_with("gate:1 ")
+            && !fault.starts_with("gate:2 ")
+            && !fault.starts_with("gate:3 ")));
+        assert_eq!(report.to_string().lines().next().unwrap(), "12/30 faults detected (40.0%)");
+    }
+
+    #[test]
+    fn test_weak_stimulus() {
+        let stimulus: Stimulus = "a=1\nb=1\nsimulate\n# expect: out=1".parse().unwrap();
+        let report = campaign(CONTENT, &stimulus).unwrap();
+        let detected: Vec<String> = report.detected().map(|fault| fault.to_string()).collect();
+
+        assert_eq!(
+            detected,
+            vec![
+                "a:1 stuck-at-0",
+                "b:1 stuck-at-0",
+                "gate:1 stuck-at-0",
+                "gate:2 stuck-at-0",
+                "gate:3 stuck-at-0",
+                "out:1 stuck-at-0"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failing_reference() {
+        let stimulus: Stimulus = "a=1\nb=1\nsimulate\n# expect: out=0".parse().unwrap();
+
+        assert!(matches!(campaign(CONTENT, &stimulus), Err(CampaignError::Reference(mismatches)) if mismatches.len() == 1));
+    }
+}
@@ -0,0 +1,395 @@
+//! Fault injection: component outputs or pins forced to a value, regardless of the circuit.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::rc::{Rc, Weak};
+
+use crate::components::info::PinDirection;
+use crate::components::{
+    tristate::Tristate, BusInput, BusOutput, Component, FileDevice, Input, InvalidPin, Keyboard, Memory, Monitor, Output,
+    PinNumber, Terminal, Tick,
+};
+
+use super::netlist::PinRef;
+use super::Circuit;
+
+mod campaign;
+mod resolution;
+
+pub use campaign::{campaign, CampaignError, CampaignReport, StuckAt};
+pub use resolution::Substitution;
+
+pub(super) use resolution::mix;
+use resolution::Resolution;
+
+#[derive(Debug, Clone)]
+pub enum FaultError<'a> {
+    UnknownComponent(&'a str),
+    UnknownPin(&'a str, PinNumber),
+}
+
+type Faults = Rc<RefCell<HashMap<PinNumber, Tristate>>>;
+type SharedResolution = Rc<RefCell<Option<Resolution>>>;
+
+/// Component wrapper installed by the circuit builder, forwarding everything to the real component
+/// unless a fault is injected.
+pub(in crate::circuit) struct FaultableComponent {
+    component: Rc<dyn Component>,
+    /// Pins affected by a component-wide fault, `None` if unknown (every pin).
+    output_pins: Option<Vec<PinNumber>>,
+    faults: Faults,
+    /// Links to other components, through which the wrapped component reads its inputs.
+    taps: RefCell<Vec<Rc<InputTap>>>,
+    /// Resolution of the undefined values read through the taps, if enabled.
+    resolution: SharedResolution,
+}
+
+impl FaultableComponent {
+    pub fn new(component: Rc<dyn Component>, output_pins: Option<Vec<PinNumber>>) -> Self {
+        Self { component, output_pins, faults: Default::default(), taps: Default::default(), resolution: Default::default() }
+    }
+
+    fn stick_pin(&self, pin: PinNumber, value: Tristate) {
+        self.faults.borrow_mut().insert(pin, value);
+    }
+
+    fn force_outputs(&self, value: Tristate) {
+        let mut faults = self.faults.borrow_mut();
+
+        match self.output_pins.as_ref() {
+            Some(pins) => faults.extend(pins.iter().map(|&pin| (pin, value))),
+            None => {
+                faults.insert(0, value);
+            }
+        }
+    }
+
+    fn clear_faults(&self) {
+        self.faults.borrow_mut().clear();
+    }
+
+    fn is_faulty(&self) -> bool {
+        !self.faults.borrow().is_empty()
+    }
+
+    fn fault_on(&self, pin: PinNumber) -> Option<Tristate> {
+        let faults = self.faults.borrow();
+
+        match self.output_pins {
+            Some(_) => faults.get(&pin).copied(),
+            // Unknown pinout: a component-wide fault is stored on pin 0.
+            None => faults.get(&pin).or_else(|| faults.get(&0)).copied(),
+        }
+    }
+}
+
+impl Component for FaultableComponent {
+    fn simulate(&self, tick: Tick) {
+        // Dropping a tap whose driver is gone lets the input pin prune the dangling link.
+        self.taps.borrow_mut().retain(|tap| tap.source.strong_count() > 0);
+        self.component.simulate(tick)
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        let value = self.component.compute(pin)?;
+
+        Ok(self.fault_on(pin).unwrap_or(value))
+    }
+
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        let tap: Rc<InputTap> = Rc::new(InputTap {
+            source: other_component,
+            pin,
+            faults: self.faults.clone(),
+            resolution: self.resolution.clone(),
+            tick: Default::default(),
+        });
+
+        self.component.set_link(pin, Rc::downgrade(&tap) as Weak<dyn Component>, other_pin)?;
+        self.taps.borrow_mut().push(tap);
+        Ok(())
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.fault_on(pin).is_some() || self.component.is_driving(pin)
+    }
+
+    // A stuck bus pin carries undefined bits.
+    fn compute_bus(&self, pin: PinNumber) -> Option<Vec<Tristate>> {
+        match self.fault_on(pin) {
+            Some(_) => None,
+            None => self.component.compute_bus(pin),
+        }
+    }
+
+    fn as_input(&self) -> Option<&dyn Input> {
+        self.component.as_input()
+    }
+
+    fn as_output(&self) -> Option<&dyn Output> {
+        self.component.as_output()
+    }
+
+    fn as_bus_input(&self) -> Option<&dyn BusInput> {
+        self.component.as_bus_input()
+    }
+
+    fn as_bus_output(&self) -> Option<&dyn BusOutput> {
+        self.component.as_bus_output()
+    }
+
+    fn as_keyboard(&self) -> Option<&dyn Keyboard> {
+        self.component.as_keyboard()
+    }
+
+    fn as_terminal(&self) -> Option<&dyn Terminal> {
+        self.component.as_terminal()
+    }
+
+    fn as_file_device(&self) -> Option<&dyn FileDevice> {
+        self.component.as_file_device()
+    }
+
+    #[cfg(feature = "debug-internals")]
+    fn as_composite(&self) -> Option<&dyn crate::components::composite::Composite> {
+        self.component.as_composite()
+    }
+
+    fn as_memory(&self) -> Option<&dyn Memory> {
+        self.component.as_memory()
+    }
+
+    fn as_monitor(&self) -> Option<&dyn Monitor> {
+        self.component.as_monitor()
+    }
+}
+
+/// Stands between an input pin and the component driving it, so that a fault on the pin can hide the driver.
+struct InputTap {
+    source: Weak<dyn Component>,
+    /// Input pin of the wrapped component.
+    pin: PinNumber,
+    faults: Faults,
+    resolution: SharedResolution,
+    /// Tick being read, given by `simulate()` before each `compute()`.
+    tick: Cell<Tick>,
+}
+
+impl Component for InputTap {
+    fn simulate(&self, tick: Tick) {
+        self.tick.set(tick);
+        if let Some(source) = self.source.upgrade() {
+            source.simulate(tick);
+        }
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        if let Some(&value) = self.faults.borrow().get(&self.pin) {
+            return Ok(value);
+        }
+
+        let value = match self.source.upgrade() {
+            Some(source) => source.compute(pin)?,
+            None => Tristate::Undefined,
+        };
+
+        match self.resolution.borrow_mut().as_mut() {
+            Some(resolution) => Ok(resolution.resolve(self.pin, self.tick.get(), value)),
+            None => Ok(value),
+        }
+    }
+
+    fn set_link(&self, _pin: PinNumber, _other_component: Weak<dyn Component>, _other_pin: PinNumber) -> Result<(), InvalidPin> {
+        Ok(())
+    }
+
+    // A stuck pin reads its fault as if it was driven.
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.faults.borrow().contains_key(&self.pin) || self.source.upgrade().is_none_or(|source| source.is_driving(pin))
+    }
+
+    fn compute_bus(&self, pin: PinNumber) -> Option<Vec<Tristate>> {
+        if self.faults.borrow().contains_key(&self.pin) {
+            return None;
+        }
+        self.source.upgrade()?.compute_bus(pin)
+    }
+}
+
+impl Circuit {
+    /// Forces every output of `name` to Undefined, as if the component was missing.
+    pub fn disable_component<'a>(&mut self, name: &'a str) -> Result<(), FaultError<'a>> {
+        self.stick_component(name, Tristate::Undefined)
+    }
+
+    /// Forces every output of `name` to `value` until `enable_component()` is called.
+    pub fn stick_component<'a>(&mut self, name: &'a str, value: Tristate) -> Result<(), FaultError<'a>> {
+        let component = self.faultable(name)?;
+
+        component.clear_faults();
+        component.force_outputs(value);
+        Ok(())
+    }
+
+    /// Forces a single pin of `name` to `value`: an output pin drives `value`, an input pin reads `value`.
+    pub fn stick_pin<'a>(&mut self, name: &'a str, pin: PinNumber, value: Tristate) -> Result<(), FaultError<'a>> {
+        let component = self.faultable(name)?;
+        let info = self.netlist.component(name).and_then(|component| component.info.as_ref());
+
+        if info.is_some_and(|info| info.pin(pin).is_none_or(|pin| pin.direction == PinDirection::Unused)) {
+            return Err(FaultError::UnknownPin(name, pin));
+        }
+
+        component.stick_pin(pin, value);
+        Ok(())
+    }
+
+    /// Removes the faults injected on `name`.
+    pub fn enable_component<'a>(&mut self, name: &'a str) -> Result<(), FaultError<'a>> {
+        self.faultable(name)?.clear_faults();
+        Ok(())
+    }
+
+    /// Names of the components with an injected fault, sorted.
+    pub fn faulty_components(&self) -> Vec<&str> {
+        let mut names: Vec<&str> =
+            self.components.iter().filter(|(_, component)| component.is_faulty()).map(|(name, _)| name.as_str()).collect();
+
+        names.sort();
+        names
+    }
+
+    /// Output components which can be reached from the outputs of `name`, sorted.
+    pub fn affected_outputs<'a>(&self, name: &'a str) -> Result<Vec<&str>, FaultError<'a>> {
+        if !self.components.contains_key(name) {
+            return Err(FaultError::UnknownComponent(name));
+        }
+
+        let netlist = &self.netlist;
+        let mut affected: Vec<&str> = Vec::new();
+        let mut visited: HashSet<PinRef> = HashSet::new();
+        let mut pending: Vec<PinRef> = netlist.output_pins(name).into_iter().map(|pin| PinRef::new(name, pin)).collect();
+
+        while let Some(driver) = pending.pop() {
+            for reader in netlist.linked_pins(&driver) {
+                if netlist.pin_direction(reader) == Some(PinDirection::Output) || !visited.insert(reader.clone()) {
+                    continue;
+                }
+                let Some((component_name, component)) = self.components.get_key_value(&reader.component) else {
+                    continue;
+                };
+                if component.as_output().is_some() {
+                    affected.push(component_name);
+                }
+
+                let info = netlist.component(component_name).and_then(|component| component.info.as_ref());
+                for output in netlist.output_pins(component_name) {
+                    let depends = info.is_some_and(|info| info.sequential || info.dependencies(output).contains(&reader.pin));
+                    if depends {
+                        pending.push(PinRef::new(component_name, output));
+                    }
+                }
+            }
+        }
+
+        affected.sort();
+        affected.dedup();
+        Ok(affected)
+    }
+
+    fn faultable<'a>(&self, name: &'a str) -> Result<&FaultableComponent, FaultError<'a>> {
+        self.components.get(name).map(Rc::as_ref).ok_or(FaultError::UnknownComponent(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FaultError;
+    use crate::circuit::Circuit;
+    use crate::components::tristate::Tristate;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 and
+        4069 not
+        output x
+        output y
+        output z
+        .links:
+        a:1 and:1
+        b:1 and:2
+        and:3 x:1
+        and:3 not:1
+        not:2 y:1
+        b:1 z:1
+    ";
+
+    fn build_circuit() -> Circuit {
+        let circuit: Circuit = CONTENT.parse().unwrap();
+
+        circuit.set_value("a", "1").unwrap();
+        circuit.set_value("b", "1").unwrap();
+        circuit
+    }
+
+    #[test]
+    fn test_disable_component() {
+        let mut circuit = build_circuit();
+
+        circuit.disable_component("and").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("x").unwrap(), "U");
+        assert_eq!(circuit.get_output("y").unwrap(), "U");
+        assert_eq!(circuit.get_output("z").unwrap(), "1");
+        assert_eq!(circuit.faulty_components(), vec!["and"]);
+
+        circuit.enable_component("and").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("x").unwrap(), "1");
+        assert_eq!(circuit.get_output("y").unwrap(), "0");
+        assert!(circuit.faulty_components().is_empty());
+    }
+
+    #[test]
+    fn test_stuck_value() {
+        let mut circuit = build_circuit();
+
+        circuit.stick_component("and", Tristate::State(false)).unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("x").unwrap(), "0");
+        assert_eq!(circuit.get_output("y").unwrap(), "1");
+
+        // Stuck input components keep their value whatever is set.
+        circuit.enable_component("and").unwrap();
+        circuit.stick_component("b", Tristate::State(false)).unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("x").unwrap(), "0");
+        assert_eq!(circuit.get_output("z").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_affected_outputs() {
+        let circuit = build_circuit();
+
+        assert_eq!(circuit.affected_outputs("and").unwrap(), vec!["x", "y"]);
+        assert_eq!(circuit.affected_outputs("not").unwrap(), vec!["y"]);
+        assert_eq!(circuit.affected_outputs("b").unwrap(), vec!["x", "y", "z"]);
+        assert!(circuit.affected_outputs("x").unwrap().is_empty());
+        assert!(matches!(circuit.affected_outputs("nope"), Err(FaultError::UnknownComponent("nope"))));
+        assert!(matches!(build_circuit().disable_component("nope"), Err(FaultError::UnknownComponent("nope"))));
+        assert!(matches!(build_circuit().stick_pin("and", 7, Tristate::State(true)), Err(FaultError::UnknownPin("and", 7))));
+    }
+
+    #[test]
+    fn test_stick_input_pin() {
+        let mut circuit = build_circuit();
+
+        // Only the reading side is affected: 'z' still sees b=1.
+        circuit.stick_pin("and", 2, Tristate::State(false)).unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("x").unwrap(), "0");
+        assert_eq!(circuit.get_output("z").unwrap(), "1");
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::components::{tristate::Tristate, PinNumber, Tick};
+
+use crate::circuit::netlist::PinRef;
+use crate::circuit::Circuit;
+
+/// Undefined value read by an input pin and replaced by a pseudo-random state.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Substitution {
+    pub tick: Tick,
+    /// Input pin which read the undefined value.
+    pub pin: PinRef,
+    pub value: bool,
+}
+
+impl fmt::Display for Substitution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tick {}: {} read U as {}", self.tick, self.pin, Tristate::State(self.value))
+    }
+}
+
+/// Seeded resolution of the undefined values read by the input pins of a component.
+pub(super) struct Resolution {
+    seed: u64,
+    /// Name of the component, so that each pin draws its own states.
+    component: String,
+    substitutions: BTreeMap<(Tick, PinNumber), bool>,
+}
+
+impl Resolution {
+    pub fn new(seed: u64, component: &str) -> Self {
+        Self { seed, component: component.to_owned(), substitutions: BTreeMap::new() }
+    }
+
+    /// Value read by `pin` at `tick`, drawn from the seed if `value` is undefined.
+    ///
+    /// The state only depends on the seed, the pin and the tick: it does not change with the evaluation order,
+    /// and a pin reading the same undefined value twice in a tick gets the same state.
+    pub fn resolve(&mut self, pin: PinNumber, tick: Tick, value: Tristate) -> Tristate {
+        if value != Tristate::Undefined {
+            return value;
+        }
+
+        let hash = self.component.bytes().fold(mix(self.seed), |hash, byte| mix(hash ^ u64::from(byte)));
+        let state = mix(mix(hash ^ pin as u64) ^ tick.get()) & 1 == 1;
+
+        self.substitutions.insert((tick, pin), state);
+        Tristate::State(state)
+    }
+}
+
+/// SplitMix64 finalizer.
+pub(in crate::circuit) fn mix(value: u64) -> u64 {
+    let value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    let value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    value ^ (value >> 31)
+}
+
+impl Circuit {
+    /// With a seed, every undefined value read by a component through a link is replaced by a pseudo-random
+    /// state drawn from the seed, the same seed always giving the same states. Comparing runs with different
+    /// seeds tells whether the behavior of the circuit depends on uninitialized state.
+    ///
+    /// Without a seed, undefined values are read as is again. Either way, the recorded substitutions are forgotten.
+    pub fn resolve_undefined(&mut self, seed: Option<u64>) {
+        for (name, component) in self.components.iter() {
+            *component.resolution.borrow_mut() = seed.map(|seed| Resolution::new(seed, name));
+        }
+    }
+
+    /// Undefined values replaced since `resolve_undefined()` was called, sorted by tick then pin.
+    pub fn substitutions(&self) -> Vec<Substitution> {
+        let mut substitutions: Vec<Substitution> = Vec::new();
+
+        for (name, component) in self.components.iter() {
+            if let Some(resolution) = component.resolution.borrow().as_ref() {
+                substitutions.extend(resolution.substitutions.iter().map(|(&(tick, pin), &value)| Substitution {
+                    tick,
+                    pin: PinRef::new(name, pin),
+                    value,
+                }));
+            }
+        }
+
+        substitutions.sort_by(|left, right| {
+            (left.tick, &left.pin.component, left.pin.pin).cmp(&(right.tick, &right.pin.component, right.pin.pin))
+        });
+        substitutions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+    use crate::components::tristate::Tristate;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 gate
+        output out
+        .links:
+        a:1 gate:1
+        b:1 gate:2
+        gate:3 out:1
+    ";
+
+    fn run(seed: Option<u64>) -> (Circuit, String) {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        circuit.resolve_undefined(seed);
+        circuit.set_value("a", "1").unwrap();
+        let outputs = (0..8)
+            .map(|_| {
+                circuit.simulate();
+                circuit.get_output("out").unwrap()
+            })
+            .collect();
+
+        (circuit, outputs)
+    }
+
+    #[test]
+    fn test_resolution() {
+        let (circuit, outputs) = run(None);
+        assert_eq!(outputs, "UUUUUUUU");
+        assert!(circuit.substitutions().is_empty());
+
+        let (circuit, outputs) = run(Some(42));
+        assert_eq!(run(Some(42)).1, outputs);
+        assert!(!outputs.contains('U'));
+        assert!((0..16).any(|seed| run(Some(seed)).1 != outputs));
+
+        // Only the gate reads an undefined value, once per tick: its output follows the state drawn for b.
+        let substitutions = circuit.substitutions();
+        let drawn: String = substitutions.iter().map(|substitution| Tristate::State(substitution.value).to_string()).collect();
+        assert_eq!(drawn, outputs);
+        assert!(substitutions.iter().all(|substitution| substitution.pin.to_string() == "gate:2"));
+        assert_eq!(substitutions[2].to_string(), format!("tick 3: gate:2 read U as {}", &outputs[2..3]));
+    }
+
+    #[test]
+    fn test_disable() {
+        let (mut circuit, _) = run(Some(1));
+
+        circuit.resolve_undefined(None);
+        assert!(circuit.substitutions().is_empty());
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "U");
+    }
+}
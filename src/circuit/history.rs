@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use crate::components::{tristate::Tristate, Tick};
+
+use super::Circuit;
+
+#[derive(Debug, Clone)]
+pub enum HistoryError<'a> {
+    UnknownSignal(&'a str),
+}
+
+/// Per-tick record of every input and output value of a circuit.
+#[derive(Debug, Default, Clone)]
+pub struct History {
+    ticks: Vec<Tick>,
+    signals: HashMap<String, Vec<Tristate>>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records the current value of every input and output of `circuit`.
+    pub fn record(&mut self, circuit: &Circuit) {
+        let recorded = self.ticks.len();
+
+        for (name, value) in circuit.signals() {
+            let values = self.signals.entry(name.to_owned()).or_default();
+
+            values.resize(recorded, Tristate::Undefined);
+            values.push(value);
+        }
+        self.ticks.push(circuit.current_tick());
+
+        for values in self.signals.values_mut() {
+            values.resize(self.ticks.len(), Tristate::Undefined);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    pub fn ticks(&self) -> &[Tick] {
+        &self.ticks
+    }
+
+    pub fn values(&self, name: &str) -> Option<&[Tristate]> {
+        self.signals.get(name).map(Vec::as_slice)
+    }
+
+    /// Renders the recorded values of `names` as a text waveform, one line per signal.
+    ///
+    /// Each tick takes two columns: `‾` is high, `_` is low and `x` is undefined,
+    /// with `/` and `\` marking rising and falling edges.
+    pub fn render_ascii<'a>(&self, names: &[&'a str]) -> Result<String, HistoryError<'a>> {
+        let name_width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+        let mut output = String::new();
+
+        for &name in names {
+            let values = self.values(name).ok_or(HistoryError::UnknownSignal(name))?;
+
+            output.push_str(&format!("{name:>name_width$} |"));
+            let mut previous: Option<Tristate> = None;
+            for &value in values {
+                let level = Self::level_char(value);
+                let edge = match (previous, value) {
+                    (Some(Tristate::State(false)), Tristate::State(true)) => '/',
+                    (Some(Tristate::State(true)), Tristate::State(false)) => '\\',
+                    _ => level,
+                };
+
+                output.push(edge);
+                output.push(level);
+                previous = Some(value);
+            }
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    #[inline]
+    fn level_char(value: Tristate) -> char {
+        match value {
+            Tristate::State(true) => '‾',
+            Tristate::State(false) => '_',
+            Tristate::Undefined => 'x',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{History, HistoryError};
+    use crate::circuit::builder::CircuitBuilder;
+    use crate::components::{tristate::Tristate, Tick};
+
+    fn build_circuit() -> crate::Circuit {
+        CircuitBuilder::default()
+            .add_component("clock", "clk")
+            .unwrap()
+            .add_component("output", "q")
+            .unwrap()
+            .link_components("clk", 1, "q", 1)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_record_all_signals() {
+        let mut circuit = build_circuit();
+        let mut history = History::new();
+
+        history.record(&circuit);
+        circuit.set_value("clk", "1").unwrap();
+        circuit.simulate();
+        history.record(&circuit);
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.ticks(), &[Tick::ZERO, Tick::new(1)]);
+        assert_eq!(history.values("clk").unwrap(), &[Tristate::Undefined, Tristate::State(true)]);
+        assert_eq!(history.values("q").unwrap(), &[Tristate::Undefined, Tristate::State(true)]);
+        assert!(history.values("unknown").is_none());
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let mut circuit = build_circuit();
+        let mut history = History::new();
+
+        circuit.set_value("clk", "1").unwrap();
+        for _ in 0..4 {
+            circuit.simulate();
+            history.record(&circuit);
+        }
+
+        assert_eq!(history.render_ascii(&["clk", "q"]).unwrap(), "clk |‾‾\\_/‾\\_\n  q |‾‾\\_/‾\\_\n");
+    }
+
+    #[test]
+    fn test_render_ascii_undefined() {
+        let circuit = build_circuit();
+        let mut history = History::new();
+
+        history.record(&circuit);
+
+        assert_eq!(history.render_ascii(&["q"]).unwrap(), "q |xx\n");
+    }
+
+    #[test]
+    fn test_render_ascii_unknown_signal() {
+        let history = History::new();
+
+        assert!(matches!(history.render_ascii(&["nope"]), Err(HistoryError::UnknownSignal("nope"))));
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+
+use super::parser::{Declaration, Parser};
+use super::{Instruction, ParseCircuitError, SyntaxErrorKind};
+
+/// Position in a document, the line numbered from 1 as in parse errors and the column being a byte offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TextPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl TextPosition {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// Replacement of the text between `start` and `end` by `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start: TextPosition,
+    pub end: TextPosition,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new(start: TextPosition, end: TextPosition, text: &str) -> Self {
+        Self { start, end, text: text.to_owned() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidEdit {
+    pub start: TextPosition,
+    pub end: TextPosition,
+}
+
+impl fmt::Display for InvalidEdit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid edit range {}:{} to {}:{}", self.start.line, self.start.column, self.end.line, self.end.column)
+    }
+}
+
+enum ParsedLine {
+    Blank,
+    Header(Declaration),
+    Instruction(Instruction),
+    Error(SyntaxErrorKind),
+}
+
+struct SourceLine {
+    content: String,
+    /// Declaration in effect after this line.
+    declaration: Option<Declaration>,
+    parsed: ParsedLine,
+}
+
+impl SourceLine {
+    fn parse(content: &str, previous: Option<Declaration>) -> Self {
+        let stripped = Parser::strip_comment(content);
+        let parsed = if stripped.is_empty() {
+            ParsedLine::Blank
+        } else if let Some(declaration) = Declaration::from_header(stripped) {
+            ParsedLine::Header(declaration)
+        } else {
+            match Parser::parse_instruction(previous, stripped) {
+                Ok(instruction) => ParsedLine::Instruction(instruction),
+                Err(kind) => ParsedLine::Error(kind),
+            }
+        };
+        let declaration = match parsed {
+            ParsedLine::Header(declaration) => Some(declaration),
+            _ => previous,
+        };
+
+        Self { content: content.to_owned(), declaration, parsed }
+    }
+}
+
+/// Parser keeping the instructions of a `.nts` document up to date as it is edited.
+///
+/// An edit only parses again the lines it touches, and the following lines whose declaration it changed.
+/// Unlike `parse_instructions()`, errors do not stop the parsing: every erroneous line is reported by
+/// `diagnostics()` and the other lines still give their instruction.
+pub struct IncrementalParser {
+    lines: Vec<SourceLine>,
+}
+
+impl IncrementalParser {
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<SourceLine> = Vec::new();
+
+        for content in text.split('\n') {
+            let previous = lines.last().and_then(|line| line.declaration);
+            lines.push(SourceLine::parse(content, previous));
+        }
+
+        Self { lines }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.iter().map(|line| line.content.as_str()).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Applies `edit`, returning the lines which were parsed again.
+    pub fn apply_edit(&mut self, edit: &TextEdit) -> Result<Range<usize>, InvalidEdit> {
+        let (start, end) = (edit.start, edit.end);
+        let invalid = || InvalidEdit { start, end };
+        let content = |position: TextPosition| {
+            let line = position.line.checked_sub(1).and_then(|index| self.lines.get(index))?;
+            line.content.is_char_boundary(position.column).then_some(&line.content)
+        };
+
+        let (Some(first), Some(last)) = (content(start), content(end)) else {
+            return Err(invalid());
+        };
+        if end < start {
+            return Err(invalid());
+        }
+
+        let replacement = format!("{}{}{}", &first[..start.column], edit.text, &last[end.column..]);
+        let (start_index, end_index) = (start.line - 1, end.line);
+        let mut declaration = self.lines[..start_index].last().and_then(|line| line.declaration);
+        let mut parsed: Vec<SourceLine> = Vec::new();
+
+        for content in replacement.split('\n') {
+            let line = SourceLine::parse(content, declaration);
+            declaration = line.declaration;
+            parsed.push(line);
+        }
+
+        let nb_parsed = parsed.len();
+        self.lines.splice(start_index..end_index, parsed);
+
+        // The following lines depend on the edit until a header, or a line already in the right declaration.
+        let mut index = start_index + nb_parsed;
+        while let Some(line) = self.lines.get(index) {
+            if matches!(line.parsed, ParsedLine::Header(_)) || line.declaration == declaration {
+                break;
+            }
+            self.lines[index] = SourceLine::parse(&line.content, declaration);
+            index += 1;
+        }
+
+        Ok(start.line..index + 1)
+    }
+
+    /// Instructions of the document with their line, erroneous lines left out.
+    pub fn instructions(&self) -> impl Iterator<Item = (usize, &Instruction)> + Clone {
+        self.lines.iter().enumerate().filter_map(|(index, line)| match &line.parsed {
+            ParsedLine::Instruction(instruction) => Some((index + 1, instruction)),
+            _ => None,
+        })
+    }
+
+    /// Syntax errors of the document, sorted by line.
+    ///
+    /// They are gathered from the parsed lines, which are not read again.
+    pub fn diagnostics(&self) -> Vec<ParseCircuitError> {
+        let mut errors: Vec<(usize, SyntaxErrorKind)> = Vec::new();
+        let mut already_declared: HashSet<Declaration> = HashSet::new();
+
+        for (index, line) in self.lines.iter().enumerate() {
+            let index = index + 1;
+
+            match &line.parsed {
+                ParsedLine::Header(declaration) => {
+                    if already_declared.is_empty() && *declaration != Declaration::Chipsets {
+                        errors.push((index, SyntaxErrorKind::FirstDeclarationMismatch));
+                    } else if already_declared.contains(declaration) {
+                        let declaration = Parser::strip_comment(&line.content).to_owned();
+                        errors.push((index, SyntaxErrorKind::DeclarationDuplicate { declaration }));
+                    }
+                    already_declared.insert(*declaration);
+                }
+                ParsedLine::Error(kind) => errors.push((index, kind.clone())),
+                ParsedLine::Blank | ParsedLine::Instruction(_) => (),
+            }
+        }
+
+        if already_declared.is_empty() {
+            errors.push((0, SyntaxErrorKind::Empty));
+        }
+        errors.extend(Parser::net_label_errors(self.instructions()));
+        errors.sort_by_key(|(line, _)| *line);
+
+        errors.into_iter().map(|(line, kind)| ParseCircuitError::Syntax { line, kind }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IncrementalParser, InvalidEdit, TextEdit, TextPosition};
+    use crate::circuit::{parse_instructions, Instruction};
+
+    const CONTENT: &str = ".chipsets:
+input a
+4069 gate
+output out
+.links:
+a:1 gate:1
+gate:2 out:1
+";
+
+    fn edit(parser: &mut IncrementalParser, start: (usize, usize), end: (usize, usize), text: &str) -> (usize, usize) {
+        let range = parser
+            .apply_edit(&TextEdit::new(TextPosition::new(start.0, start.1), TextPosition::new(end.0, end.1), text))
+            .unwrap();
+
+        (range.start, range.end)
+    }
+
+    fn diagnostics(parser: &IncrementalParser) -> Vec<String> {
+        parser.diagnostics().iter().map(ToString::to_string).collect()
+    }
+
+    fn assert_in_sync(parser: &IncrementalParser) {
+        let instructions: Vec<Instruction> = parser.instructions().map(|(_, instruction)| instruction.clone()).collect();
+
+        assert_eq!(instructions, parse_instructions(&parser.text()).unwrap());
+    }
+
+    #[test]
+    fn test_edits() {
+        let mut parser = IncrementalParser::new(CONTENT);
+
+        assert!(parser.diagnostics().is_empty());
+        assert_in_sync(&parser);
+
+        // Renaming the gate type only parses its line.
+        assert_eq!(edit(&mut parser, (3, 0), (3, 4), "4081"), (3, 4));
+        assert!(
+            matches!(parser.instructions().nth(1), Some((3, Instruction::AddComponent { component_type, .. })) if component_type == "4081")
+        );
+
+        // Inserting lines parses them alone.
+        assert_eq!(edit(&mut parser, (4, 10), (4, 10), "\ninput b # second input\n"), (4, 7));
+        assert_eq!(edit(&mut parser, (10, 0), (10, 0), "b:1 gate:2\n"), (10, 12));
+        assert!(parser.diagnostics().is_empty());
+        assert_in_sync(&parser);
+
+        // Without the chipsets header, the lines up to the links header are parsed again.
+        assert_eq!(edit(&mut parser, (1, 0), (2, 0), ""), (1, 6));
+        assert_eq!(diagnostics(&parser).len(), 5);
+        assert_eq!(edit(&mut parser, (1, 0), (1, 0), ".chipsets:\n"), (1, 7));
+        assert!(parser.diagnostics().is_empty());
+        assert_in_sync(&parser);
+        assert_eq!(
+            parser.text(),
+            CONTENT.replace("4069", "4081").replace("output out\n", "output out\ninput b # second input\n\n") + "b:1 gate:2\n"
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut parser = IncrementalParser::new("");
+
+        assert_eq!(diagnostics(&parser), vec!["line 0: Syntax error: There is no instructions inside content"]);
+        edit(&mut parser, (1, 0), (1, 0), "input a\n.links:\n.chipsets:\n.links:\nn:1 x:1\nlabel\nx:a y:1");
+        assert_eq!(
+            diagnostics(&parser),
+            vec![
+                "line 1: Syntax error: The first instruction must be the chipsets declaration",
+                "line 2: Syntax error: The first instruction must be the chipsets declaration",
+                "line 4: Syntax error: Redeclaration of \".links:\"",
+                "line 6: Syntax error: Link declaration must respect this form: name1:pin1 name2:pin2",
+                "line 7: Syntax error: \"a\" is not a valid pin number",
+            ]
+        );
+        assert_eq!(parser.instructions().map(|(line, _)| line).collect::<Vec<_>>(), vec![5]);
+
+        let invalid = TextEdit::new(TextPosition::new(2, 3), TextPosition::new(9, 0), "");
+        assert_eq!(parser.apply_edit(&invalid), Err(InvalidEdit { start: invalid.start, end: invalid.end }));
+        let reversed = TextEdit::new(TextPosition::new(2, 3), TextPosition::new(1, 0), "");
+        assert!(parser.apply_edit(&reversed).is_err());
+        assert!(parser.apply_edit(&TextEdit::new(TextPosition::new(0, 0), TextPosition::new(1, 0), "")).is_err());
+    }
+}
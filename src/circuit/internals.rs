@@ -0,0 +1,101 @@
+use crate::components::composite::{Composite, GateProbe, InternalGate};
+use crate::components::{tristate::Tristate, Component};
+
+use super::Circuit;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InternalGateError<'a> {
+    UnknownComponent(&'a str),
+    /// The component is not made of gates which can be inspected.
+    NotComposite(&'a str),
+    UnknownGate(&'a str, usize),
+}
+
+impl Circuit {
+    /// Gates inside the composite component `name`, e.g. the four NAND gates of a 4011.
+    pub fn internal_gates<'a>(&self, name: &'a str) -> Result<Vec<InternalGate>, InternalGateError<'a>> {
+        Ok(self.composite(name)?.internal_gates())
+    }
+
+    /// Inputs and output of gate `index` of `name`, as of the last tick.
+    pub fn probe_gate<'a>(&self, name: &'a str, index: usize) -> Result<GateProbe, InternalGateError<'a>> {
+        self.composite(name)?.probe_gate(index).ok_or(InternalGateError::UnknownGate(name, index))
+    }
+
+    /// Forces the value driven by gate `index` of `name`, read as is by the components linked to its output,
+    /// or releases it with `None`. Unlike `stick_pin()`, the gate keeps computing its own value, which
+    /// `probe_gate()` still gives.
+    pub fn force_gate<'a>(&mut self, name: &'a str, index: usize, value: Option<Tristate>) -> Result<(), InternalGateError<'a>> {
+        self.composite(name)?.force_gate(index, value).then_some(()).ok_or(InternalGateError::UnknownGate(name, index))
+    }
+
+    fn composite<'a>(&self, name: &'a str) -> Result<&dyn Composite, InternalGateError<'a>> {
+        self.components
+            .get(name)
+            .ok_or(InternalGateError::UnknownComponent(name))?
+            .as_composite()
+            .ok_or(InternalGateError::NotComposite(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InternalGateError;
+    use crate::circuit::Circuit;
+    use crate::components::composite::{GateProbe, InternalGate};
+    use crate::components::tristate::Tristate;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4011 nand
+        output out
+        .links:
+        a:1 nand:8
+        b:1 nand:9
+        nand:10 nand:12
+        nand:10 nand:13
+        nand:11 out:1
+    ";
+
+    #[test]
+    fn test_inspect_gates() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        let gates = circuit.internal_gates("nand").unwrap();
+
+        assert_eq!(gates.len(), 4);
+        assert_eq!(gates[2], InternalGate { kind: "NAND", inputs: vec![8, 9], output: 10 });
+        assert_eq!(circuit.internal_gates("a"), Err(InternalGateError::NotComposite("a")));
+        assert_eq!(circuit.internal_gates("c"), Err(InternalGateError::UnknownComponent("c")));
+
+        // NAND of a NAND: the chip is an AND gate.
+        circuit.set_value("a", "1").unwrap();
+        circuit.set_value("b", "1").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+
+        let (low, high) = (Tristate::State(false), Tristate::State(true));
+        assert_eq!(circuit.probe_gate("nand", 2), Ok(GateProbe { inputs: vec![high, high], output: low, forced: None }));
+        assert_eq!(circuit.probe_gate("nand", 3), Ok(GateProbe { inputs: vec![low, low], output: high, forced: None }));
+        assert_eq!(circuit.probe_gate("nand", 4), Err(InternalGateError::UnknownGate("nand", 4)));
+    }
+
+    #[test]
+    fn test_force_gate() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        let (low, high) = (Tristate::State(false), Tristate::State(true));
+
+        circuit.set_value("a", "0").unwrap();
+        circuit.set_value("b", "0").unwrap();
+        circuit.force_gate("nand", 2, Some(low)).unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+        assert_eq!(circuit.probe_gate("nand", 2).unwrap(), GateProbe { inputs: vec![low, low], output: high, forced: Some(low) });
+
+        circuit.force_gate("nand", 2, None).unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "0");
+        assert_eq!(circuit.force_gate("nand", 7, None), Err(InternalGateError::UnknownGate("nand", 7)));
+    }
+}
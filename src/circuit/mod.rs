@@ -1,33 +1,256 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
-use super::components::{tristate::Tristate, Component, Tick};
+use super::components::{
+    factory::ComponentFactory, single_pin::clock_component::ClockWaveform, tristate::Tristate, Component, Tick,
+};
+use super::pin;
 
+pub mod analysis;
+mod assertions;
 mod builder;
+mod bulk;
+mod bus;
+mod checks;
+mod conflicts;
+mod csv_oracle;
+mod determinism;
+mod devices;
+mod diagnostics;
+mod explain;
+pub mod fault;
+mod history;
+mod incremental;
+#[cfg(feature = "debug-internals")]
+mod internals;
+mod netlist;
+pub mod optimize;
 mod parser;
+mod query;
+mod simulator;
+mod stimulus;
+mod subcircuit;
+pub mod synthesize;
+mod testbench;
+mod truth_table;
+mod undefined_watch;
+mod user_data;
 
-pub use parser::{BuildErrorKind, ParseCircuitError, SyntaxErrorKind};
+pub use crate::pin::ConflictPolicy;
+pub use assertions::AssertionFailure;
+pub use builder::{CircuitBuildError, CircuitBuilder};
+pub use bulk::{RunSummary, SignalId};
+pub use bus::{BusError, MAX_BUS_WIDTH};
+pub use checks::EmbeddedCheck;
+pub use csv_oracle::{CsvError, CsvErrorKind, CsvReport};
+pub use determinism::{audit_determinism, DeterminismReport, Divergence};
+pub use devices::DeviceError;
+pub use diagnostics::{Diagnostic, Severity};
+pub use explain::{ExplanationStep, InputRead, TickExplanation};
+pub use history::{History, HistoryError};
+pub use incremental::{IncrementalParser, InvalidEdit, TextEdit, TextPosition};
+#[cfg(feature = "debug-internals")]
+pub use internals::InternalGateError;
+pub use netlist::{Netlist, NetlistComponent, PinRef};
+pub use parser::{parse_instructions, BuildErrorKind, Endpoint, Instruction, ParseCircuitError, SyntaxErrorKind};
+pub use query::{Query, QueryError};
+pub use simulator::Simulator;
+pub use stimulus::{Mismatch, ParseStimulusError, Stimulus, StimulusErrorKind};
+pub use testbench::Testbench;
+pub use truth_table::{TruthTable, TruthTableError};
+pub use undefined_watch::UndefinedOrigin;
+pub use user_data::UnknownComponent;
 
 #[derive(Debug, Clone)]
 pub enum SetInputError<'a> {
     UnknownName(&'a str),
     NotAnInput(&'a str),
     ValueParseError(&'a str),
+    NotAClock(&'a str),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationError {
+    /// Links to components dropped since they were made were found, and pruned, during the tick.
+    DanglesDetected(usize),
+    /// Under `ConflictPolicy::Error`, input pins whose drivers disagreed, read as undefined during the tick.
+    DriverConflicts(usize),
+    /// Both `DanglesDetected` and `DriverConflicts` happened during the tick.
+    DanglesAndConflicts { dangles: usize, conflicts: usize },
+    /// The tick counter reached its maximum: nothing was simulated.
+    TickOverflow,
+    /// Embedded checks of the tick which failed, reported for subcircuits only.
+    ChecksFailed(Vec<Mismatch>),
+    /// The circuit embedded as the component `name` reported `error` during the tick.
+    Subcircuit { name: String, error: Box<SimulationError> },
+    /// `error` of the circuit itself, reported along with the `Subcircuit` error of the same tick.
+    WithSubcircuit { error: Box<SimulationError>, subcircuit: Box<SimulationError> },
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DanglesDetected(count) => write!(f, "{count} link(s) to dropped components were pruned"),
+            Self::DriverConflicts(count) => write!(f, "{count} input(s) read disagreeing drivers"),
+            Self::DanglesAndConflicts { dangles, conflicts } => {
+                write!(f, "{dangles} link(s) to dropped components were pruned, {conflicts} input(s) read disagreeing drivers")
+            }
+            Self::TickOverflow => write!(f, "The tick counter overflowed"),
+            Self::ChecksFailed(mismatches) => {
+                let mismatches: Vec<String> = mismatches.iter().map(Mismatch::to_string).collect();
+                write!(f, "Failed checks: {}", mismatches.join(", "))
+            }
+            Self::Subcircuit { name, error } => write!(f, "Subcircuit \"{name}\": {error}"),
+            Self::WithSubcircuit { error, subcircuit } => write!(f, "{error}; {subcircuit}"),
+        }
+    }
 }
 
 pub struct Circuit {
     current_tick: Tick,
-    components: HashMap<String, Rc<dyn Component>>,
+    components: HashMap<String, Rc<fault::FaultableComponent>>,
+    /// Components in the order they are simulated, drivers first.
+    schedule: Vec<Rc<fault::FaultableComponent>>,
+    /// Values still to be given to inputs declared with a sequence, the next one first.
+    input_sequences: HashMap<String, VecDeque<Tristate>>,
+    netlist: Netlist,
+    conflict_policy: ConflictPolicy,
+    undefined_watch: Option<undefined_watch::UndefinedWatch>,
+    /// First tick at which each output held a defined value.
+    first_defined: HashMap<String, Tick>,
+    embedded_checks: Vec<checks::EmbeddedCheck>,
+    user_data: user_data::UserData,
+    /// Circuits embedded as components, by component name.
+    subcircuits: HashMap<String, Rc<subcircuit::SubcircuitComponent>>,
+    /// Inputs and outputs sorted by name, indexed by `SignalId`.
+    signals: Vec<(String, Rc<fault::FaultableComponent>)>,
 }
 
 impl Circuit {
+    /// Parses `.nts` content, resolving chipset types through `factory` (e.g. a `ComponentRegistry`).
+    pub fn parse_with_factory<Factory>(content: &str, factory: Factory) -> Result<Self, ParseCircuitError>
+    where
+        Factory: ComponentFactory,
+        Factory::Type: std::fmt::Debug + Clone + ToString,
+    {
+        parser::Parser::read_with_factory(content, factory, None)
+    }
+
+    /// Parses `.nts` content whose file parameters are relative to `base_path`, usually the directory of the file.
+    pub fn parse_relative_to<P: AsRef<std::path::Path>>(content: &str, base_path: P) -> Result<Self, ParseCircuitError> {
+        parser::Parser::read_with_factory(content, crate::components::factory::DefaultComponentFactory, Some(base_path.as_ref()))
+    }
+
+    /// Same as `parse_with_factory()`, the file parameters being relative to `base_path` if given.
+    pub fn parse_with_factory_relative_to<Factory>(
+        content: &str,
+        factory: Factory,
+        base_path: Option<&std::path::Path>,
+    ) -> Result<Self, ParseCircuitError>
+    where
+        Factory: ComponentFactory,
+        Factory::Type: std::fmt::Debug + Clone + ToString,
+    {
+        parser::Parser::read_with_factory(content, factory, base_path)
+    }
+
+    /// # Panics
+    ///
+    /// If the tick counter overflows, see `try_simulate()`.
     pub fn simulate(&mut self) {
-        self.current_tick += 1;
+        match self.try_simulate() {
+            Err(SimulationError::TickOverflow) => panic!("{}", SimulationError::TickOverflow),
+            // Dangling links are pruned, and conflicts read as undefined, whatever the outcome.
+            Ok(())
+            | Err(
+                SimulationError::DanglesDetected(_)
+                | SimulationError::DriverConflicts(_)
+                | SimulationError::DanglesAndConflicts { .. }
+                | SimulationError::ChecksFailed(_)
+                | SimulationError::Subcircuit { .. }
+                | SimulationError::WithSubcircuit { .. },
+            ) => (),
+        }
+    }
+
+    /// Same as `simulate()`, reporting the links to dropped components found during the tick and the driver
+    /// conflicts if the conflict policy is `ConflictPolicy::Error`, else the errors of the subcircuits.
+    /// The tick is completed anyway, those links being read as if they had never been made.
+    pub fn try_simulate(&mut self) -> Result<(), SimulationError> {
+        let tick = self.current_tick.checked_next().ok_or(SimulationError::TickOverflow)?;
 
-        for (_, component) in self.components.iter() {
+        self.step(tick)
+    }
+
+    /// Simulates `tick`, the counts of dangling links and driver conflicts of an enclosing circuit being
+    /// kept aside meanwhile, so that a subcircuit reports its own ones.
+    pub(crate) fn step(&mut self, tick: Tick) -> Result<(), SimulationError> {
+        let enclosing_counts = (pin::take_dangling_links(), pin::take_driver_conflicts());
+
+        self.current_tick = tick;
+        self.apply_input_sequences();
+
+        let previous_policy = pin::set_conflict_policy(self.conflict_policy);
+        for component in self.schedule.iter() {
             component.simulate(self.current_tick);
         }
+        pin::set_conflict_policy(previous_policy);
+
+        self.record_undefined_outputs();
+        self.record_first_defined();
+
+        let (dangling_links, conflicts) = (pin::take_dangling_links(), pin::take_driver_conflicts());
+        pin::restore_counts(enclosing_counts.0, enclosing_counts.1);
+
+        // Every error is taken, so that none is reported again at the next tick.
+        let subcircuit_error = self
+            .subcircuits
+            .iter()
+            .filter_map(|(name, subcircuit)| Some((name, subcircuit.take_error()?)))
+            .min_by_key(|(name, _)| *name)
+            .map(|(name, error)| SimulationError::Subcircuit { name: name.clone(), error: Box::new(error) });
+
+        let conflicts = if self.conflict_policy == ConflictPolicy::Error { conflicts } else { 0 };
+        let error = match (dangling_links, conflicts) {
+            (0, 0) => None,
+            (0, conflicts) => Some(SimulationError::DriverConflicts(conflicts)),
+            (dangles, 0) => Some(SimulationError::DanglesDetected(dangles)),
+            (dangles, conflicts) => Some(SimulationError::DanglesAndConflicts { dangles, conflicts }),
+        };
+        match (error, subcircuit_error) {
+            (None, None) => Ok(()),
+            (Some(error), None) | (None, Some(error)) => Err(error),
+            (Some(error), Some(subcircuit)) => {
+                Err(SimulationError::WithSubcircuit { error: Box::new(error), subcircuit: Box::new(subcircuit) })
+            }
+        }
+    }
+
+    /// Simulates as many ticks as fit within `duration` of wall-clock time and returns their number.
+    /// The last tick may end slightly after the deadline.
+    pub fn simulate_for(&mut self, duration: Duration) -> usize {
+        let start = Instant::now();
+        let mut ticks: usize = 0;
+
+        while start.elapsed() < duration {
+            self.simulate();
+            ticks += 1;
+        }
+
+        ticks
+    }
+
+    fn apply_input_sequences(&mut self) {
+        let components = &self.components;
+
+        self.input_sequences.retain(|name, values| {
+            if let Some(value) = values.pop_front() {
+                components[name].as_input().unwrap().set_state_for_next_tick(value);
+            }
+            !values.is_empty()
+        });
     }
 
     pub fn set_value<'a>(&self, name: &'a str, value: &'a str) -> Result<(), SetInputError<'a>> {
@@ -39,7 +262,7 @@ impl Circuit {
         };
 
         self.components
-            .get(&name.to_owned())
+            .get(name)
             .ok_or(SetInputError::UnknownName(name))?
             .as_input()
             .ok_or(SetInputError::NotAnInput(name))?
@@ -48,17 +271,78 @@ impl Circuit {
         Ok(())
     }
 
+    pub const CLOCK_OVERRIDE: &'static str = "clock-override";
+
+    /// Same as `set_value()`, also returning a warning if `name` is a clock: the value replaces its next toggle,
+    /// the clock then toggling from it.
+    pub fn set_value_checked<'a>(&self, name: &'a str, value: &'a str) -> Result<Option<Diagnostic>, SetInputError<'a>> {
+        self.set_value(name, value)?;
+
+        Ok(self.is_clock(name).unwrap_or(false).then(|| {
+            let message = format!("'{name}' is a clock: setting it to {value} overrides its toggling");
+            Diagnostic::new(Severity::Warning, Self::CLOCK_OVERRIDE, message).with_component(name)
+        }))
+    }
+
+    /// Makes the clock `name` follow `waveform` from the next tick instead of toggling.
+    pub fn configure_clock<'a>(&self, name: &'a str, waveform: ClockWaveform) -> Result<(), SetInputError<'a>> {
+        let input = self
+            .components
+            .get(name)
+            .ok_or(SetInputError::UnknownName(name))?
+            .as_input()
+            .ok_or(SetInputError::NotAnInput(name))?;
+
+        if !input.is_clock() {
+            return Err(SetInputError::NotAClock(name));
+        }
+        input.set_waveform(waveform);
+
+        Ok(())
+    }
+
+    /// Whether the input `name` is a clock, `None` if `name` is not an input.
+    pub fn is_clock(&self, name: &str) -> Option<bool> {
+        Some(self.components.get(name)?.as_input()?.is_clock())
+    }
+
     pub fn get_input(&self, name: &str) -> Option<String> {
-        Some(self.components.get(&name.to_owned())?.as_input()?.get_current_state().to_string())
+        Some(self.components.get(name)?.as_input()?.get_current_state().to_string())
     }
 
     pub fn get_output(&self, name: &str) -> Option<String> {
-        Some(self.components.get(&name.to_owned())?.as_output()?.get_value().to_string())
+        Some(self.components.get(name)?.as_output()?.get_value().to_string())
+    }
+
+    /// Circuit embedded as the component `name` by `CircuitBuilder::add_subcircuit()`.
+    pub fn subcircuit(&self, name: &str) -> Option<std::cell::Ref<'_, Circuit>> {
+        Some(self.subcircuits.get(name)?.circuit())
     }
+
+    /// Current value of the net named `label`, as driven by its first pin.
+    pub fn get_net(&self, label: &str) -> Option<Tristate> {
+        let driver = self.netlist.net(label)?.first()?;
+
+        Some(self.pin_value(&driver.component, driver.pin))
+    }
+
+    pub fn netlist(&self) -> &Netlist {
+        &self.netlist
+    }
+
+    pub fn current_tick(&self) -> Tick {
+        self.current_tick
+    }
+
+    /// Iterates over the names and current values of every input and output, sorted by name.
+    pub fn signals(&self) -> impl Iterator<Item = (&str, Tristate)> {
+        self.signals.iter().map(|(name, component)| (name.as_str(), bulk::signal_value(component)))
+    }
+
     /* Helpers for unit tests */
     #[cfg(test)]
     pub(super) fn has_component(&self, name: &str) -> bool {
-        self.components.contains_key(&name.to_owned())
+        self.components.contains_key(name)
     }
 }
 
@@ -66,7 +350,7 @@ impl fmt::Display for Circuit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "tick: {}", self.current_tick)?;
 
-        let mut components: Vec<(&String, &Rc<dyn Component>)> = self.components.iter().collect();
+        let mut components: Vec<(&String, &Rc<fault::FaultableComponent>)> = self.components.iter().collect();
         components.sort_by_key(|(name, _)| *name);
 
         writeln!(f, "input(s):")?;
@@ -79,7 +363,16 @@ impl fmt::Display for Circuit {
         writeln!(f, "output(s):")?;
         for (name, component) in components.iter() {
             if let Some(component) = component.as_output() {
-                writeln!(f, "  {}: {}", name, component.get_value())?
+                writeln!(f, "  {}: {}", name, component.render())?
+            }
+        }
+
+        let terminals: Vec<_> =
+            components.iter().filter_map(|(name, component)| Some((name, component.as_terminal()?))).collect();
+        if !terminals.is_empty() {
+            writeln!(f, "terminal(s):")?;
+            for (name, terminal) in terminals {
+                writeln!(f, "  {}: {:?}", name, terminal.text())?
             }
         }
 
@@ -99,7 +392,11 @@ impl std::str::FromStr for Circuit {
 mod tests {
     use super::builder::CircuitBuilder;
     use super::Circuit;
-    use super::SetInputError;
+    use super::{ConflictPolicy, SetInputError, SimulationError};
+    use crate::components::composite::parallel_gates::Component4069;
+    use crate::components::registry::ComponentRegistry;
+    use crate::components::{single_pin::clock_component::ClockWaveform, tristate::Tristate, Tick};
+    use std::time::Duration;
 
     #[test]
     fn test_create_and_handle_nanotekspice_circuit() {
@@ -118,23 +415,157 @@ mod tests {
 
         assert_eq!(circuit.get_input("in").unwrap(), "U");
         assert_eq!(circuit.get_output("out").unwrap(), "U");
-        assert_eq!(circuit.current_tick, 0);
+        assert_eq!(circuit.current_tick, Tick::ZERO);
 
         circuit.set_value("in", "1").unwrap();
         circuit.simulate();
 
-        assert_eq!(circuit.current_tick, 1);
+        assert_eq!(circuit.current_tick.get(), 1);
         assert_eq!(circuit.get_input("in").unwrap(), "1");
         assert_eq!(circuit.get_output("out").unwrap(), "1");
 
         circuit.set_value("in", "0").unwrap();
         circuit.simulate();
 
-        assert_eq!(circuit.current_tick, 2);
+        assert_eq!(circuit.current_tick.get(), 2);
         assert_eq!(circuit.get_input("in").unwrap(), "0");
         assert_eq!(circuit.get_output("out").unwrap(), "0");
     }
 
+    #[test]
+    fn test_simulate_for() {
+        let mut circuit: Circuit = CircuitBuilder::default().add_component("clock", "cl").unwrap().build().unwrap();
+
+        assert_eq!(circuit.simulate_for(Duration::ZERO), 0);
+        let ticks = circuit.simulate_for(Duration::from_millis(10));
+        assert!(ticks > 0);
+        assert_eq!(circuit.current_tick().get(), ticks as u64);
+    }
+
+    #[test]
+    fn test_try_simulate_tick_overflow() {
+        let mut circuit: Circuit = CircuitBuilder::default().add_component("clock", "cl").unwrap().build().unwrap();
+
+        circuit.current_tick = Tick::new(u64::MAX - 1);
+        assert_eq!(circuit.try_simulate(), Ok(()));
+        assert_eq!(circuit.current_tick(), Tick::MAX);
+        assert_eq!(circuit.try_simulate(), Err(SimulationError::TickOverflow));
+        assert_eq!(circuit.current_tick(), Tick::MAX);
+    }
+
+    #[test]
+    fn test_try_simulate_dangling_links() {
+        let mut circuit: Circuit =
+            ".chipsets:\ntrue t\ninput a\n4071 or\noutput out\n.links:\nt:1 or:1\na:1 or:2\nor:3 out:1\n".parse().unwrap();
+
+        circuit.set_value("a", "0").unwrap();
+        assert_eq!(circuit.try_simulate(), Ok(()));
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+
+        // Drop "t" behind the circuit's back, as a removal at runtime would.
+        circuit.components.remove("t");
+        circuit.schedule.retain(|component| circuit.components.values().any(|kept| std::rc::Rc::ptr_eq(kept, component)));
+
+        assert_eq!(circuit.try_simulate(), Err(SimulationError::DanglesDetected(1)));
+        assert_eq!(circuit.get_output("out").unwrap(), "U");
+        assert_eq!(circuit.try_simulate(), Ok(()));
+
+        circuit.set_value("a", "1").unwrap();
+        assert_eq!(circuit.try_simulate(), Ok(()));
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+    }
+
+    #[test]
+    fn test_try_simulate_dangles_and_conflicts() {
+        let content =
+            ".chipsets:\ntrue t\ninput a\ninput b\n4071 or\noutput out\n.links:\nt:1 or:1\na:1 or:2\nor:3 out:1\nb:1 or:2\n";
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        circuit.set_conflict_policy(ConflictPolicy::Error);
+        circuit.set_value("a", "0").unwrap();
+        circuit.set_value("b", "1").unwrap();
+        circuit.components.remove("t");
+        circuit.schedule.retain(|component| circuit.components.values().any(|kept| std::rc::Rc::ptr_eq(kept, component)));
+
+        let error = circuit.try_simulate().unwrap_err();
+        assert_eq!(error, SimulationError::DanglesAndConflicts { dangles: 1, conflicts: 1 });
+        assert_eq!(error.to_string(), "1 link(s) to dropped components were pruned, 1 input(s) read disagreeing drivers");
+        assert_eq!(circuit.try_simulate(), Err(SimulationError::DriverConflicts(1)));
+    }
+
+    #[test]
+    fn test_simulate_deep_chain_without_info() {
+        const DEPTH: usize = 10_000;
+
+        // Registered without info, its pins are probed when linked. The chain runs from the last name to the first
+        // one, so that simulating in name order would recurse through the whole chain.
+        let mut registry = ComponentRegistry::with_default_components();
+        registry.register("inverter", || Box::new(Component4069::new()));
+        let names: Vec<String> = (0..DEPTH).rev().map(|index| format!("not{index:05}")).collect();
+        let mut builder =
+            CircuitBuilder::new(registry).add_component("input", "in").unwrap().add_component("output", "out").unwrap();
+        for name in names.iter() {
+            builder = builder.add_component("inverter", name).unwrap();
+        }
+        builder = builder.link_components("in", 1, &names[0], 1).unwrap();
+        for pair in names.windows(2) {
+            builder = builder.link_components(&pair[0], 2, &pair[1], 1).unwrap();
+        }
+        let mut circuit = builder.link_components(&names[DEPTH - 1], 2, "out", 1).unwrap().build().unwrap();
+
+        circuit.set_value("in", "1").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+        assert_eq!(circuit.netlist().dependencies("not00000"), vec!["not00001"]);
+    }
+
+    #[test]
+    fn test_simulate_deep_chain() {
+        const DEPTH: usize = 10_000;
+
+        let names: Vec<String> = (0..DEPTH).map(|index| format!("not{index}")).collect();
+        let mut builder = CircuitBuilder::default().add_component("input", "in").unwrap().add_component("output", "out").unwrap();
+        for name in names.iter() {
+            builder = builder.add_component("4069", name).unwrap();
+        }
+        builder = builder.link_components("in", 1, &names[0], 1).unwrap();
+        for pair in names.windows(2) {
+            builder = builder.link_components(&pair[0], 2, &pair[1], 1).unwrap();
+        }
+        let mut circuit = builder.link_components(&names[DEPTH - 1], 2, "out", 1).unwrap().build().unwrap();
+
+        circuit.set_value("in", "1").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+
+        circuit.set_value("in", "0").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_get_net() {
+        let content = "
+            .chipsets:
+            input a
+            4069 not
+            output out
+            .links:
+            a:1 not:1
+            inverted out:1
+            .nets:
+            inverted: not:2
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        circuit.set_value("a", "1").unwrap();
+        circuit.simulate();
+
+        assert_eq!(circuit.get_net("inverted"), Some(Tristate::State(false)));
+        assert_eq!(circuit.get_output("out").unwrap(), "0");
+        assert_eq!(circuit.get_net("a"), None);
+    }
+
     #[test]
     fn test_error_for_non_existing_component_name() {
         let circuit: Circuit = CircuitBuilder::default().add_component("input", "in").unwrap().build().unwrap();
@@ -149,4 +580,76 @@ mod tests {
 
         assert!(matches!(circuit.set_value("out", "1"), Err(SetInputError::NotAnInput("out"))))
     }
+
+    #[test]
+    fn test_set_clock_value() {
+        let mut circuit: Circuit = ".chipsets:\ninput in\nclock clk\noutput out\n.links:\nclk:1 out:1\n".parse().unwrap();
+
+        assert_eq!((circuit.is_clock("clk"), circuit.is_clock("in"), circuit.is_clock("out")), (Some(true), Some(false), None));
+        assert_eq!(circuit.set_value_checked("in", "1").unwrap(), None);
+        assert!(matches!(circuit.set_value_checked("clk", "2"), Err(SetInputError::ValueParseError("2"))));
+
+        let warning = circuit.set_value_checked("clk", "1").unwrap().unwrap();
+        assert_eq!(warning.to_string(), "warning[clock-override]: 'clk' is a clock: setting it to 1 overrides its toggling");
+        assert_eq!(warning.components, vec!["clk"]);
+        circuit.simulate();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_clock_waveform() {
+        let content = ".chipsets:\ninput in\nclock clk period=4 duty=25 phase=1\nclock fast\noutput out\noutput out2\n.links:\nclk:1 out:1\nfast:1 out2:1\n";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let waveform = |circuit: &mut Circuit, output: &str, ticks: usize| {
+            (0..ticks)
+                .map(|_| {
+                    circuit.simulate();
+                    circuit.get_output(output).unwrap()
+                })
+                .collect::<String>()
+        };
+
+        assert_eq!(waveform(&mut circuit, "out", 8), "01000100");
+        circuit.set_value("clk", "1").unwrap();
+        assert_eq!(waveform(&mut circuit, "out", 4), "1100");
+
+        circuit.configure_clock("fast", ClockWaveform::new(3, 67, 0).unwrap()).unwrap();
+        // Ticks 13 to 18, the waveform being aligned on the tick number.
+        assert_eq!(waveform(&mut circuit, "out2", 6), "110110");
+
+        assert!(matches!(
+            circuit.configure_clock("in", ClockWaveform::new(2, 50, 0).unwrap()),
+            Err(SetInputError::NotAClock("in"))
+        ));
+        assert!(matches!(
+            circuit.configure_clock("out", ClockWaveform::new(2, 50, 0).unwrap()),
+            Err(SetInputError::NotAnInput("out"))
+        ));
+        assert!(!ClockWaveform::new(2, 50, 0).unwrap().state_at(Tick::ZERO));
+        assert!(ClockWaveform::new(2, 50, 0).unwrap().state_at(Tick::new(1)));
+        assert_eq!(ClockWaveform::new(0, 50, 0), None);
+        assert_eq!(ClockWaveform::new(4, 101, 0), None);
+        assert!(content.replace("period=4", "period=0").parse::<Circuit>().is_err());
+        assert!(content.replace("duty=25", "duty=150").parse::<Circuit>().is_err());
+    }
+
+    #[test]
+    fn test_button() {
+        let mut circuit: Circuit = ".chipsets:\nbutton push\noutput out\n.links:\npush:1 out:1\n".parse().unwrap();
+        let tick = |circuit: &mut Circuit| {
+            circuit.simulate();
+            circuit.get_output("out").unwrap()
+        };
+
+        assert_eq!(tick(&mut circuit), "0");
+        circuit.set_value("push", "1").unwrap();
+        assert_eq!(tick(&mut circuit), "1");
+        assert_eq!(tick(&mut circuit), "0");
+        assert_eq!(tick(&mut circuit), "0");
+        circuit.set_value("push", "U").unwrap();
+        assert_eq!(tick(&mut circuit), "U");
+        assert_eq!(tick(&mut circuit), "0");
+        assert_eq!(circuit.is_clock("push"), Some(false));
+    }
 }
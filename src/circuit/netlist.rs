@@ -0,0 +1,359 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::components::{
+    info::{ComponentInfo, PinDirection},
+    PinNumber,
+};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct PinRef {
+    pub component: String,
+    pub pin: PinNumber,
+}
+
+impl PinRef {
+    pub fn new(component: &str, pin: PinNumber) -> Self {
+        Self { component: component.to_owned(), pin }
+    }
+}
+
+impl std::fmt::Display for PinRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.component, self.pin)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NetlistComponent {
+    pub component_type: String,
+    pub info: Option<ComponentInfo>,
+}
+
+/// Structural view of a circuit: component types, the links declared between their pins
+/// and the labels given to nets.
+#[derive(Debug, Default, Clone)]
+pub struct Netlist {
+    components: HashMap<String, NetlistComponent>,
+    links: Vec<(PinRef, PinRef)>,
+    /// Indices in `links` of the links each pin appears in.
+    links_by_pin: HashMap<PinRef, Vec<usize>>,
+    nets: HashMap<String, Vec<PinRef>>,
+    /// Linked pins of the components without info, with whether they drove their links when linked.
+    probed_pins: HashMap<String, Vec<(PinNumber, bool)>>,
+}
+
+impl Netlist {
+    pub(super) fn add_component(&mut self, name: &str, component_type: &str, info: Option<ComponentInfo>) {
+        self.components.insert(name.to_owned(), NetlistComponent { component_type: component_type.to_owned(), info });
+    }
+
+    pub(super) fn add_link(&mut self, left: PinRef, right: PinRef) {
+        let index = self.links.len();
+
+        if left != right {
+            self.links_by_pin.entry(right.clone()).or_default().push(index);
+        }
+        self.links_by_pin.entry(left.clone()).or_default().push(index);
+        self.links.push((left, right));
+    }
+
+    /// Records whether `pin`, of a component without info, drives its links. The evaluation order relies on it.
+    pub(super) fn probe_pin(&mut self, pin: &PinRef, driving: bool) {
+        let pins = self.probed_pins.entry(pin.component.clone()).or_default();
+
+        if !pins.iter().any(|(number, _)| *number == pin.pin) {
+            pins.push((pin.pin, driving));
+        }
+    }
+
+    pub(super) fn add_net_pin(&mut self, label: &str, pin: PinRef) {
+        let pins = self.nets.entry(label.to_owned()).or_default();
+
+        if !pins.contains(&pin) {
+            pins.push(pin);
+        }
+    }
+
+    pub fn component(&self, name: &str) -> Option<&NetlistComponent> {
+        self.components.get(name)
+    }
+
+    /// Component names, sorted.
+    pub fn component_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.components.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    pub fn links(&self) -> &[(PinRef, PinRef)] {
+        &self.links
+    }
+
+    /// Net labels, sorted.
+    pub fn net_labels(&self) -> Vec<&str> {
+        let mut labels: Vec<&str> = self.nets.keys().map(String::as_str).collect();
+        labels.sort();
+        labels
+    }
+
+    /// Pins of the net named `label`, in declaration order: the first one drives the net.
+    pub fn net(&self, label: &str) -> Option<&[PinRef]> {
+        self.nets.get(label).map(Vec::as_slice)
+    }
+
+    /// Label of the net `pin` belongs to, if any.
+    pub fn label_of(&self, pin: &PinRef) -> Option<&str> {
+        self.nets.iter().find(|(_, pins)| pins.contains(pin)).map(|(label, _)| label.as_str())
+    }
+
+    /// Net label of `pin` if it has one, `component:pin` otherwise.
+    pub fn pin_name(&self, pin: &PinRef) -> String {
+        match self.label_of(pin) {
+            Some(label) => label.to_owned(),
+            None => pin.to_string(),
+        }
+    }
+
+    /// Direction of a pin as declared by its component info, if known.
+    pub fn pin_direction(&self, pin: &PinRef) -> Option<PinDirection> {
+        Some(self.components.get(&pin.component)?.info.as_ref()?.pin(pin.pin)?.direction)
+    }
+
+    /// Every pin linked to `pin`, in declaration order.
+    pub fn linked_pins(&self, pin: &PinRef) -> Vec<&PinRef> {
+        let indices = match self.links_by_pin.get(pin) {
+            Some(indices) => indices.as_slice(),
+            None => &[],
+        };
+
+        indices
+            .iter()
+            .map(|&index| {
+                let (left, right) = &self.links[index];
+                if left == pin {
+                    right
+                } else {
+                    left
+                }
+            })
+            .collect()
+    }
+
+    /// Pins that can drive `pin`: linked pins which are not known to be inputs.
+    pub fn drivers_of(&self, pin: &PinRef) -> Vec<&PinRef> {
+        self.linked_pins(pin)
+            .into_iter()
+            .filter(|other| !matches!(self.pin_direction(other), Some(PinDirection::Input) | Some(PinDirection::Unused)))
+            .collect()
+    }
+
+    /// Pins of `name` which read a value from other components.
+    pub fn input_pins(&self, name: &str) -> Vec<PinNumber> {
+        self.pins_matching(name, |direction| matches!(direction, PinDirection::Input | PinDirection::Bidirectional))
+    }
+
+    /// Pins of `name` which provide a value to other components.
+    pub fn output_pins(&self, name: &str) -> Vec<PinNumber> {
+        self.pins_matching(name, |direction| matches!(direction, PinDirection::Output | PinDirection::Bidirectional))
+    }
+
+    /// Names of the components `name` directly reads from, sorted. The pins of components without info are
+    /// told apart by whether they drove their links when linked.
+    pub fn dependencies(&self, name: &str) -> Vec<&str> {
+        let mut dependencies: Vec<&str> = self
+            .read_pins(name)
+            .into_iter()
+            .flat_map(|pin| self.sources_of(&PinRef::new(name, pin)))
+            .map(|driver| driver.component.as_str())
+            .filter(|&driver| driver != name)
+            .collect();
+
+        dependencies.sort();
+        dependencies.dedup();
+        dependencies
+    }
+
+    /// Orders components so that each one comes after the components it reads from.
+    /// Feedback loops are broken at the first component revisited.
+    pub fn evaluation_order(&self) -> Vec<&str> {
+        self.traverse().0
+    }
+
+    /// Components closing a feedback loop, i.e. where `evaluation_order()` breaks one, sorted.
+    pub fn feedback_components(&self) -> Vec<&str> {
+        let mut components = self.traverse().1;
+
+        components.sort();
+        components.dedup();
+        components
+    }
+
+    /// Iterative post-order DFS over the dependencies, returning the evaluation order and the
+    /// components found again while still being visited.
+    fn traverse(&self) -> (Vec<&str>, Vec<&str>) {
+        let mut order: Vec<&str> = Vec::with_capacity(self.components.len());
+        let mut feedback: Vec<&str> = Vec::new();
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut in_progress: HashSet<&str> = HashSet::new();
+
+        for root in self.component_names() {
+            if !visited.insert(root) {
+                continue;
+            }
+
+            // (component, its dependencies, index of the next one to visit)
+            let mut stack: Vec<(&str, Vec<&str>, usize)> = vec![(root, self.dependencies(root), 0)];
+            in_progress.insert(root);
+            while let Some((name, dependencies, next)) = stack.last_mut() {
+                if let Some(&dependency) = dependencies.get(*next) {
+                    *next += 1;
+                    if visited.insert(dependency) {
+                        let dependencies = self.dependencies(dependency);
+                        in_progress.insert(dependency);
+                        stack.push((dependency, dependencies, 0));
+                    } else if in_progress.contains(dependency) {
+                        feedback.push(dependency);
+                    }
+                } else {
+                    if self.reads_itself(name) {
+                        feedback.push(name);
+                    }
+                    in_progress.remove(*name);
+                    order.push(name);
+                    stack.pop();
+                }
+            }
+        }
+
+        (order, feedback)
+    }
+
+    fn reads_itself(&self, name: &str) -> bool {
+        self.read_pins(name)
+            .into_iter()
+            .any(|pin| self.sources_of(&PinRef::new(name, pin)).iter().any(|driver| driver.component == name))
+    }
+
+    /// Input pins of `name`, or its probed pins which did not drive their links if it has no info.
+    fn read_pins(&self, name: &str) -> Vec<PinNumber> {
+        match self.components.get(name).and_then(|component| component.info.as_ref()) {
+            Some(_) => self.input_pins(name),
+            None => {
+                self.probed_pins.get(name).into_iter().flatten().filter(|(_, driving)| !driving).map(|(pin, _)| *pin).collect()
+            }
+        }
+    }
+
+    /// Same as `drivers_of()`, without the probed pins which did not drive their links.
+    fn sources_of(&self, pin: &PinRef) -> Vec<&PinRef> {
+        self.drivers_of(pin)
+            .into_iter()
+            .filter(|driver| match self.probed_pins.get(&driver.component) {
+                Some(pins) => pins.iter().all(|(number, driving)| *number != driver.pin || *driving),
+                None => true,
+            })
+            .collect()
+    }
+
+    fn pins_matching<F>(&self, name: &str, predicate: F) -> Vec<PinNumber>
+    where
+        F: Fn(PinDirection) -> bool,
+    {
+        match self.components.get(name).and_then(|component| component.info.as_ref()) {
+            Some(info) => info.pins().iter().filter(|pin| predicate(pin.direction)).map(|pin| pin.number).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Netlist, PinRef};
+    use crate::components::factory::{ComponentFactory, DefaultComponentFactory};
+    use crate::components::types::ComponentType;
+
+    fn add(netlist: &mut Netlist, name: &str, component_type: ComponentType) {
+        netlist.add_component(name, &component_type.to_string(), DefaultComponentFactory.component_info(&component_type));
+    }
+
+    fn build_netlist() -> Netlist {
+        let mut netlist = Netlist::default();
+
+        add(&mut netlist, "out", ComponentType::Output);
+        add(&mut netlist, "gate", ComponentType::C4081);
+        add(&mut netlist, "a", ComponentType::Input);
+        add(&mut netlist, "b", ComponentType::Input);
+        netlist.add_link(PinRef::new("a", 1), PinRef::new("gate", 1));
+        netlist.add_link(PinRef::new("gate", 2), PinRef::new("b", 1));
+        netlist.add_link(PinRef::new("out", 1), PinRef::new("gate", 3));
+        netlist
+    }
+
+    #[test]
+    fn test_drivers_of() {
+        let netlist = build_netlist();
+
+        assert_eq!(netlist.drivers_of(&PinRef::new("gate", 2)), vec![&PinRef::new("b", 1)]);
+        assert_eq!(netlist.drivers_of(&PinRef::new("out", 1)), vec![&PinRef::new("gate", 3)]);
+        assert!(netlist.drivers_of(&PinRef::new("a", 1)).is_empty());
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let netlist = build_netlist();
+
+        assert_eq!(netlist.dependencies("gate"), vec!["a", "b"]);
+        assert_eq!(netlist.dependencies("out"), vec!["gate"]);
+        assert!(netlist.dependencies("a").is_empty());
+    }
+
+    #[test]
+    fn test_evaluation_order() {
+        let netlist = build_netlist();
+
+        assert_eq!(netlist.evaluation_order(), vec!["a", "b", "gate", "out"]);
+    }
+
+    #[test]
+    fn test_net_labels() {
+        let mut netlist = build_netlist();
+
+        netlist.add_net_pin("carry", PinRef::new("gate", 3));
+        netlist.add_net_pin("carry", PinRef::new("out", 1));
+        netlist.add_net_pin("carry", PinRef::new("out", 1));
+
+        assert_eq!(netlist.net_labels(), vec!["carry"]);
+        assert_eq!(netlist.net("carry").unwrap(), &[PinRef::new("gate", 3), PinRef::new("out", 1)]);
+        assert_eq!(netlist.label_of(&PinRef::new("out", 1)), Some("carry"));
+        assert_eq!(netlist.pin_name(&PinRef::new("out", 1)), "carry");
+        assert_eq!(netlist.pin_name(&PinRef::new("a", 1)), "a:1");
+        assert!(netlist.net("nope").is_none());
+    }
+
+    #[test]
+    fn test_evaluation_order_with_feedback() {
+        let mut netlist = Netlist::default();
+
+        add(&mut netlist, "nor", ComponentType::C4001);
+        netlist.add_link(PinRef::new("nor", 3), PinRef::new("nor", 5));
+        netlist.add_link(PinRef::new("nor", 4), PinRef::new("nor", 2));
+
+        assert_eq!(netlist.evaluation_order(), vec!["nor"]);
+        assert_eq!(netlist.feedback_components(), vec!["nor"]);
+    }
+
+    #[test]
+    fn test_feedback_components() {
+        let mut netlist = build_netlist();
+
+        assert!(netlist.feedback_components().is_empty());
+
+        add(&mut netlist, "not", ComponentType::C4069);
+        add(&mut netlist, "and", ComponentType::C4081);
+        netlist.add_link(PinRef::new("not", 1), PinRef::new("and", 3));
+        netlist.add_link(PinRef::new("and", 1), PinRef::new("not", 2));
+
+        // "and" is visited first, the loop is closed when "not" reads it back.
+        assert_eq!(netlist.feedback_components(), vec!["and"]);
+    }
+}
@@ -0,0 +1,731 @@
+//! Passes simplifying parsed instructions before building the circuit.
+//!
+//! ```text
+//! let instructions = parse_instructions(content)?;
+//! let (instructions, report) = optimize(&instructions, &DefaultComponentFactory, OptimizationPasses::ALL);
+//! let circuit = CircuitBuilder::default().add_instructions(&instructions)?.build()?;
+//! ```
+//!
+//! - Constant propagation links the readers of combinational outputs computed only from `true`/`false`
+//!   components to a constant of the same value.
+//! - Buffer collapsing links the readers of a non-inverting output, or of two inverters in series,
+//!   to the original driver.
+//! - Dead-logic elimination removes the components no input, output or device depends on.
+//!
+//! Passes are repeated until none of them changes anything. Net labels whose links were changed are dropped.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::components::factory::ComponentFactory;
+use crate::components::info::{ComponentInfo, PinDirection};
+use crate::components::params::Params;
+use crate::components::{tristate::Tristate, Component, PinNumber};
+
+use super::builder::CircuitBuilder;
+use super::netlist::{Netlist, PinRef};
+use super::parser::{Endpoint, Instruction};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct OptimizationPasses {
+    pub constant_propagation: bool,
+    pub buffer_collapsing: bool,
+    pub dead_logic_elimination: bool,
+}
+
+impl OptimizationPasses {
+    pub const ALL: Self = Self { constant_propagation: true, buffer_collapsing: true, dead_logic_elimination: true };
+    pub const NONE: Self = Self { constant_propagation: false, buffer_collapsing: false, dead_logic_elimination: false };
+}
+
+/// What the passes changed, names sorted.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct OptimizationReport {
+    /// Output pins, as `name:pin`, whose readers were linked to a constant.
+    pub folded: Vec<String>,
+    /// Output pins, as `name:pin`, whose readers were linked to the driver of the buffer.
+    pub collapsed: Vec<String>,
+    /// Components removed because nothing observable depends on them.
+    pub removed: Vec<String>,
+    /// Constant components added for the folded ones.
+    pub added: Vec<String>,
+    /// Net labels dropped because their links were changed.
+    pub dropped_labels: Vec<String>,
+}
+
+impl OptimizationReport {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for OptimizationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (title, names) in [
+            ("folded", &self.folded),
+            ("collapsed", &self.collapsed),
+            ("removed", &self.removed),
+            ("added", &self.added),
+            ("dropped labels", &self.dropped_labels),
+        ] {
+            if !names.is_empty() {
+                writeln!(f, "{title}: {}", names.join(", "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs the enabled `passes` on `instructions`, resolving component types through `factory`.
+/// Components of types unknown to the factory, or without info, are left untouched.
+pub fn optimize<Factory>(
+    instructions: &[Instruction],
+    factory: &Factory,
+    passes: OptimizationPasses,
+) -> (Vec<Instruction>, OptimizationReport)
+where
+    Factory: ComponentFactory,
+    Factory::Type: fmt::Debug + Clone,
+{
+    let mut design = Design::new(instructions, factory);
+    let mut report = OptimizationReport::default();
+
+    loop {
+        let mut changed = false;
+
+        if passes.constant_propagation {
+            changed |= design.propagate_constants(&mut report);
+        }
+        if passes.buffer_collapsing {
+            changed |= design.collapse_buffers(&mut report);
+        }
+        if passes.dead_logic_elimination {
+            changed |= design.eliminate_dead_logic(&mut report);
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let instructions = design.into_instructions(&mut report);
+    for names in [&mut report.folded, &mut report.collapsed, &mut report.removed, &mut report.added, &mut report.dropped_labels] {
+        names.sort();
+    }
+    (instructions, report)
+}
+
+#[derive(Clone, Copy)]
+enum BufferKind {
+    Identity,
+    Inverter,
+}
+
+struct DesignComponent {
+    component_type: String,
+    values: Option<Vec<Tristate>>,
+    params: Params,
+    info: Option<ComponentInfo>,
+}
+
+struct Design<'f, Factory> {
+    factory: &'f Factory,
+    /// Component names in declaration order.
+    order: Vec<String>,
+    components: HashMap<String, DesignComponent>,
+    links: Vec<(PinRef, PinRef)>,
+    /// Labelled nets, the driver first.
+    nets: Vec<(String, Vec<PinRef>)>,
+    /// Instructions which could not be resolved, given back as is.
+    unresolved: Vec<Instruction>,
+    buffers: HashMap<(String, PinNumber), Option<(BufferKind, PinNumber)>>,
+}
+
+impl<'f, Factory> Design<'f, Factory>
+where
+    Factory: ComponentFactory,
+    Factory::Type: fmt::Debug + Clone,
+{
+    const TRUE: &'static str = "true";
+    const FALSE: &'static str = "false";
+
+    fn new(instructions: &[Instruction], factory: &'f Factory) -> Self {
+        let mut design = Self {
+            factory,
+            order: Vec::new(),
+            components: HashMap::new(),
+            links: Vec::new(),
+            nets: Vec::new(),
+            unresolved: Vec::new(),
+            buffers: HashMap::new(),
+        };
+
+        for instruction in instructions.iter() {
+            match instruction {
+                Instruction::AddComponent { name, component_type, values, params } => {
+                    let info =
+                        factory.parse_type(component_type).and_then(|parsed| factory.component_info_with_params(&parsed, params));
+
+                    design.order.push(name.clone());
+                    design.components.insert(
+                        name.clone(),
+                        DesignComponent {
+                            component_type: component_type.clone(),
+                            values: values.clone(),
+                            params: params.clone(),
+                            info,
+                        },
+                    );
+                }
+                Instruction::DeclareNet { label, pins } if !pins.is_empty() => {
+                    let pins: Vec<PinRef> = pins.iter().map(|(name, pin)| PinRef::new(name, *pin)).collect();
+
+                    design.links.extend(pins[1..].iter().map(|pin| (pins[0].clone(), pin.clone())));
+                    design.nets.push((label.clone(), pins));
+                }
+                _ => (),
+            }
+        }
+        for instruction in instructions.iter() {
+            if let Instruction::LinkComponents { left, right } = instruction {
+                if !design.add_link_instruction(left, right) {
+                    design.unresolved.push(instruction.clone());
+                }
+            }
+        }
+
+        design
+    }
+
+    /// Same resolution of labels as `CircuitBuilder::add_instructions()`.
+    fn add_link_instruction(&mut self, left: &Endpoint, right: &Endpoint) -> bool {
+        let (label, pin) = match (left, right) {
+            (Endpoint::Pin(left, left_pin), Endpoint::Pin(right, right_pin)) => {
+                self.links.push((PinRef::new(left, *left_pin), PinRef::new(right, *right_pin)));
+                return true;
+            }
+            (Endpoint::Label(label), Endpoint::Pin(name, pin)) | (Endpoint::Pin(name, pin), Endpoint::Label(label)) => {
+                (label, PinRef::new(name, *pin))
+            }
+            (Endpoint::Label(_), Endpoint::Label(_)) => return false,
+        };
+        let Some((_, pins)) = self.nets.iter_mut().find(|(net_label, _)| net_label == label) else {
+            return false;
+        };
+
+        self.links.push((pins[0].clone(), pin.clone()));
+        if !pins.contains(&pin) {
+            pins.push(pin);
+        }
+        true
+    }
+
+    fn netlist(&self) -> Netlist {
+        let mut netlist = Netlist::default();
+
+        for name in self.order.iter() {
+            let component = &self.components[name];
+            netlist.add_component(name, &component.component_type, component.info.clone());
+        }
+        for (left, right) in self.links.iter() {
+            netlist.add_link(left.clone(), right.clone());
+        }
+        netlist
+    }
+
+    fn info(&self, name: &str) -> Option<&ComponentInfo> {
+        self.components.get(name)?.info.as_ref()
+    }
+
+    fn is_constant(&self, name: &str) -> bool {
+        matches!(self.components.get(name), Some(component) if [Self::TRUE, Self::FALSE].contains(&component.component_type.as_str()))
+    }
+
+    fn is_combinational(&self, name: &str) -> bool {
+        matches!(self.info(name), Some(info) if !info.sequential)
+    }
+
+    /// Linked pins which only read `pin`, `None` if another output is linked to it.
+    fn readers_of<'n>(netlist: &'n Netlist, pin: &PinRef) -> Option<Vec<&'n PinRef>> {
+        let linked = netlist.linked_pins(pin);
+
+        linked.iter().all(|other| netlist.pin_direction(other) == Some(PinDirection::Input)).then_some(linked)
+    }
+
+    fn single_driver<'n>(netlist: &'n Netlist, pin: &PinRef) -> Option<&'n PinRef> {
+        let drivers: HashSet<&PinRef> = netlist.drivers_of(pin).into_iter().collect();
+
+        match drivers.len() {
+            1 => drivers.into_iter().next(),
+            _ => None,
+        }
+    }
+
+    fn remove_component(&mut self, name: &str) {
+        self.order.retain(|other| other != name);
+        self.components.remove(name);
+        self.links.retain(|(left, right)| left.component != name && right.component != name);
+    }
+
+    fn relink(&mut self, from: &PinRef, to: &PinRef, reader: &PinRef) {
+        self.links.retain(|(left, right)| !((left == from && right == reader) || (left == reader && right == from)));
+        if !self.links.iter().any(|(left, right)| left == to && right == reader) {
+            self.links.push((to.clone(), reader.clone()));
+        }
+    }
+
+    /// Name of a constant component of `value`, added if there is none.
+    fn constant(&mut self, value: bool, report: &mut OptimizationReport) -> String {
+        let component_type = if value { Self::TRUE } else { Self::FALSE };
+
+        if let Some(name) = self.order.iter().find(|name| self.components[*name].component_type == component_type) {
+            return name.clone();
+        }
+
+        let name = (0..)
+            .map(|index| if index == 0 { component_type.to_owned() } else { format!("{component_type}_{index}") })
+            .find(|name| !self.components.contains_key(name))
+            .unwrap();
+        let info = self.factory.parse_type(component_type).and_then(|parsed| self.factory.component_info(&parsed));
+
+        self.order.push(name.clone());
+        self.components.insert(
+            name.clone(),
+            DesignComponent { component_type: component_type.to_owned(), values: None, params: Params::new(), info },
+        );
+        report.added.push(name.clone());
+        name
+    }
+
+    fn propagate_constants(&mut self, report: &mut OptimizationReport) -> bool {
+        if self.factory.parse_type(Self::TRUE).is_none() || self.factory.parse_type(Self::FALSE).is_none() {
+            return false;
+        }
+
+        let mut changed = false;
+        let mut netlist = self.netlist();
+
+        for name in self.order.clone() {
+            for pin in netlist.output_pins(&name) {
+                let output = PinRef::new(&name, pin);
+                let Some(value) = self.fold(&netlist, &output) else {
+                    continue;
+                };
+                let readers: Vec<PinRef> = netlist.linked_pins(&output).into_iter().cloned().collect();
+                let constant = PinRef::new(&self.constant(value, report), 1);
+
+                for reader in readers.iter() {
+                    self.relink(&output, &constant, reader);
+                }
+                report.folded.push(output.to_string());
+                netlist = self.netlist();
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Value of `output` if the inputs it depends on are all driven by constants.
+    fn fold(&self, netlist: &Netlist, output: &PinRef) -> Option<bool> {
+        let name = output.component.as_str();
+        if self.is_constant(name) || !self.is_combinational(name) {
+            return None;
+        }
+        match Self::readers_of(netlist, output) {
+            Some(readers) if !readers.is_empty() => (),
+            _ => return None,
+        }
+
+        let mut inputs: Vec<(PinNumber, bool)> = Vec::new();
+        for pin in self.info(name)?.dependencies(output.pin) {
+            let drivers = netlist.drivers_of(&PinRef::new(name, pin));
+
+            if drivers.is_empty() || !drivers.iter().all(|driver| self.is_constant(&driver.component)) {
+                return None;
+            }
+            let mut values = drivers.iter().map(|driver| self.components[&driver.component].component_type == Self::TRUE);
+            let value = values.next()?;
+            // Constants driving different values conflict, which is left to the simulation.
+            if values.any(|other| other != value) {
+                return None;
+            }
+            inputs.push((pin, value));
+        }
+        if inputs.is_empty() {
+            return None;
+        }
+
+        let mut builder = CircuitBuilder::new(self.factory)
+            .add_component_with_params(&self.components[name].component_type, name, &self.components[name].params)
+            .and_then(|builder| builder.add_component(Self::TRUE, "1"))
+            .and_then(|builder| builder.add_component(Self::FALSE, "0"))
+            .ok()?;
+        for (pin, value) in inputs {
+            builder = builder.link_components(if value { "1" } else { "0" }, 1, name, pin).ok()?;
+        }
+
+        match builder.build().ok()?.pin_value(name, output.pin) {
+            Tristate::State(value) => Some(value),
+            Tristate::Undefined => None,
+        }
+    }
+
+    /// How the output `pin` of `name` follows its only input, evaluated once per component type.
+    /// Components with parameters are not considered.
+    fn buffer_kind(&mut self, name: &str, pin: PinNumber) -> Option<(BufferKind, PinNumber)> {
+        if !self.is_combinational(name) || self.is_constant(name) || !self.components[name].params.is_empty() {
+            return None;
+        }
+        let component_type = self.components[name].component_type.clone();
+        if let Some(kind) = self.buffers.get(&(component_type.clone(), pin)) {
+            return *kind;
+        }
+
+        let kind = match self.info(name)?.dependencies(pin)[..] {
+            [input] => {
+                let circuit = CircuitBuilder::new(self.factory)
+                    .add_component(&component_type, "buffer")
+                    .and_then(|builder| builder.add_component("input", "in"))
+                    .and_then(|builder| builder.link_components("in", 1, "buffer", input))
+                    .and_then(|builder| builder.build())
+                    .ok();
+
+                circuit.and_then(|mut circuit| {
+                    let mut outputs = [false, true].into_iter().map(|value| {
+                        circuit.components["in"].as_input().unwrap().set_state_for_next_tick(value.into());
+                        circuit.simulate();
+                        circuit.pin_value("buffer", pin)
+                    });
+
+                    match (outputs.next()?, outputs.next()?) {
+                        (Tristate::State(false), Tristate::State(true)) => Some((BufferKind::Identity, input)),
+                        (Tristate::State(true), Tristate::State(false)) => Some((BufferKind::Inverter, input)),
+                        _ => None,
+                    }
+                })
+            }
+            _ => None,
+        };
+
+        self.buffers.insert((component_type, pin), kind);
+        kind
+    }
+
+    fn collapse_buffers(&mut self, report: &mut OptimizationReport) -> bool {
+        let mut changed = false;
+        let mut netlist = self.netlist();
+
+        for name in self.order.clone() {
+            let outputs: Vec<PinNumber> = netlist.output_pins(&name);
+
+            for pin in outputs {
+                let output = PinRef::new(&name, pin);
+                let Some(source) = self.buffer_source(&netlist, &output) else {
+                    continue;
+                };
+                let Some(readers) = Self::readers_of(&netlist, &output) else {
+                    continue;
+                };
+                if readers.is_empty() || source.component == name {
+                    continue;
+                }
+
+                let readers: Vec<PinRef> = readers.into_iter().cloned().collect();
+                for reader in readers.iter() {
+                    self.relink(&output, &source, reader);
+                }
+                report.collapsed.push(output.to_string());
+                netlist = self.netlist();
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Pin driving the same value as `output`, through a buffer or two inverters in series.
+    fn buffer_source(&mut self, netlist: &Netlist, output: &PinRef) -> Option<PinRef> {
+        let (kind, input) = self.buffer_kind(&output.component, output.pin)?;
+        let driver = Self::single_driver(netlist, &PinRef::new(&output.component, input))?.clone();
+
+        match kind {
+            BufferKind::Identity => Some(driver),
+            BufferKind::Inverter => match self.buffer_kind(&driver.component, driver.pin)? {
+                (BufferKind::Inverter, input) => Self::single_driver(netlist, &PinRef::new(&driver.component, input)).cloned(),
+                (BufferKind::Identity, _) => None,
+            },
+        }
+    }
+
+    /// Whether `name` can be seen from outside the circuit: inputs, outputs, devices, memories, monitors,
+    /// and components whose pins are unknown.
+    fn is_observable(&self, name: &str) -> bool {
+        let component_type = &self.components[name].component_type;
+        let Some(parsed) = self.factory.parse_type(component_type) else {
+            return true;
+        };
+        if self.info(name).is_none() {
+            return true;
+        }
+        let Ok(component) = self.factory.create_component(parsed, &self.components[name].params) else {
+            return true;
+        };
+
+        component.as_input().is_some()
+            || component.as_output().is_some()
+            || component.as_bus_input().is_some()
+            || component.as_bus_output().is_some()
+            || component.as_keyboard().is_some()
+            || component.as_terminal().is_some()
+            || component.as_file_device().is_some()
+            || component.as_memory().is_some()
+            || component.as_monitor().is_some()
+    }
+
+    fn eliminate_dead_logic(&mut self, report: &mut OptimizationReport) -> bool {
+        let netlist = self.netlist();
+        let mut kept: HashSet<String> = self.order.iter().filter(|name| self.is_observable(name)).cloned().collect();
+        let mut pending: Vec<String> = kept.iter().cloned().collect();
+
+        while let Some(name) = pending.pop() {
+            let dependencies: Vec<&str> = if self.info(&name).is_some() {
+                netlist.dependencies(&name)
+            } else {
+                self.links
+                    .iter()
+                    .filter_map(|(left, right)| match (left.component == name, right.component == name) {
+                        (true, false) => Some(right.component.as_str()),
+                        (false, true) => Some(left.component.as_str()),
+                        _ => None,
+                    })
+                    .collect()
+            };
+
+            for dependency in dependencies {
+                if kept.insert(dependency.to_owned()) {
+                    pending.push(dependency.to_owned());
+                }
+            }
+        }
+
+        let removed: Vec<String> = self.order.iter().filter(|name| !kept.contains(*name)).cloned().collect();
+        for name in removed.iter() {
+            self.remove_component(name);
+        }
+        report.removed.extend(removed.iter().cloned());
+        !removed.is_empty()
+    }
+
+    fn into_instructions(mut self, report: &mut OptimizationReport) -> Vec<Instruction> {
+        let mut instructions: Vec<Instruction> = self
+            .order
+            .iter()
+            .map(|name| {
+                let component = &self.components[name];
+                Instruction::AddComponent {
+                    name: name.clone(),
+                    component_type: component.component_type.clone(),
+                    values: component.values.clone(),
+                    params: component.params.clone(),
+                }
+            })
+            .collect();
+
+        // A net is kept if all its links are still there: it declares them again.
+        for (label, pins) in self.nets.iter() {
+            let driver = &pins[0];
+            let positions: Option<Vec<usize>> = pins[1..]
+                .iter()
+                .map(|pin| {
+                    self.links
+                        .iter()
+                        .position(|(left, right)| (left == driver && right == pin) || (left == pin && right == driver))
+                })
+                .collect();
+            let positions = match positions {
+                Some(positions) if self.components.contains_key(&driver.component) => positions,
+                _ => {
+                    report.dropped_labels.push(label.clone());
+                    continue;
+                }
+            };
+
+            let mut positions = positions;
+            positions.sort_unstable();
+            for position in positions.into_iter().rev() {
+                self.links.remove(position);
+            }
+            instructions.push(Instruction::DeclareNet {
+                label: label.clone(),
+                pins: pins.iter().map(|pin| (pin.component.clone(), pin.pin)).collect(),
+            });
+        }
+
+        instructions.extend(self.links.into_iter().map(|(left, right)| Instruction::LinkComponents {
+            left: Endpoint::Pin(left.component, left.pin),
+            right: Endpoint::Pin(right.component, right.pin),
+        }));
+        instructions.extend(self.unresolved);
+        instructions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{optimize, OptimizationPasses, OptimizationReport};
+    use crate::circuit::builder::CircuitBuilder;
+    use crate::circuit::parser::parse_instructions;
+    use crate::circuit::{Circuit, PinRef};
+    use crate::components::factory::DefaultComponentFactory;
+
+    fn run(content: &str, passes: OptimizationPasses) -> (Circuit, OptimizationReport) {
+        let instructions = parse_instructions(content).unwrap();
+        let (instructions, report) = optimize(&instructions, &DefaultComponentFactory, passes);
+
+        (CircuitBuilder::default().add_instructions(&instructions).unwrap().build().unwrap(), report)
+    }
+
+    #[test]
+    fn test_constant_propagation() {
+        let content = "
+            .chipsets:
+            true t
+            false f
+            4081 and
+            4071 or
+            input a
+            output out
+            .links:
+            t:1 and:1
+            f:1 and:2
+            and:3 or:1
+            a:1 or:2
+            or:3 out:1
+        ";
+        let (mut circuit, report) = run(content, OptimizationPasses::ALL);
+
+        // and = 0, or = a | 0 is not constant.
+        assert_eq!(report.folded, vec!["and:3"]);
+        assert_eq!(report.removed, vec!["and", "t"]);
+        assert!(report.added.is_empty());
+        assert_eq!(circuit.netlist().component_names(), vec!["a", "f", "or", "out"]);
+
+        circuit.set_value("a", "1").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+    }
+
+    #[test]
+    fn test_conflicting_constants_not_folded() {
+        let content = "
+            .chipsets:
+            true t
+            false f
+            4069 inv
+            output o
+            .links:
+            t:1 inv:1
+            f:1 inv:1
+            inv:2 o:1
+        ";
+        let (mut optimized, report) = run(content, OptimizationPasses::ALL);
+        let (mut original, _) = run(content, OptimizationPasses::NONE);
+
+        assert!(report.folded.is_empty());
+        optimized.simulate();
+        original.simulate();
+        assert_eq!(optimized.get_output("o").unwrap(), original.get_output("o").unwrap());
+        assert_eq!(optimized.get_output("o").unwrap(), "U");
+    }
+
+    #[test]
+    fn test_constant_added() {
+        let content = ".chipsets:\nfalse f\n4011 nand\noutput out\n.links:\nf:1 nand:1\nf:1 nand:2\nnand:3 out:1\n";
+        let (circuit, report) = run(content, OptimizationPasses::ALL);
+
+        assert_eq!(report.folded, vec!["nand:3"]);
+        assert_eq!(report.added, vec!["true"]);
+        assert_eq!(report.removed, vec!["f", "nand"]);
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+    }
+
+    #[test]
+    fn test_buffer_collapsing() {
+        let content = "
+            .chipsets:
+            input a
+            4069 not
+            output out
+            output inverted
+            .links:
+            a:1 not:1
+            not:2 not:3
+            not:4 out:1
+            not:2 inverted:1
+        ";
+        let (mut circuit, report) = run(content, OptimizationPasses::ALL);
+
+        assert_eq!(report.collapsed, vec!["not:4"]);
+        assert!(report.removed.is_empty());
+        assert_eq!(circuit.netlist().linked_pins(&PinRef::new("out", 1)), vec![&PinRef::new("a", 1)]);
+
+        circuit.set_value("a", "1").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+        assert_eq!(circuit.get_output("inverted").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_dead_logic_elimination() {
+        let content = "
+            .chipsets:
+            input a
+            input b
+            4081 and
+            4069 not
+            output out
+            .links:
+            a:1 and:1
+            b:1 and:2
+            a:1 not:1
+            and:3 out:1
+        ";
+        let (_, report) = run(content, OptimizationPasses::ALL);
+
+        assert_eq!(report.removed, vec!["not"]);
+        assert_eq!(report.to_string(), "removed: not\n");
+    }
+
+    #[test]
+    fn test_dropped_labels() {
+        let content = "
+            .chipsets:
+            input a
+            4069 not
+            output out
+            output inverted
+            .links:
+            a:1 not:1
+            not:2 not:3
+            not:2 inverted:1
+            middle out:1
+            .nets:
+            middle: not:4
+            input: a:1 not:1
+        ";
+        let (circuit, report) = run(content, OptimizationPasses::ALL);
+
+        assert_eq!(report.dropped_labels, vec!["middle"]);
+        assert_eq!(circuit.netlist().net_labels(), vec!["input"]);
+    }
+
+    #[test]
+    fn test_no_passes() {
+        let content = ".chipsets:\ntrue t\n4069 not\noutput out\n.links:\nt:1 not:1\n";
+        let (circuit, report) = run(content, OptimizationPasses::NONE);
+
+        assert!(report.is_empty());
+        assert_eq!(circuit.netlist().component_names(), vec!["not", "out", "t"]);
+    }
+}
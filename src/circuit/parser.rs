@@ -1,12 +1,18 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
-use crate::components::PinNumber;
+use std::path::Path;
+
+use crate::components::factory::{ComponentFactory, DefaultComponentFactory};
+use crate::components::params::{CreateError, Params};
+use crate::components::{tristate::Tristate, PinNumber};
 
 use super::builder::{CircuitBuildError, CircuitBuilder};
+use super::checks::parse_checks;
 use super::Circuit;
 
-static CHIPSET_DECLARATION: &str = ".chipsets:";
-static LINK_DECLARATION: &str = ".links:";
+const CHIPSET_DECLARATION: &str = ".chipsets:";
+const LINK_DECLARATION: &str = ".links:";
+const NET_DECLARATION: &str = ".nets:";
 
 #[derive(Debug, Clone)]
 pub enum ParseCircuitError {
@@ -17,8 +23,12 @@ pub enum ParseCircuitError {
 #[derive(Debug, Clone)]
 pub enum SyntaxErrorKind {
     InvalidChipsetFormat,
+    InvalidInputValue { value: String },
     InvalidLinkFormat,
     InvalidLinkPin { pin: String },
+    InvalidNetFormat,
+    InvalidCheckFormat,
+    NetLabelDuplicate { label: String },
     FirstDeclarationMismatch,
     DeclarationDuplicate { declaration: String },
     Empty,
@@ -30,7 +40,10 @@ pub enum BuildErrorKind {
     ComponentNameOverride { name: String },
     ComponentNameUnknown { value: String },
     ComponentTypeUnknown { value: String },
+    NotAnInput { name: String },
+    NotExposable { name: String },
     ComponentLinkIssue { name: String, component_type: String, pin: PinNumber },
+    ComponentCreation { name: String, error: CreateError },
 }
 
 impl std::fmt::Display for ParseCircuitError {
@@ -46,7 +59,10 @@ impl std::fmt::Display for SyntaxErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::InvalidChipsetFormat => {
-                write!(f, "Chipset declaration must respect this form: type name")
+                write!(f, "Chipset declaration must respect this form: type name [key=value ...]")
+            }
+            Self::InvalidInputValue { value } => {
+                write!(f, "\"{value}\" is not a valid input value")
             }
             Self::InvalidLinkFormat => {
                 write!(f, "Link declaration must respect this form: name1:pin1 name2:pin2")
@@ -54,6 +70,15 @@ impl std::fmt::Display for SyntaxErrorKind {
             Self::InvalidLinkPin { pin } => {
                 write!(f, "\"{pin}\" is not a valid pin number")
             }
+            Self::InvalidNetFormat => {
+                write!(f, "Net declaration must respect this form: label: name1:pin1 name2:pin2 ...")
+            }
+            Self::InvalidCheckFormat => {
+                write!(f, "Check pragma must respect this form: #! check tick=N name1=value1 name2=value2 ...")
+            }
+            Self::NetLabelDuplicate { label } => {
+                write!(f, "A net with label \"{label}\" already exists")
+            }
             Self::FirstDeclarationMismatch => {
                 write!(f, "The first instruction must be the chipsets declaration")
             }
@@ -78,9 +103,16 @@ impl std::fmt::Display for BuildErrorKind {
             Self::ComponentTypeUnknown { value } => {
                 write!(f, "Unknown component type \"{value}\".")
             }
+            Self::NotAnInput { name } => {
+                write!(f, "\"{name}\" is not an input, it cannot take a sequence of values.")
+            }
+            Self::NotExposable { name } => {
+                write!(f, "\"{name}\" is neither an input nor an output, it cannot be a pin of a subcircuit.")
+            }
             Self::ComponentLinkIssue { name, component_type, pin } => {
                 write!(f, "\"{name}\": {component_type} component does not have pin {pin}.")
             }
+            Self::ComponentCreation { name, error } => write!(f, "\"{name}\": {error}."),
         }
     }
 }
@@ -95,9 +127,17 @@ where
             CircuitBuildError::ComponentNameOverride(name) => BuildErrorKind::ComponentNameOverride { name: name.to_owned() },
             CircuitBuildError::ComponentNameUnknown(value) => BuildErrorKind::ComponentNameUnknown { value: value.to_owned() },
             CircuitBuildError::ComponentTypeUnknown(value) => BuildErrorKind::ComponentTypeUnknown { value: value.to_owned() },
+            CircuitBuildError::NotAnInput(name) => BuildErrorKind::NotAnInput { name: name.to_owned() },
+            CircuitBuildError::NotExposable(name) => BuildErrorKind::NotExposable { name: name.to_owned() },
             CircuitBuildError::ComponentLinkIssue(name, component_type, pin) => {
                 BuildErrorKind::ComponentLinkIssue { name: name.to_owned(), component_type: component_type.to_string(), pin }
             }
+            CircuitBuildError::SubcircuitLinkIssue(name, pin) => {
+                BuildErrorKind::ComponentLinkIssue { name: name.to_owned(), component_type: "subcircuit".to_owned(), pin }
+            }
+            CircuitBuildError::ComponentCreation(name, error) => {
+                BuildErrorKind::ComponentCreation { name: name.to_owned(), error }
+            }
         }
     }
 }
@@ -106,48 +146,52 @@ pub struct Parser;
 
 impl Parser {
     pub fn read(input: &str) -> Result<Circuit, ParseCircuitError> {
-        let lines = Self::parse_lines(input).map_err(|(line, kind)| ParseCircuitError::Syntax { line, kind })?;
-
-        let mut builder = CircuitBuilder::default();
-
-        for line in lines.into_iter() {
-            let build_result = match line.instruction {
-                Instruction::AddComponent { name, component_type } => builder.add_component(component_type, name),
-                Instruction::LinkComponents { left_name, left_pin, right_name, right_pin } => {
-                    builder.link_components(left_name, left_pin, right_name, right_pin)
-                }
-            };
-
-            builder = build_result.map_err(|err| ParseCircuitError::Build { line: line.index, kind: err.into() })?;
-        }
-
-        builder.build().map_err(|err| ParseCircuitError::Build { line: 0, kind: err.into() })
+        Self::read_with_factory(input, DefaultComponentFactory, None)
     }
 
-    fn parse_lines<'a>(input: &'a str) -> Result<Vec<Line<'a>>, (usize, SyntaxErrorKind)> {
-        let mut output: Vec<Line<'a>> = Vec::new();
+    /// File parameters are relative to `base_path` if given, to the working directory otherwise.
+    pub fn read_with_factory<Factory>(
+        input: &str,
+        factory: Factory,
+        base_path: Option<&Path>,
+    ) -> Result<Circuit, ParseCircuitError>
+    where
+        Factory: ComponentFactory,
+        Factory::Type: std::fmt::Debug + Clone + ToString,
+    {
+        let lines = Self::parse_lines(input).map_err(|(line, kind)| ParseCircuitError::Syntax { line, kind })?;
+        let embedded_checks = parse_checks(input).map_err(|(line, kind)| ParseCircuitError::Syntax { line, kind })?;
+        let (line_numbers, instructions): (Vec<usize>, Vec<Instruction>) =
+            lines.into_iter().map(|line| (line.index, line.instruction)).unzip();
+
+        let builder = CircuitBuilder::new(factory);
+        let builder = match base_path {
+            Some(base_path) => builder.base_path(base_path),
+            None => builder,
+        };
+
+        builder
+            .add_instructions(&instructions)
+            .map_err(|(index, err)| ParseCircuitError::Build { line: line_numbers[index], kind: err.into() })?
+            .build()
+            .map(|circuit| Circuit { embedded_checks, ..circuit })
+            .map_err(|err| ParseCircuitError::Build { line: 0, kind: err.into() })
+    }
 
-        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-        enum Declaration {
-            Chipsets,
-            Links,
-        }
+    fn parse_lines(input: &str) -> Result<Vec<Line>, (usize, SyntaxErrorKind)> {
+        let mut output: Vec<Line> = Vec::new();
 
         let mut current_declaration: Option<Declaration> = None;
         let mut already_declared: HashSet<Declaration> = HashSet::new();
 
-        let initializers: HashMap<&str, Declaration> =
-            HashMap::from([(CHIPSET_DECLARATION, Declaration::Chipsets), (LINK_DECLARATION, Declaration::Links)]);
-
         for (index, content) in input.lines().enumerate() {
             let index = index + 1;
-            let content = if let Some(comment_idx) = content.find('#') { &content[..comment_idx] } else { content };
-            let content = content.trim();
+            let content = Self::strip_comment(content);
             if content.is_empty() {
                 continue;
             }
 
-            if let Some(&declaration) = initializers.get(content) {
+            if let Some(declaration) = Declaration::from_header(content) {
                 if !already_declared.insert(declaration) {
                     return Err((index, SyntaxErrorKind::DeclarationDuplicate { declaration: content.to_owned() }));
                 }
@@ -156,13 +200,7 @@ impl Parser {
                 }
                 current_declaration = Some(declaration);
             } else {
-                let instruction: Result<Instruction<'a>, SyntaxErrorKind> = match current_declaration {
-                    Some(Declaration::Chipsets) => Self::parse_chipset_line(content),
-                    Some(Declaration::Links) => Self::parse_link_line(content),
-                    None => Err(SyntaxErrorKind::FirstDeclarationMismatch),
-                };
-
-                let instruction = instruction.map_err(|kind| (index, kind))?;
+                let instruction = Self::parse_instruction(current_declaration, content).map_err(|kind| (index, kind))?;
 
                 output.push(Line { index, instruction })
             }
@@ -172,53 +210,207 @@ impl Parser {
             return Err((0, SyntaxErrorKind::Empty));
         }
 
-        Ok(output)
+        match Self::net_label_errors(output.iter().map(|line| (line.index, &line.instruction))).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(output),
+        }
     }
 
-    fn parse_chipset_line<'a>(content: &'a str) -> Result<Instruction<'a>, SyntaxErrorKind> {
-        let content: Vec<&str> = content.split_whitespace().collect();
+    /// Line content without its comment and surrounding whitespace.
+    pub(super) fn strip_comment(content: &str) -> &str {
+        let content = if let Some(comment_idx) = content.find('#') { &content[..comment_idx] } else { content };
 
-        if let [component_type, component_name] = content[..] {
-            Ok(Instruction::AddComponent { name: component_name, component_type })
-        } else {
-            Err(SyntaxErrorKind::InvalidChipsetFormat)
+        content.trim()
+    }
+
+    /// Parses a line which is not a declaration header, `content` being stripped of its comment.
+    pub(super) fn parse_instruction(declaration: Option<Declaration>, content: &str) -> Result<Instruction, SyntaxErrorKind> {
+        match declaration {
+            Some(Declaration::Chipsets) => Self::parse_chipset_line(content),
+            Some(Declaration::Links) => Self::parse_link_line(content),
+            Some(Declaration::Nets) => Self::parse_net_line(content),
+            None => Err(SyntaxErrorKind::FirstDeclarationMismatch),
         }
     }
 
-    fn parse_link_line<'a>(content: &'a str) -> Result<Instruction<'a>, SyntaxErrorKind> {
+    fn parse_chipset_line(content: &str) -> Result<Instruction, SyntaxErrorKind> {
         let content: Vec<&str> = content.split_whitespace().collect();
-        if let [left_component_link, right_component_link] = content[..] {
-            fn parse_simple_link<'a>(content: &'a str) -> Result<(&'a str, PinNumber), SyntaxErrorKind> {
-                let content: Vec<&str> = content.split(':').collect();
-
-                if let [component_name, component_pin] = content[..] {
-                    Ok((
-                        component_name,
-                        component_pin
-                            .parse::<PinNumber>()
-                            .map_err(|_| SyntaxErrorKind::InvalidLinkPin { pin: component_pin.to_owned() })?,
-                    ))
-                } else {
-                    Err(SyntaxErrorKind::InvalidLinkFormat)
-                }
+
+        let [component_type, component_name, ref parameters @ ..] = content[..] else {
+            return Err(SyntaxErrorKind::InvalidChipsetFormat);
+        };
+        let mut values: Option<Vec<Tristate>> = None;
+        let mut params = Params::new();
+
+        for parameter in parameters {
+            let (key, value) = parameter.split_once('=').ok_or(SyntaxErrorKind::InvalidChipsetFormat)?;
+            if key.is_empty() || params.get(key).is_some() || (key == "values" && values.is_some()) {
+                return Err(SyntaxErrorKind::InvalidChipsetFormat);
             }
 
-            let (left_name, left_pin) = parse_simple_link(left_component_link)?;
-            let (right_name, right_pin) = parse_simple_link(right_component_link)?;
+            // The values of an input are a sequence fed by the circuit, not a parameter of the component.
+            if key == "values" {
+                values = Some(
+                    value
+                        .split(',')
+                        .map(|value| value.parse().map_err(|_| SyntaxErrorKind::InvalidInputValue { value: value.to_owned() }))
+                        .collect::<Result<_, _>>()?,
+                );
+            } else {
+                params.insert(key, value);
+            }
+        }
+
+        Ok(Instruction::AddComponent {
+            name: component_name.to_owned(),
+            component_type: component_type.to_owned(),
+            values,
+            params,
+        })
+    }
 
-            Ok(Instruction::LinkComponents { left_name, left_pin, right_name, right_pin })
+    fn parse_link_line(content: &str) -> Result<Instruction, SyntaxErrorKind> {
+        let content: Vec<&str> = content.split_whitespace().collect();
+        if let [left_component_link, right_component_link] = content[..] {
+            let left = Self::parse_endpoint(left_component_link)?;
+            let right = Self::parse_endpoint(right_component_link)?;
+
+            Ok(Instruction::LinkComponents { left, right })
         } else {
             Err(SyntaxErrorKind::InvalidLinkFormat)
         }
     }
+
+    fn parse_net_line(content: &str) -> Result<Instruction, SyntaxErrorKind> {
+        let Some((label, pins)) = content.split_once(':') else {
+            return Err(SyntaxErrorKind::InvalidNetFormat);
+        };
+        let label = label.trim();
+        if label.is_empty() || label.contains(char::is_whitespace) {
+            return Err(SyntaxErrorKind::InvalidNetFormat);
+        }
+
+        let pins: Vec<(String, PinNumber)> = pins
+            .split_whitespace()
+            .map(|pin| match Self::parse_endpoint(pin)? {
+                Endpoint::Pin(name, pin) => Ok((name, pin)),
+                Endpoint::Label(_) => Err(SyntaxErrorKind::InvalidNetFormat),
+            })
+            .collect::<Result<_, _>>()?;
+        if pins.is_empty() {
+            return Err(SyntaxErrorKind::InvalidNetFormat);
+        }
+
+        Ok(Instruction::DeclareNet { label: label.to_owned(), pins })
+    }
+
+    /// Parses `name:pin`, or a bare net label.
+    fn parse_endpoint(content: &str) -> Result<Endpoint, SyntaxErrorKind> {
+        let content: Vec<&str> = content.split(':').collect();
+
+        match content[..] {
+            [component_name, component_pin] => Ok(Endpoint::Pin(
+                component_name.to_owned(),
+                component_pin
+                    .parse::<PinNumber>()
+                    .map_err(|_| SyntaxErrorKind::InvalidLinkPin { pin: component_pin.to_owned() })?,
+            )),
+            [label] => Ok(Endpoint::Label(label.to_owned())),
+            _ => Err(SyntaxErrorKind::InvalidLinkFormat),
+        }
+    }
+
+    /// Net labels must be unique, and links can only use declared labels.
+    pub(super) fn net_label_errors<'a, I>(lines: I) -> Vec<(usize, SyntaxErrorKind)>
+    where
+        I: Iterator<Item = (usize, &'a Instruction)> + Clone,
+    {
+        let mut labels: HashSet<&str> = HashSet::new();
+        let mut errors: Vec<(usize, SyntaxErrorKind)> = Vec::new();
+
+        for (index, instruction) in lines.clone() {
+            if let Instruction::DeclareNet { label, .. } = instruction {
+                if !labels.insert(label) {
+                    errors.push((index, SyntaxErrorKind::NetLabelDuplicate { label: label.to_owned() }));
+                }
+            }
+        }
+        for (index, instruction) in lines {
+            if let Instruction::LinkComponents { left, right } = instruction {
+                let is_unknown =
+                    |endpoint: &Endpoint| matches!(endpoint, Endpoint::Label(label) if !labels.contains(label.as_str()));
+
+                if is_unknown(left) || is_unknown(right) {
+                    errors.push((index, SyntaxErrorKind::InvalidLinkFormat));
+                }
+            }
+        }
+
+        errors.sort_by_key(|(index, _)| *index);
+        errors
+    }
+}
+
+/// Parses `.nts` content into its instructions, in source order, without building the circuit.
+/// They can be transformed then given to `CircuitBuilder::add_instructions()`.
+pub fn parse_instructions(content: &str) -> Result<Vec<Instruction>, ParseCircuitError> {
+    let lines = Parser::parse_lines(content).map_err(|(line, kind)| ParseCircuitError::Syntax { line, kind })?;
+
+    Ok(lines.into_iter().map(|line| line.instruction).collect())
 }
 
-struct Line<'a> {
+struct Line {
     pub index: usize,
-    pub instruction: Instruction<'a>,
+    pub instruction: Instruction,
+}
+
+/// Section of a `.nts` file, opened by its header line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum Declaration {
+    Chipsets,
+    Links,
+    Nets,
+}
+
+impl Declaration {
+    /// Declaration opened by `content`, if it is a header line stripped of its comment.
+    pub(super) fn from_header(content: &str) -> Option<Self> {
+        match content {
+            CHIPSET_DECLARATION => Some(Self::Chipsets),
+            LINK_DECLARATION => Some(Self::Links),
+            NET_DECLARATION => Some(Self::Nets),
+            _ => None,
+        }
+    }
+}
+
+/// Statement of a `.nts` file.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Instruction {
+    /// `values` is the sequence given to an input, one value per tick.
+    /// `params` are the other `key=value` pairs of the chipset line.
+    AddComponent {
+        name: String,
+        component_type: String,
+        values: Option<Vec<Tristate>>,
+        params: Params,
+    },
+    LinkComponents {
+        left: Endpoint,
+        right: Endpoint,
+    },
+    /// The first pin drives the net.
+    DeclareNet {
+        label: String,
+        pins: Vec<(String, PinNumber)>,
+    },
 }
 
-enum Instruction<'a> {
-    AddComponent { name: &'a str, component_type: &'a str },
-    LinkComponents { left_name: &'a str, left_pin: PinNumber, right_name: &'a str, right_pin: PinNumber },
+/// One side of a link.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Endpoint {
+    /// `name:pin`
+    Pin(String, PinNumber),
+    /// Label of a net, standing for its first pin.
+    Label(String),
 }
@@ -0,0 +1,262 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::components::{tristate::Tristate, Tick};
+
+use super::History;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    UnexpectedToken(String),
+    UnexpectedEnd,
+    InvalidValue(String),
+    UnknownSignal(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedToken(token) => write!(f, "Unexpected \"{token}\" in query"),
+            Self::UnexpectedEnd => write!(f, "Unexpected end of query"),
+            Self::InvalidValue(value) => write!(f, "Invalid value \"{value}\""),
+            Self::UnknownSignal(name) => write!(f, "Unknown signal \"{name}\""),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Condition {
+    Equals(String, Tristate),
+    Rose(String),
+    Fell(String),
+    Changed(String),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+/// Condition on the values recorded at a tick, such as `out == 1 && clk rose`.
+///
+/// A signal is compared with `==` or `!=` to `0`, `1` or `U`, or followed by `rose`, `fell` or `changed`,
+/// which compare its value with the one recorded just before (never true for the first record).
+/// Conditions combine with `!`, `&&`, `||` and parentheses, `&&` binding tighter than `||`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    condition: Condition,
+}
+
+const OPERATORS: [&str; 7] = ["==", "!=", "&&", "||", "!", "(", ")"];
+
+fn tokenize(content: &str) -> Vec<&str> {
+    let mut tokens: Vec<&str> = Vec::new();
+    let mut rest = content.trim_start();
+
+    while !rest.is_empty() {
+        let length = match OPERATORS.iter().find(|operator| rest.starts_with(*operator)) {
+            Some(operator) => operator.len(),
+            None => rest.find(|c: char| c.is_whitespace() || "=!&|()".contains(c)).unwrap_or(rest.len()).max(1),
+        };
+
+        tokens.push(&rest[..length]);
+        rest = rest[length..].trim_start();
+    }
+
+    tokens
+}
+
+struct QueryParser<'a> {
+    tokens: Vec<&'a str>,
+    position: usize,
+}
+
+impl<'a> QueryParser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Result<&'a str, QueryError> {
+        let token = self.peek().ok_or(QueryError::UnexpectedEnd)?;
+
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn name(&mut self) -> Result<String, QueryError> {
+        match self.next()? {
+            token if OPERATORS.contains(&token) => Err(QueryError::UnexpectedToken(token.to_owned())),
+            token => Ok(token.to_owned()),
+        }
+    }
+
+    fn or(&mut self) -> Result<Condition, QueryError> {
+        let mut condition = self.and()?;
+
+        while self.peek() == Some("||") {
+            self.position += 1;
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, QueryError> {
+        let mut condition = self.unary()?;
+
+        while self.peek() == Some("&&") {
+            self.position += 1;
+            condition = Condition::And(Box::new(condition), Box::new(self.unary()?));
+        }
+        Ok(condition)
+    }
+
+    fn unary(&mut self) -> Result<Condition, QueryError> {
+        match self.peek() {
+            Some("!") => {
+                self.position += 1;
+                Ok(Condition::Not(Box::new(self.unary()?)))
+            }
+            Some("(") => {
+                self.position += 1;
+                let condition = self.or()?;
+
+                match self.next()? {
+                    ")" => Ok(condition),
+                    token => Err(QueryError::UnexpectedToken(token.to_owned())),
+                }
+            }
+            _ => self.atom(),
+        }
+    }
+
+    fn atom(&mut self) -> Result<Condition, QueryError> {
+        let name = self.name()?;
+
+        match self.next()? {
+            operator @ ("==" | "!=") => {
+                let value = self.name()?;
+                let value: Tristate = value.parse().map_err(|_| QueryError::InvalidValue(value))?;
+                let condition = Condition::Equals(name, value);
+
+                Ok(if operator == "==" { condition } else { Condition::Not(Box::new(condition)) })
+            }
+            "rose" => Ok(Condition::Rose(name)),
+            "fell" => Ok(Condition::Fell(name)),
+            "changed" => Ok(Condition::Changed(name)),
+            token => Err(QueryError::UnexpectedToken(token.to_owned())),
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = QueryParser { tokens: tokenize(s), position: 0 };
+        let condition = parser.or()?;
+
+        match parser.peek() {
+            Some(token) => Err(QueryError::UnexpectedToken(token.to_owned())),
+            None => Ok(Self { condition }),
+        }
+    }
+}
+
+impl Condition {
+    fn check(&self, history: &History) -> Result<(), QueryError> {
+        match self {
+            Self::Equals(name, _) | Self::Rose(name) | Self::Fell(name) | Self::Changed(name) => {
+                history.values(name).map(|_| ()).ok_or_else(|| QueryError::UnknownSignal(name.clone()))
+            }
+            Self::Not(condition) => condition.check(history),
+            Self::And(left, right) | Self::Or(left, right) => left.check(history).and(right.check(history)),
+        }
+    }
+
+    /// Whether the condition holds for record `index`, every signal being known.
+    fn matches(&self, history: &History, index: usize) -> bool {
+        let transition = |name: &str| {
+            let values = history.values(name).unwrap();
+
+            (index.checked_sub(1).map(|previous| values[previous]), values[index])
+        };
+
+        match self {
+            Self::Equals(name, value) => history.values(name).unwrap()[index] == *value,
+            Self::Rose(name) => transition(name) == (Some(Tristate::State(false)), Tristate::State(true)),
+            Self::Fell(name) => transition(name) == (Some(Tristate::State(true)), Tristate::State(false)),
+            Self::Changed(name) => matches!(transition(name), (Some(previous), value) if previous != value),
+            Self::Not(condition) => !condition.matches(history, index),
+            Self::And(left, right) => left.matches(history, index) && right.matches(history, index),
+            Self::Or(left, right) => left.matches(history, index) || right.matches(history, index),
+        }
+    }
+}
+
+impl History {
+    /// Recorded ticks at which `query` holds, e.g. `"out == 1 && clk rose"`. See `Query` for the syntax.
+    pub fn query(&self, query: &str) -> Result<Vec<Tick>, QueryError> {
+        self.query_parsed(&query.parse()?)
+    }
+
+    /// Same as `query()`, with a query parsed beforehand.
+    pub fn query_parsed(&self, query: &Query) -> Result<Vec<Tick>, QueryError> {
+        query.condition.check(self)?;
+
+        Ok((0..self.len()).filter(|&index| query.condition.matches(self, index)).map(|index| self.ticks()[index]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Query, QueryError};
+    use crate::circuit::{Circuit, History};
+    use crate::components::Tick;
+
+    fn record() -> History {
+        let mut circuit: Circuit =
+            ".chipsets:\nclock clk\ninput en\n4081 gate\noutput out\n.links:\nclk:1 gate:1\nen:1 gate:2\ngate:3 out:1\n"
+                .parse()
+                .unwrap();
+        let mut history = History::new();
+
+        circuit.set_value("clk", "0").unwrap();
+        for en in ["0", "0", "1", "1", "1", "U"] {
+            circuit.set_value("en", en).unwrap();
+            circuit.simulate();
+            history.record(&circuit);
+        }
+        history
+    }
+
+    fn ticks(history: &History, query: &str) -> Vec<u64> {
+        history.query(query).unwrap().into_iter().map(Tick::get).collect()
+    }
+
+    #[test]
+    fn test_query() {
+        let history = record();
+
+        // clk: 0 1 0 1 0 1, en: 0 0 1 1 1 U
+        assert_eq!(ticks(&history, "out == 1 && clk rose"), vec![4]);
+        assert_eq!(ticks(&history, "clk rose"), vec![2, 4, 6]);
+        assert_eq!(ticks(&history, "clk fell || en == U"), vec![3, 5, 6]);
+        assert_eq!(ticks(&history, "!(en==0) && en changed"), vec![3, 6]);
+        assert_eq!(ticks(&history, "out != 0"), vec![4, 6]);
+        assert_eq!(ticks(&history, "clk == 1 && (en == 1 || out == U)"), vec![4, 6]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let history = record();
+
+        assert_eq!(history.query("nope rose"), Err(QueryError::UnknownSignal("nope".to_owned())));
+        assert_eq!(history.query("out == 2"), Err(QueryError::InvalidValue("2".to_owned())));
+        assert_eq!(history.query("out == 1 &&"), Err(QueryError::UnexpectedEnd));
+        assert_eq!(history.query("(out rose"), Err(QueryError::UnexpectedEnd));
+        assert_eq!(history.query("out rose)"), Err(QueryError::UnexpectedToken(")".to_owned())));
+        assert_eq!(history.query("out jumped").unwrap_err().to_string(), "Unexpected \"jumped\" in query");
+
+        let query: Query = "clk rose".parse().unwrap();
+        assert_eq!(history.query_parsed(&query).unwrap().len(), 3);
+        assert!(History::new().query_parsed(&query).is_err());
+    }
+}
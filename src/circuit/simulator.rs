@@ -0,0 +1,162 @@
+use crate::components::tristate::Tristate;
+
+use super::{Circuit, Mismatch, SetInputError, Stimulus};
+
+/// Independent circuits advanced in lock-step, e.g. the variants of a parameter sweep driven by the same inputs.
+///
+/// Circuits are kept in the order they were added, and simulated one after the other at each tick:
+/// their components are not `Send`, so they cannot be spread over threads.
+#[derive(Default)]
+pub struct Simulator {
+    circuits: Vec<(String, Circuit)>,
+}
+
+impl Simulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `circuit` as `name`, returning the circuit it replaces.
+    pub fn add(&mut self, name: &str, circuit: Circuit) -> Option<Circuit> {
+        match self.circuits.iter_mut().find(|(other, _)| other == name) {
+            Some((_, previous)) => Some(std::mem::replace(previous, circuit)),
+            None => {
+                self.circuits.push((name.to_owned(), circuit));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Circuit> {
+        let index = self.circuits.iter().position(|(other, _)| other == name)?;
+
+        Some(self.circuits.remove(index).1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.circuits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.circuits.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.circuits.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn circuit(&self, name: &str) -> Option<&Circuit> {
+        self.circuits.iter().find(|(other, _)| other == name).map(|(_, circuit)| circuit)
+    }
+
+    pub fn circuit_mut(&mut self, name: &str) -> Option<&mut Circuit> {
+        self.circuits.iter_mut().find(|(other, _)| other == name).map(|(_, circuit)| circuit)
+    }
+
+    /// Sets the input `name` of every circuit which has one, returning how many do.
+    pub fn set_value<'a>(&self, name: &'a str, value: &'a str) -> Result<usize, SetInputError<'a>> {
+        value.parse::<Tristate>().map_err(|_| SetInputError::ValueParseError(value))?;
+
+        Ok(self.circuits.iter().filter(|(_, circuit)| circuit.set_value(name, value).is_ok()).count())
+    }
+
+    /// Runs one tick on every circuit.
+    pub fn simulate(&mut self) {
+        for (_, circuit) in self.circuits.iter_mut() {
+            circuit.simulate();
+        }
+    }
+
+    /// Value of the output `name` of each circuit, `None` for the circuits without it.
+    pub fn get_output(&self, name: &str) -> Vec<(&str, Option<String>)> {
+        self.circuits.iter().map(|(circuit_name, circuit)| (circuit_name.as_str(), circuit.get_output(name))).collect()
+    }
+
+    /// Replays `stimulus` on every circuit, each step being applied to all of them before the next one,
+    /// and returns the failed expectations of each circuit.
+    pub fn run(&mut self, stimulus: &Stimulus) -> Vec<(&str, Vec<Mismatch>)> {
+        let mut mismatches: Vec<Vec<Mismatch>> = vec![Vec::new(); self.circuits.len()];
+
+        for index in 0..stimulus.nb_steps() {
+            for ((_, circuit), mismatches) in self.circuits.iter_mut().zip(mismatches.iter_mut()) {
+                stimulus.run_step(index, circuit, mismatches);
+            }
+        }
+
+        self.names().zip(mismatches).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Simulator;
+    use crate::circuit::{Circuit, Stimulus};
+
+    fn gate(component_type: &str) -> Circuit {
+        format!(
+            "
+            .chipsets:
+            input a
+            input b
+            {component_type} gate
+            output out
+            .links:
+            a:1 gate:1
+            b:1 gate:2
+            gate:3 out:1
+            "
+        )
+        .parse()
+        .unwrap()
+    }
+
+    fn simulator() -> Simulator {
+        let mut simulator = Simulator::new();
+
+        for component_type in ["4081", "4071", "4030"] {
+            assert!(simulator.add(component_type, gate(component_type)).is_none());
+        }
+        simulator
+    }
+
+    #[test]
+    fn test_lock_step() {
+        let mut simulator = simulator();
+
+        assert_eq!(simulator.set_value("a", "1").unwrap(), 3);
+        assert_eq!(simulator.set_value("b", "0").unwrap(), 3);
+        assert_eq!(simulator.set_value("c", "0").unwrap(), 0);
+        assert!(simulator.set_value("a", "2").is_err());
+        simulator.simulate();
+
+        let outputs: Vec<(&str, Option<String>)> = simulator.get_output("out");
+        assert_eq!(outputs, vec![("4081", Some("0".to_owned())), ("4071", Some("1".to_owned())), ("4030", Some("1".to_owned()))]);
+        assert!(simulator.names().all(|name| simulator.circuit(name).unwrap().current_tick().get() == 1));
+
+        assert!(simulator.add("4071", gate("4001")).is_some());
+        assert_eq!(simulator.len(), 3);
+        assert_eq!(simulator.circuit("4071").unwrap().current_tick().get(), 0);
+        assert!(simulator.remove("4030").is_some());
+        assert!(simulator.remove("4030").is_none());
+        assert_eq!(simulator.names().collect::<Vec<_>>(), vec!["4081", "4071"]);
+    }
+
+    #[test]
+    fn test_shared_stimulus() {
+        let mut simulator = simulator();
+        let stimulus: Stimulus = "a=1\nb=1\nsimulate\n# expect: out=1\nb=0\nsimulate\n# expect: out=0".parse().unwrap();
+
+        let results = simulator.run(&stimulus);
+        let failed_lines: Vec<(&str, Vec<String>)> =
+            results.iter().map(|(name, mismatches)| (*name, mismatches.iter().map(ToString::to_string).collect())).collect();
+        assert_eq!(
+            failed_lines,
+            vec![
+                ("4081", vec![]),
+                ("4071", vec!["line 7: expected out=0, got 1".to_owned()]),
+                ("4030", vec!["line 4: expected out=1, got 0".to_owned(), "line 7: expected out=0, got 1".to_owned()]),
+            ]
+        );
+        assert_eq!(simulator.circuit_mut("4030").unwrap().current_tick().get(), 2);
+    }
+}
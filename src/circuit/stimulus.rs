@@ -0,0 +1,225 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::components::{tristate::Tristate, Tick};
+
+use super::Circuit;
+
+#[derive(Debug, Clone)]
+pub struct ParseStimulusError {
+    pub line: usize,
+    pub kind: StimulusErrorKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum StimulusErrorKind {
+    InvalidCommand { command: String },
+    InvalidValue { value: String },
+}
+
+impl fmt::Display for ParseStimulusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for StimulusErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCommand { command } => write!(f, "Unknown command \"{command}\""),
+            Self::InvalidValue { value } => write!(f, "Invalid value \"{value}\""),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Step {
+    Set(String, Tristate),
+    Simulate,
+    Expect(String, Tristate),
+}
+
+/// Step of a stimulus which did not go as expected.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Mismatch {
+    /// `name` is not an input of the circuit.
+    Input { line: usize, name: String },
+    /// `got` is `None` if `name` is not an output of the circuit.
+    Output { line: usize, name: String, expected: Tristate, got: Option<Tristate> },
+    /// The circuit was already past `tick` when the check was run.
+    Tick { line: usize, tick: Tick },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Input { line, name } => write!(f, "line {line}: \"{name}\" is not an input"),
+            Self::Output { line, name, expected, got: Some(got) } => {
+                write!(f, "line {line}: expected {name}={expected}, got {got}")
+            }
+            Self::Output { line, name, got: None, .. } => write!(f, "line {line}: \"{name}\" is not an output"),
+            Self::Tick { line, tick } => write!(f, "line {line}: tick {tick} is already past"),
+        }
+    }
+}
+
+/// Shell commands replayed on a circuit, as written by `Testbench::stimulus()`:
+/// `name=value`, `simulate`, and `# expect: name=value` checks (`?` values are not checked).
+/// `display` and other comments are ignored.
+#[derive(Debug, Default, Clone)]
+pub struct Stimulus {
+    steps: Vec<(usize, Step)>,
+}
+
+impl Stimulus {
+    /// Replays every step on `circuit`, returning the failed expectations.
+    pub fn run(&self, circuit: &mut Circuit) -> Vec<Mismatch> {
+        let mut mismatches: Vec<Mismatch> = Vec::new();
+
+        for index in 0..self.steps.len() {
+            self.run_step(index, circuit, &mut mismatches);
+        }
+
+        mismatches
+    }
+
+    pub(super) fn nb_steps(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub(super) fn run_step(&self, index: usize, circuit: &mut Circuit, mismatches: &mut Vec<Mismatch>) {
+        let (line, step) = &self.steps[index];
+
+        match step {
+            Step::Set(name, value) => {
+                if circuit.set_value(name, &value.to_string()).is_err() {
+                    mismatches.push(Mismatch::Input { line: *line, name: name.clone() });
+                }
+            }
+            Step::Simulate => circuit.simulate(),
+            Step::Expect(name, expected) => {
+                let got = circuit.get_output(name).map(|value| value.parse().unwrap());
+
+                if got != Some(*expected) {
+                    mismatches.push(Mismatch::Output { line: *line, name: name.clone(), expected: *expected, got });
+                }
+            }
+        }
+    }
+
+    /// Number of `simulate` steps.
+    pub fn nb_ticks(&self) -> usize {
+        self.steps.iter().filter(|(_, step)| *step == Step::Simulate).count()
+    }
+
+    /// Number of checked expectations.
+    pub fn nb_expectations(&self) -> usize {
+        self.steps.iter().filter(|(_, step)| matches!(step, Step::Expect(..))).count()
+    }
+}
+
+impl FromStr for Stimulus {
+    type Err = ParseStimulusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut steps: Vec<(usize, Step)> = Vec::new();
+
+        for (index, content) in s.lines().enumerate() {
+            let line = index + 1;
+            let error = |kind: StimulusErrorKind| ParseStimulusError { line, kind };
+            let content = content.trim();
+
+            let (assignment, expect) = match content.strip_prefix('#') {
+                Some(comment) => match comment.trim().strip_prefix("expect:") {
+                    Some(assignment) => (assignment.trim(), true),
+                    None => continue,
+                },
+                None => (content, false),
+            };
+
+            match assignment {
+                "" | "display" if !expect => (),
+                "simulate" if !expect => steps.push((line, Step::Simulate)),
+                _ => {
+                    let Some((name, value)) = assignment.split_once('=') else {
+                        return Err(error(StimulusErrorKind::InvalidCommand { command: assignment.to_owned() }));
+                    };
+                    let (name, value) = (name.trim(), value.trim());
+                    if expect && value == "?" {
+                        continue;
+                    }
+
+                    let value: Tristate =
+                        value.parse().map_err(|_| error(StimulusErrorKind::InvalidValue { value: value.to_owned() }))?;
+                    steps.push((
+                        line,
+                        if expect { Step::Expect(name.to_owned(), value) } else { Step::Set(name.to_owned(), value) },
+                    ));
+                }
+            }
+        }
+
+        Ok(Self { steps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mismatch, Stimulus, StimulusErrorKind};
+    use crate::circuit::{Circuit, Testbench};
+    use crate::components::tristate::Tristate;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 gate
+        output out
+        .links:
+        a:1 gate:1
+        b:1 gate:2
+        gate:3 out:1
+    ";
+
+    #[test]
+    fn test_run() {
+        let stimulus: Stimulus =
+            "a=1\nb=1\nsimulate\ndisplay\n# expect: out=1\n# a comment\nb=0\nsimulate\n# expect: out=1\n# expect: out=?"
+                .parse()
+                .unwrap();
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        assert_eq!(stimulus.nb_ticks(), 2);
+        assert_eq!(stimulus.nb_expectations(), 2);
+        let mismatches = stimulus.run(&mut circuit);
+        assert_eq!(circuit.current_tick().get(), 2);
+        assert_eq!(
+            mismatches,
+            vec![Mismatch::Output {
+                line: 9,
+                name: "out".to_owned(),
+                expected: Tristate::State(true),
+                got: Some(Tristate::State(false))
+            }]
+        );
+        assert_eq!(mismatches[0].to_string(), "line 9: expected out=1, got 0");
+        assert_eq!("c=1".parse::<Stimulus>().unwrap().run(&mut circuit), vec![Mismatch::Input { line: 1, name: "c".to_owned() }]);
+    }
+
+    #[test]
+    fn test_testbench_stimulus_is_valid() {
+        let circuit: Circuit = CONTENT.parse().unwrap();
+        let stimulus: Stimulus = Testbench::new(&circuit).stimulus().parse().unwrap();
+
+        assert_eq!(stimulus.nb_ticks(), 4);
+        assert_eq!(stimulus.nb_expectations(), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!("a=1\nrun".parse::<Stimulus>().unwrap_err().kind, StimulusErrorKind::InvalidCommand { .. }));
+        assert!(matches!("a=2".parse::<Stimulus>().unwrap_err().kind, StimulusErrorKind::InvalidValue { .. }));
+        assert!(matches!("# expect: out=x".parse::<Stimulus>().unwrap_err().kind, StimulusErrorKind::InvalidValue { .. }));
+        assert!(matches!("# expect: simulate".parse::<Stimulus>().unwrap_err().kind, StimulusErrorKind::InvalidCommand { .. }));
+    }
+}
@@ -0,0 +1,302 @@
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use crate::components::info::{ComponentInfo, PinDirection};
+use crate::components::{tristate::Tristate, Component, InvalidPin, PinNumber, Tick};
+use crate::pin::{PinContainer, PinSpecification};
+
+use super::fault::FaultableComponent;
+use super::{Circuit, SimulationError};
+
+/// Circuit embedded as a single component, each of its pins standing for an input or an output of the circuit.
+///
+/// An exposed input takes the value read by its pin at the same tick, and an exposed output gives its value
+/// to its pin once the circuit was simulated. The circuit is simulated as by `Circuit::try_simulate()`, following
+/// the ticks of the enclosing circuit: its inputs sequences, conflict policy and embedded checks apply, the error
+/// of a tick being reported by the enclosing circuit. The components of the circuit keep their own names: the
+/// circuit stays reachable with `Circuit::subcircuit()`.
+pub(super) struct SubcircuitComponent {
+    pins: PinContainer,
+    circuit: RefCell<Circuit>,
+    error: Cell<Option<SimulationError>>,
+    inputs: Vec<(PinNumber, Rc<FaultableComponent>)>,
+    outputs: Vec<(PinNumber, Rc<FaultableComponent>)>,
+}
+
+/// Inner component `name` is missing, or neither an input nor an output of the circuit.
+pub(super) struct NotExposable<'a>(pub &'a str);
+
+impl SubcircuitComponent {
+    /// Exposes each `(pin, name)` of `pin_map`, the pins being numbered from 1.
+    pub fn new<'a>(circuit: Circuit, pin_map: &[(PinNumber, &'a str)]) -> Result<Self, NotExposable<'a>> {
+        let mut inputs: Vec<(PinNumber, Rc<FaultableComponent>)> = Vec::new();
+        let mut outputs: Vec<(PinNumber, Rc<FaultableComponent>)> = Vec::new();
+        let mut pins_spec: HashMap<PinNumber, PinSpecification> = HashMap::new();
+
+        for &(pin, name) in pin_map {
+            let component = circuit.components.get(name).ok_or(NotExposable(name))?;
+
+            if component.as_input().is_some() {
+                inputs.push((pin, component.clone()));
+                pins_spec.insert(pin, PinSpecification::UnidirectionalInput());
+            } else if component.as_output().is_some() {
+                outputs.push((pin, component.clone()));
+                pins_spec.insert(pin, PinSpecification::UnidirectionalOutput());
+            } else {
+                return Err(NotExposable(name));
+            }
+        }
+
+        let nb_pins = pin_map.iter().map(|&(pin, _)| pin).max().unwrap_or(0);
+        Ok(Self {
+            pins: PinContainer::new(nb_pins, pins_spec),
+            circuit: RefCell::new(circuit),
+            error: Cell::new(None),
+            inputs,
+            outputs,
+        })
+    }
+
+    pub fn circuit(&self) -> Ref<'_, Circuit> {
+        self.circuit.borrow()
+    }
+
+    /// Error reported by the circuit during the last tick, if not taken yet.
+    pub fn take_error(&self) -> Option<SimulationError> {
+        self.error.take()
+    }
+
+    pub fn is_input(&self, pin: PinNumber) -> bool {
+        self.inputs.iter().any(|(input, _)| *input == pin)
+    }
+
+    pub fn info(&self, name: &str, pin_map: &[(PinNumber, &str)]) -> ComponentInfo {
+        let nb_pins = pin_map.iter().map(|&(pin, _)| pin).max().unwrap_or(0);
+        let circuit = self.circuit();
+        let sequential = circuit.netlist.component_names().into_iter().any(|inner| {
+            circuit.netlist.component(inner).and_then(|component| component.info.as_ref()).is_some_and(|info| info.sequential)
+        });
+
+        pin_map
+            .iter()
+            .fold(ComponentInfo::new(name, "Subcircuit", nb_pins), |info, &(pin, inner)| {
+                let direction = if self.is_input(pin) { PinDirection::Input } else { PinDirection::Output };
+                info.with_pin(pin, inner, direction)
+            })
+            .with_sequential(sequential)
+    }
+}
+
+impl Component for SubcircuitComponent {
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            for (pin, input) in self.inputs.iter() {
+                input.as_input().unwrap().set_state_for_next_tick(self.pins.compute_input(*pin).unwrap());
+            }
+            let mut circuit = self.circuit.borrow_mut();
+            // The circuit was already simulated at its own tick 0, when built.
+            if tick > circuit.current_tick {
+                let error = circuit.step(tick).err().or_else(|| {
+                    let mismatches = circuit.current_check_mismatches();
+                    (!mismatches.is_empty()).then_some(SimulationError::ChecksFailed(mismatches))
+                });
+                self.error.set(error);
+            }
+            for (pin, output) in self.outputs.iter() {
+                outputs[pin].set(output.as_output().unwrap().get_value());
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::builder::CircuitBuilder;
+    use crate::circuit::{Circuit, CircuitBuildError, Mismatch, SimulationError};
+    use crate::components::tristate::Tristate;
+    use crate::pin::ConflictPolicy;
+
+    const HALF_ADDER: &str = "
+        .chipsets:
+        input a
+        input b
+        4030 xor
+        4081 and
+        output sum
+        output carry
+        .links:
+        a:1 xor:1
+        b:1 xor:2
+        a:1 and:1
+        b:1 and:2
+        xor:3 sum:1
+        and:3 carry:1
+    ";
+
+    fn half_adder() -> Circuit {
+        HALF_ADDER.parse().unwrap()
+    }
+
+    #[test]
+    fn test_add_subcircuit() {
+        let pin_map = [(1, "a"), (2, "b"), (3, "sum"), (4, "carry")];
+        let mut circuit = CircuitBuilder::default()
+            .add_component("input", "x")
+            .unwrap()
+            .add_component("input", "y")
+            .unwrap()
+            .add_subcircuit("adder", half_adder(), &pin_map)
+            .unwrap()
+            .add_component("output", "s")
+            .unwrap()
+            .add_component("output", "c")
+            .unwrap()
+            .link_components("x", 1, "adder", 1)
+            .unwrap()
+            .link_components("y", 1, "adder", 2)
+            .unwrap()
+            .link_components("adder", 3, "s", 1)
+            .unwrap()
+            .link_components("adder", 4, "c", 1)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        for (x, y, sum, carry) in [("0", "0", "0", "0"), ("1", "0", "1", "0"), ("0", "1", "1", "0"), ("1", "1", "0", "1")] {
+            circuit.set_value("x", x).unwrap();
+            circuit.set_value("y", y).unwrap();
+            circuit.simulate();
+            assert_eq!((circuit.get_output("s").unwrap(), circuit.get_output("c").unwrap()), (sum.to_owned(), carry.to_owned()));
+        }
+
+        let adder = circuit.subcircuit("adder").unwrap();
+        assert_eq!(adder.get_input("a").unwrap(), "1");
+        assert_eq!(adder.get_output("carry").unwrap(), "1");
+        assert!(circuit.subcircuit("x").is_none());
+
+        let info = circuit.netlist().component("adder").unwrap().info.as_ref().unwrap();
+        assert_eq!(info.to_string(), "adder: Subcircuit\n  combinational\n  pin  1: a (input)\n  pin  2: b (input)\n  pin  3: sum (output)\n  pin  4: carry (output)\n");
+    }
+
+    #[test]
+    fn test_subcircuit_ticks() {
+        let mut inner: Circuit = "
+            #! check tick=3 out=1
+            .chipsets:
+            input a values=1,0
+            input b
+            output out
+            .links:
+            a:1 out:1
+            b:1 out:1
+        "
+        .parse()
+        .unwrap();
+        inner.set_conflict_policy(ConflictPolicy::Error);
+        let mut circuit = CircuitBuilder::default()
+            .add_component("input", "y")
+            .unwrap()
+            .add_subcircuit("sub", inner, &[(1, "b"), (2, "out")])
+            .unwrap()
+            .add_component("output", "o")
+            .unwrap()
+            .link_components("y", 1, "sub", 1)
+            .unwrap()
+            .link_components("sub", 2, "o", 1)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut step = |y: &str| {
+            circuit.set_value("y", y).unwrap();
+            circuit.try_simulate()
+        };
+
+        // The input sequence of the subcircuit applies, its conflicts being errors under its own policy.
+        assert_eq!(step("1"), Ok(()));
+        assert_eq!(
+            step("1"),
+            Err(SimulationError::Subcircuit { name: "sub".to_owned(), error: Box::new(SimulationError::DriverConflicts(1)) })
+        );
+        let mismatch =
+            Mismatch::Output { line: 2, name: "out".to_owned(), expected: Tristate::State(true), got: Some(false.into()) };
+        assert_eq!(
+            step("0"),
+            Err(SimulationError::Subcircuit {
+                name: "sub".to_owned(),
+                error: Box::new(SimulationError::ChecksFailed(vec![mismatch]))
+            })
+        );
+        assert_eq!(circuit.subcircuit("sub").unwrap().current_tick().get(), 3);
+        assert_eq!(circuit.get_output("o").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_subcircuit_error_with_dangles() {
+        let inner: Circuit =
+            "#! check tick=1 out=1\n.chipsets:\ninput a values=0\noutput out\n.links:\na:1 out:1\n".parse().unwrap();
+        let mut circuit = CircuitBuilder::default()
+            .add_subcircuit("sub", inner, &[(1, "out")])
+            .unwrap()
+            .add_component("true", "t")
+            .unwrap()
+            .add_component("output", "o")
+            .unwrap()
+            .link_components("t", 1, "o", 1)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // Drop "t" behind the circuit's back, as a removal at runtime would.
+        circuit.components.remove("t");
+        circuit.schedule.retain(|component| circuit.components.values().any(|kept| std::rc::Rc::ptr_eq(kept, component)));
+
+        let mismatch =
+            Mismatch::Output { line: 1, name: "out".to_owned(), expected: Tristate::State(true), got: Some(false.into()) };
+        let error = circuit.try_simulate().unwrap_err();
+        assert_eq!(
+            error,
+            SimulationError::WithSubcircuit {
+                error: Box::new(SimulationError::DanglesDetected(1)),
+                subcircuit: Box::new(SimulationError::Subcircuit {
+                    name: "sub".to_owned(),
+                    error: Box::new(SimulationError::ChecksFailed(vec![mismatch]))
+                }),
+            }
+        );
+        assert!(error
+            .to_string()
+            .starts_with("1 link(s) to dropped components were pruned; Subcircuit \"sub\": Failed checks: "));
+    }
+
+    #[test]
+    fn test_add_subcircuit_errors() {
+        let builder = CircuitBuilder::default().add_component("input", "x").unwrap();
+        assert!(matches!(builder.add_subcircuit("x", half_adder(), &[]), Err(CircuitBuildError::ComponentNameOverride("x"))));
+
+        let builder = CircuitBuilder::default();
+        assert!(matches!(
+            builder.add_subcircuit("adder", half_adder(), &[(1, "xor")]),
+            Err(CircuitBuildError::NotExposable("xor"))
+        ));
+
+        let builder = CircuitBuilder::default();
+        assert!(matches!(builder.add_subcircuit("adder", half_adder(), &[(1, "c")]), Err(CircuitBuildError::NotExposable("c"))));
+
+        let builder = CircuitBuilder::default().add_component("input", "x").unwrap();
+        let builder = builder.add_subcircuit("adder", half_adder(), &[(1, "a")]).unwrap();
+        assert!(matches!(builder.link_components("x", 1, "adder", 2), Err(CircuitBuildError::SubcircuitLinkIssue("adder", 2))));
+    }
+}
@@ -0,0 +1,511 @@
+//! Boolean expressions compiled into circuits made of CD4000 gate chips.
+//!
+//! ```text
+//! carry = (a & b) | (cin & (a ^ b))
+//! sum = a ^ b ^ cin
+//! ```
+//!
+//! Statements are separated by newlines or `;`. Operators, by decreasing precedence:
+//! `!` (NOT), `&` (AND), `^` (XOR), `|` (OR). `0` and `1` are constants.
+//! A name defined by a previous statement refers to that result instead of a new input.
+//! Identical subexpressions are only built once.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::components::composite::parallel_gates::{Component4030, Component4069, Component4071, Component4081};
+use crate::components::PinNumber;
+
+use super::builder::CircuitBuilder;
+use super::netlist::PinRef;
+use super::{BuildErrorKind, Circuit};
+
+#[derive(Debug, Clone)]
+pub struct SynthesisError {
+    pub statement: usize,
+    pub kind: SynthesisErrorKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum SynthesisErrorKind {
+    InvalidStatement,
+    UnexpectedToken { token: String },
+    UnexpectedEnd,
+    OutputRedefinition { name: String },
+    NameConflict { name: String },
+    NoOutput,
+    Build(BuildErrorKind),
+}
+
+impl fmt::Display for SynthesisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "statement {}: {}", self.statement, self.kind)
+    }
+}
+
+impl fmt::Display for SynthesisErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidStatement => write!(f, "Statement must respect this form: name = expression"),
+            Self::UnexpectedToken { token } => write!(f, "Unexpected \"{token}\""),
+            Self::UnexpectedEnd => write!(f, "Unexpected end of expression"),
+            Self::OutputRedefinition { name } => write!(f, "\"{name}\" is already defined"),
+            Self::NameConflict { name } => write!(f, "\"{name}\" is used both as an input and an output"),
+            Self::NoOutput => write!(f, "There is no statement"),
+            Self::Build(kind) => write!(f, "{kind}"),
+        }
+    }
+}
+
+/// Builds a circuit from boolean expressions.
+pub fn from_expression(content: &str) -> Result<Circuit, SynthesisError> {
+    Synthesis::from_expression(content)?.build()
+}
+
+/// Gate-level result of a synthesis, which can be built or written as `.nts` content.
+#[derive(Debug, Clone)]
+pub struct Synthesis {
+    chipsets: Vec<(&'static str, String)>,
+    links: Vec<(PinRef, PinRef)>,
+}
+
+impl Synthesis {
+    pub fn from_expression(content: &str) -> Result<Self, SynthesisError> {
+        let mut graph = ExpressionGraph::default();
+        let mut outputs: Vec<(String, usize)> = Vec::new();
+
+        for (idx, statement) in content.split(['\n', ';']).map(str::trim).filter(|s| !s.is_empty()).enumerate() {
+            let statement_idx = idx + 1;
+            let error = |kind: SynthesisErrorKind| SynthesisError { statement: statement_idx, kind };
+
+            let (name, expression) = statement.split_once('=').ok_or(error(SynthesisErrorKind::InvalidStatement))?;
+            let name = name.trim();
+            if !is_identifier(name) {
+                return Err(error(SynthesisErrorKind::InvalidStatement));
+            }
+            if outputs.iter().any(|(output, _)| output == name) {
+                return Err(error(SynthesisErrorKind::OutputRedefinition { name: name.to_owned() }));
+            }
+
+            let tokens = tokenize(expression).map_err(error)?;
+            let node =
+                ExpressionParser { tokens: &tokens, position: 0, graph: &mut graph, outputs: &outputs }.parse().map_err(error)?;
+
+            if graph.inputs().any(|input| input == name) {
+                return Err(error(SynthesisErrorKind::NameConflict { name: name.to_owned() }));
+            }
+            outputs.push((name.to_owned(), node));
+        }
+
+        if outputs.is_empty() {
+            return Err(SynthesisError { statement: 0, kind: SynthesisErrorKind::NoOutput });
+        }
+
+        Ok(Self::lower(&graph, &outputs))
+    }
+
+    pub fn chipsets(&self) -> &[(&'static str, String)] {
+        &self.chipsets
+    }
+
+    pub fn links(&self) -> &[(PinRef, PinRef)] {
+        &self.links
+    }
+
+    pub fn build(&self) -> Result<Circuit, SynthesisError> {
+        let error = |kind: BuildErrorKind| SynthesisError { statement: 0, kind: SynthesisErrorKind::Build(kind) };
+        let mut builder = CircuitBuilder::default();
+
+        for (component_type, name) in self.chipsets.iter() {
+            builder = builder.add_component(component_type, name).map_err(|err| error(err.into()))?;
+        }
+        for (left, right) in self.links.iter() {
+            builder = builder
+                .link_components(&left.component, left.pin, &right.component, right.pin)
+                .map_err(|err| error(err.into()))?;
+        }
+
+        builder.build().map_err(|err| error(err.into()))
+    }
+
+    fn lower(graph: &ExpressionGraph, outputs: &[(String, usize)]) -> Self {
+        let mut synthesis = Self { chipsets: Vec::new(), links: Vec::new() };
+        let mut allocator = GateAllocator::default();
+        let mut drivers: Vec<PinRef> = Vec::with_capacity(graph.nodes.len());
+
+        // Nodes are created after their operands, so they are already in evaluation order.
+        for node in graph.nodes.iter() {
+            let driver = match node {
+                Node::Input(name) => {
+                    synthesis.chipsets.push(("input", name.clone()));
+                    PinRef::new(name, 1)
+                }
+                Node::Const(value) => {
+                    let (component_type, name) = if *value { ("true", "const.1") } else { ("false", "const.0") };
+                    synthesis.chipsets.push((component_type, name.to_owned()));
+                    PinRef::new(name, 1)
+                }
+                Node::Not(operand) => {
+                    let (chip, input, output) = allocator.allocate_not(&mut synthesis.chipsets);
+                    synthesis.links.push((drivers[*operand].clone(), PinRef::new(&chip, input)));
+                    PinRef::new(&chip, output)
+                }
+                Node::Binary(operator, left, right) => {
+                    let (chip, input_left, input_right, output) = allocator.allocate_binary(*operator, &mut synthesis.chipsets);
+                    synthesis.links.push((drivers[*left].clone(), PinRef::new(&chip, input_left)));
+                    synthesis.links.push((drivers[*right].clone(), PinRef::new(&chip, input_right)));
+                    PinRef::new(&chip, output)
+                }
+            };
+            drivers.push(driver);
+        }
+
+        for (name, node) in outputs.iter() {
+            synthesis.chipsets.push(("output", name.clone()));
+            synthesis.links.push((drivers[*node].clone(), PinRef::new(name, 1)));
+        }
+
+        synthesis
+    }
+}
+
+impl fmt::Display for Synthesis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, ".chipsets:")?;
+        for (component_type, name) in self.chipsets.iter() {
+            writeln!(f, "{component_type} {name}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, ".links:")?;
+        for (left, right) in self.links.iter() {
+            writeln!(f, "{left} {right}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/* Expression graph */
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum Operator {
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+enum Node {
+    Input(String),
+    Const(bool),
+    Not(usize),
+    Binary(Operator, usize, usize),
+}
+
+#[derive(Default)]
+struct ExpressionGraph {
+    nodes: Vec<Node>,
+    index: HashMap<Node, usize>,
+}
+
+impl ExpressionGraph {
+    fn insert(&mut self, node: Node) -> usize {
+        // Operators are commutative: normalize operand order so that `a & b` and `b & a` are shared.
+        let node = match node {
+            Node::Binary(operator, left, right) if right < left => Node::Binary(operator, right, left),
+            node => node,
+        };
+
+        if let Some(&id) = self.index.get(&node) {
+            return id;
+        }
+
+        let id = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.index.insert(node, id);
+        id
+    }
+
+    fn inputs(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().filter_map(|node| match node {
+            Node::Input(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/* Parsing */
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Token<'a> {
+    Identifier(&'a str),
+    Const(bool),
+    Not,
+    And,
+    Or,
+    Xor,
+    OpenParen,
+    CloseParen,
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token<'_>>, SynthesisErrorKind> {
+    let mut tokens: Vec<Token<'_>> = Vec::new();
+    let mut chars = expression.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '!' => Token::Not,
+            '&' => Token::And,
+            '|' => Token::Or,
+            '^' => Token::Xor,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(idx, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = idx + c.len_utf8();
+                    chars.next();
+                }
+
+                match &expression[start..end] {
+                    "0" => Token::Const(false),
+                    "1" => Token::Const(true),
+                    word if is_identifier(word) => Token::Identifier(word),
+                    word => return Err(SynthesisErrorKind::UnexpectedToken { token: word.to_owned() }),
+                }
+            }
+            c => return Err(SynthesisErrorKind::UnexpectedToken { token: c.to_string() }),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+struct ExpressionParser<'t, 'a> {
+    tokens: &'t [Token<'a>],
+    position: usize,
+    graph: &'t mut ExpressionGraph,
+    outputs: &'t [(String, usize)],
+}
+
+impl ExpressionParser<'_, '_> {
+    fn parse(mut self) -> Result<usize, SynthesisErrorKind> {
+        let node = self.parse_binary(0)?;
+
+        match self.tokens.get(self.position) {
+            None => Ok(node),
+            Some(token) => Err(SynthesisErrorKind::UnexpectedToken { token: format!("{token:?}") }),
+        }
+    }
+
+    /// Precedence climbing over `|` (0), `^` (1) and `&` (2).
+    fn parse_binary(&mut self, level: usize) -> Result<usize, SynthesisErrorKind> {
+        const LEVELS: [(Token<'static>, Operator); 3] =
+            [(Token::Or, Operator::Or), (Token::Xor, Operator::Xor), (Token::And, Operator::And)];
+
+        let Some((token, operator)) = LEVELS.get(level) else {
+            return self.parse_unary();
+        };
+
+        let mut node = self.parse_binary(level + 1)?;
+        while self.tokens.get(self.position) == Some(token) {
+            self.position += 1;
+            let right = self.parse_binary(level + 1)?;
+            node = self.graph.insert(Node::Binary(*operator, node, right));
+        }
+
+        Ok(node)
+    }
+
+    fn parse_unary(&mut self) -> Result<usize, SynthesisErrorKind> {
+        let token = self.tokens.get(self.position).ok_or(SynthesisErrorKind::UnexpectedEnd)?;
+        self.position += 1;
+
+        match token {
+            Token::Not => {
+                let operand = self.parse_unary()?;
+                Ok(self.graph.insert(Node::Not(operand)))
+            }
+            Token::OpenParen => {
+                let node = self.parse_binary(0)?;
+                match self.tokens.get(self.position) {
+                    Some(Token::CloseParen) => {
+                        self.position += 1;
+                        Ok(node)
+                    }
+                    Some(token) => Err(SynthesisErrorKind::UnexpectedToken { token: format!("{token:?}") }),
+                    None => Err(SynthesisErrorKind::UnexpectedEnd),
+                }
+            }
+            Token::Const(value) => Ok(self.graph.insert(Node::Const(*value))),
+            Token::Identifier(name) => match self.outputs.iter().find(|(output, _)| output == name) {
+                Some((_, node)) => Ok(*node),
+                None => Ok(self.graph.insert(Node::Input(name.to_string()))),
+            },
+            token => Err(SynthesisErrorKind::UnexpectedToken { token: format!("{token:?}") }),
+        }
+    }
+}
+
+/* Gate packing */
+
+#[derive(Default)]
+struct GateAllocator {
+    used: HashMap<&'static str, (usize, usize)>,
+}
+
+impl GateAllocator {
+    /// Returns the chip name and gate index of the next free gate of a chip type,
+    /// declaring a new chip when the previous one is full.
+    fn allocate(
+        &mut self,
+        component_type: &'static str,
+        prefix: &str,
+        nb_gates: usize,
+        chipsets: &mut Vec<(&'static str, String)>,
+    ) -> (String, usize) {
+        let (nb_chips, next_gate) = self.used.entry(component_type).or_insert((0, nb_gates));
+
+        if *next_gate == nb_gates {
+            *nb_chips += 1;
+            *next_gate = 0;
+            chipsets.push((component_type, format!("{prefix}.{nb_chips}")));
+        }
+
+        let gate = *next_gate;
+        *next_gate += 1;
+        (format!("{prefix}.{nb_chips}"), gate)
+    }
+
+    fn allocate_not(&mut self, chipsets: &mut Vec<(&'static str, String)>) -> (String, PinNumber, PinNumber) {
+        let gates = Component4069::PER_GATES;
+        let (chip, gate) = self.allocate("4069", "not", gates.len(), chipsets);
+        let (input, output) = gates[gate];
+
+        (chip, input, output)
+    }
+
+    fn allocate_binary(
+        &mut self,
+        operator: Operator,
+        chipsets: &mut Vec<(&'static str, String)>,
+    ) -> (String, PinNumber, PinNumber, PinNumber) {
+        let (component_type, prefix, gates) = match operator {
+            Operator::And => ("4081", "and", Component4081::PER_GATES),
+            Operator::Or => ("4071", "or", Component4071::PER_GATES),
+            Operator::Xor => ("4030", "xor", Component4030::PER_GATES),
+        };
+        let (chip, gate) = self.allocate(component_type, prefix, gates.len(), chipsets);
+        let (input_left, input_right, output) = gates[gate];
+
+        (chip, input_left, input_right, output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_expression, Synthesis, SynthesisErrorKind};
+
+    fn evaluate(content: &str, inputs: &[(&str, &str)], output: &str) -> String {
+        let mut circuit = from_expression(content).unwrap();
+
+        for (name, value) in inputs {
+            circuit.set_value(name, value).unwrap();
+        }
+        circuit.simulate();
+        circuit.get_output(output).unwrap()
+    }
+
+    #[test]
+    fn test_truth_table() {
+        for a in ["0", "1"] {
+            for b in ["0", "1"] {
+                for c in ["0", "1"] {
+                    let expected = (a == "1" && b == "1") || c == "0";
+                    let result = evaluate("out = (a & b) | !c", &[("a", a), ("b", b), ("c", c)], "out");
+
+                    assert_eq!(result, if expected { "1" } else { "0" }, "a={a} b={b} c={c}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_precedence() {
+        // & binds tighter than ^ which binds tighter than |
+        assert_eq!(evaluate("out = a | b ^ c & d", &[("a", "0"), ("b", "1"), ("c", "1"), ("d", "0")], "out"), "1");
+        assert_eq!(evaluate("out = !a & b", &[("a", "0"), ("b", "1")], "out"), "1");
+    }
+
+    #[test]
+    fn test_constants_and_reused_outputs() {
+        let content = "x = a & 1; y = !x | 0";
+
+        assert_eq!(evaluate(content, &[("a", "1")], "x"), "1");
+        assert_eq!(evaluate(content, &[("a", "1")], "y"), "0");
+    }
+
+    #[test]
+    fn test_common_subexpressions_are_shared() {
+        let synthesis = Synthesis::from_expression("x = (a & b) | c\ny = (b & a) ^ c").unwrap();
+        let chips: Vec<&str> = synthesis.chipsets().iter().map(|(component_type, _)| *component_type).collect();
+
+        assert_eq!(chips, vec!["input", "input", "4081", "input", "4071", "4030", "output", "output"]);
+        assert_eq!(synthesis.links().len(), 8);
+    }
+
+    #[test]
+    fn test_gates_are_packed_into_chips() {
+        let synthesis = Synthesis::from_expression("out = a & b & c & d & e & f").unwrap();
+        let nb_and_chips = synthesis.chipsets().iter().filter(|(component_type, _)| *component_type == "4081").count();
+
+        assert_eq!(nb_and_chips, 2);
+        assert_eq!(
+            evaluate(
+                "out = a & b & c & d & e & f",
+                &[("a", "1"), ("b", "1"), ("c", "1"), ("d", "1"), ("e", "1"), ("f", "1")],
+                "out"
+            ),
+            "1"
+        );
+    }
+
+    #[test]
+    fn test_nts_output_can_be_parsed() {
+        let synthesis = Synthesis::from_expression("out = a ^ b").unwrap();
+        let text = synthesis.to_string();
+
+        assert_eq!(
+            text,
+            ".chipsets:\ninput a\ninput b\n4030 xor.1\noutput out\n\n.links:\na:1 xor.1:1\nb:1 xor.1:2\nxor.1:3 out:1\n"
+        );
+        assert!(text.parse::<crate::Circuit>().is_ok());
+    }
+
+    #[test]
+    fn test_errors() {
+        fn kind(content: &str) -> SynthesisErrorKind {
+            Synthesis::from_expression(content).unwrap_err().kind
+        }
+
+        assert!(matches!(kind(""), SynthesisErrorKind::NoOutput));
+        assert!(matches!(kind("a & b"), SynthesisErrorKind::InvalidStatement));
+        assert!(matches!(kind("out = a &"), SynthesisErrorKind::UnexpectedEnd));
+        assert!(matches!(kind("out = (a"), SynthesisErrorKind::UnexpectedEnd));
+        assert!(matches!(kind("out = a b"), SynthesisErrorKind::UnexpectedToken { .. }));
+        assert!(matches!(kind("out = a + b"), SynthesisErrorKind::UnexpectedToken { .. }));
+        assert!(matches!(kind("out = a; out = b"), SynthesisErrorKind::OutputRedefinition { .. }));
+        assert!(matches!(kind("a = a"), SynthesisErrorKind::NameConflict { .. }));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::components::Component;
+
+use super::Circuit;
+
+/// Generates verification skeletons for a circuit: its inputs swept in binary order, clocks toggled
+/// at each vector, and an expected-value stub (`?`) for each output, to be filled in by hand.
+#[derive(Debug, Clone)]
+pub struct Testbench {
+    inputs: Vec<String>,
+    clocks: Vec<String>,
+    outputs: Vec<String>,
+    nb_vectors: usize,
+}
+
+impl Testbench {
+    pub const EXPECTED_STUB: &'static str = "?";
+
+    /// Enough vectors for every input combination, up to 16.
+    pub fn new(circuit: &Circuit) -> Self {
+        let mut inputs: Vec<String> = Vec::new();
+        let mut clocks: Vec<String> = Vec::new();
+        let mut outputs: Vec<String> = Vec::new();
+
+        for name in circuit.netlist().component_names() {
+            let component = &circuit.components[name];
+
+            if component.as_input().is_some() {
+                let is_clock = circuit.netlist().component(name).is_some_and(|component| component.component_type == "clock");
+                if is_clock { &mut clocks } else { &mut inputs }.push(name.to_owned());
+            } else if component.as_output().is_some() {
+                outputs.push(name.to_owned());
+            }
+        }
+
+        let nb_vectors = 1 << inputs.len().min(4);
+        Self { inputs, clocks, outputs, nb_vectors }
+    }
+
+    pub fn with_vectors(mut self, nb_vectors: usize) -> Self {
+        self.nb_vectors = nb_vectors;
+        self
+    }
+
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    pub fn clocks(&self) -> &[String] {
+        &self.clocks
+    }
+
+    pub fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    /// Shell commands applying every vector, followed by the expected outputs as comments.
+    pub fn stimulus(&self) -> String {
+        let mut content =
+            format!("# {} input(s), {} clock(s), {} output(s)\n", self.inputs.len(), self.clocks.len(), self.outputs.len());
+
+        for vector in 0..self.nb_vectors {
+            content.push_str(&format!("\n# vector {}\n", vector + 1));
+            for (name, value) in self.vector(vector) {
+                content.push_str(&format!("{name}={value}\n"));
+            }
+            content.push_str("simulate\ndisplay\n");
+            for name in self.outputs.iter() {
+                content.push_str(&format!("# expect: {name}={}\n", Self::EXPECTED_STUB));
+            }
+        }
+
+        content
+    }
+
+    /// Integration test reading the circuit from `nts_path` and checking every vector.
+    pub fn rust_test(&self, test_name: &str, nts_path: &str) -> String {
+        let mut content = String::from("use std::fs::read_to_string;\n\nuse nanotekspice::Circuit;\n\n");
+
+        content.push_str(&format!("#[test]\nfn {test_name}() {{\n"));
+        content.push_str(&format!("    let mut circuit: Circuit = read_to_string({nts_path:?}).unwrap().parse().unwrap();\n"));
+
+        for vector in 0..self.nb_vectors {
+            content.push_str(&format!("\n    // Vector {}\n", vector + 1));
+            for (name, value) in self.vector(vector) {
+                content.push_str(&format!("    circuit.set_value({name:?}, \"{value}\").unwrap();\n"));
+            }
+            content.push_str("    circuit.simulate();\n");
+            for name in self.outputs.iter() {
+                content.push_str(&format!(
+                    "    assert_eq!(circuit.get_output({name:?}).unwrap(), \"{}\"); // TODO: expected value\n",
+                    Self::EXPECTED_STUB
+                ));
+            }
+        }
+
+        content.push_str("}\n");
+        content
+    }
+
+    /// Input values of the vector at `index`: the index in binary, first input as most significant bit,
+    /// and clocks alternating between 0 and 1.
+    fn vector(&self, index: usize) -> Vec<(&str, u8)> {
+        let nb_inputs = self.inputs.len();
+        let inputs = self.inputs.iter().enumerate().map(|(idx, name)| {
+            let bit = nb_inputs - 1 - idx;
+            (name.as_str(), if bit < usize::BITS as usize { ((index >> bit) & 1) as u8 } else { 0 })
+        });
+        let clocks = self.clocks.iter().map(|name| (name.as_str(), (index % 2) as u8));
+
+        inputs.chain(clocks).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Testbench;
+    use crate::circuit::Circuit;
+
+    fn build_circuit() -> Circuit {
+        let content = "
+            .chipsets:
+            input b
+            input a
+            clock clk
+            4081 gate
+            output out
+            .links:
+            a:1 gate:1
+            b:1 gate:2
+            gate:3 out:1
+        ";
+
+        content.parse().unwrap()
+    }
+
+    #[test]
+    fn test_signals() {
+        let testbench = Testbench::new(&build_circuit());
+
+        assert_eq!(testbench.inputs(), &["a", "b"]);
+        assert_eq!(testbench.clocks(), &["clk"]);
+        assert_eq!(testbench.outputs(), &["out"]);
+    }
+
+    #[test]
+    fn test_stimulus() {
+        let stimulus = Testbench::new(&build_circuit()).stimulus();
+
+        assert!(stimulus.starts_with("# 2 input(s), 1 clock(s), 1 output(s)\n"));
+        assert!(stimulus.contains("\n# vector 1\na=0\nb=0\nclk=0\nsimulate\ndisplay\n# expect: out=?\n"));
+        assert!(stimulus.contains("\n# vector 3\na=1\nb=0\nclk=0\n"));
+        assert!(stimulus.ends_with("\n# vector 4\na=1\nb=1\nclk=1\nsimulate\ndisplay\n# expect: out=?\n"));
+    }
+
+    #[test]
+    fn test_rust_test() {
+        let test = Testbench::new(&build_circuit()).with_vectors(1).rust_test("and_gate", "tests/.nts/and.nts");
+
+        assert_eq!(
+            test,
+            "use std::fs::read_to_string;
+
+use nanotekspice::Circuit;
+
+#[test]
+fn and_gate() {
+    let mut circuit: Circuit = read_to_string(\"tests/.nts/and.nts\").unwrap().parse().unwrap();
+
+    // Vector 1
+    circuit.set_value(\"a\", \"0\").unwrap();
+    circuit.set_value(\"b\", \"0\").unwrap();
+    circuit.set_value(\"clk\", \"0\").unwrap();
+    circuit.simulate();
+    assert_eq!(circuit.get_output(\"out\").unwrap(), \"?\"); // TODO: expected value
+}
+"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt;
+
+use crate::components::{factory::ComponentFactory, info::PinDirection, tristate::Tristate, Component, PinNumber};
+
+use super::builder::CircuitBuilder;
+use super::Circuit;
+
+#[derive(Debug, Clone)]
+pub enum TruthTableError {
+    UnknownComponent(String),
+    NotCombinational(String),
+    TooManyInputs { count: usize, max: usize },
+}
+
+impl fmt::Display for TruthTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownComponent(name) => write!(f, "Unknown component \"{name}\""),
+            Self::NotCombinational(name) => write!(f, "\"{name}\" is not a combinational component"),
+            Self::TooManyInputs { count, max } => write!(f, "Too many inputs ({count}), at most {max} are supported"),
+        }
+    }
+}
+
+/// Outputs computed for every combination of inputs.
+///
+/// Tables are computed on fresh circuits, leaving the simulated one untouched.
+#[derive(Debug, Clone)]
+pub struct TruthTable {
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    rows: Vec<(Vec<Tristate>, Vec<Tristate>)>,
+}
+
+impl TruthTable {
+    pub const MAX_INPUTS: usize = 10;
+
+    /// Sweeps the inputs of the circuits returned by `fresh`, which must all be identical.
+    /// Each row is computed on a new circuit after one simulation.
+    pub fn of_circuit<F>(mut fresh: F) -> Result<Self, TruthTableError>
+    where
+        F: FnMut() -> Circuit,
+    {
+        let circuit = fresh();
+        let (inputs, outputs): (Vec<String>, Vec<String>) =
+            circuit.signals().map(|(name, _)| name.to_owned()).partition(|name| circuit.components[name].as_input().is_some());
+
+        Self::sweep(inputs, outputs, fresh)
+    }
+
+    /// Sweeps the inputs of a copy of the component `name`, built with `factory`.
+    /// Columns are named after pin numbers.
+    pub fn of_component<Factory>(circuit: &Circuit, name: &str, factory: &Factory) -> Result<Self, TruthTableError>
+    where
+        Factory: ComponentFactory,
+        Factory::Type: fmt::Debug + Clone,
+    {
+        let component = circuit.netlist().component(name).ok_or_else(|| TruthTableError::UnknownComponent(name.to_owned()))?;
+        let info = component.info.as_ref().filter(|info| !info.sequential);
+        let info = info.ok_or_else(|| TruthTableError::NotCombinational(name.to_owned()))?;
+        let pins =
+            |direction: PinDirection| -> Vec<PinNumber> { info.pins_with_direction(direction).map(|pin| pin.number).collect() };
+        let (input_pins, output_pins) = (pins(PinDirection::Input), pins(PinDirection::Output));
+
+        let build = || -> Circuit {
+            let mut builder = CircuitBuilder::new(factory).add_component(&component.component_type, name).unwrap();
+            for pin in input_pins.iter() {
+                let pin_name = pin.to_string();
+                builder = builder.add_component("input", &pin_name).unwrap().link_components(&pin_name, 1, name, *pin).unwrap();
+            }
+            for pin in output_pins.iter() {
+                let pin_name = pin.to_string();
+                builder = builder.add_component("output", &pin_name).unwrap().link_components(name, *pin, &pin_name, 1).unwrap();
+            }
+            builder.build().unwrap()
+        };
+
+        let names = |pins: &[PinNumber]| pins.iter().map(PinNumber::to_string).collect();
+        Self::sweep(names(&input_pins), names(&output_pins), build)
+    }
+
+    fn sweep<F>(inputs: Vec<String>, outputs: Vec<String>, mut fresh: F) -> Result<Self, TruthTableError>
+    where
+        F: FnMut() -> Circuit,
+    {
+        if inputs.len() > Self::MAX_INPUTS {
+            return Err(TruthTableError::TooManyInputs { count: inputs.len(), max: Self::MAX_INPUTS });
+        }
+
+        let rows = (0..(1usize << inputs.len()))
+            .map(|index| {
+                let mut circuit = fresh();
+                // First input as most significant bit.
+                let values: Vec<Tristate> =
+                    (0..inputs.len()).map(|idx| ((index >> (inputs.len() - 1 - idx)) & 1 == 1).into()).collect();
+
+                for (name, &value) in inputs.iter().zip(values.iter()) {
+                    circuit.components[name].as_input().unwrap().set_state_for_next_tick(value);
+                }
+                circuit.simulate();
+
+                let results = outputs.iter().map(|name| circuit.components[name].as_output().unwrap().get_value()).collect();
+                (values, results)
+            })
+            .collect();
+
+        Ok(Self { inputs, outputs, rows })
+    }
+
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    /// Input values and resulting output values, in the order of `inputs()` and `outputs()`.
+    pub fn rows(&self) -> &[(Vec<Tristate>, Vec<Tristate>)] {
+        &self.rows
+    }
+}
+
+impl fmt::Display for TruthTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = |f: &mut fmt::Formatter<'_>, inputs: &[String], outputs: &[String]| -> fmt::Result {
+            let widths = self.inputs.iter().chain(self.outputs.iter()).map(String::len);
+            let mut cells = inputs.iter().chain(outputs.iter()).zip(widths).map(|(cell, width)| format!("{cell:>width$}"));
+            let inputs: Vec<String> = cells.by_ref().take(inputs.len()).collect();
+            let outputs: Vec<String> = cells.collect();
+
+            writeln!(f, "{} | {}", inputs.join(" "), outputs.join(" "))
+        };
+
+        row(f, &self.inputs, &self.outputs)?;
+        for (inputs, outputs) in self.rows.iter() {
+            let to_strings = |values: &[Tristate]| values.iter().map(Tristate::to_string).collect::<Vec<String>>();
+            row(f, &to_strings(inputs), &to_strings(outputs))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TruthTable, TruthTableError};
+    use crate::circuit::Circuit;
+    use crate::components::factory::DefaultComponentFactory;
+    use crate::components::tristate::Tristate;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4030 gate
+        output out
+        .links:
+        a:1 gate:1
+        b:1 gate:2
+        gate:3 out:1
+    ";
+
+    #[test]
+    fn test_circuit_table() {
+        let table = TruthTable::of_circuit(|| CONTENT.parse().unwrap()).unwrap();
+
+        assert_eq!(table.inputs(), &["a", "b"]);
+        assert_eq!(table.outputs(), &["out"]);
+        assert_eq!(table.rows().len(), 4);
+        assert_eq!(table.rows()[1], (vec![Tristate::State(false), Tristate::State(true)], vec![Tristate::State(true)]));
+        assert_eq!(table.to_string(), "a b | out\n0 0 |   0\n0 1 |   1\n1 0 |   1\n1 1 |   0\n");
+    }
+
+    #[test]
+    fn test_component_table() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        circuit.set_value("a", "1").unwrap();
+        circuit.simulate();
+
+        let table = TruthTable::of_component(&circuit, "gate", &DefaultComponentFactory).unwrap();
+
+        assert_eq!(table.inputs(), &["1", "2", "5", "6", "8", "9", "12", "13"]);
+        assert_eq!(table.outputs(), &["3", "4", "10", "11"]);
+        assert_eq!(table.rows().len(), 256);
+        // Gate 1 (pins 1, 2 -> 3) with inputs 1 and 0.
+        let (_, outputs) = &table.rows()[0b1000_0000];
+        assert_eq!(outputs[0], Tristate::State(true));
+
+        // The simulated circuit was not touched.
+        assert_eq!(circuit.current_tick().get(), 1);
+        assert_eq!(circuit.get_output("out").unwrap(), "U");
+    }
+
+    #[test]
+    fn test_errors() {
+        let circuit: Circuit = CONTENT.parse().unwrap();
+
+        assert!(matches!(
+            TruthTable::of_component(&circuit, "nope", &DefaultComponentFactory),
+            Err(TruthTableError::UnknownComponent(_))
+        ));
+
+        let content = (0..=TruthTable::MAX_INPUTS).map(|idx| format!("input i{idx}\n")).collect::<String>();
+        assert!(matches!(
+            TruthTable::of_circuit(|| format!(".chipsets:\n{content}").parse().unwrap()),
+            Err(TruthTableError::TooManyInputs { count: 11, max: 10 })
+        ));
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::components::{tristate::Tristate, Component, Tick};
+
+use super::netlist::PinRef;
+use super::Circuit;
+
+/// Where the undefined value of an output came from, recorded the first time the output was undefined.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UndefinedOrigin {
+    pub output: String,
+    pub tick: Tick,
+    /// Undefined pins from the output back to the origin: each input pin is followed by the pin driving it,
+    /// each output pin by the input it depends on.
+    pub chain: Vec<PinRef>,
+}
+
+impl UndefinedOrigin {
+    /// Pin where the undefined value appeared: an output computed from defined inputs (or without inputs),
+    /// or an input which is not linked.
+    pub fn origin(&self) -> &PinRef {
+        self.chain.last().unwrap()
+    }
+}
+
+impl fmt::Display for UndefinedOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chain: Vec<String> = self.chain.iter().map(PinRef::to_string).collect();
+
+        write!(f, "{}: undefined since tick {}, from {} ({})", self.output, self.tick, self.origin(), chain.join(" <- "))
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct UndefinedWatch {
+    origins: HashMap<String, UndefinedOrigin>,
+}
+
+impl Circuit {
+    /// Enables or disables recording of the origin of undefined outputs after each tick.
+    /// Disabling the watch forgets what was recorded.
+    pub fn watch_undefined(&mut self, enabled: bool) {
+        match (enabled, self.undefined_watch.is_some()) {
+            (true, false) => {
+                self.undefined_watch = Some(UndefinedWatch::default());
+                self.record_undefined_outputs();
+            }
+            (false, true) => self.undefined_watch = None,
+            _ => (),
+        }
+    }
+
+    /// Recorded origins, sorted by output name. Empty if the watch is disabled.
+    pub fn undefined_origins(&self) -> Vec<&UndefinedOrigin> {
+        let Some(watch) = self.undefined_watch.as_ref() else {
+            return Vec::new();
+        };
+        let mut origins: Vec<&UndefinedOrigin> = watch.origins.values().collect();
+
+        origins.sort_by(|left, right| left.output.cmp(&right.output));
+        origins
+    }
+
+    pub(super) fn record_undefined_outputs(&mut self) {
+        let Some(watch) = self.undefined_watch.as_ref() else {
+            return;
+        };
+
+        let mut new_origins: Vec<UndefinedOrigin> = Vec::new();
+        for (name, component) in self.components.iter() {
+            let Some(output) = component.as_output() else {
+                continue;
+            };
+            if output.get_value() != Tristate::Undefined || watch.origins.contains_key(name) {
+                continue;
+            }
+
+            let chain = match self.netlist.input_pins(name).first() {
+                Some(&pin) => self.trace_undefined(PinRef::new(name, pin)),
+                None => vec![],
+            };
+            new_origins.push(UndefinedOrigin { output: name.clone(), tick: self.current_tick, chain });
+        }
+
+        let watch = self.undefined_watch.as_mut().unwrap();
+        for origin in new_origins.into_iter().filter(|origin| !origin.chain.is_empty()) {
+            watch.origins.insert(origin.output.clone(), origin);
+        }
+    }
+
+    pub(super) fn record_first_defined(&mut self) {
+        for (name, component) in self.components.iter() {
+            let defined = component.as_output().is_some_and(|output| output.get_value() != Tristate::Undefined);
+
+            if defined && !self.first_defined.contains_key(name) {
+                self.first_defined.insert(name.clone(), self.current_tick);
+            }
+        }
+    }
+
+    /// First tick at which the output `name` held a defined value, `None` if it never did.
+    pub fn first_defined_tick(&self, name: &str) -> Option<Tick> {
+        self.first_defined.get(name).copied()
+    }
+
+    /// Tick by which every output has held a defined value at least once, `None` while one never did.
+    ///
+    /// This is the initialization latency of the circuit.
+    pub fn all_outputs_defined_tick(&self) -> Option<Tick> {
+        self.components
+            .iter()
+            .filter(|(_, component)| component.as_output().is_some())
+            .try_fold(Tick::ZERO, |latest, (name, _)| Some(latest.max(self.first_defined_tick(name)?)))
+    }
+
+    /// Follows undefined values upstream from the input pin `pin`.
+    fn trace_undefined(&self, pin: PinRef) -> Vec<PinRef> {
+        let mut chain: Vec<PinRef> = Vec::new();
+        let mut visited: HashSet<PinRef> = HashSet::new();
+        let mut input = pin;
+
+        loop {
+            chain.push(input.clone());
+            visited.insert(input.clone());
+
+            let driver = self
+                .netlist
+                .drivers_of(&input)
+                .into_iter()
+                .find(|driver| self.pin_value(&driver.component, driver.pin) == Tristate::Undefined);
+            let Some(driver) = driver.filter(|driver| !visited.contains(*driver)).cloned() else {
+                return chain;
+            };
+
+            chain.push(driver.clone());
+            visited.insert(driver.clone());
+
+            let dependencies = match self.netlist.component(&driver.component).and_then(|component| component.info.as_ref()) {
+                Some(info) => info.dependencies(driver.pin),
+                None => Vec::new(),
+            };
+            let next = dependencies
+                .into_iter()
+                .map(|pin| PinRef::new(&driver.component, pin))
+                .find(|pin| !visited.contains(pin) && self.explain_input(&pin.component, pin.pin).value == Tristate::Undefined);
+            match next {
+                Some(next) => input = next,
+                None => return chain,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+    use crate::components::{tristate::Tristate, Tick};
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 gate
+        output out
+        output direct
+        .links:
+        a:1 gate:1
+        b:1 gate:2
+        gate:3 out:1
+        b:1 direct:1
+    ";
+
+    #[test]
+    fn test_origin_of_unset_input() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        circuit.set_value("a", "1").unwrap();
+        circuit.simulate();
+        circuit.watch_undefined(true);
+        circuit.simulate();
+
+        let origins = circuit.undefined_origins();
+        assert_eq!(origins.len(), 2);
+        assert_eq!(origins[0].output, "direct");
+        assert_eq!(origins[1].output, "out");
+        assert_eq!(origins[1].tick.get(), 1);
+        assert_eq!(origins[1].origin().to_string(), "b:1");
+        assert_eq!(origins[1].to_string(), "out: undefined since tick 1, from b:1 (out:1 <- gate:3 <- gate:2 <- b:1)");
+    }
+
+    #[test]
+    fn test_first_occurrence_is_kept() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        circuit.set_value("a", "1").unwrap();
+        circuit.set_value("b", "1").unwrap();
+        circuit.simulate();
+        circuit.watch_undefined(true);
+        assert!(circuit.undefined_origins().is_empty());
+
+        circuit.set_value("a", "U").unwrap();
+        circuit.simulate();
+        circuit.simulate();
+
+        let origins = circuit.undefined_origins();
+        assert_eq!(origins.len(), 1);
+        assert_eq!(origins[0].tick.get(), 2);
+        assert_eq!(origins[0].origin().to_string(), "a:1");
+        assert_eq!(circuit.get_output("direct").unwrap(), Tristate::State(true).to_string());
+    }
+
+    #[test]
+    fn test_unlinked_pin() {
+        let content = "
+            .chipsets:
+            input a
+            4081 gate
+            output out
+            .links:
+            a:1 gate:1
+            gate:3 out:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        circuit.set_value("a", "1").unwrap();
+        circuit.simulate();
+        circuit.watch_undefined(true);
+
+        assert_eq!(circuit.undefined_origins()[0].origin().to_string(), "gate:2");
+        assert_eq!(circuit.undefined_origins()[0].tick.get(), 1);
+
+        circuit.watch_undefined(false);
+        assert!(circuit.undefined_origins().is_empty());
+    }
+
+    #[test]
+    fn test_first_defined_tick() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        circuit.set_value("a", "0").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.first_defined_tick("out").map(Tick::get), Some(1));
+        assert_eq!(circuit.first_defined_tick("direct"), None);
+        assert_eq!(circuit.all_outputs_defined_tick(), None);
+
+        circuit.simulate();
+        circuit.set_value("b", "1").unwrap();
+        circuit.set_value("a", "U").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.first_defined_tick("out").map(Tick::get), Some(1));
+        assert_eq!(circuit.first_defined_tick("direct").map(Tick::get), Some(3));
+        assert_eq!(circuit.all_outputs_defined_tick().map(Tick::get), Some(3));
+        assert_eq!(circuit.first_defined_tick("a"), None);
+
+        let constant: Circuit = ".chipsets:\ntrue high\noutput out\n.links:\nhigh:1 out:1".parse().unwrap();
+        assert_eq!(constant.all_outputs_defined_tick(), Some(Tick::ZERO));
+    }
+}
@@ -0,0 +1,147 @@
+use std::any::{Any, TypeId};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use super::Circuit;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownComponent<'a>(pub &'a str);
+
+impl fmt::Display for UnknownComponent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown component \"{}\"", self.0)
+    }
+}
+
+/// Values and tags attached to components by the application simulating the circuit.
+#[derive(Default)]
+pub(super) struct UserData {
+    /// One value per type for each component.
+    values: HashMap<String, HashMap<TypeId, Box<dyn Any>>>,
+    tags: HashMap<String, BTreeSet<String>>,
+}
+
+impl Circuit {
+    fn check_component<'a>(&self, name: &'a str) -> Result<(), UnknownComponent<'a>> {
+        match self.components.contains_key(name) {
+            true => Ok(()),
+            false => Err(UnknownComponent(name)),
+        }
+    }
+
+    /// Attaches `data` to the component `name`, returning the value of the same type it replaces.
+    ///
+    /// Values are stored by type: a component holds at most one value of each type.
+    pub fn set_user_data<'a, T: Any>(&mut self, name: &'a str, data: T) -> Result<Option<T>, UnknownComponent<'a>> {
+        self.check_component(name)?;
+
+        let values = self.user_data.values.entry(name.to_owned()).or_default();
+        let previous = values.insert(TypeId::of::<T>(), Box::new(data));
+        Ok(previous.map(|previous| *previous.downcast().unwrap()))
+    }
+
+    pub fn user_data<T: Any>(&self, name: &str) -> Option<&T> {
+        self.user_data.values.get(name)?.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    pub fn user_data_mut<T: Any>(&mut self, name: &str) -> Option<&mut T> {
+        self.user_data.values.get_mut(name)?.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+
+    pub fn remove_user_data<T: Any>(&mut self, name: &str) -> Option<T> {
+        let data = self.user_data.values.get_mut(name)?.remove(&TypeId::of::<T>())?;
+
+        Some(*data.downcast().unwrap())
+    }
+
+    /// Tags the component `name`, returning `false` if it already had `tag`.
+    pub fn add_tag<'a>(&mut self, name: &'a str, tag: &str) -> Result<bool, UnknownComponent<'a>> {
+        self.check_component(name)?;
+
+        Ok(self.user_data.tags.entry(name.to_owned()).or_default().insert(tag.to_owned()))
+    }
+
+    /// Removes `tag` from the component `name`, returning `false` if it did not have it.
+    pub fn remove_tag(&mut self, name: &str, tag: &str) -> bool {
+        self.user_data.tags.get_mut(name).is_some_and(|tags| tags.remove(tag))
+    }
+
+    pub fn has_tag(&self, name: &str, tag: &str) -> bool {
+        self.user_data.tags.get(name).is_some_and(|tags| tags.contains(tag))
+    }
+
+    /// Tags of the component `name`, sorted.
+    pub fn tags(&self, name: &str) -> Vec<&str> {
+        self.user_data.tags.get(name).map_or_else(Vec::new, |tags| tags.iter().map(String::as_str).collect())
+    }
+
+    /// Components tagged with `tag`, sorted.
+    pub fn components_tagged(&self, tag: &str) -> Vec<&str> {
+        let mut names: Vec<&str> =
+            self.user_data.tags.iter().filter(|(_, tags)| tags.contains(tag)).map(|(name, _)| name.as_str()).collect();
+
+        names.sort();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnknownComponent;
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 gate
+        output out
+        .links:
+        a:1 gate:1
+        b:1 gate:2
+        gate:3 out:1
+    ";
+
+    #[derive(Debug, PartialEq)]
+    struct Position(i32, i32);
+
+    #[test]
+    fn test_user_data() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        assert_eq!(circuit.set_user_data("gate", Position(1, 2)), Ok(None));
+        assert_eq!(circuit.set_user_data("gate", "grading note".to_owned()), Ok(None));
+        assert_eq!(circuit.set_user_data("nope", 0_u8), Err(UnknownComponent("nope")));
+
+        assert_eq!(circuit.user_data::<Position>("gate"), Some(&Position(1, 2)));
+        assert_eq!(circuit.user_data::<String>("gate").unwrap(), "grading note");
+        assert_eq!(circuit.user_data::<Position>("a"), None);
+        assert_eq!(circuit.user_data::<u8>("gate"), None);
+
+        circuit.user_data_mut::<Position>("gate").unwrap().0 = 5;
+        assert_eq!(circuit.set_user_data("gate", Position(0, 0)), Ok(Some(Position(5, 2))));
+        assert_eq!(circuit.remove_user_data::<Position>("gate"), Some(Position(0, 0)));
+        assert_eq!(circuit.remove_user_data::<Position>("gate"), None);
+        assert!(circuit.user_data::<String>("gate").is_some());
+    }
+
+    #[test]
+    fn test_tags() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        assert_eq!(circuit.add_tag("b", "selected"), Ok(true));
+        assert_eq!(circuit.add_tag("a", "selected"), Ok(true));
+        assert_eq!(circuit.add_tag("a", "selected"), Ok(false));
+        assert_eq!(circuit.add_tag("a", "input"), Ok(true));
+        assert_eq!(circuit.add_tag("nope", "selected"), Err(UnknownComponent("nope")));
+
+        assert_eq!(circuit.components_tagged("selected"), vec!["a", "b"]);
+        assert_eq!(circuit.tags("a"), vec!["input", "selected"]);
+        assert!(circuit.has_tag("b", "selected"));
+        assert!(circuit.remove_tag("b", "selected"));
+        assert!(!circuit.remove_tag("b", "selected"));
+        assert!(!circuit.has_tag("b", "selected"));
+        assert_eq!(circuit.components_tagged("selected"), vec!["a"]);
+        assert!(circuit.tags("out").is_empty());
+    }
+}
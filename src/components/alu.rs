@@ -0,0 +1,275 @@
+//! Arithmetic and logic unit of a configurable width.
+//!
+//! With `W` the width, operand A is on pins 1 to `W` and operand B on pins `W + 1` to `2W`, from the least
+//! significant bit, then come the 3 opcode pins, from OP0, and the carry input. The result is on the next `W`
+//! pins, followed by the carry output and the zero flag.
+
+use std::{collections::HashMap, rc::Weak};
+
+use crate::pin::{PinContainer, PinSpecification};
+
+use super::info::{ComponentInfo, PinDirection};
+use super::params::{CreateError, Params};
+use super::{tristate::Tristate, Component, InvalidPin, PinNumber, Tick};
+
+/// Operation selected by the opcode pins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AluOperation {
+    /// A + B + CI, CO being the carry.
+    Add = 0,
+    /// A - B - CI, CO being the borrow.
+    Sub = 1,
+    And = 2,
+    Or = 3,
+    Xor = 4,
+    /// Complement of A.
+    Not = 5,
+    /// A shifted towards its most significant bit, CO being the bit shifted out.
+    ShiftLeft = 6,
+    /// A shifted towards its least significant bit, CO being the bit shifted out.
+    ShiftRight = 7,
+}
+
+impl AluOperation {
+    pub const ALL: [Self; 8] =
+        [Self::Add, Self::Sub, Self::And, Self::Or, Self::Xor, Self::Not, Self::ShiftLeft, Self::ShiftRight];
+
+    /// Result and carry output for operands of `width` bits, `None` if a value the operation needs is undefined.
+    pub fn apply(self, width: usize, a: Option<u64>, b: Option<u64>, carry: Option<bool>) -> Option<(u64, bool)> {
+        let mask = u64::MAX >> (64 - width);
+
+        let (result, carry) = match self {
+            Self::Add => {
+                let sum = a? + b? + u64::from(carry?);
+                (sum, sum > mask)
+            }
+            Self::Sub => {
+                let subtrahend = b? + u64::from(carry?);
+                (a?.wrapping_sub(subtrahend), a? < subtrahend)
+            }
+            Self::And => (a? & b?, false),
+            Self::Or => (a? | b?, false),
+            Self::Xor => (a? ^ b?, false),
+            Self::Not => (!a?, false),
+            Self::ShiftLeft => (a? << 1, (a? >> (width - 1)) & 1 == 1),
+            Self::ShiftRight => (a? >> 1, a? & 1 == 1),
+        };
+
+        Some((result & mask, carry))
+    }
+}
+
+pub struct AluComponent {
+    pins: PinContainer,
+    width: usize,
+}
+
+impl AluComponent {
+    /// Width of the `alu` component type without a `width` parameter.
+    pub const DEFAULT_WIDTH: usize = 4;
+    pub const MAX_WIDTH: usize = 32;
+    /// `width`: number of bits of the operands, from 1 to 32, 4 by default.
+    pub const PARAMETERS: &'static [&'static str] = &["width"];
+
+    pub fn new(width: usize) -> Self {
+        Self { pins: PinContainer::new(Self::nb_pins(width), Self::build_pins_spec(width)), width }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(Self::width_from_params(params)?))
+    }
+
+    /// Width given by the `width` parameter.
+    pub fn width_from_params(params: &Params) -> Result<usize, CreateError> {
+        match params.parse("width")? {
+            None => Ok(Self::DEFAULT_WIDTH),
+            Some(width) if (1..=Self::MAX_WIDTH).contains(&width) => Ok(width),
+            Some(width) => Err(CreateError::InvalidValue { key: "width".to_owned(), value: width.to_string() }),
+        }
+    }
+
+    pub fn info(name: &str, width: usize) -> ComponentInfo {
+        let description = format!("{width}-bit arithmetic and logic unit");
+        let mut info = ComponentInfo::new(name, &description, Self::nb_pins(width));
+
+        for bit in 0..width {
+            info = info
+                .with_pin(Self::a_pins(width)[bit], &format!("A{bit}"), PinDirection::Input)
+                .with_pin(Self::b_pins(width)[bit], &format!("B{bit}"), PinDirection::Input)
+                .with_pin(Self::f_pins(width)[bit], &format!("F{bit}"), PinDirection::Output);
+        }
+        for (bit, pin) in Self::op_pins(width).into_iter().enumerate() {
+            info = info.with_pin(pin, &format!("OP{bit}"), PinDirection::Input);
+        }
+        info.with_pin(Self::carry_in_pin(width), "CI", PinDirection::Input)
+            .with_pin(Self::carry_out_pin(width), "CO", PinDirection::Output)
+            .with_pin(Self::zero_pin(width), "Z", PinDirection::Output)
+    }
+
+    pub fn nb_pins(width: usize) -> usize {
+        3 * width + 6
+    }
+
+    pub fn a_pins(width: usize) -> Vec<PinNumber> {
+        (1..=width).collect()
+    }
+
+    pub fn b_pins(width: usize) -> Vec<PinNumber> {
+        (width + 1..=2 * width).collect()
+    }
+
+    pub fn op_pins(width: usize) -> [PinNumber; 3] {
+        [2 * width + 1, 2 * width + 2, 2 * width + 3]
+    }
+
+    pub fn carry_in_pin(width: usize) -> PinNumber {
+        2 * width + 4
+    }
+
+    pub fn f_pins(width: usize) -> Vec<PinNumber> {
+        (2 * width + 5..=3 * width + 4).collect()
+    }
+
+    pub fn carry_out_pin(width: usize) -> PinNumber {
+        3 * width + 5
+    }
+
+    pub fn zero_pin(width: usize) -> PinNumber {
+        3 * width + 6
+    }
+
+    #[inline]
+    fn build_pins_spec(width: usize) -> HashMap<PinNumber, PinSpecification> {
+        let outputs = Self::carry_out_pin(width)..=Self::zero_pin(width);
+
+        (1..=Self::carry_in_pin(width))
+            .map(|pin| (pin, PinSpecification::UnidirectionalInput()))
+            .chain(Self::f_pins(width).into_iter().chain(outputs).map(|pin| (pin, PinSpecification::UnidirectionalOutput())))
+            .collect()
+    }
+
+    /// `pins` read as an integer, the first pin being the least significant bit. `None` if one of them is undefined.
+    fn read_bits(&self, pins: &[PinNumber]) -> Option<u64> {
+        pins.iter().enumerate().try_fold(0, |value, (bit, &pin)| match self.pins.compute_input(pin).unwrap() {
+            Tristate::State(true) => Some(value | (1 << bit)),
+            Tristate::State(false) => Some(value),
+            Tristate::Undefined => None,
+        })
+    }
+}
+
+impl Component for AluComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let width = self.width;
+            let a = self.read_bits(&Self::a_pins(width));
+            let b = self.read_bits(&Self::b_pins(width));
+            let carry = self.read_bits(&[Self::carry_in_pin(width)]).map(|carry| carry == 1);
+            let result =
+                self.read_bits(&Self::op_pins(width)).and_then(|code| AluOperation::ALL[code as usize].apply(width, a, b, carry));
+
+            for (bit, pin) in Self::f_pins(width).into_iter().enumerate() {
+                let value = result.map_or(Tristate::Undefined, |(value, _)| Tristate::State((value >> bit) & 1 == 1));
+                outputs.get(&pin).unwrap().set(value);
+            }
+            let (carry, zero) = match result {
+                Some((value, carry)) => (Tristate::State(carry), Tristate::State(value == 0)),
+                None => (Tristate::Undefined, Tristate::Undefined),
+            };
+            outputs.get(&Self::carry_out_pin(width)).unwrap().set(carry);
+            outputs.get(&Self::zero_pin(width)).unwrap().set(zero);
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+impl Default for AluComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_WIDTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AluOperation;
+    use crate::circuit::Circuit;
+
+    #[test]
+    fn test_operations() {
+        assert_eq!(AluOperation::Add.apply(4, Some(9), Some(8), Some(false)), Some((1, true)));
+        assert_eq!(AluOperation::Add.apply(4, Some(9), Some(5), Some(true)), Some((15, false)));
+        assert_eq!(AluOperation::Sub.apply(4, Some(9), Some(5), Some(false)), Some((4, false)));
+        assert_eq!(AluOperation::Sub.apply(4, Some(5), Some(5), Some(true)), Some((15, true)));
+        assert_eq!(AluOperation::And.apply(4, Some(0b1100), Some(0b1010), None), Some((0b1000, false)));
+        assert_eq!(AluOperation::Or.apply(4, Some(0b1100), Some(0b1010), None), Some((0b1110, false)));
+        assert_eq!(AluOperation::Xor.apply(4, Some(0b1100), Some(0b1010), None), Some((0b0110, false)));
+        assert_eq!(AluOperation::Not.apply(4, Some(0b1100), None, None), Some((0b0011, false)));
+        assert_eq!(AluOperation::ShiftLeft.apply(4, Some(0b1100), None, None), Some((0b1000, true)));
+        assert_eq!(AluOperation::ShiftRight.apply(4, Some(0b1100), None, None), Some((0b0110, false)));
+        assert_eq!(AluOperation::Add.apply(4, Some(1), Some(1), None), None);
+        assert_eq!(AluOperation::And.apply(4, Some(1), None, Some(false)), None);
+    }
+
+    #[test]
+    fn test_alu_component() {
+        // 2-bit ALU: A on 1-2, B on 3-4, opcode on 5-7, CI on 8, F on 9-10, CO on 11, Z on 12.
+        let content = "
+            .chipsets:
+            input a0
+            input a1
+            input b0
+            input b1
+            input op0
+            input op1
+            input op2
+            input ci
+            alu alu width=2
+            output f0
+            output f1
+            output co
+            output z
+            .links:
+            a0:1 alu:1
+            a1:1 alu:2
+            b0:1 alu:3
+            b1:1 alu:4
+            op0:1 alu:5
+            op1:1 alu:6
+            op2:1 alu:7
+            ci:1 alu:8
+            alu:9 f0:1
+            alu:10 f1:1
+            alu:11 co:1
+            alu:12 z:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let mut run = |a: u64, b: u64, operation: AluOperation, ci: &str| {
+            circuit.set_bus("a", a).unwrap();
+            circuit.set_bus("b", b).unwrap();
+            circuit.set_bus("op", operation as u64).unwrap();
+            circuit.set_value("ci", ci).unwrap();
+            circuit.simulate();
+            ["co", "f1", "f0", "z"].map(|name| circuit.get_output(name).unwrap()).concat()
+        };
+
+        assert_eq!(run(3, 1, AluOperation::Add, "0"), "1001");
+        assert_eq!(run(1, 1, AluOperation::Add, "1"), "0110");
+        assert_eq!(run(1, 2, AluOperation::Sub, "0"), "1110");
+        assert_eq!(run(2, 2, AluOperation::Xor, "U"), "0001");
+        assert_eq!(run(2, 2, AluOperation::Add, "U"), "UUUU");
+
+        assert!(content.replace("width=2", "width=33").parse::<Circuit>().is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        BusInput, Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Input of `width` bits whose value is set as an integer from outside the circuit, bit `n` on pin `n + 1`.
+pub struct BusInputComponent {
+    pins: PinContainer,
+    width: usize,
+    value_for_next_tick: Cell<Option<u64>>,
+}
+
+impl BusInputComponent {
+    pub fn new(width: usize) -> Self {
+        Self { pins: PinContainer::new(width, Self::build_pins_spec(width)), width, value_for_next_tick: Default::default() }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(super::width_from_params(params)?))
+    }
+
+    pub fn info(name: &str, width: usize) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, "Bus input whose value is set from outside the circuit", width);
+
+        for bit in 0..width {
+            info = info.with_pin(bit + 1, &format!("out{bit}"), PinDirection::Output);
+        }
+        info
+    }
+
+    #[inline]
+    fn build_pins_spec(width: usize) -> HashMap<PinNumber, PinSpecification> {
+        (1..=width).map(|pin| (pin, PinSpecification::UnidirectionalOutput())).collect()
+    }
+}
+
+impl Component for BusInputComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            if let Some(value) = self.value_for_next_tick.replace(None) {
+                for bit in 0..self.width {
+                    outputs.get(&(bit + 1)).unwrap().set(Tristate::State((value >> bit) & 1 == 1));
+                }
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_bus_input(&self) -> Option<&dyn BusInput> {
+        Some(self)
+    }
+}
+
+impl BusInput for BusInputComponent {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn get_bus_value(&self) -> Option<u64> {
+        super::read_bits((1..=self.width).map(|pin| self.compute(pin).unwrap()))
+    }
+
+    fn set_bus_value(&self, value: u64) {
+        self.value_for_next_tick.set(Some(value));
+    }
+}
+
+impl Default for BusInputComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(super::DEFAULT_WIDTH)
+    }
+}
@@ -0,0 +1,85 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        BusOutput, Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Output of `width` bits whose value is read as an integer from outside the circuit, bit `n` on pin `n + 1`.
+pub struct BusOutputComponent {
+    pins: PinContainer,
+    width: usize,
+    result: Cell<Option<u64>>,
+}
+
+impl BusOutputComponent {
+    pub fn new(width: usize) -> Self {
+        Self { pins: PinContainer::new(width, Self::build_pins_spec(width)), width, result: Default::default() }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(super::width_from_params(params)?))
+    }
+
+    pub fn info(name: &str, width: usize) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, "Bus output whose value is read from outside the circuit", width);
+
+        for bit in 0..width {
+            info = info.with_pin(bit + 1, &format!("in{bit}"), PinDirection::Input);
+        }
+        info
+    }
+
+    #[inline]
+    fn build_pins_spec(width: usize) -> HashMap<PinNumber, PinSpecification> {
+        (1..=width).map(|pin| (pin, PinSpecification::UnidirectionalInput())).collect()
+    }
+}
+
+impl Component for BusOutputComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |_| {
+            let value = super::read_bits((1..=self.width).map(|pin| self.pins.compute_input(pin).unwrap()));
+
+            self.result.set(value);
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_bus_output(&self) -> Option<&dyn BusOutput> {
+        Some(self)
+    }
+}
+
+impl BusOutput for BusOutputComponent {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn get_bus_value(&self) -> Option<u64> {
+        self.result.get()
+    }
+}
+
+impl Default for BusOutputComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(super::DEFAULT_WIDTH)
+    }
+}
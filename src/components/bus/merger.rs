@@ -0,0 +1,79 @@
+use std::{cell::RefCell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Gathers the bits read on pins 1 to `width` on its bus pin `width + 1`, to be linked to the bus pin of a
+/// `splitter`. Read as a single bit, the bus pin is undefined.
+pub struct MergerComponent {
+    pins: PinContainer,
+    width: usize,
+    bits: RefCell<Vec<Tristate>>,
+}
+
+impl MergerComponent {
+    pub fn new(width: usize) -> Self {
+        Self {
+            pins: PinContainer::new(width + 1, Self::build_pins_spec(width)),
+            width,
+            bits: RefCell::new(vec![Tristate::Undefined; width]),
+        }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(super::width_from_params(params)?))
+    }
+
+    pub fn info(name: &str, width: usize) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, "Merges bits into a bus", width + 1);
+
+        for bit in 0..width {
+            info = info.with_pin(bit + 1, &format!("in{bit}"), PinDirection::Input);
+        }
+        info.with_pin(width + 1, "bus", PinDirection::Output)
+            .with_dependencies(width + 1, &(1..=width).collect::<Vec<PinNumber>>())
+    }
+
+    #[inline]
+    fn build_pins_spec(width: usize) -> HashMap<PinNumber, PinSpecification> {
+        (1..=width).map(|pin| (pin, PinSpecification::UnidirectionalInput())).collect()
+    }
+}
+
+impl Component for MergerComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |_| {
+            *self.bits.borrow_mut() = (1..=self.width).map(|pin| self.pins.compute_input(pin).unwrap()).collect();
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn compute_bus(&self, pin: PinNumber) -> Option<Vec<Tristate>> {
+        (pin == self.width + 1).then(|| self.bits.borrow().clone())
+    }
+}
+
+impl Default for MergerComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(super::DEFAULT_WIDTH)
+    }
+}
@@ -0,0 +1,29 @@
+pub mod bus_input;
+pub mod bus_output;
+pub mod merger;
+pub mod splitter;
+
+use crate::components::{params::CreateError, params::Params, tristate::Tristate};
+
+/// Number of pins of a bus component without a `width` parameter.
+pub const DEFAULT_WIDTH: usize = 8;
+/// `width`: number of bits, from 1 to 64, 8 by default.
+pub const PARAMETERS: &[&str] = &["width"];
+
+/// Width given by the `width` parameter.
+pub fn width_from_params(params: &Params) -> Result<usize, CreateError> {
+    match params.parse("width")? {
+        None => Ok(DEFAULT_WIDTH),
+        Some(width) if (1..=u64::BITS as usize).contains(&width) => Ok(width),
+        Some(width) => Err(CreateError::InvalidValue { key: "width".to_owned(), value: width.to_string() }),
+    }
+}
+
+/// `bits` read as an integer, bit 0 first. `None` if one of them is undefined.
+fn read_bits(bits: impl Iterator<Item = Tristate>) -> Option<u64> {
+    bits.enumerate().try_fold(0, |value, (index, bit)| match bit {
+        Tristate::State(true) => Some(value | (1 << index)),
+        Tristate::State(false) => Some(value),
+        Tristate::Undefined => None,
+    })
+}
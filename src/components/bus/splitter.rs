@@ -0,0 +1,149 @@
+use std::{cell::RefCell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Exposes on pins 2 to `width + 1` the bits carried by the bus pin 1, which is linked to the bus pin of a
+/// `merger`. Bits missing from the bus are undefined.
+pub struct SplitterComponent {
+    pins: PinContainer,
+    width: usize,
+    /// Component driving the bus pin, with its pin.
+    source: RefCell<Option<(Weak<dyn Component>, PinNumber)>>,
+}
+
+impl SplitterComponent {
+    pub const BUS: PinNumber = 1;
+
+    pub fn new(width: usize) -> Self {
+        Self { pins: PinContainer::new(width + 1, Self::build_pins_spec(width)), width, source: Default::default() }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(super::width_from_params(params)?))
+    }
+
+    pub fn info(name: &str, width: usize) -> ComponentInfo {
+        let mut info =
+            ComponentInfo::new(name, "Splits a bus into its bits", width + 1).with_pin(Self::BUS, "bus", PinDirection::Input);
+
+        for bit in 0..width {
+            info = info.with_pin(bit + 2, &format!("out{bit}"), PinDirection::Output).with_dependencies(bit + 2, &[Self::BUS]);
+        }
+        info
+    }
+
+    #[inline]
+    fn build_pins_spec(width: usize) -> HashMap<PinNumber, PinSpecification> {
+        (2..=width + 1).map(|pin| (pin, PinSpecification::UnidirectionalOutput())).collect()
+    }
+
+    fn read_bus(&self, tick: Tick) -> Vec<Tristate> {
+        let source = self.source.borrow().as_ref().and_then(|(source, pin)| Some((source.upgrade()?, *pin)));
+
+        source
+            .and_then(|(source, pin)| {
+                source.simulate(tick);
+                source.compute_bus(pin)
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Component for SplitterComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        if pin == Self::BUS {
+            *self.source.borrow_mut() = Some((other_component, other_pin));
+            return Ok(());
+        }
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let bits = self.read_bus(tick);
+
+            for bit in 0..self.width {
+                outputs.get(&(bit + 2)).unwrap().set(bits.get(bit).copied().unwrap_or(Tristate::Undefined));
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        pin != Self::BUS && self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+impl Default for SplitterComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(super::DEFAULT_WIDTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        businput a width=4
+        merger bits width=4
+        splitter wide width=6
+        4069 not
+        busoutput inverted width=4
+        output extra
+        output raw
+        .links:
+        a:1 bits:1
+        a:2 bits:2
+        a:3 bits:3
+        a:4 bits:4
+        bits:5 wide:1
+        wide:2 not:1
+        wide:3 not:3
+        wide:4 not:5
+        wide:5 not:9
+        not:2 inverted:1
+        not:4 inverted:2
+        not:6 inverted:3
+        not:8 inverted:4
+        wide:6 extra:1
+        bits:5 raw:1
+    ";
+
+    #[test]
+    fn test_bus_link() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        for value in [0b1010, 0b0000, 0b0111] {
+            circuit.set_bus("a", value).unwrap();
+            circuit.simulate();
+            assert_eq!(circuit.get_bus("inverted"), Some(!value & 0b1111), "{value:#b}");
+        }
+        // Bits beyond the width of the merger, and the bus pin read as a single bit.
+        assert_eq!(circuit.get_output("extra").unwrap(), "U");
+        assert_eq!(circuit.get_output("raw").unwrap(), "U");
+    }
+
+    #[test]
+    fn test_unlinked_bus() {
+        let content = CONTENT.replace("bits:5 wide:1\n", "");
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        circuit.set_bus("a", 0b1010).unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_bus("inverted"), None);
+    }
+}
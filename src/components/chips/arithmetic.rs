@@ -0,0 +1,181 @@
+use crate::components::{tristate::Tristate, PinNumber};
+
+use super::{Chip, ChipComponent, ChipPins};
+
+/* Final Components Declaration */
+pub type Component4008 = ChipComponent<Chip4008>;
+pub type Component4560 = ChipComponent<Chip4560>;
+/* ---------------------------- */
+
+// Both adders share the 4008 pinout.
+const A: [PinNumber; 4] = [7, 5, 3, 1];
+const B: [PinNumber; 4] = [6, 4, 2, 15];
+const CARRY_IN: PinNumber = 9;
+const SUM: [PinNumber; 4] = [10, 11, 12, 13];
+const CARRY_OUT: PinNumber = 14;
+
+const INPUTS: &[(PinNumber, &str)] = &[
+    (A[0], "A1"),
+    (A[1], "A2"),
+    (A[2], "A3"),
+    (A[3], "A4"),
+    (B[0], "B1"),
+    (B[1], "B2"),
+    (B[2], "B3"),
+    (B[3], "B4"),
+    (CARRY_IN, "Cin"),
+];
+const OUTPUTS: &[(PinNumber, &str)] = &[(SUM[0], "S1"), (SUM[1], "S2"), (SUM[2], "S3"), (SUM[3], "S4"), (CARRY_OUT, "Cout")];
+const POWER: &[(PinNumber, &str)] = &[(8, "VSS"), (16, "VDD")];
+
+/// Reads `a + b + carry_in`, `None` if an input is undefined.
+pub(super) fn read_sum(pins: &ChipPins<'_>, a: &[PinNumber], b: &[PinNumber], carry_in: PinNumber) -> Option<u64> {
+    let carry = pins.read_bits(&[carry_in])?;
+
+    Some(pins.read_bits(a)? + pins.read_bits(b)? + carry)
+}
+
+/// Outputs of a cascadable magnitude comparator, as `(a > b, a == b, a < b)`.
+///
+/// When `a == b` the result comes from the cascade inputs of the previous, less significant stage.
+pub(super) fn compare(
+    a: Option<u64>,
+    b: Option<u64>,
+    cascade_greater: Tristate,
+    cascade_equal: Tristate,
+    cascade_less: Tristate,
+) -> (Tristate, Tristate, Tristate) {
+    match a.zip(b).map(|(a, b)| a.cmp(&b)) {
+        Some(std::cmp::Ordering::Greater) => (true.into(), false.into(), false.into()),
+        Some(std::cmp::Ordering::Less) => (false.into(), false.into(), true.into()),
+        Some(std::cmp::Ordering::Equal) => (!cascade_equal & !cascade_less, cascade_equal, !cascade_equal & !cascade_greater),
+        None => (Tristate::Undefined, Tristate::Undefined, Tristate::Undefined),
+    }
+}
+
+/// 4-bit binary full adder.
+pub struct Chip4008;
+
+impl Chip for Chip4008 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "4-bit binary full adder";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = INPUTS;
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = OUTPUTS;
+    const POWER: &'static [(PinNumber, &'static str)] = POWER;
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        let sum = read_sum(pins, &A, &B, CARRY_IN);
+
+        pins.write_bits(&SUM, sum);
+        pins.set(CARRY_OUT, sum.map_or(Tristate::Undefined, |sum| (sum > 0b1111).into()));
+    }
+}
+
+/// 4-bit NBCD adder: the sum of two decimal digits, corrected by 6 when greater than 9.
+pub struct Chip4560;
+
+impl Chip for Chip4560 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "4-bit NBCD adder";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = INPUTS;
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = OUTPUTS;
+    const POWER: &'static [(PinNumber, &'static str)] = POWER;
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        let sum = read_sum(pins, &A, &B, CARRY_IN);
+        let carry = sum.map(|sum| sum > 9);
+
+        pins.write_bits(&SUM, sum.zip(carry).map(|(sum, carry)| if carry { (sum + 6) & 0b1111 } else { sum }));
+        pins.set(CARRY_OUT, carry.map_or(Tristate::Undefined, Tristate::from));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    fn adder(component_type: &str) -> Circuit {
+        let content = format!(
+            "
+            .chipsets:
+            input a0
+            input a1
+            input a2
+            input a3
+            input b0
+            input b1
+            input b2
+            input b3
+            input cin
+            {component_type} adder
+            output s0
+            output s1
+            output s2
+            output s3
+            output s4
+            .links:
+            a0:1 adder:7
+            a1:1 adder:5
+            a2:1 adder:3
+            a3:1 adder:1
+            b0:1 adder:6
+            b1:1 adder:4
+            b2:1 adder:2
+            b3:1 adder:15
+            cin:1 adder:9
+            adder:10 s0:1
+            adder:11 s1:1
+            adder:12 s2:1
+            adder:13 s3:1
+            adder:14 s4:1
+            "
+        );
+
+        content.parse().unwrap()
+    }
+
+    fn add(circuit: &mut Circuit, a: u64, b: u64, carry: u64) -> Option<u64> {
+        circuit.set_bus("a", a).unwrap();
+        circuit.set_bus("b", b).unwrap();
+        circuit.set_value("cin", &carry.to_string()).unwrap();
+        circuit.simulate();
+        circuit.get_bus("s")
+    }
+
+    #[test]
+    fn test_4008() {
+        let mut circuit = adder("4008");
+
+        assert_eq!(add(&mut circuit, 11, 7, 0), Some(18));
+        assert_eq!(add(&mut circuit, 15, 15, 1), Some(31));
+        assert_eq!(add(&mut circuit, 2, 3, 1), Some(6));
+    }
+
+    #[test]
+    fn test_4560() {
+        let mut circuit = adder("4560");
+
+        for a in 0..10 {
+            for b in 0..10 {
+                for carry in 0..2 {
+                    // Carry out in bit 4, decimal units in bits 0 to 3.
+                    let sum = a + b + carry;
+                    assert_eq!(add(&mut circuit, a, b, carry), Some((sum / 10) << 4 | (sum % 10)), "{a} + {b} + {carry}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_undefined_input() {
+        let mut circuit = adder("4560");
+
+        circuit.set_bus("a", 1).unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("s4").unwrap(), "U");
+    }
+}
@@ -0,0 +1,110 @@
+use crate::components::{tristate::Tristate, PinNumber};
+
+use super::{Chip, ChipComponent, ChipPins};
+
+/* Final Components Declaration */
+pub type Component4555 = ChipComponent<Chip4555>;
+/* ---------------------------- */
+
+/// Dual 1-of-4 decoder/demultiplexer with active high outputs: the output selected by B and A in each
+/// section is 1, the others are 0, and all of them are 0 while the active low enable E is 1.
+pub struct Chip4555;
+
+impl Chip4555 {
+    /// `(E, [A, B], [Q0, Q1, Q2, Q3])` of each section.
+    const SECTIONS: [(PinNumber, [PinNumber; 2], [PinNumber; 4]); 2] =
+        [(1, [2, 3], [4, 5, 6, 7]), (15, [14, 13], [12, 11, 10, 9])];
+}
+
+impl Chip for Chip4555 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "Dual 1-of-4 decoder/demultiplexer";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::SECTIONS[0].0, "EA"),
+        (Self::SECTIONS[0].1[0], "AA"),
+        (Self::SECTIONS[0].1[1], "BA"),
+        (Self::SECTIONS[1].0, "EB"),
+        (Self::SECTIONS[1].1[0], "AB"),
+        (Self::SECTIONS[1].1[1], "BB"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::SECTIONS[0].2[0], "Q0A"),
+        (Self::SECTIONS[0].2[1], "Q1A"),
+        (Self::SECTIONS[0].2[2], "Q2A"),
+        (Self::SECTIONS[0].2[3], "Q3A"),
+        (Self::SECTIONS[1].2[0], "Q0B"),
+        (Self::SECTIONS[1].2[1], "Q1B"),
+        (Self::SECTIONS[1].2[2], "Q2B"),
+        (Self::SECTIONS[1].2[3], "Q3B"),
+    ];
+    const POWER: &'static [(PinNumber, &'static str)] = &[(8, "VSS"), (16, "VDD")];
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        for (enable, select, outputs) in Self::SECTIONS {
+            let enabled = !pins.input(enable);
+            let selected = pins.read_bits(&select);
+
+            for (index, pin) in outputs.into_iter().enumerate() {
+                let active = match selected {
+                    Some(selected) => enabled & (selected == index as u64).into(),
+                    None => enabled & Tristate::Undefined,
+                };
+
+                pins.set(pin, active);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    #[test]
+    fn test_4555() {
+        let content = "
+            .chipsets:
+            input a
+            input b
+            input enable
+            false low
+            4555 decoder
+            output q0
+            output q1
+            output q2
+            output q3
+            output other
+            .links:
+            enable:1 decoder:15
+            a:1 decoder:14
+            b:1 decoder:13
+            decoder:12 q0:1
+            decoder:11 q1:1
+            decoder:10 q2:1
+            decoder:9 q3:1
+            low:1 decoder:1
+            low:1 decoder:2
+            low:1 decoder:3
+            decoder:4 other:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let mut outputs = |a: &str, b: &str, enable: &str| {
+            for (name, value) in [("a", a), ("b", b), ("enable", enable)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            ["q0", "q1", "q2", "q3", "other"].map(|name| circuit.get_output(name).unwrap()).concat()
+        };
+
+        assert_eq!(outputs("0", "0", "0"), "10001");
+        assert_eq!(outputs("1", "0", "0"), "01001");
+        assert_eq!(outputs("0", "1", "0"), "00101");
+        assert_eq!(outputs("1", "1", "0"), "00011");
+        assert_eq!(outputs("1", "1", "1"), "00001");
+        assert_eq!(outputs("U", "1", "0"), "UUUU1");
+        assert_eq!(outputs("U", "1", "1"), "00001");
+        assert_eq!(outputs("0", "0", "U"), "U0001");
+    }
+}
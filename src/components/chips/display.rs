@@ -0,0 +1,145 @@
+use crate::components::{tristate::Tristate, PinNumber};
+
+use super::{Chip, ChipComponent, ChipPins};
+
+/* Final Components Declaration */
+pub type Component4511 = ChipComponent<Chip4511>;
+/* ---------------------------- */
+
+/// Segments lit for each decimal digit, segment `a` in bit 0 to segment `g` in bit 6.
+///
+/// As on the 4511, the 6 and the 9 are drawn without their tail (segments `a` and `d` respectively).
+pub const DIGIT_SEGMENTS: [u8; 10] = [0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7C, 0x07, 0x7F, 0x67];
+
+/// BCD-to-7-segment latch/decoder/driver.
+///
+/// While LE is low the BCD input goes through the latch, which holds it once LE is high. Codes above 9
+/// blank the display, as does a low BL'. A low LT' lights every segment, whatever the other inputs.
+pub struct Chip4511;
+
+impl Chip4511 {
+    const BCD: [PinNumber; 4] = [7, 1, 2, 6];
+    const LAMP_TEST: PinNumber = 3;
+    const BLANKING: PinNumber = 4;
+    const LATCH_ENABLE: PinNumber = 5;
+    /// Segments `a` to `g`.
+    const SEGMENTS: [PinNumber; 7] = [13, 12, 11, 10, 9, 15, 14];
+}
+
+impl Chip for Chip4511 {
+    /// Latched BCD code.
+    type State = Option<u64>;
+
+    const DESCRIPTION: &'static str = "BCD-to-7-segment latch/decoder/driver";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::BCD[0], "A"),
+        (Self::BCD[1], "B"),
+        (Self::BCD[2], "C"),
+        (Self::BCD[3], "D"),
+        (Self::LAMP_TEST, "LT'"),
+        (Self::BLANKING, "BL'"),
+        (Self::LATCH_ENABLE, "LE"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::SEGMENTS[0], "a"),
+        (Self::SEGMENTS[1], "b"),
+        (Self::SEGMENTS[2], "c"),
+        (Self::SEGMENTS[3], "d"),
+        (Self::SEGMENTS[4], "e"),
+        (Self::SEGMENTS[5], "f"),
+        (Self::SEGMENTS[6], "g"),
+    ];
+    const POWER: &'static [(PinNumber, &'static str)] = &[(8, "VSS"), (16, "VDD")];
+
+    fn evaluate(latch: &mut Option<u64>, pins: &ChipPins<'_>) {
+        let code = pins.read_bits(&Self::BCD);
+
+        match pins.input(Self::LATCH_ENABLE) {
+            Tristate::State(false) => *latch = code,
+            Tristate::State(true) => (),
+            // The latch only keeps its value if it would not change either way.
+            Tristate::Undefined => {
+                if *latch != code {
+                    *latch = None;
+                }
+            }
+        }
+
+        let segments = match (pins.input(Self::LAMP_TEST), pins.input(Self::BLANKING)) {
+            (Tristate::State(false), _) => Some(0x7F),
+            (Tristate::Undefined, _) => None,
+            (_, Tristate::State(false)) => Some(0),
+            (_, Tristate::Undefined) => None,
+            (_, Tristate::State(true)) => {
+                latch.map(|code| DIGIT_SEGMENTS.get(code as usize).map_or(0, |&segments| segments.into()))
+            }
+        };
+        pins.write_bits(&Self::SEGMENTS, segments);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input bcd0
+        input bcd1
+        input bcd2
+        input bcd3
+        input lamp_test
+        input blanking
+        input latch
+        4511 decoder
+        7seg display
+        .links:
+        bcd0:1 decoder:7
+        bcd1:1 decoder:1
+        bcd2:1 decoder:2
+        bcd3:1 decoder:6
+        lamp_test:1 decoder:3
+        blanking:1 decoder:4
+        latch:1 decoder:5
+        decoder:13 display:1
+        decoder:12 display:2
+        decoder:11 display:3
+        decoder:10 display:4
+        decoder:9 display:5
+        decoder:15 display:6
+        decoder:14 display:7
+    ";
+
+    fn show(circuit: &mut Circuit, code: u64) -> String {
+        circuit.set_bus("bcd", code).unwrap();
+        circuit.simulate();
+        circuit.to_string().lines().find_map(|line| line.strip_prefix("  display: ")).unwrap().to_owned()
+    }
+
+    #[test]
+    fn test_4511() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        for (input, value) in [("lamp_test", "1"), ("blanking", "1"), ("latch", "0")] {
+            circuit.set_value(input, value).unwrap();
+        }
+        for digit in 0..10 {
+            assert_eq!(show(&mut circuit, digit), digit.to_string());
+        }
+        assert_eq!(show(&mut circuit, 12), "0000000");
+
+        circuit.set_value("lamp_test", "0").unwrap();
+        assert_eq!(show(&mut circuit, 3), "8");
+        circuit.set_value("lamp_test", "1").unwrap();
+        circuit.set_value("blanking", "0").unwrap();
+        assert_eq!(show(&mut circuit, 3), "0000000");
+        circuit.set_value("blanking", "1").unwrap();
+
+        circuit.set_value("latch", "1").unwrap();
+        assert_eq!(show(&mut circuit, 5), "3");
+        circuit.set_value("latch", "U").unwrap();
+        assert_eq!(show(&mut circuit, 3), "3");
+        assert_eq!(show(&mut circuit, 4), "UUUUUUU");
+    }
+}
@@ -0,0 +1,318 @@
+//! Memory chips, whose contents can be read with `Component::as_memory()`.
+
+use crate::components::{tristate::Tristate, PinNumber};
+use crate::pin::PinMode;
+
+use super::{Chip, ChipComponent, ChipPins, ClockEdge};
+
+/* Final Components Declaration */
+pub type Component2716 = ChipComponent<Chip2716>;
+pub type Component4801 = ChipComponent<Chip4801>;
+/* ---------------------------- */
+
+/// 256x8 static RAM with active low chip enable `CE`, output enable `OE` and write enable `WE`.
+///
+/// The data bus is written at the rising edge of `WE` while `CE` is low, and driven with the addressed byte
+/// while `CE` and `OE` are low and `WE` is high. Bytes are undefined until written.
+pub struct Chip4801;
+
+impl Chip4801 {
+    pub const SIZE: usize = 256;
+    const ADDRESS: [PinNumber; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+    const DATA: [PinNumber; 8] = [9, 10, 11, 12, 13, 14, 15, 16];
+    const CHIP_ENABLE: PinNumber = 17;
+    const OUTPUT_ENABLE: PinNumber = 18;
+    const WRITE_ENABLE: PinNumber = 19;
+}
+
+#[derive(Debug)]
+pub struct Ram4801State {
+    bytes: Vec<Option<u8>>,
+    write: ClockEdge,
+}
+
+impl Default for Ram4801State {
+    fn default() -> Self {
+        Self { bytes: vec![None; Chip4801::SIZE], write: Default::default() }
+    }
+}
+
+impl Chip for Chip4801 {
+    type State = Ram4801State;
+
+    const DESCRIPTION: &'static str = "256x8 static RAM";
+    const NB_PINS: usize = 19;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::ADDRESS[0], "A0"),
+        (Self::ADDRESS[1], "A1"),
+        (Self::ADDRESS[2], "A2"),
+        (Self::ADDRESS[3], "A3"),
+        (Self::ADDRESS[4], "A4"),
+        (Self::ADDRESS[5], "A5"),
+        (Self::ADDRESS[6], "A6"),
+        (Self::ADDRESS[7], "A7"),
+        (Self::CHIP_ENABLE, "CE"),
+        (Self::OUTPUT_ENABLE, "OE"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[];
+    const BIDIRECTIONAL: &'static [(PinNumber, &'static str)] = &[
+        (Self::DATA[0], "D0"),
+        (Self::DATA[1], "D1"),
+        (Self::DATA[2], "D2"),
+        (Self::DATA[3], "D3"),
+        (Self::DATA[4], "D4"),
+        (Self::DATA[5], "D5"),
+        (Self::DATA[6], "D6"),
+        (Self::DATA[7], "D7"),
+    ];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(Self::WRITE_ENABLE, "WE")];
+    const POWER: &'static [(PinNumber, &'static str)] = &[];
+
+    fn evaluate(state: &mut Ram4801State, pins: &ChipPins<'_>) {
+        let enabled = pins.input(Self::CHIP_ENABLE) == Tristate::State(false);
+        let write_enable = pins.input(Self::WRITE_ENABLE);
+        let reading = enabled && write_enable == Tristate::State(true) && pins.input(Self::OUTPUT_ENABLE) == false.into();
+        let address = pins.read_bits(&Self::ADDRESS).map(|address| address as usize);
+
+        for pin in Self::DATA {
+            pins.switch_mode(pin, if reading { PinMode::Output } else { PinMode::Input });
+        }
+
+        if state.write.rising(write_enable) && enabled {
+            // Writing at an unknown address is ignored.
+            if let Some(address) = address {
+                state.bytes[address] = pins.read_bits(&Self::DATA).map(|byte| byte as u8);
+            }
+        }
+        if reading {
+            pins.write_bits(&Self::DATA, address.and_then(|address| state.bytes[address]).map(u64::from));
+        }
+    }
+
+    fn memory(state: &Ram4801State) -> Option<&[Option<u8>]> {
+        Some(&state.bytes)
+    }
+
+    fn memory_mut(state: &mut Ram4801State) -> Option<&mut [Option<u8>]> {
+        Some(&mut state.bytes)
+    }
+}
+
+/// 2Kx8 EPROM with its DIP-24 pinout, erased (every byte at 0xFF) until an image is loaded.
+///
+/// The outputs are driven with the addressed byte while the active low `CE` and `OE` are low, and at 0 otherwise.
+pub struct Chip2716;
+
+impl Chip2716 {
+    pub const SIZE: usize = 2048;
+    const ADDRESS: [PinNumber; 11] = [8, 7, 6, 5, 4, 3, 2, 1, 23, 22, 19];
+    const DATA: [PinNumber; 8] = [9, 10, 11, 13, 14, 15, 16, 17];
+    const CHIP_ENABLE: PinNumber = 18;
+    const OUTPUT_ENABLE: PinNumber = 20;
+}
+
+#[derive(Debug)]
+pub struct Rom2716State {
+    bytes: Vec<Option<u8>>,
+}
+
+impl Default for Rom2716State {
+    fn default() -> Self {
+        Self { bytes: vec![Some(0xFF); Chip2716::SIZE] }
+    }
+}
+
+impl Chip for Chip2716 {
+    type State = Rom2716State;
+
+    const DESCRIPTION: &'static str = "2Kx8 EPROM";
+    const NB_PINS: usize = 24;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::ADDRESS[0], "A0"),
+        (Self::ADDRESS[1], "A1"),
+        (Self::ADDRESS[2], "A2"),
+        (Self::ADDRESS[3], "A3"),
+        (Self::ADDRESS[4], "A4"),
+        (Self::ADDRESS[5], "A5"),
+        (Self::ADDRESS[6], "A6"),
+        (Self::ADDRESS[7], "A7"),
+        (Self::ADDRESS[8], "A8"),
+        (Self::ADDRESS[9], "A9"),
+        (Self::ADDRESS[10], "A10"),
+        (Self::CHIP_ENABLE, "CE"),
+        (Self::OUTPUT_ENABLE, "OE"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::DATA[0], "O0"),
+        (Self::DATA[1], "O1"),
+        (Self::DATA[2], "O2"),
+        (Self::DATA[3], "O3"),
+        (Self::DATA[4], "O4"),
+        (Self::DATA[5], "O5"),
+        (Self::DATA[6], "O6"),
+        (Self::DATA[7], "O7"),
+    ];
+    const POWER: &'static [(PinNumber, &'static str)] = &[(12, "GND"), (21, "VPP"), (24, "VCC")];
+
+    fn evaluate(state: &mut Rom2716State, pins: &ChipPins<'_>) {
+        let enabled = !pins.input(Self::CHIP_ENABLE) & !pins.input(Self::OUTPUT_ENABLE);
+        let byte = match enabled {
+            Tristate::State(true) => pins.read_bits(&Self::ADDRESS).and_then(|address| state.bytes[address as usize]),
+            Tristate::State(false) => Some(0),
+            Tristate::Undefined => None,
+        };
+
+        pins.write_bits(&Self::DATA, byte.map(u64::from));
+    }
+
+    fn memory(state: &Rom2716State) -> Option<&[Option<u8>]> {
+        Some(&state.bytes)
+    }
+
+    fn memory_mut(state: &mut Rom2716State) -> Option<&mut [Option<u8>]> {
+        Some(&mut state.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::{Circuit, DeviceError};
+    use crate::components::image::ImageError;
+
+    fn build_circuit() -> Circuit {
+        let content = format!(
+            ".chipsets:\n{}input ce\ninput oe\ninput we\n4801 ram\n.links:\n{}ce:1 ram:17\noe:1 ram:18\nwe:1 ram:19\n",
+            (0..8).map(|bit| format!("input a{bit}\ninput d{bit}\noutput q{bit}\n")).collect::<String>(),
+            (0..8)
+                .map(|bit| format!("a{bit}:1 ram:{}\nd{bit}:1 ram:{}\nram:{} q{bit}:1\n", bit + 1, bit + 9, bit + 9))
+                .collect::<String>(),
+        );
+
+        content.parse().unwrap()
+    }
+
+    fn step(circuit: &mut Circuit, address: u64, data: u64, controls: [&str; 3]) -> Option<u64> {
+        circuit.set_bus("a", address).unwrap();
+        circuit.set_bus("d", data).unwrap();
+        for (name, value) in ["ce", "oe", "we"].into_iter().zip(controls) {
+            circuit.set_value(name, value).unwrap();
+        }
+        circuit.simulate();
+        circuit.get_bus("q")
+    }
+
+    #[test]
+    fn test_write_then_read() {
+        let mut circuit = build_circuit();
+
+        step(&mut circuit, 0x42, 0xA5, ["0", "1", "0"]);
+        assert_eq!(step(&mut circuit, 0x42, 0xA5, ["0", "1", "1"]), Some(0));
+        step(&mut circuit, 0x43, 0x5A, ["0", "1", "0"]);
+        step(&mut circuit, 0x43, 0x5A, ["0", "1", "1"]);
+
+        assert_eq!(step(&mut circuit, 0x42, 0, ["0", "0", "1"]), Some(0xA5));
+        assert_eq!(step(&mut circuit, 0x43, 0, ["0", "0", "1"]), Some(0x5A));
+        assert_eq!(step(&mut circuit, 0x44, 0, ["0", "0", "1"]), None);
+        assert_eq!(circuit.read_memory("ram", 0x42).unwrap(), Some(0xA5));
+        assert_eq!(circuit.read_memory("ram", 0x44).unwrap(), None);
+        assert_eq!(circuit.read_memory("ram", 0x100).unwrap(), None);
+    }
+
+    #[test]
+    fn test_preloaded_ram() {
+        let mut circuit = build_circuit();
+
+        circuit.load_rom("ram", &[1, 2, 3]).unwrap();
+        assert_eq!(step(&mut circuit, 2, 0, ["0", "0", "1"]), Some(3));
+        assert_eq!(circuit.read_memory("ram", 3).unwrap(), None);
+    }
+
+    #[test]
+    fn test_2716() {
+        let content = format!(
+            ".chipsets:\n{}input ce\ninput oe\n2716 rom\n.links:\n{}ce:1 rom:18\noe:1 rom:20\n",
+            (0..11).map(|bit| format!("input a{bit}\n")).collect::<String>()
+                + (0..8).map(|bit| format!("output q{bit}\n")).collect::<String>().as_str(),
+            [8, 7, 6, 5, 4, 3, 2, 1, 23, 22, 19]
+                .iter()
+                .enumerate()
+                .map(|(bit, pin)| format!("a{bit}:1 rom:{pin}\n"))
+                .collect::<String>()
+                + [9, 10, 11, 13, 14, 15, 16, 17]
+                    .iter()
+                    .enumerate()
+                    .map(|(bit, pin)| format!("rom:{pin} q{bit}:1\n"))
+                    .collect::<String>()
+                    .as_str(),
+        );
+        let mut circuit: Circuit = content.parse().unwrap();
+        let mut image = vec![0; 2048];
+        image[0x7FF] = 0x12;
+        image[0x400] = 0x34;
+
+        circuit.load_rom("rom", &image).unwrap();
+        let mut read = |address: u64, ce: &str, oe: &str| {
+            circuit.set_bus("a", address).unwrap();
+            circuit.set_value("ce", ce).unwrap();
+            circuit.set_value("oe", oe).unwrap();
+            circuit.simulate();
+            circuit.get_bus("q")
+        };
+
+        assert_eq!(read(0x7FF, "0", "0"), Some(0x12));
+        assert_eq!(read(0x400, "0", "0"), Some(0x34));
+        assert_eq!(read(0x400, "1", "0"), Some(0));
+        assert_eq!(read(0x400, "0", "1"), Some(0));
+        assert_eq!(read(0x400, "U", "0"), None);
+        assert!(matches!(
+            circuit.load_rom("rom", &[0; 2049]),
+            Err(DeviceError::ImageTooLarge { name: "rom", size: 2049, capacity: 2048 })
+        ));
+    }
+
+    #[test]
+    fn test_erased_2716() {
+        let circuit: Circuit = ".chipsets:\n2716 rom\n".parse().unwrap();
+
+        assert_eq!(circuit.read_memory("rom", 0).unwrap(), Some(0xFF));
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut circuit = build_circuit();
+
+        // Not written while CE is high, and the data bus is not driven.
+        step(&mut circuit, 0x10, 0xFF, ["1", "1", "0"]);
+        step(&mut circuit, 0x10, 0xFF, ["1", "1", "1"]);
+        assert_eq!(circuit.read_memory("ram", 0x10).unwrap(), None);
+        assert_eq!(step(&mut circuit, 0x10, 0, ["1", "0", "1"]), Some(0));
+    }
+
+    #[test]
+    fn test_load_image() {
+        let directory = std::env::temp_dir();
+        let (hex, bin) = (directory.join("nts_ram_image.hex"), directory.join("nts_ram_image.bin"));
+        std::fs::write(&hex, ":02004000123478\n:0101000000FE\n:00000001FF\n").unwrap();
+        std::fs::write(&bin, [7, 8]).unwrap();
+
+        let circuit: Circuit = format!(".chipsets:\n4801 ram file={}\n", bin.display()).parse().unwrap();
+        assert_eq!(circuit.read_memory("ram", 1).unwrap(), Some(8));
+        assert_eq!(circuit.read_memory("ram", 2).unwrap(), None);
+        assert!(format!(".chipsets:\n4801 ram file={} format=hex\n", bin.display()).parse::<Circuit>().is_err());
+
+        let circuit: Circuit = ".chipsets:\n2716 rom\n4801 ram\n".parse().unwrap();
+        circuit.load_memory("rom", &hex).unwrap();
+        assert_eq!(circuit.read_memory("rom", 0x40).unwrap(), Some(0x12));
+        assert_eq!(circuit.read_memory("rom", 0x41).unwrap(), Some(0x34));
+        assert_eq!(circuit.read_memory("rom", 0x100).unwrap(), Some(0));
+        assert_eq!(circuit.read_memory("rom", 0x42).unwrap(), Some(0xFF));
+        assert!(matches!(
+            circuit.load_memory("ram", &hex),
+            Err(DeviceError::Image { name: "ram", error: ImageError::OutOfRange { end: 0x101, capacity: 256 } })
+        ));
+        // Left untouched by the image which does not fit.
+        assert_eq!(circuit.read_memory("ram", 0x40).unwrap(), None);
+        std::fs::remove_file(hex).unwrap();
+        std::fs::remove_file(bin).unwrap();
+    }
+}
@@ -0,0 +1,421 @@
+//! Integrated circuits whose outputs are computed from their pins by a plain function,
+//! with an optional internal state for sequential chips.
+
+use std::{cell::Cell, cell::RefCell, collections::HashMap, marker::PhantomData, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        tristate::Tristate,
+        Component, InvalidPin, Memory, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinMode, PinSpecification},
+};
+
+pub mod arithmetic;
+pub mod decoders;
+pub mod display;
+pub mod memory;
+pub mod selectors;
+pub mod sequential;
+pub mod ttl;
+
+/// Pinout and behavior of a chip, turned into a component by `ChipComponent`.
+pub trait Chip: 'static {
+    /// Internal state kept between ticks, `()` for combinational chips.
+    type State: Default;
+
+    const DESCRIPTION: &'static str;
+    const NB_PINS: usize;
+    const INPUTS: &'static [(PinNumber, &'static str)];
+    const OUTPUTS: &'static [(PinNumber, &'static str)];
+    /// Pins switched between input and output with `ChipPins::switch_mode()`, inputs at first.
+    const BIDIRECTIONAL: &'static [(PinNumber, &'static str)] = &[];
+    /// Clock inputs, in addition to `INPUTS`.
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[];
+    /// Power supply pins, not simulated.
+    const POWER: &'static [(PinNumber, &'static str)];
+    /// Outputs which only depend on some of the inputs.
+    const DEPENDENCIES: &'static [(PinNumber, &'static [PinNumber])] = &[];
+    /// Maximum number of inputs some outputs can drive.
+    const FAN_OUT: &'static [(PinNumber, usize)] = &[];
+
+    /// Computes the outputs for the current tick.
+    fn evaluate(state: &mut Self::State, pins: &ChipPins<'_>);
+
+    /// Bytes stored by memory chips, exposed through `Component::as_memory()`.
+    fn memory(_state: &Self::State) -> Option<&[Option<u8>]> {
+        None
+    }
+
+    /// Mutable access to the bytes returned by `memory()`.
+    fn memory_mut(_state: &mut Self::State) -> Option<&mut [Option<u8>]> {
+        None
+    }
+}
+
+/// Access to the pins of a chip during `Chip::evaluate()`.
+pub struct ChipPins<'a> {
+    container: &'a PinContainer,
+    outputs: &'a HashMap<PinNumber, &'a Cell<Tristate>>,
+}
+
+impl ChipPins<'_> {
+    pub fn input(&self, pin: PinNumber) -> Tristate {
+        self.container.compute_input(pin).unwrap()
+    }
+
+    /// Reads `pins` as an integer, the first pin being the least significant bit.
+    /// `None` if one of them is undefined.
+    pub fn read_bits(&self, pins: &[PinNumber]) -> Option<u64> {
+        pins.iter().enumerate().try_fold(0, |value, (bit, &pin)| match self.input(pin) {
+            Tristate::State(true) => Some(value | (1 << bit)),
+            Tristate::State(false) => Some(value),
+            Tristate::Undefined => None,
+        })
+    }
+
+    /// Turns a bidirectional pin into an input, read as undefined in output mode, or an output, read as 0 by
+    /// the other pins of its net in input mode.
+    pub fn switch_mode(&self, pin: PinNumber, mode: PinMode) {
+        self.container.switch_pin_to_mode(pin, mode).unwrap();
+    }
+
+    pub fn set(&self, pin: PinNumber, value: Tristate) {
+        self.outputs.get(&pin).unwrap().set(value);
+    }
+
+    /// Writes the bits of `value` on `pins`, the first pin being the least significant bit.
+    /// Every pin is undefined if `value` is `None`.
+    pub fn write_bits(&self, pins: &[PinNumber], value: Option<u64>) {
+        for (bit, &pin) in pins.iter().enumerate() {
+            self.set(pin, value.map_or(Tristate::Undefined, |value| Tristate::State((value >> bit) & 1 == 1)));
+        }
+    }
+}
+
+/// Remembers the last value of a clock to detect its edges.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ClockEdge {
+    previous: Tristate,
+}
+
+impl ClockEdge {
+    /// Records `value`, returning `true` on a 0 to 1 transition.
+    pub fn rising(&mut self, value: Tristate) -> bool {
+        let previous = std::mem::replace(&mut self.previous, value);
+
+        previous == Tristate::State(false) && value == Tristate::State(true)
+    }
+
+    /// Records `value`, returning `true` on a 1 to 0 transition.
+    pub fn falling(&mut self, value: Tristate) -> bool {
+        let previous = std::mem::replace(&mut self.previous, value);
+
+        previous == Tristate::State(true) && value == Tristate::State(false)
+    }
+}
+
+/// Stored bit of a D flip-flop, loaded on the rising edges of its clock.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DFlipFlop {
+    clock: ClockEdge,
+    q: Tristate,
+}
+
+impl DFlipFlop {
+    /// Updates the flip-flop, `set` and `reset` being active high and asynchronous.
+    /// Returns `(Q, !Q)`, both high while `set` and `reset` are active together.
+    pub fn update(&mut self, clock: Tristate, data: Tristate, set: Tristate, reset: Tristate) -> (Tristate, Tristate) {
+        let rising = self.clock.rising(clock);
+
+        match (set, reset) {
+            (Tristate::State(true), Tristate::State(true)) => {
+                self.q = Tristate::Undefined;
+                return (Tristate::State(true), Tristate::State(true));
+            }
+            (Tristate::State(true), Tristate::State(false)) => self.q = Tristate::State(true),
+            (Tristate::State(false), Tristate::State(true)) => self.q = Tristate::State(false),
+            (Tristate::State(false), Tristate::State(false)) => {
+                if rising {
+                    self.q = data;
+                }
+            }
+            _ => self.q = Tristate::Undefined,
+        }
+
+        (self.q, !self.q)
+    }
+}
+
+/// Stored bit of a JK flip-flop, updated on the rising edges of its clock.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JKFlipFlop {
+    clock: ClockEdge,
+    q: Tristate,
+}
+
+impl JKFlipFlop {
+    /// Updates the flip-flop, `set` and `reset` being active high and asynchronous.
+    /// On a rising edge, holds with J = K = 0, resets with K alone, sets with J alone and toggles with both.
+    /// Returns `(Q, !Q)`, both high while `set` and `reset` are active together.
+    pub fn update(&mut self, clock: Tristate, j: Tristate, k: Tristate, set: Tristate, reset: Tristate) -> (Tristate, Tristate) {
+        let rising = self.clock.rising(clock);
+
+        match (set, reset) {
+            (Tristate::State(true), Tristate::State(true)) => {
+                self.q = Tristate::Undefined;
+                return (Tristate::State(true), Tristate::State(true));
+            }
+            (Tristate::State(true), Tristate::State(false)) => self.q = Tristate::State(true),
+            (Tristate::State(false), Tristate::State(true)) => self.q = Tristate::State(false),
+            (Tristate::State(false), Tristate::State(false)) => {
+                if rising {
+                    self.q = match (j, k) {
+                        (Tristate::State(false), Tristate::State(false)) => self.q,
+                        (Tristate::State(false), Tristate::State(true)) => Tristate::State(false),
+                        (Tristate::State(true), Tristate::State(false)) => Tristate::State(true),
+                        (Tristate::State(true), Tristate::State(true)) => !self.q,
+                        // Known when the undefined input would not change the result.
+                        _ => (j & !self.q) | (!k & self.q),
+                    };
+                }
+            }
+            _ => self.q = Tristate::Undefined,
+        }
+
+        (self.q, !self.q)
+    }
+}
+
+/// Stored bit of an RS latch, with active high inputs.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RSLatch {
+    q: Tristate,
+}
+
+impl RSLatch {
+    /// Updates the latch and returns Q, `set` winning over `reset` when both are active if `set_dominant`.
+    pub fn update(&mut self, set: Tristate, reset: Tristate, set_dominant: bool) -> Tristate {
+        self.q = if set_dominant { set | (!reset & self.q) } else { !reset & (set | self.q) };
+        self.q
+    }
+}
+
+/// Value of a counter wrapping around at `MODULUS`, undefined until it is loaded or reset.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Counter<const MODULUS: u64> {
+    value: Option<u64>,
+}
+
+impl<const MODULUS: u64> Counter<MODULUS> {
+    pub const MAX: u64 = MODULUS - 1;
+
+    pub fn value(&self) -> Option<u64> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.value = Some(0);
+    }
+
+    /// Loads `value`, kept undefined if it is `None` or out of range.
+    pub fn load(&mut self, value: Option<u64>) {
+        self.value = value.filter(|&value| value < MODULUS);
+    }
+
+    pub fn increment(&mut self) {
+        self.value = self.value.map(|value| (value + 1) % MODULUS);
+    }
+
+    pub fn decrement(&mut self) {
+        self.value = self.value.map(|value| value.checked_sub(1).unwrap_or(Self::MAX));
+    }
+}
+
+pub struct ChipComponent<C: Chip> {
+    pins: PinContainer,
+    state: RefCell<C::State>,
+    _chip: PhantomData<C>,
+}
+
+impl<C: Chip> ChipComponent<C> {
+    pub fn new() -> Self {
+        Self { pins: PinContainer::new(C::NB_PINS, Self::build_pins_spec()), state: Default::default(), _chip: PhantomData }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, C::DESCRIPTION, C::NB_PINS).with_sequential(!C::CLOCKS.is_empty());
+
+        for &(pin, pin_name) in C::POWER {
+            info = info.with_pin(pin, pin_name, PinDirection::Unused);
+        }
+        for &(pin, pin_name) in C::INPUTS {
+            info = info.with_pin(pin, pin_name, PinDirection::Input);
+        }
+        for &(pin, pin_name) in C::BIDIRECTIONAL {
+            info = info.with_pin(pin, pin_name, PinDirection::Bidirectional);
+        }
+        for &(pin, pin_name) in C::CLOCKS {
+            info = info.with_clock_pin(pin, pin_name);
+        }
+        for &(pin, pin_name) in C::OUTPUTS {
+            info = info.with_pin(pin, pin_name, PinDirection::Output);
+        }
+        for &(pin, inputs) in C::DEPENDENCIES {
+            info = info.with_dependencies(pin, inputs);
+        }
+        for &(pin, max) in C::FAN_OUT {
+            info = info.with_fan_out(pin, max);
+        }
+
+        info
+    }
+
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        let inputs = C::INPUTS.iter().chain(C::CLOCKS).map(|&(pin, _)| (pin, PinSpecification::UnidirectionalInput()));
+        let outputs = C::OUTPUTS.iter().map(|&(pin, _)| (pin, PinSpecification::UnidirectionalOutput()));
+        let bidirectional = C::BIDIRECTIONAL.iter().map(|&(pin, _)| (pin, PinSpecification::Bidirectional(PinMode::Input)));
+
+        inputs.chain(outputs).chain(bidirectional).collect()
+    }
+}
+
+impl<C: Chip> Default for ChipComponent<C> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Chip> Component for ChipComponent<C> {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            C::evaluate(&mut self.state.borrow_mut(), &ChipPins { container: &self.pins, outputs });
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_memory(&self) -> Option<&dyn Memory> {
+        C::memory(&self.state.borrow()).is_some().then_some(self)
+    }
+}
+
+impl<C: Chip> Memory for ChipComponent<C> {
+    fn size(&self) -> usize {
+        C::memory(&self.state.borrow()).map_or(0, <[Option<u8>]>::len)
+    }
+
+    fn read(&self, address: usize) -> Option<u8> {
+        C::memory(&self.state.borrow())?.get(address).copied().flatten()
+    }
+
+    fn dump(&self) -> Vec<Option<u8>> {
+        C::memory(&self.state.borrow()).map_or_else(Vec::new, <[Option<u8>]>::to_vec)
+    }
+
+    fn store(&self, address: usize, bytes: &[u8]) {
+        if let Some(memory) = C::memory_mut(&mut self.state.borrow_mut()) {
+            memory.iter_mut().skip(address).zip(bytes).for_each(|(stored, &byte)| *stored = Some(byte));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClockEdge, Counter, DFlipFlop, JKFlipFlop, RSLatch};
+    use crate::components::tristate::Tristate;
+
+    #[test]
+    fn test_clock_edge() {
+        let mut edge = ClockEdge::default();
+
+        assert!(!edge.rising(Tristate::State(true)));
+        assert!(!edge.rising(Tristate::State(false)));
+        assert!(edge.rising(Tristate::State(true)));
+        assert!(!edge.rising(Tristate::State(true)));
+        assert!(!edge.rising(Tristate::Undefined));
+        assert!(!edge.rising(Tristate::State(true)));
+    }
+
+    #[test]
+    fn test_d_flip_flop() {
+        let (zero, one, undefined) = (Tristate::State(false), Tristate::State(true), Tristate::Undefined);
+        let mut flip_flop = DFlipFlop::default();
+
+        assert_eq!(flip_flop.update(zero, one, zero, zero), (undefined, undefined));
+        assert_eq!(flip_flop.update(one, one, zero, zero), (one, zero));
+        assert_eq!(flip_flop.update(zero, zero, zero, zero), (one, zero));
+        assert_eq!(flip_flop.update(one, zero, zero, zero), (zero, one));
+        assert_eq!(flip_flop.update(one, zero, one, zero), (one, zero));
+        assert_eq!(flip_flop.update(one, zero, zero, one), (zero, one));
+        assert_eq!(flip_flop.update(one, zero, one, one), (one, one));
+        assert_eq!(flip_flop.update(one, zero, zero, zero), (undefined, undefined));
+    }
+
+    #[test]
+    fn test_jk_flip_flop() {
+        let (zero, one, undefined) = (Tristate::State(false), Tristate::State(true), Tristate::Undefined);
+        let mut flip_flop = JKFlipFlop::default();
+
+        assert_eq!(flip_flop.update(zero, one, one, zero, zero), (undefined, undefined));
+        assert_eq!(flip_flop.update(one, one, one, zero, zero), (undefined, undefined));
+        assert_eq!(flip_flop.update(zero, one, zero, zero, zero), (undefined, undefined));
+        assert_eq!(flip_flop.update(one, one, zero, zero, zero), (one, zero));
+        assert_eq!(flip_flop.update(zero, one, one, zero, zero), (one, zero));
+        assert_eq!(flip_flop.update(one, one, one, zero, zero), (zero, one));
+        assert_eq!(flip_flop.update(zero, zero, zero, zero, zero), (zero, one));
+        assert_eq!(flip_flop.update(one, zero, zero, zero, zero), (zero, one));
+        assert_eq!(flip_flop.update(zero, zero, undefined, zero, zero), (zero, one));
+        assert_eq!(flip_flop.update(one, zero, undefined, zero, zero), (zero, one));
+        assert_eq!(flip_flop.update(zero, undefined, one, zero, zero), (zero, one));
+        assert_eq!(flip_flop.update(one, undefined, one, zero, zero), (undefined, undefined));
+        assert_eq!(flip_flop.update(one, zero, zero, one, zero), (one, zero));
+        assert_eq!(flip_flop.update(one, zero, zero, zero, one), (zero, one));
+        assert_eq!(flip_flop.update(one, zero, zero, one, one), (one, one));
+    }
+
+    #[test]
+    fn test_rs_latch() {
+        let (zero, one, undefined) = (Tristate::State(false), Tristate::State(true), Tristate::Undefined);
+        let mut latch = RSLatch::default();
+
+        assert_eq!(latch.update(zero, zero, true), undefined);
+        assert_eq!(latch.update(undefined, zero, true), undefined);
+        assert_eq!(latch.update(one, zero, true), one);
+        assert_eq!(latch.update(zero, zero, true), one);
+        assert_eq!(latch.update(undefined, zero, true), one);
+        assert_eq!(latch.update(one, one, true), one);
+        assert_eq!(latch.update(one, one, false), zero);
+        assert_eq!(latch.update(zero, undefined, false), zero);
+        assert_eq!(latch.update(undefined, zero, false), undefined);
+    }
+
+    #[test]
+    fn test_counter() {
+        let mut counter = Counter::<10>::default();
+
+        counter.increment();
+        assert_eq!(counter.value(), None);
+        counter.reset();
+        counter.decrement();
+        assert_eq!(counter.value(), Some(9));
+        counter.increment();
+        assert_eq!(counter.value(), Some(0));
+        counter.load(Some(12));
+        assert_eq!(counter.value(), None);
+        counter.load(Some(7));
+        assert_eq!(counter.value(), Some(7));
+    }
+}
@@ -0,0 +1,207 @@
+//! Multiplexers and demultiplexers without power pins, the select inputs following the data inputs.
+
+use crate::components::{tristate::Tristate, PinNumber};
+
+use super::{Chip, ChipComponent, ChipPins};
+
+/* Final Components Declaration */
+pub type ComponentMux2 = ChipComponent<ChipMux2>;
+pub type ComponentMux4 = ChipComponent<ChipMux4>;
+pub type ComponentDemux2 = ChipComponent<ChipDemux2>;
+pub type ComponentDemux4 = ChipComponent<ChipDemux4>;
+/* ---------------------------- */
+
+/// Copies the data input selected by `select` to `output`. With an undefined select, the output is
+/// only known if all the data inputs agree.
+fn multiplex(pins: &ChipPins<'_>, data: &[PinNumber], select: &[PinNumber], output: PinNumber) {
+    let value = match pins.read_bits(select) {
+        Some(selected) => pins.input(data[selected as usize]),
+        None => {
+            let first = pins.input(data[0]);
+
+            if data[1..].iter().all(|&pin| pins.input(pin) == first) {
+                first
+            } else {
+                Tristate::Undefined
+            }
+        }
+    };
+
+    pins.set(output, value);
+}
+
+/// Copies `data` to the output selected by `select`, the other outputs being 0.
+fn demultiplex(pins: &ChipPins<'_>, data: PinNumber, select: &[PinNumber], outputs: &[PinNumber]) {
+    let value = pins.input(data);
+    let selected = pins.read_bits(select);
+
+    for (index, &pin) in outputs.iter().enumerate() {
+        let active = match selected {
+            Some(selected) => Tristate::from(selected == index as u64),
+            None => Tristate::Undefined,
+        };
+
+        pins.set(pin, value & active);
+    }
+}
+
+/// 2-to-1 multiplexer: Y is I1 while S is 1, I0 otherwise.
+pub struct ChipMux2;
+
+impl Chip for ChipMux2 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "2-to-1 multiplexer";
+    const NB_PINS: usize = 4;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[(1, "I0"), (2, "I1"), (3, "S")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(4, "Y")];
+    const POWER: &'static [(PinNumber, &'static str)] = &[];
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        multiplex(pins, &[1, 2], &[3], 4);
+    }
+}
+
+/// 4-to-1 multiplexer: Y is the data input numbered by S1 and S0.
+pub struct ChipMux4;
+
+impl Chip for ChipMux4 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "4-to-1 multiplexer";
+    const NB_PINS: usize = 7;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[(1, "I0"), (2, "I1"), (3, "I2"), (4, "I3"), (5, "S0"), (6, "S1")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(7, "Y")];
+    const POWER: &'static [(PinNumber, &'static str)] = &[];
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        multiplex(pins, &[1, 2, 3, 4], &[5, 6], 7);
+    }
+}
+
+/// 1-to-2 demultiplexer: D is copied to Y1 while S is 1, to Y0 otherwise.
+pub struct ChipDemux2;
+
+impl Chip for ChipDemux2 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "1-to-2 demultiplexer";
+    const NB_PINS: usize = 4;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[(1, "D"), (2, "S")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(3, "Y0"), (4, "Y1")];
+    const POWER: &'static [(PinNumber, &'static str)] = &[];
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        demultiplex(pins, 1, &[2], &[3, 4]);
+    }
+}
+
+/// 1-to-4 demultiplexer: D is copied to the output numbered by S1 and S0.
+pub struct ChipDemux4;
+
+impl Chip for ChipDemux4 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "1-to-4 demultiplexer";
+    const NB_PINS: usize = 7;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[(1, "D"), (2, "S0"), (3, "S1")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(4, "Y0"), (5, "Y1"), (6, "Y2"), (7, "Y3")];
+    const POWER: &'static [(PinNumber, &'static str)] = &[];
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        demultiplex(pins, 1, &[2, 3], &[4, 5, 6, 7]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    // A 4-bit value is routed through a mux4 then a demux4 sharing the same select inputs.
+    const CONTENT: &str = "
+        .chipsets:
+        input i0
+        input i1
+        input i2
+        input i3
+        input s0
+        input s1
+        mux4 mux
+        demux4 demux
+        output y
+        output y0
+        output y1
+        output y2
+        output y3
+        .links:
+        i0:1 mux:1
+        i1:1 mux:2
+        i2:1 mux:3
+        i3:1 mux:4
+        s0:1 mux:5
+        s1:1 mux:6
+        mux:7 y:1
+        mux:7 demux:1
+        s0:1 demux:2
+        s1:1 demux:3
+        demux:4 y0:1
+        demux:5 y1:1
+        demux:6 y2:1
+        demux:7 y3:1
+    ";
+
+    #[test]
+    fn test_mux4_demux4() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        let mut route = |select: &str| {
+            circuit.set_bus("i", 0b0100).unwrap();
+            circuit.set_value("s0", &select[1..]).unwrap();
+            circuit.set_value("s1", &select[..1]).unwrap();
+            circuit.simulate();
+            ["y", "y3", "y2", "y1", "y0"].map(|name| circuit.get_output(name).unwrap()).concat()
+        };
+
+        assert_eq!(route("00"), "00000");
+        assert_eq!(route("10"), "10100");
+        assert_eq!(route("11"), "00000");
+        assert_eq!(route("1U"), "UUUUU");
+    }
+
+    #[test]
+    fn test_mux2_demux2() {
+        let content = "
+            .chipsets:
+            input a
+            input b
+            input s
+            mux2 mux
+            demux2 demux
+            output y
+            output y0
+            output y1
+            .links:
+            a:1 mux:1
+            b:1 mux:2
+            s:1 mux:3
+            mux:4 y:1
+            mux:4 demux:1
+            s:1 demux:2
+            demux:3 y0:1
+            demux:4 y1:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let mut route = |a: &str, b: &str, s: &str| {
+            circuit.set_value("a", a).unwrap();
+            circuit.set_value("b", b).unwrap();
+            circuit.set_value("s", s).unwrap();
+            circuit.simulate();
+            ["y", "y1", "y0"].map(|name| circuit.get_output(name).unwrap()).concat()
+        };
+
+        assert_eq!(route("1", "0", "0"), "101");
+        assert_eq!(route("1", "0", "1"), "000");
+        assert_eq!(route("0", "1", "1"), "110");
+        assert_eq!(route("1", "1", "U"), "1UU");
+        assert_eq!(route("0", "1", "U"), "UUU");
+    }
+}
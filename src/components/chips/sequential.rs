@@ -0,0 +1,1266 @@
+//! 4000-series chips keeping a state between ticks.
+
+use crate::components::{tristate::Tristate, PinNumber};
+use crate::pin::PinMode;
+
+use super::{Chip, ChipComponent, ChipPins, ClockEdge, Counter, DFlipFlop, JKFlipFlop, RSLatch};
+
+/* Final Components Declaration */
+pub type Component4006 = ChipComponent<Chip4006>;
+pub type Component4013 = ChipComponent<Chip4013>;
+pub type Component4014 = ChipComponent<Chip4014>;
+pub type Component4015 = ChipComponent<Chip4015>;
+pub type Component4027 = ChipComponent<Chip4027>;
+pub type Component4029 = ChipComponent<Chip4029>;
+pub type Component4043 = ChipComponent<Chip4043>;
+pub type Component4044 = ChipComponent<Chip4044>;
+pub type Component4060 = ChipComponent<Chip4060>;
+pub type Component4510 = ChipComponent<Chip4510>;
+pub type Component4516 = ChipComponent<Chip4516>;
+pub type Component40103 = ChipComponent<Chip40103>;
+/* ---------------------------- */
+
+const POWER_14: &[(PinNumber, &str)] = &[(7, "VSS"), (14, "VDD")];
+const POWER_16: &[(PinNumber, &str)] = &[(8, "VSS"), (16, "VDD")];
+
+/// 18-stage static shift register made of four sections of 4, 5, 4 and 5 stages, each with its own data input.
+/// Every stage takes the value of the previous one on the falling edges of CLOCK. The 5-stage sections are also
+/// tapped after their fourth stage, and the sections are chained by linking the last tap of one to the data
+/// input of the next.
+pub struct Chip4006;
+
+impl Chip4006 {
+    const CLOCK: PinNumber = 3;
+    /// `(DATA, first stage, number of stages)` of each section.
+    const SECTIONS: [(PinNumber, usize, usize); 4] = [(1, 0, 4), (4, 4, 5), (5, 9, 4), (6, 13, 5)];
+    /// `(stage, output)` of each tap.
+    const TAPS: [(usize, PinNumber); 6] = [(3, 13), (7, 11), (8, 12), (12, 10), (16, 8), (17, 9)];
+}
+
+#[derive(Debug, Default)]
+pub struct ShiftRegister4006State {
+    stages: [Tristate; 18],
+    clock: ClockEdge,
+}
+
+impl Chip for Chip4006 {
+    type State = ShiftRegister4006State;
+
+    const DESCRIPTION: &'static str = "18-stage static shift register";
+    const NB_PINS: usize = 14;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[(1, "D1"), (4, "D2"), (5, "D3"), (6, "D4")];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(Self::CLOCK, "CLOCK")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] =
+        &[(13, "D1+4"), (11, "D2+4"), (12, "D2+5"), (10, "D3+4"), (8, "D4+4"), (9, "D4+5")];
+    const POWER: &'static [(PinNumber, &'static str)] = &[(2, "NC"), (7, "VSS"), (14, "VDD")];
+
+    fn evaluate(state: &mut ShiftRegister4006State, pins: &ChipPins<'_>) {
+        // Every input is read before shifting, since a section may be fed by a tap of this chip.
+        let data = Self::SECTIONS.map(|(pin, _, _)| pins.input(pin));
+
+        if state.clock.falling(pins.input(Self::CLOCK)) {
+            for ((_, first, length), data) in Self::SECTIONS.into_iter().zip(data) {
+                state.stages.copy_within(first..first + length - 1, first + 1);
+                state.stages[first] = data;
+            }
+        }
+
+        for (stage, pin) in Self::TAPS {
+            pins.set(pin, state.stages[stage]);
+        }
+    }
+}
+
+/// Dual D flip-flop with active high asynchronous set and reset.
+pub struct Chip4013;
+
+impl Chip4013 {
+    /// `(CLOCK, RESET, DATA, SET, Q, !Q)` of each flip-flop.
+    const PER_FLIP_FLOPS: [(PinNumber, PinNumber, PinNumber, PinNumber, PinNumber, PinNumber); 2] =
+        [(3, 4, 5, 6, 1, 2), (11, 10, 9, 8, 13, 12)];
+}
+
+impl Chip for Chip4013 {
+    type State = [DFlipFlop; 2];
+
+    const DESCRIPTION: &'static str = "Dual D flip-flop with set and reset";
+    const NB_PINS: usize = 14;
+    const INPUTS: &'static [(PinNumber, &'static str)] =
+        &[(4, "RESET1"), (5, "DATA1"), (6, "SET1"), (10, "RESET2"), (9, "DATA2"), (8, "SET2")];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(3, "CLOCK1"), (11, "CLOCK2")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(1, "Q1"), (2, "Q1'"), (13, "Q2"), (12, "Q2'")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_14;
+
+    fn evaluate(state: &mut [DFlipFlop; 2], pins: &ChipPins<'_>) {
+        for (flip_flop, (clock, reset, data, set, q, q_bar)) in state.iter_mut().zip(Self::PER_FLIP_FLOPS) {
+            let (value, inverted) = flip_flop.update(pins.input(clock), pins.input(data), pins.input(set), pins.input(reset));
+
+            pins.set(q, value);
+            pins.set(q_bar, inverted);
+        }
+    }
+}
+
+/// 8-stage synchronous parallel-in/serial-out shift register.
+///
+/// On the rising edges of CLOCK, the stages load P1 to P8 while P/S is high, and otherwise shift towards Q8,
+/// the first stage taking SERIAL. Only the last three stages are brought out.
+pub struct Chip4014;
+
+impl Chip4014 {
+    const CLOCK: PinNumber = 10;
+    const PARALLEL_SERIAL: PinNumber = 9;
+    const SERIAL: PinNumber = 11;
+    /// P1 to P8.
+    const PARALLEL: [PinNumber; 8] = [7, 6, 5, 4, 13, 14, 15, 1];
+    /// `(stage, output)` of Q6, Q7 and Q8.
+    const OUTPUTS: [(usize, PinNumber); 3] = [(5, 2), (6, 12), (7, 3)];
+}
+
+#[derive(Debug, Default)]
+pub struct ShiftRegister4014State {
+    stages: [Tristate; 8],
+    clock: ClockEdge,
+}
+
+impl Chip for Chip4014 {
+    type State = ShiftRegister4014State;
+
+    const DESCRIPTION: &'static str = "8-stage parallel-in/serial-out shift register";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::PARALLEL[0], "P1"),
+        (Self::PARALLEL[1], "P2"),
+        (Self::PARALLEL[2], "P3"),
+        (Self::PARALLEL[3], "P4"),
+        (Self::PARALLEL[4], "P5"),
+        (Self::PARALLEL[5], "P6"),
+        (Self::PARALLEL[6], "P7"),
+        (Self::PARALLEL[7], "P8"),
+        (Self::PARALLEL_SERIAL, "P/S"),
+        (Self::SERIAL, "SERIAL"),
+    ];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(Self::CLOCK, "CLOCK")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(2, "Q6"), (12, "Q7"), (3, "Q8")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(state: &mut ShiftRegister4014State, pins: &ChipPins<'_>) {
+        if state.clock.rising(pins.input(Self::CLOCK)) {
+            let loaded = Self::PARALLEL.map(|pin| pins.input(pin));
+            let mut shifted = [pins.input(Self::SERIAL); 8];
+            shifted[1..].copy_from_slice(&state.stages[..7]);
+
+            state.stages = match pins.input(Self::PARALLEL_SERIAL) {
+                Tristate::State(true) => loaded,
+                Tristate::State(false) => shifted,
+                // Only the stages which would get the same value either way are known.
+                Tristate::Undefined => std::array::from_fn(|stage| match loaded[stage] == shifted[stage] {
+                    true => loaded[stage],
+                    false => Tristate::Undefined,
+                }),
+            };
+        }
+
+        for (stage, pin) in Self::OUTPUTS {
+            pins.set(pin, state.stages[stage]);
+        }
+    }
+}
+
+/// Dual 4-stage static shift register with serial input and parallel outputs.
+///
+/// On the rising edges of its CLOCK, each section shifts towards Q4, Q1 taking DATA. A high RESET clears
+/// the section whatever its clock.
+pub struct Chip4015;
+
+impl Chip4015 {
+    /// `(CLOCK, RESET, DATA, [Q1, Q2, Q3, Q4])` of each section.
+    const SECTIONS: [(PinNumber, PinNumber, PinNumber, [PinNumber; 4]); 2] =
+        [(9, 6, 7, [5, 4, 3, 10]), (1, 14, 15, [13, 12, 11, 2])];
+}
+
+/// State of a section of the 4015.
+#[derive(Debug, Default)]
+pub struct ShiftRegister4015State {
+    stages: [Tristate; 4],
+    clock: ClockEdge,
+}
+
+impl Chip for Chip4015 {
+    type State = [ShiftRegister4015State; 2];
+
+    const DESCRIPTION: &'static str = "Dual 4-stage static shift register";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[(6, "RESET A"), (7, "DATA A"), (14, "RESET B"), (15, "DATA B")];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(9, "CLOCK A"), (1, "CLOCK B")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] =
+        &[(5, "Q1A"), (4, "Q2A"), (3, "Q3A"), (10, "Q4A"), (13, "Q1B"), (12, "Q2B"), (11, "Q3B"), (2, "Q4B")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(state: &mut [ShiftRegister4015State; 2], pins: &ChipPins<'_>) {
+        // Every input is read before shifting, since a section may be fed by the other one.
+        let data = Self::SECTIONS.map(|(_, _, data, _)| pins.input(data));
+
+        for ((section, (clock, reset, _, outputs)), data) in state.iter_mut().zip(Self::SECTIONS).zip(data) {
+            if section.clock.rising(pins.input(clock)) {
+                section.stages.copy_within(..3, 1);
+                section.stages[0] = data;
+            }
+
+            match pins.input(reset) {
+                Tristate::State(true) => section.stages = [Tristate::State(false); 4],
+                Tristate::State(false) => (),
+                // Only the stages already cleared are known.
+                Tristate::Undefined => {
+                    for stage in section.stages.iter_mut().filter(|stage| **stage != Tristate::State(false)) {
+                        *stage = Tristate::Undefined;
+                    }
+                }
+            }
+
+            for (stage, pin) in section.stages.into_iter().zip(outputs) {
+                pins.set(pin, stage);
+            }
+        }
+    }
+}
+
+/// Dual JK flip-flop with active high asynchronous set and reset.
+pub struct Chip4027;
+
+impl Chip4027 {
+    /// `(CLOCK, J, K, SET, RESET, Q, !Q)` of each flip-flop.
+    const PER_FLIP_FLOPS: [(PinNumber, PinNumber, PinNumber, PinNumber, PinNumber, PinNumber, PinNumber); 2] =
+        [(13, 10, 11, 9, 12, 15, 14), (3, 6, 5, 7, 4, 1, 2)];
+}
+
+impl Chip for Chip4027 {
+    type State = [JKFlipFlop; 2];
+
+    const DESCRIPTION: &'static str = "Dual JK flip-flop with set and reset";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] =
+        &[(10, "J1"), (11, "K1"), (9, "SET1"), (12, "RESET1"), (6, "J2"), (5, "K2"), (7, "SET2"), (4, "RESET2")];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(13, "CLOCK1"), (3, "CLOCK2")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(15, "Q1"), (14, "Q1'"), (1, "Q2"), (2, "Q2'")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(state: &mut [JKFlipFlop; 2], pins: &ChipPins<'_>) {
+        for (flip_flop, (clock, j, k, set, reset, q, q_bar)) in state.iter_mut().zip(Self::PER_FLIP_FLOPS) {
+            let (value, inverted) =
+                flip_flop.update(pins.input(clock), pins.input(j), pins.input(k), pins.input(set), pins.input(reset));
+
+            pins.set(q, value);
+            pins.set(q_bar, inverted);
+        }
+    }
+}
+
+/// Updates four RS latches sharing an active high enable. Their Q outputs are released while it is low,
+/// read as 0 by the other pins of their nets, the latches keeping their state.
+fn evaluate_quad_latch(
+    latches: &mut [RSLatch; 4],
+    pins: &ChipPins<'_>,
+    enable: PinNumber,
+    per_latches: [(PinNumber, PinNumber, PinNumber); 4],
+    active_low: bool,
+) {
+    let enabled = pins.input(enable);
+
+    for (latch, (set, reset, q)) in latches.iter_mut().zip(per_latches) {
+        let (set, reset) = match active_low {
+            true => (!pins.input(set), !pins.input(reset)),
+            false => (pins.input(set), pins.input(reset)),
+        };
+        // The NOR latches favor set, the NAND ones reset.
+        let value = latch.update(set, reset, !active_low);
+
+        match enabled {
+            Tristate::State(true) => {
+                pins.switch_mode(q, PinMode::Output);
+                pins.set(q, value);
+            }
+            Tristate::State(false) => pins.switch_mode(q, PinMode::Input),
+            Tristate::Undefined => {
+                pins.switch_mode(q, PinMode::Output);
+                pins.set(q, Tristate::Undefined);
+            }
+        }
+    }
+}
+
+/// Presettable up/down counter, counting in binary while B/D is high and in BCD otherwise.
+///
+/// Same as the 4510/4516 without RESET, whose pin selects the mode: P1 to P4 are loaded asynchronously while PE
+/// is high, and the count changes on the rising edges of CLOCK while PE and CI' are low. A value above 9 left by
+/// the binary mode goes back to 0 counting up in BCD, and to 9 counting down.
+pub struct Chip4029;
+
+impl Chip4029 {
+    const PRESET_ENABLE: PinNumber = 1;
+    const CARRY_IN: PinNumber = 5;
+    const CARRY_OUT: PinNumber = 7;
+    const BINARY_DECADE: PinNumber = 9;
+    const UP_DOWN: PinNumber = 10;
+    const CLOCK: PinNumber = 15;
+    const P: [PinNumber; 4] = [4, 12, 13, 3];
+    const Q: [PinNumber; 4] = [6, 11, 14, 2];
+
+    /// Value following `value` when counting up or down modulo `modulus`.
+    fn count(value: u64, up: bool, modulus: u64) -> u64 {
+        match (up, value) {
+            (true, value) if value + 1 >= modulus => 0,
+            (true, value) => value + 1,
+            (false, 0) => modulus - 1,
+            (false, value) => (value - 1).min(modulus - 1),
+        }
+    }
+
+    /// `binary` or `decade` depending on B/D, known while B/D is undefined only if they are the same.
+    fn by_mode<T: PartialEq>(mode: Tristate, binary: T, decade: T) -> Option<T> {
+        match mode {
+            Tristate::State(true) => Some(binary),
+            Tristate::State(false) => Some(decade),
+            Tristate::Undefined => (binary == decade).then_some(binary),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Counter4029State {
+    value: Option<u64>,
+    clock: ClockEdge,
+}
+
+impl Chip for Chip4029 {
+    type State = Counter4029State;
+
+    const DESCRIPTION: &'static str = "Presettable binary/BCD up/down counter";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::PRESET_ENABLE, "PE"),
+        (Self::CARRY_IN, "CI'"),
+        (Self::BINARY_DECADE, "B/D"),
+        (Self::UP_DOWN, "U/D"),
+        (Self::P[0], "P1"),
+        (Self::P[1], "P2"),
+        (Self::P[2], "P3"),
+        (Self::P[3], "P4"),
+    ];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(Self::CLOCK, "CLOCK")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] =
+        &[(Self::Q[0], "Q1"), (Self::Q[1], "Q2"), (Self::Q[2], "Q3"), (Self::Q[3], "Q4"), (Self::CARRY_OUT, "CO'")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(state: &mut Counter4029State, pins: &ChipPins<'_>) {
+        let rising = state.clock.rising(pins.input(Self::CLOCK));
+        let (carry_in, up, binary) = (pins.input(Self::CARRY_IN), pins.input(Self::UP_DOWN), pins.input(Self::BINARY_DECADE));
+
+        match pins.input(Self::PRESET_ENABLE) {
+            Tristate::State(true) => state.value = pins.read_bits(&Self::P),
+            Tristate::State(false) if rising => match (carry_in, up) {
+                (Tristate::State(true), _) => (),
+                (Tristate::State(false), Tristate::State(up)) => {
+                    state.value = state
+                        .value
+                        .and_then(|value| Self::by_mode(binary, Self::count(value, up, 16), Self::count(value, up, 10)))
+                }
+                _ => state.value = None,
+            },
+            Tristate::State(false) => (),
+            Tristate::Undefined => state.value = None,
+        }
+
+        let value = state.value;
+        let is = |expected: u64| value.map_or(Tristate::Undefined, |value| (value == expected).into());
+        let last = Self::by_mode(binary, is(15), is(9)).unwrap_or(Tristate::Undefined);
+
+        pins.write_bits(&Self::Q, value);
+        pins.set(Self::CARRY_OUT, !(!carry_in & ((last & up) | (is(0) & !up))));
+    }
+}
+
+/// Quad NOR RS latch with active high inputs and three-state outputs.
+pub struct Chip4043;
+
+impl Chip4043 {
+    const ENABLE: PinNumber = 5;
+    /// `(S, R, Q)` of each latch.
+    const PER_LATCHES: [(PinNumber, PinNumber, PinNumber); 4] = [(4, 3, 2), (6, 7, 9), (12, 11, 10), (14, 15, 1)];
+}
+
+impl Chip for Chip4043 {
+    type State = [RSLatch; 4];
+
+    const DESCRIPTION: &'static str = "Quad NOR RS latch with three-state outputs";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] =
+        &[(4, "S1"), (3, "R1"), (6, "S2"), (7, "R2"), (12, "S3"), (11, "R3"), (14, "S4"), (15, "R4"), (Self::ENABLE, "ENABLE")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[];
+    const BIDIRECTIONAL: &'static [(PinNumber, &'static str)] = &[(2, "Q1"), (9, "Q2"), (10, "Q3"), (1, "Q4")];
+    const POWER: &'static [(PinNumber, &'static str)] = &[(8, "VSS"), (13, "NC"), (16, "VDD")];
+
+    fn evaluate(state: &mut [RSLatch; 4], pins: &ChipPins<'_>) {
+        evaluate_quad_latch(state, pins, Self::ENABLE, Self::PER_LATCHES, false);
+    }
+}
+
+/// Quad NAND RS latch with active low inputs and three-state outputs.
+pub struct Chip4044;
+
+impl Chip4044 {
+    const ENABLE: PinNumber = 5;
+    /// `(S', R', Q)` of each latch.
+    const PER_LATCHES: [(PinNumber, PinNumber, PinNumber); 4] = [(3, 4, 13), (7, 6, 9), (11, 12, 10), (15, 14, 1)];
+}
+
+impl Chip for Chip4044 {
+    type State = [RSLatch; 4];
+
+    const DESCRIPTION: &'static str = "Quad NAND RS latch with three-state outputs";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (3, "S1'"),
+        (4, "R1'"),
+        (7, "S2'"),
+        (6, "R2'"),
+        (11, "S3'"),
+        (12, "R3'"),
+        (15, "S4'"),
+        (14, "R4'"),
+        (Self::ENABLE, "ENABLE"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[];
+    const BIDIRECTIONAL: &'static [(PinNumber, &'static str)] = &[(13, "Q1"), (9, "Q2"), (10, "Q3"), (1, "Q4")];
+    const POWER: &'static [(PinNumber, &'static str)] = &[(2, "NC"), (8, "VSS"), (16, "VDD")];
+
+    fn evaluate(state: &mut [RSLatch; 4], pins: &ChipPins<'_>) {
+        evaluate_quad_latch(state, pins, Self::ENABLE, Self::PER_LATCHES, true);
+    }
+}
+
+/// 14-stage ripple counter with a built-in oscillator.
+///
+/// The oscillator is modeled as an internal clock toggling at every tick, its external RC network being left
+/// out: φO gives the clock and φO' its complement. The counter advances on the falling edges of the clock,
+/// so that Qn divides the tick rate by 2^(n + 1). A high RESET clears the counter and holds the clock low.
+pub struct Chip4060;
+
+impl Chip4060 {
+    const RESET: PinNumber = 12;
+    const CLOCK_OUT: PinNumber = 9;
+    const CLOCK_OUT_INVERTED: PinNumber = 10;
+    /// `(stage, output)` of each output, Q1 to Q3 and Q11 not being brought out.
+    const OUTPUTS: [(u32, PinNumber); 10] =
+        [(4, 7), (5, 5), (6, 4), (7, 6), (8, 14), (9, 13), (10, 15), (12, 1), (13, 2), (14, 3)];
+}
+
+#[derive(Debug, Default)]
+pub struct RippleCounter4060State {
+    oscillator: Tristate,
+    counter: Counter<{ 1 << 14 }>,
+    clock: ClockEdge,
+}
+
+impl Chip for Chip4060 {
+    type State = RippleCounter4060State;
+
+    const DESCRIPTION: &'static str = "14-stage ripple counter with oscillator";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[(Self::RESET, "RESET")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::OUTPUTS[0].1, "Q4"),
+        (Self::OUTPUTS[1].1, "Q5"),
+        (Self::OUTPUTS[2].1, "Q6"),
+        (Self::OUTPUTS[3].1, "Q7"),
+        (Self::OUTPUTS[4].1, "Q8"),
+        (Self::OUTPUTS[5].1, "Q9"),
+        (Self::OUTPUTS[6].1, "Q10"),
+        (Self::OUTPUTS[7].1, "Q12"),
+        (Self::OUTPUTS[8].1, "Q13"),
+        (Self::OUTPUTS[9].1, "Q14"),
+        (Self::CLOCK_OUT, "φO"),
+        (Self::CLOCK_OUT_INVERTED, "φO'"),
+    ];
+    /// φI only takes the RC network of the oscillator.
+    const POWER: &'static [(PinNumber, &'static str)] = &[(8, "VSS"), (11, "φI"), (16, "VDD")];
+
+    fn evaluate(state: &mut RippleCounter4060State, pins: &ChipPins<'_>) {
+        match pins.input(Self::RESET) {
+            Tristate::State(true) => {
+                state.oscillator = Tristate::State(false);
+                state.counter.reset();
+            }
+            Tristate::State(false) => {
+                // A stopped oscillator starts low.
+                state.oscillator = match state.oscillator {
+                    Tristate::Undefined => Tristate::State(false),
+                    oscillator => !oscillator,
+                };
+            }
+            Tristate::Undefined => {
+                state.oscillator = Tristate::Undefined;
+                state.counter.load(None);
+            }
+        }
+        if state.clock.falling(state.oscillator) {
+            state.counter.increment();
+        }
+
+        let value = state.counter.value();
+        for (stage, pin) in Self::OUTPUTS {
+            pins.set(pin, value.map_or(Tristate::Undefined, |value| Tristate::State(value >> (stage - 1) & 1 == 1)));
+        }
+        pins.set(Self::CLOCK_OUT, state.oscillator);
+        pins.set(Self::CLOCK_OUT_INVERTED, !state.oscillator);
+    }
+}
+
+/// Presettable 4-bit up/down counter wrapping at `MODULUS`. RESET clears it and PRESET ENABLE loads P1-P4,
+/// both asynchronously; otherwise it counts on the rising edges of CLOCK while the active low CARRY IN is 0,
+/// up when UP/DOWN is 1. CARRY OUT goes low on the last count of the direction while CARRY IN is low: counters
+/// are cascaded by clocking the next one with it.
+pub struct ChipPresettableCounter<const MODULUS: u64>;
+
+/// BCD up/down counter.
+pub type Chip4510 = ChipPresettableCounter<10>;
+/// Binary up/down counter.
+pub type Chip4516 = ChipPresettableCounter<16>;
+
+impl<const MODULUS: u64> ChipPresettableCounter<MODULUS> {
+    const PRESET_ENABLE: PinNumber = 1;
+    const CARRY_IN: PinNumber = 5;
+    const CARRY_OUT: PinNumber = 7;
+    const RESET: PinNumber = 9;
+    const UP_DOWN: PinNumber = 10;
+    const CLOCK: PinNumber = 15;
+    const P: [PinNumber; 4] = [4, 12, 13, 3];
+    const Q: [PinNumber; 4] = [6, 11, 14, 2];
+}
+
+#[derive(Debug, Default)]
+pub struct PresettableCounterState<const MODULUS: u64> {
+    counter: Counter<MODULUS>,
+    clock: ClockEdge,
+}
+
+impl<const MODULUS: u64> Chip for ChipPresettableCounter<MODULUS> {
+    type State = PresettableCounterState<MODULUS>;
+
+    const DESCRIPTION: &'static str = match MODULUS {
+        10 => "Presettable BCD up/down counter",
+        _ => "Presettable 4-bit up/down binary counter",
+    };
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::PRESET_ENABLE, "PE"),
+        (Self::CARRY_IN, "CI'"),
+        (Self::RESET, "RESET"),
+        (Self::UP_DOWN, "U/D"),
+        (Self::P[0], "P1"),
+        (Self::P[1], "P2"),
+        (Self::P[2], "P3"),
+        (Self::P[3], "P4"),
+    ];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(Self::CLOCK, "CLOCK")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] =
+        &[(Self::Q[0], "Q1"), (Self::Q[1], "Q2"), (Self::Q[2], "Q3"), (Self::Q[3], "Q4"), (Self::CARRY_OUT, "CO'")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(state: &mut PresettableCounterState<MODULUS>, pins: &ChipPins<'_>) {
+        let rising = state.clock.rising(pins.input(Self::CLOCK));
+        let (carry_in, up) = (pins.input(Self::CARRY_IN), pins.input(Self::UP_DOWN));
+
+        match (pins.input(Self::RESET), pins.input(Self::PRESET_ENABLE)) {
+            (Tristate::State(true), _) => state.counter.reset(),
+            (Tristate::State(false), Tristate::State(true)) => state.counter.load(pins.read_bits(&Self::P)),
+            (Tristate::State(false), Tristate::State(false)) if rising => match (carry_in, up) {
+                (Tristate::State(true), _) => (),
+                (Tristate::State(false), Tristate::State(true)) => state.counter.increment(),
+                (Tristate::State(false), Tristate::State(false)) => state.counter.decrement(),
+                _ => state.counter.load(None),
+            },
+            (Tristate::State(false), Tristate::State(false)) => (),
+            _ => state.counter.load(None),
+        }
+
+        let value = state.counter.value();
+        let is = |expected: u64| value.map_or(Tristate::Undefined, |value| (value == expected).into());
+
+        pins.write_bits(&Self::Q, value);
+        pins.set(Self::CARRY_OUT, !(!carry_in & ((is(Counter::<MODULUS>::MAX) & up) | (is(0) & !up))));
+    }
+}
+
+/// Presettable 8-bit down counter, whose count is only seen through the active low CO'/ZD'.
+///
+/// CLEAR' sets the count to 255 and AL' loads P0-P7, both asynchronously. Otherwise, on the rising edges of
+/// CLOCK, SPE' low loads P0-P7 whatever CI'/CE' is, and the count goes down while CI'/CE' is low, wrapping
+/// from 0 to 255. CO'/ZD' is low while the count is 0 and CI'/CE' is low.
+pub struct Chip40103;
+
+impl Chip40103 {
+    const CLOCK: PinNumber = 1;
+    const CLEAR: PinNumber = 2;
+    const CARRY_IN: PinNumber = 3;
+    const ASYNCHRONOUS_LOAD: PinNumber = 9;
+    const CARRY_OUT: PinNumber = 14;
+    const SYNCHRONOUS_LOAD: PinNumber = 15;
+    const P: [PinNumber; 8] = [4, 5, 6, 7, 10, 11, 12, 13];
+}
+
+#[derive(Debug, Default)]
+pub struct DownCounter40103State {
+    counter: Counter<256>,
+    clock: ClockEdge,
+}
+
+impl Chip for Chip40103 {
+    type State = DownCounter40103State;
+
+    const DESCRIPTION: &'static str = "Presettable 8-bit synchronous down counter";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::CLEAR, "CLEAR'"),
+        (Self::CARRY_IN, "CI'/CE'"),
+        (Self::ASYNCHRONOUS_LOAD, "AL'"),
+        (Self::SYNCHRONOUS_LOAD, "SPE'"),
+        (Self::P[0], "P0"),
+        (Self::P[1], "P1"),
+        (Self::P[2], "P2"),
+        (Self::P[3], "P3"),
+        (Self::P[4], "P4"),
+        (Self::P[5], "P5"),
+        (Self::P[6], "P6"),
+        (Self::P[7], "P7"),
+    ];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(Self::CLOCK, "CLOCK")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(Self::CARRY_OUT, "CO'/ZD'")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(state: &mut DownCounter40103State, pins: &ChipPins<'_>) {
+        let rising = state.clock.rising(pins.input(Self::CLOCK));
+        let carry_in = pins.input(Self::CARRY_IN);
+
+        match (pins.input(Self::CLEAR), pins.input(Self::ASYNCHRONOUS_LOAD)) {
+            (Tristate::State(false), _) => state.counter.load(Some(Counter::<256>::MAX)),
+            (Tristate::State(true), Tristate::State(false)) => state.counter.load(pins.read_bits(&Self::P)),
+            (Tristate::State(true), Tristate::State(true)) if rising => match (pins.input(Self::SYNCHRONOUS_LOAD), carry_in) {
+                (Tristate::State(false), _) => state.counter.load(pins.read_bits(&Self::P)),
+                (Tristate::State(true), Tristate::State(true)) => (),
+                (Tristate::State(true), Tristate::State(false)) => state.counter.decrement(),
+                _ => state.counter.load(None),
+            },
+            (Tristate::State(true), Tristate::State(true)) => (),
+            _ => state.counter.load(None),
+        }
+
+        let zero = state.counter.value().map_or(Tristate::Undefined, |value| (value == 0).into());
+
+        pins.set(Self::CARRY_OUT, !(zero & !carry_in));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    #[test]
+    fn test_4006() {
+        // Sections chained into a single 18-stage register, its taps giving the data 4, 8, 9, 13, 17 and 18
+        // falling edges later.
+        let content = "
+            .chipsets:
+            input clock
+            input d
+            4006 register
+            output tap4
+            output tap8
+            output tap9
+            output tap13
+            output tap17
+            output tap18
+            .links:
+            clock:1 register:3
+            d:1 register:1
+            register:13 register:4
+            register:12 register:5
+            register:10 register:6
+            register:13 tap4:1
+            register:11 tap8:1
+            register:12 tap9:1
+            register:10 tap13:1
+            register:8 tap17:1
+            register:9 tap18:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let taps = ["tap4", "tap8", "tap9", "tap13", "tap17", "tap18"];
+        let mut shift = |d: &str| {
+            circuit.set_value("d", d).unwrap();
+            for clock in ["1", "0"] {
+                circuit.set_value("clock", clock).unwrap();
+                circuit.simulate();
+            }
+            taps.map(|name| circuit.get_output(name).unwrap()).concat()
+        };
+
+        assert_eq!(shift("1"), "UUUUUU");
+        let outputs: Vec<String> = (2..=18).map(|_| shift("0")).collect();
+        assert_eq!(outputs[2], "1UUUUU");
+        assert_eq!(outputs[3], "0UUUUU");
+        assert_eq!(outputs[6], "01UUUU");
+        assert_eq!(outputs[7], "001UUU");
+        assert_eq!(outputs[11], "0001UU");
+        assert_eq!(outputs[15], "00001U");
+        assert_eq!(outputs[16], "000001");
+        assert_eq!(shift("0"), "000000");
+    }
+
+    #[test]
+    fn test_4014() {
+        let content = "
+            .chipsets:
+            input clock
+            input load
+            input serial
+            input p0
+            input p1
+            input p2
+            input p3
+            input p4
+            input p5
+            input p6
+            input p7
+            4014 register
+            output q6
+            output q7
+            output q8
+            .links:
+            clock:1 register:10
+            load:1 register:9
+            serial:1 register:11
+            p0:1 register:7
+            p1:1 register:6
+            p2:1 register:5
+            p3:1 register:4
+            p4:1 register:13
+            p5:1 register:14
+            p6:1 register:15
+            p7:1 register:1
+            register:2 q6:1
+            register:12 q7:1
+            register:3 q8:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let tick = |circuit: &mut Circuit, clock: &str| {
+            circuit.set_value("clock", clock).unwrap();
+            circuit.simulate();
+            ["q6", "q7", "q8"].map(|name| circuit.get_output(name).unwrap()).concat()
+        };
+
+        // P1 to P8 = 1, 0, 1, 0, 0, 1, 1, 0.
+        circuit.set_bus("p", 0b0110_0101).unwrap();
+        circuit.set_value("serial", "1").unwrap();
+        circuit.set_value("load", "1").unwrap();
+        assert_eq!(tick(&mut circuit, "0"), "UUU");
+        assert_eq!(tick(&mut circuit, "1"), "110");
+
+        // The parallel inputs are only loaded on an edge.
+        circuit.set_bus("p", 0).unwrap();
+        assert_eq!(tick(&mut circuit, "1"), "110");
+
+        circuit.set_value("load", "0").unwrap();
+        let shifted: Vec<String> = (0..6)
+            .map(|_| {
+                tick(&mut circuit, "0");
+                tick(&mut circuit, "1")
+            })
+            .collect();
+        assert_eq!(shifted, vec!["011", "001", "100", "010", "101", "110"]);
+        assert_eq!(tick(&mut circuit, "0"), "110");
+        assert_eq!(tick(&mut circuit, "1"), "111");
+
+        // With an undefined P/S, a stage is only known if loading and shifting agree.
+        circuit.set_bus("p", 0b0100_0000).unwrap();
+        circuit.set_value("load", "U").unwrap();
+        tick(&mut circuit, "0");
+        assert_eq!(tick(&mut circuit, "1"), "U1U");
+    }
+
+    #[test]
+    fn test_4015() {
+        // Section B is fed by Q4A: both sections make an 8-stage register.
+        let content = "
+            .chipsets:
+            input clock
+            input data
+            input reset_a
+            input reset_b
+            4015 register
+            output q1
+            output q2
+            output q3
+            output q4
+            output q5
+            output q6
+            output q7
+            output q8
+            .links:
+            clock:1 register:9
+            clock:1 register:1
+            data:1 register:7
+            reset_a:1 register:6
+            reset_b:1 register:14
+            register:10 register:15
+            register:5 q1:1
+            register:4 q2:1
+            register:3 q3:1
+            register:10 q4:1
+            register:13 q5:1
+            register:12 q6:1
+            register:11 q7:1
+            register:2 q8:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let tick = |circuit: &mut Circuit, clock: &str, data: &str| {
+            circuit.set_value("clock", clock).unwrap();
+            circuit.set_value("data", data).unwrap();
+            circuit.simulate();
+            ["q1", "q2", "q3", "q4", "q5", "q6", "q7", "q8"].map(|name| circuit.get_output(name).unwrap()).concat()
+        };
+
+        circuit.set_value("reset_a", "1").unwrap();
+        circuit.set_value("reset_b", "U").unwrap();
+        assert_eq!(tick(&mut circuit, "0", "1"), "0000UUUU");
+
+        circuit.set_value("reset_a", "0").unwrap();
+        circuit.set_value("reset_b", "1").unwrap();
+        assert_eq!(tick(&mut circuit, "1", "1"), "10000000");
+        assert_eq!(tick(&mut circuit, "1", "0"), "10000000");
+
+        circuit.set_value("reset_b", "0").unwrap();
+        let shifted: Vec<String> = ["0", "1", "1", "0", "0", "0"]
+            .into_iter()
+            .map(|data| {
+                tick(&mut circuit, "0", data);
+                tick(&mut circuit, "1", data)
+            })
+            .collect();
+        assert_eq!(shifted, vec!["01000000", "10100000", "11010000", "01101000", "00110100", "00011010"]);
+
+        // Resetting a section leaves the other one.
+        circuit.set_value("reset_a", "1").unwrap();
+        assert_eq!(tick(&mut circuit, "0", "1"), "00001010");
+        circuit.set_value("reset_a", "U").unwrap();
+        assert_eq!(tick(&mut circuit, "0", "1"), "00001010");
+    }
+
+    #[test]
+    fn test_4013_keeps_state_between_edges() {
+        let content = "
+            .chipsets:
+            clock cl
+            input d
+            input set
+            input reset
+            4013 flip_flop
+            output q
+            output q_bar
+            .links:
+            cl:1 flip_flop:3
+            d:1 flip_flop:5
+            set:1 flip_flop:6
+            reset:1 flip_flop:4
+            flip_flop:1 q:1
+            flip_flop:2 q_bar:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        circuit.set_value("cl", "0").unwrap();
+        let mut outputs = |d: &str, set: &str, reset: &str| {
+            for (name, value) in [("d", d), ("set", set), ("reset", reset)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            ["q", "q_bar"].map(|name| circuit.get_output(name).unwrap())
+        };
+
+        assert_eq!(outputs("1", "0", "0"), ["U", "U"]);
+        assert_eq!(outputs("1", "0", "0"), ["1", "0"]);
+        assert_eq!(outputs("0", "0", "0"), ["1", "0"]);
+        assert_eq!(outputs("0", "0", "0"), ["0", "1"]);
+        assert_eq!(outputs("1", "0", "0"), ["0", "1"]);
+        assert_eq!(outputs("1", "0", "1"), ["0", "1"]);
+        assert_eq!(outputs("0", "1", "0"), ["1", "0"]);
+        assert_eq!(outputs("0", "1", "1"), ["1", "1"]);
+    }
+
+    #[test]
+    fn test_4027_modes() {
+        let content = "
+            .chipsets:
+            clock cl
+            input j
+            input k
+            input set
+            input reset
+            4027 flip_flop
+            output q
+            output q_bar
+            .links:
+            cl:1 flip_flop:3
+            j:1 flip_flop:6
+            k:1 flip_flop:5
+            set:1 flip_flop:7
+            reset:1 flip_flop:4
+            flip_flop:1 q:1
+            flip_flop:2 q_bar:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        circuit.set_value("cl", "0").unwrap();
+        let mut outputs = |j: &str, k: &str, set: &str, reset: &str| {
+            for (name, value) in [("j", j), ("k", k), ("set", set), ("reset", reset)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            ["q", "q_bar"].map(|name| circuit.get_output(name).unwrap())
+        };
+
+        // The clock rises every other tick.
+        assert_eq!(outputs("1", "0", "0", "0"), ["U", "U"]);
+        assert_eq!(outputs("1", "0", "0", "0"), ["1", "0"]);
+        assert_eq!(outputs("0", "0", "0", "0"), ["1", "0"]);
+        assert_eq!(outputs("0", "0", "0", "0"), ["1", "0"]);
+        assert_eq!(outputs("1", "1", "0", "0"), ["1", "0"]);
+        assert_eq!(outputs("1", "1", "0", "0"), ["0", "1"]);
+        assert_eq!(outputs("1", "1", "0", "0"), ["0", "1"]);
+        assert_eq!(outputs("1", "1", "0", "0"), ["1", "0"]);
+        assert_eq!(outputs("0", "1", "0", "0"), ["1", "0"]);
+        assert_eq!(outputs("0", "1", "0", "0"), ["0", "1"]);
+        assert_eq!(outputs("0", "0", "1", "0"), ["1", "0"]);
+        assert_eq!(outputs("0", "0", "0", "1"), ["0", "1"]);
+        assert_eq!(outputs("0", "0", "1", "1"), ["1", "1"]);
+    }
+
+    #[test]
+    fn test_quad_latches() {
+        // Set, reset and both active, with the inputs of the 4044 inverted.
+        for (component_type, s, r, q, active) in [("4043", 4, 3, 2, "1"), ("4044", 3, 4, 13, "0")] {
+            let inactive = if active == "1" { "0" } else { "1" };
+            let content = format!(
+                ".chipsets:\ninput s\ninput r\ninput enable\n{component_type} latch\noutput q\n.links:\n\
+                 s:1 latch:{s}\nr:1 latch:{r}\nenable:1 latch:5\nlatch:{q} q:1\n"
+            );
+            let mut circuit: Circuit = content.parse().unwrap();
+            let mut output = |s: &str, r: &str, enable: &str| {
+                for (name, value) in [("s", s), ("r", r), ("enable", enable)] {
+                    circuit.set_value(name, value).unwrap();
+                }
+                circuit.simulate();
+                circuit.get_output("q").unwrap()
+            };
+
+            assert_eq!(output(inactive, inactive, "1"), "U", "{component_type}");
+            assert_eq!(output(active, inactive, "1"), "1", "{component_type}");
+            assert_eq!(output(inactive, inactive, "1"), "1", "{component_type}");
+            assert_eq!(output(inactive, active, "0"), "0", "{component_type}");
+            assert_eq!(output(inactive, inactive, "1"), "0", "{component_type}");
+            assert_eq!(output(active, inactive, "U"), "U", "{component_type}");
+            assert_eq!(output(inactive, inactive, "1"), "1", "{component_type}");
+            assert_eq!(output(active, active, "1"), if component_type == "4043" { "1" } else { "0" }, "{component_type}");
+        }
+    }
+
+    const PRESETTABLE_COUNTER: &str = "
+        .chipsets:
+        input clock
+        input preset
+        input carry_in
+        input reset
+        input up
+        input p0
+        input p1
+        input p2
+        input p3
+        {type} counter
+        output q0
+        output q1
+        output q2
+        output q3
+        output carry_out
+        .links:
+        clock:1 counter:15
+        preset:1 counter:1
+        carry_in:1 counter:5
+        reset:1 counter:9
+        up:1 counter:10
+        p0:1 counter:4
+        p1:1 counter:12
+        p2:1 counter:13
+        p3:1 counter:3
+        counter:6 q0:1
+        counter:11 q1:1
+        counter:14 q2:1
+        counter:2 q3:1
+        counter:7 carry_out:1
+    ";
+
+    #[test]
+    fn test_4060() {
+        let content = "
+            .chipsets:
+            input reset
+            4060 counter
+            output clock
+            output q4
+            output q5
+            .links:
+            reset:1 counter:12
+            counter:9 clock:1
+            counter:7 q4:1
+            counter:5 q5:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let tick = |circuit: &mut Circuit| {
+            circuit.simulate();
+            ["clock", "q4", "q5"].map(|name| circuit.get_output(name).unwrap()).concat()
+        };
+
+        circuit.set_value("reset", "1").unwrap();
+        assert_eq!(tick(&mut circuit), "000");
+        assert_eq!(tick(&mut circuit), "000");
+
+        // The clock toggles at every tick: Q4 toggles every 16 ticks, Q5 every 32 ticks.
+        circuit.set_value("reset", "0").unwrap();
+        let ticks: Vec<String> = (1..=64).map(|_| tick(&mut circuit)).collect();
+        assert_eq!(ticks[0], "100");
+        assert_eq!(ticks[1], "000");
+        assert_eq!(ticks[14], "100");
+        assert_eq!(ticks[15], "010");
+        assert_eq!(ticks[16], "110");
+        assert_eq!(ticks[31], "001");
+        assert_eq!(ticks[47], "011");
+        assert_eq!(ticks[62], "111");
+        assert_eq!(ticks[63], "000");
+
+        circuit.set_value("reset", "1").unwrap();
+        assert_eq!(tick(&mut circuit), "000");
+        circuit.set_value("reset", "U").unwrap();
+        assert_eq!(tick(&mut circuit), "UUU");
+    }
+
+    #[test]
+    fn test_4516() {
+        let mut circuit: Circuit = PRESETTABLE_COUNTER.replace("{type}", "4516").parse().unwrap();
+        circuit.set_bus("p", 14).unwrap();
+        let mut step = |clock: &str, preset: &str, carry_in: &str, reset: &str, up: &str| {
+            for (name, value) in [("clock", clock), ("preset", preset), ("carry_in", carry_in), ("reset", reset), ("up", up)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            (circuit.get_bus("q"), circuit.get_output("carry_out").unwrap())
+        };
+
+        assert_eq!(step("0", "0", "0", "0", "1"), (None, "U".to_owned()));
+        // Reset takes priority over preset, which takes priority over counting.
+        assert_eq!(step("1", "1", "0", "1", "1"), (Some(0), "1".to_owned()));
+        assert_eq!(step("0", "1", "0", "0", "1"), (Some(14), "1".to_owned()));
+        assert_eq!(step("1", "1", "0", "0", "1"), (Some(14), "1".to_owned()));
+        assert_eq!(step("0", "0", "0", "0", "1"), (Some(14), "1".to_owned()));
+        assert_eq!(step("1", "0", "0", "0", "1"), (Some(15), "0".to_owned()));
+        // A high carry in inhibits counting and the carry out.
+        assert_eq!(step("0", "0", "1", "0", "1"), (Some(15), "1".to_owned()));
+        assert_eq!(step("1", "0", "1", "0", "1"), (Some(15), "1".to_owned()));
+        assert_eq!(step("0", "0", "0", "0", "1"), (Some(15), "0".to_owned()));
+        assert_eq!(step("1", "0", "0", "0", "1"), (Some(0), "1".to_owned()));
+        assert_eq!(step("0", "0", "0", "0", "0"), (Some(0), "0".to_owned()));
+        assert_eq!(step("1", "0", "0", "0", "0"), (Some(15), "1".to_owned()));
+        assert_eq!(step("0", "0", "0", "0", "0"), (Some(15), "1".to_owned()));
+        assert_eq!(step("1", "0", "0", "0", "0"), (Some(14), "1".to_owned()));
+    }
+
+    #[test]
+    fn test_4510() {
+        let mut circuit: Circuit = PRESETTABLE_COUNTER.replace("{type}", "4510").parse().unwrap();
+        circuit.set_bus("p", 8).unwrap();
+        let mut step = |clock: &str, preset: &str, up: &str| {
+            for (name, value) in [("clock", clock), ("preset", preset), ("carry_in", "0"), ("reset", "0"), ("up", up)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            (circuit.get_bus("q"), circuit.get_output("carry_out").unwrap())
+        };
+
+        assert_eq!(step("0", "1", "1"), (Some(8), "1".to_owned()));
+        assert_eq!(step("1", "0", "1"), (Some(9), "0".to_owned()));
+        assert_eq!(step("0", "0", "1"), (Some(9), "0".to_owned()));
+        assert_eq!(step("1", "0", "1"), (Some(0), "1".to_owned()));
+        assert_eq!(step("0", "0", "0"), (Some(0), "0".to_owned()));
+        assert_eq!(step("1", "0", "0"), (Some(9), "1".to_owned()));
+        assert_eq!(step("0", "0", "0"), (Some(9), "1".to_owned()));
+        assert_eq!(step("1", "0", "0"), (Some(8), "1".to_owned()));
+    }
+
+    #[test]
+    fn test_4029() {
+        // B/D is on the pin of RESET.
+        let content = PRESETTABLE_COUNTER
+            .replace("{type}", "4029")
+            .replace("input reset", "input binary")
+            .replace("reset:1 counter:9", "binary:1 counter:9");
+        let mut circuit: Circuit = content.parse().unwrap();
+        circuit.set_bus("p", 8).unwrap();
+        let mut step = |clock: &str, preset: &str, binary: &str, up: &str| {
+            for (name, value) in [("clock", clock), ("preset", preset), ("carry_in", "0"), ("binary", binary), ("up", up)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            (circuit.get_bus("q"), circuit.get_output("carry_out").unwrap())
+        };
+
+        // Preset to 8, then counting up in BCD and in binary.
+        assert_eq!(step("0", "1", "0", "1"), (Some(8), "1".to_owned()));
+        assert_eq!(step("1", "0", "0", "1"), (Some(9), "0".to_owned()));
+        assert_eq!(step("0", "0", "0", "1"), (Some(9), "0".to_owned()));
+        assert_eq!(step("1", "0", "0", "1"), (Some(0), "1".to_owned()));
+        assert_eq!(step("0", "1", "1", "1"), (Some(8), "1".to_owned()));
+        for expected in 9..15 {
+            step("1", "0", "1", "1");
+            assert_eq!(step("0", "0", "1", "1"), (Some(expected), "1".to_owned()));
+        }
+        assert_eq!(step("1", "0", "1", "1"), (Some(15), "0".to_owned()));
+
+        // Down in BCD from a binary value, then an undefined mode.
+        step("0", "0", "0", "0");
+        assert_eq!(step("1", "0", "0", "0"), (Some(9), "1".to_owned()));
+        step("0", "0", "U", "0");
+        assert_eq!(step("1", "0", "U", "0"), (Some(8), "1".to_owned()));
+        step("0", "0", "U", "1");
+        assert_eq!(step("1", "0", "U", "1"), (Some(9), "U".to_owned()));
+        step("0", "0", "U", "1");
+        assert_eq!(step("1", "0", "U", "1"), (None, "U".to_owned()));
+    }
+
+    #[test]
+    fn test_4516_cascade() {
+        // The carry out of the low counter clocks the high one.
+        let content = "
+            .chipsets:
+            input clock
+            input up
+            input reset
+            false low
+            4516 c0
+            4516 c1
+            output q0
+            output q1
+            output q2
+            output q3
+            output q4
+            output q5
+            output q6
+            output q7
+            .links:
+            clock:1 c0:15
+            c0:7 c1:15
+            up:1 c0:10
+            up:1 c1:10
+            reset:1 c0:9
+            reset:1 c1:9
+            low:1 c0:1
+            low:1 c1:1
+            low:1 c0:5
+            low:1 c1:5
+            c0:6 q0:1
+            c0:11 q1:1
+            c0:14 q2:1
+            c0:2 q3:1
+            c1:6 q4:1
+            c1:11 q5:1
+            c1:14 q6:1
+            c1:2 q7:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let mut count = |up: &str, reset: &str, ticks: usize| {
+            circuit.set_value("up", up).unwrap();
+            circuit.set_value("reset", reset).unwrap();
+            for _ in 0..ticks {
+                for value in ["0", "1"] {
+                    circuit.set_value("clock", value).unwrap();
+                    circuit.simulate();
+                }
+            }
+            circuit.get_bus("q")
+        };
+
+        assert_eq!(count("1", "1", 1), Some(0));
+        assert_eq!(count("1", "0", 15), Some(15));
+        assert_eq!(count("1", "0", 1), Some(16));
+        assert_eq!(count("1", "0", 35), Some(51));
+        assert_eq!(count("0", "0", 4), Some(47));
+        assert_eq!(count("0", "0", 48), Some(255));
+    }
+
+    #[test]
+    fn test_40103() {
+        let pins = [4, 5, 6, 7, 10, 11, 12, 13];
+        let mut content = "
+            .chipsets:
+            input clock
+            input clear
+            input carry_in
+            input async_load
+            input sync_load
+            40103 counter
+            output zero
+        "
+        .to_owned();
+        content += &(0..pins.len()).map(|bit| format!("input p{bit}\n")).collect::<String>();
+        content += "
+            .links:
+            clock:1 counter:1
+            clear:1 counter:2
+            carry_in:1 counter:3
+            async_load:1 counter:9
+            sync_load:1 counter:15
+            counter:14 zero:1
+        ";
+        content += &pins.into_iter().enumerate().map(|(bit, pin)| format!("p{bit}:1 counter:{pin}\n")).collect::<String>();
+        let mut circuit: Circuit = content.parse().unwrap();
+        let step = |circuit: &mut Circuit, clock: &str, clear: &str, carry_in: &str, async_load: &str, sync_load: &str| {
+            for (name, value) in
+                [("clock", clock), ("clear", clear), ("carry_in", carry_in), ("async_load", async_load), ("sync_load", sync_load)]
+            {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            circuit.get_output("zero").unwrap()
+        };
+
+        // Undefined until loaded, then preset to 2 on a rising edge.
+        circuit.set_bus("p", 2).unwrap();
+        assert_eq!(step(&mut circuit, "0", "1", "0", "1", "0"), "U");
+        assert_eq!(step(&mut circuit, "1", "1", "0", "1", "0"), "1");
+
+        // Counting down to 0, held by CI'/CE' high, then wrapping to 255.
+        step(&mut circuit, "0", "1", "0", "1", "1");
+        assert_eq!(step(&mut circuit, "1", "1", "0", "1", "1"), "1");
+        step(&mut circuit, "0", "1", "0", "1", "1");
+        assert_eq!(step(&mut circuit, "1", "1", "0", "1", "1"), "0");
+        step(&mut circuit, "0", "1", "1", "1", "1");
+        assert_eq!(step(&mut circuit, "1", "1", "1", "1", "1"), "1");
+        assert_eq!(step(&mut circuit, "0", "1", "0", "1", "1"), "0");
+        assert_eq!(step(&mut circuit, "1", "1", "0", "1", "1"), "1");
+
+        // Asynchronous load of 0, cleared to 255, then 255 clock edges down to 0.
+        circuit.set_bus("p", 0).unwrap();
+        assert_eq!(step(&mut circuit, "0", "1", "0", "0", "1"), "0");
+        assert_eq!(step(&mut circuit, "0", "0", "0", "0", "1"), "1");
+        for _ in 0..254 {
+            step(&mut circuit, "1", "1", "0", "1", "1");
+            assert_eq!(step(&mut circuit, "0", "1", "0", "1", "1"), "1");
+        }
+        assert_eq!(step(&mut circuit, "1", "1", "0", "1", "1"), "0");
+
+        assert_eq!(step(&mut circuit, "0", "1", "0", "U", "1"), "U");
+    }
+}
@@ -0,0 +1,675 @@
+//! 74-series chips, with their TTL datasheet pinouts.
+
+use crate::components::{tristate::Tristate, PinNumber};
+use crate::pin::PinMode;
+
+use super::arithmetic::{compare, read_sum};
+use super::{Chip, ChipComponent, ChipPins, ClockEdge, Counter, DFlipFlop};
+
+/* Final Components Declaration */
+pub type Component74283 = ChipComponent<Chip74283>;
+pub type Component7485 = ChipComponent<Chip7485>;
+pub type Component74138 = ChipComponent<Chip74138>;
+pub type Component74157 = ChipComponent<Chip74157>;
+pub type Component7474 = ChipComponent<Chip7474>;
+pub type Component74193 = ChipComponent<Chip74193>;
+pub type Component74245 = ChipComponent<Chip74245>;
+/* ---------------------------- */
+
+const POWER_14: &[(PinNumber, &str)] = &[(7, "GND"), (14, "VCC")];
+const POWER_16: &[(PinNumber, &str)] = &[(8, "GND"), (16, "VCC")];
+
+/// 4-bit binary full adder with fast carry.
+pub struct Chip74283;
+
+impl Chip74283 {
+    const A: [PinNumber; 4] = [5, 3, 14, 12];
+    const B: [PinNumber; 4] = [6, 2, 15, 11];
+    const CARRY_IN: PinNumber = 7;
+    const SUM: [PinNumber; 4] = [4, 1, 13, 10];
+    const CARRY_OUT: PinNumber = 9;
+}
+
+impl Chip for Chip74283 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "4-bit binary full adder with fast carry";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::A[0], "A1"),
+        (Self::A[1], "A2"),
+        (Self::A[2], "A3"),
+        (Self::A[3], "A4"),
+        (Self::B[0], "B1"),
+        (Self::B[1], "B2"),
+        (Self::B[2], "B3"),
+        (Self::B[3], "B4"),
+        (Self::CARRY_IN, "C0"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] =
+        &[(Self::SUM[0], "S1"), (Self::SUM[1], "S2"), (Self::SUM[2], "S3"), (Self::SUM[3], "S4"), (Self::CARRY_OUT, "C4")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        let sum = read_sum(pins, &Self::A, &Self::B, Self::CARRY_IN);
+
+        pins.write_bits(&Self::SUM, sum);
+        pins.set(Self::CARRY_OUT, sum.map_or(Tristate::Undefined, |sum| (sum > 0b1111).into()));
+    }
+}
+
+/// 4-bit magnitude comparator, cascadable through its A>B, A=B and A<B inputs.
+pub struct Chip7485;
+
+impl Chip7485 {
+    const A: [PinNumber; 4] = [10, 12, 13, 15];
+    const B: [PinNumber; 4] = [9, 11, 14, 1];
+    const CASCADE_LESS: PinNumber = 2;
+    const CASCADE_EQUAL: PinNumber = 3;
+    const CASCADE_GREATER: PinNumber = 4;
+    const GREATER: PinNumber = 5;
+    const EQUAL: PinNumber = 6;
+    const LESS: PinNumber = 7;
+}
+
+impl Chip for Chip7485 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "4-bit magnitude comparator";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::A[0], "A0"),
+        (Self::A[1], "A1"),
+        (Self::A[2], "A2"),
+        (Self::A[3], "A3"),
+        (Self::B[0], "B0"),
+        (Self::B[1], "B1"),
+        (Self::B[2], "B2"),
+        (Self::B[3], "B3"),
+        (Self::CASCADE_LESS, "IA<B"),
+        (Self::CASCADE_EQUAL, "IA=B"),
+        (Self::CASCADE_GREATER, "IA>B"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(Self::GREATER, "A>B"), (Self::EQUAL, "A=B"), (Self::LESS, "A<B")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        let (greater, equal, less) = compare(
+            pins.read_bits(&Self::A),
+            pins.read_bits(&Self::B),
+            pins.input(Self::CASCADE_GREATER),
+            pins.input(Self::CASCADE_EQUAL),
+            pins.input(Self::CASCADE_LESS),
+        );
+
+        pins.set(Self::GREATER, greater);
+        pins.set(Self::EQUAL, equal);
+        pins.set(Self::LESS, less);
+    }
+}
+
+/// 3-to-8 line decoder with active low outputs, enabled when G1 = 1, G2A = 0 and G2B = 0.
+pub struct Chip74138;
+
+impl Chip74138 {
+    const SELECT: [PinNumber; 3] = [1, 2, 3];
+    const ENABLE_2A: PinNumber = 4;
+    const ENABLE_2B: PinNumber = 5;
+    const ENABLE_1: PinNumber = 6;
+    const OUTPUTS: [PinNumber; 8] = [15, 14, 13, 12, 11, 10, 9, 7];
+}
+
+impl Chip for Chip74138 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "3-to-8 line decoder";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::SELECT[0], "A"),
+        (Self::SELECT[1], "B"),
+        (Self::SELECT[2], "C"),
+        (Self::ENABLE_2A, "G2A"),
+        (Self::ENABLE_2B, "G2B"),
+        (Self::ENABLE_1, "G1"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::OUTPUTS[0], "Y0"),
+        (Self::OUTPUTS[1], "Y1"),
+        (Self::OUTPUTS[2], "Y2"),
+        (Self::OUTPUTS[3], "Y3"),
+        (Self::OUTPUTS[4], "Y4"),
+        (Self::OUTPUTS[5], "Y5"),
+        (Self::OUTPUTS[6], "Y6"),
+        (Self::OUTPUTS[7], "Y7"),
+    ];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        let enabled = pins.input(Self::ENABLE_1) & !pins.input(Self::ENABLE_2A) & !pins.input(Self::ENABLE_2B);
+        let selected = pins.read_bits(&Self::SELECT);
+
+        for (index, &pin) in Self::OUTPUTS.iter().enumerate() {
+            let active = match selected {
+                Some(selected) => enabled & (selected == index as u64).into(),
+                None => enabled & Tristate::Undefined,
+            };
+
+            pins.set(pin, !active);
+        }
+    }
+}
+
+/// Quad 2-to-1 line multiplexer: each Y output copies A when S = 0, B when S = 1,
+/// and is forced to 0 when the active low strobe G is 1.
+pub struct Chip74157;
+
+impl Chip74157 {
+    const SELECT: PinNumber = 1;
+    const STROBE: PinNumber = 15;
+    /// `(A, B, Y)` of each multiplexer.
+    const PER_MULTIPLEXERS: [(PinNumber, PinNumber, PinNumber); 4] = [(2, 3, 4), (5, 6, 7), (11, 10, 9), (14, 13, 12)];
+}
+
+impl Chip for Chip74157 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "Quad 2-to-1 line multiplexer";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::SELECT, "S"),
+        (Self::STROBE, "G"),
+        (2, "1A"),
+        (3, "1B"),
+        (5, "2A"),
+        (6, "2B"),
+        (11, "3A"),
+        (10, "3B"),
+        (14, "4A"),
+        (13, "4B"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(4, "1Y"), (7, "2Y"), (9, "3Y"), (12, "4Y")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+    const DEPENDENCIES: &'static [(PinNumber, &'static [PinNumber])] = &[
+        (4, &[Self::SELECT, Self::STROBE, 2, 3]),
+        (7, &[Self::SELECT, Self::STROBE, 5, 6]),
+        (9, &[Self::SELECT, Self::STROBE, 11, 10]),
+        (12, &[Self::SELECT, Self::STROBE, 14, 13]),
+    ];
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        let select = pins.input(Self::SELECT);
+        let enabled = !pins.input(Self::STROBE);
+
+        for (a, b, output) in Self::PER_MULTIPLEXERS {
+            let (a, b) = (pins.input(a), pins.input(b));
+            let value = match select {
+                Tristate::State(false) => a,
+                Tristate::State(true) => b,
+                Tristate::Undefined if a == b => a,
+                Tristate::Undefined => Tristate::Undefined,
+            };
+
+            pins.set(output, enabled & value);
+        }
+    }
+}
+
+/// Dual D flip-flop with active low asynchronous preset and clear.
+pub struct Chip7474;
+
+impl Chip7474 {
+    /// `(CLR, D, CLK, PRE, Q, !Q)` of each flip-flop.
+    const PER_FLIP_FLOPS: [(PinNumber, PinNumber, PinNumber, PinNumber, PinNumber, PinNumber); 2] =
+        [(1, 2, 3, 4, 5, 6), (13, 12, 11, 10, 9, 8)];
+}
+
+impl Chip for Chip7474 {
+    type State = [DFlipFlop; 2];
+
+    const DESCRIPTION: &'static str = "Dual D flip-flop with preset and clear";
+    const NB_PINS: usize = 14;
+    const INPUTS: &'static [(PinNumber, &'static str)] =
+        &[(1, "1CLR"), (2, "1D"), (4, "1PRE"), (13, "2CLR"), (12, "2D"), (10, "2PRE")];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(3, "1CLK"), (11, "2CLK")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[(5, "1Q"), (6, "1Q'"), (9, "2Q"), (8, "2Q'")];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_14;
+
+    fn evaluate(state: &mut [DFlipFlop; 2], pins: &ChipPins<'_>) {
+        for (flip_flop, (clear, data, clock, preset, q, q_bar)) in state.iter_mut().zip(Self::PER_FLIP_FLOPS) {
+            let (value, inverted) =
+                flip_flop.update(pins.input(clock), pins.input(data), !pins.input(preset), !pins.input(clear));
+
+            pins.set(q, value);
+            pins.set(q_bar, inverted);
+        }
+    }
+}
+
+/// Synchronous 4-bit up/down binary counter, with asynchronous clear and active low load.
+pub struct Chip74193;
+
+impl Chip74193 {
+    const DATA: [PinNumber; 4] = [15, 1, 10, 9];
+    const Q: [PinNumber; 4] = [3, 2, 6, 7];
+    const DOWN: PinNumber = 4;
+    const UP: PinNumber = 5;
+    const LOAD: PinNumber = 11;
+    const CARRY: PinNumber = 12;
+    const BORROW: PinNumber = 13;
+    const CLEAR: PinNumber = 14;
+}
+
+#[derive(Debug, Default)]
+pub struct Counter74193State {
+    counter: Counter<16>,
+    up: ClockEdge,
+    down: ClockEdge,
+}
+
+impl Chip for Chip74193 {
+    type State = Counter74193State;
+
+    const DESCRIPTION: &'static str = "Synchronous 4-bit up/down binary counter";
+    const NB_PINS: usize = 16;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::DATA[0], "A"),
+        (Self::DATA[1], "B"),
+        (Self::DATA[2], "C"),
+        (Self::DATA[3], "D"),
+        (Self::LOAD, "LOAD"),
+        (Self::CLEAR, "CLR"),
+    ];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(Self::UP, "UP"), (Self::DOWN, "DOWN")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::Q[0], "QA"),
+        (Self::Q[1], "QB"),
+        (Self::Q[2], "QC"),
+        (Self::Q[3], "QD"),
+        (Self::CARRY, "CO"),
+        (Self::BORROW, "BO"),
+    ];
+    const POWER: &'static [(PinNumber, &'static str)] = POWER_16;
+
+    fn evaluate(state: &mut Counter74193State, pins: &ChipPins<'_>) {
+        let (up, down) = (pins.input(Self::UP), pins.input(Self::DOWN));
+        let (count_up, count_down) = (state.up.rising(up), state.down.rising(down));
+
+        match (pins.input(Self::CLEAR), pins.input(Self::LOAD)) {
+            (Tristate::State(true), _) => state.counter.reset(),
+            (Tristate::State(false), Tristate::State(false)) => state.counter.load(pins.read_bits(&Self::DATA)),
+            (Tristate::State(false), Tristate::State(true)) => {
+                if count_up && down == Tristate::State(true) {
+                    state.counter.increment();
+                } else if count_down && up == Tristate::State(true) {
+                    state.counter.decrement();
+                }
+            }
+            _ => state.counter.load(None),
+        }
+
+        let value = state.counter.value();
+        let is = |expected: u64| value.map_or(Tristate::Undefined, |value| (value == expected).into());
+
+        pins.write_bits(&Self::Q, value);
+        pins.set(Self::CARRY, !(is(Counter::<16>::MAX) & !up));
+        pins.set(Self::BORROW, !(is(0) & !down));
+    }
+}
+
+/// Octal bus transceiver: with the active low output enable OE at 0, B copies A when DIR = 1,
+/// and A copies B when DIR = 0. Both ports are inputs while disabled.
+pub struct Chip74245;
+
+impl Chip74245 {
+    const DIRECTION: PinNumber = 1;
+    const OUTPUT_ENABLE: PinNumber = 19;
+    const A: [PinNumber; 8] = [2, 3, 4, 5, 6, 7, 8, 9];
+    const B: [PinNumber; 8] = [18, 17, 16, 15, 14, 13, 12, 11];
+}
+
+impl Chip for Chip74245 {
+    type State = ();
+
+    const DESCRIPTION: &'static str = "Octal bus transceiver";
+    const NB_PINS: usize = 20;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[(Self::DIRECTION, "DIR"), (Self::OUTPUT_ENABLE, "OE")];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[];
+    const BIDIRECTIONAL: &'static [(PinNumber, &'static str)] = &[
+        (Self::A[0], "A1"),
+        (Self::A[1], "A2"),
+        (Self::A[2], "A3"),
+        (Self::A[3], "A4"),
+        (Self::A[4], "A5"),
+        (Self::A[5], "A6"),
+        (Self::A[6], "A7"),
+        (Self::A[7], "A8"),
+        (Self::B[0], "B1"),
+        (Self::B[1], "B2"),
+        (Self::B[2], "B3"),
+        (Self::B[3], "B4"),
+        (Self::B[4], "B5"),
+        (Self::B[5], "B6"),
+        (Self::B[6], "B7"),
+        (Self::B[7], "B8"),
+    ];
+    const POWER: &'static [(PinNumber, &'static str)] = &[(10, "GND"), (20, "VCC")];
+
+    fn evaluate(_state: &mut (), pins: &ChipPins<'_>) {
+        let (sources, destinations): (&[PinNumber], &[PinNumber]) =
+            match (pins.input(Self::OUTPUT_ENABLE), pins.input(Self::DIRECTION)) {
+                (Tristate::State(false), Tristate::State(true)) => (&Self::A, &Self::B),
+                (Tristate::State(false), Tristate::State(false)) => (&Self::B, &Self::A),
+                (Tristate::State(true), _) => (&[], &[]),
+                _ => {
+                    // Unknown direction: both ports are driven undefined.
+                    for pin in Self::A.into_iter().chain(Self::B) {
+                        pins.switch_mode(pin, PinMode::Output);
+                        pins.set(pin, Tristate::Undefined);
+                    }
+                    return;
+                }
+            };
+
+        for &pin in Self::A.iter().chain(&Self::B) {
+            pins.switch_mode(pin, if destinations.contains(&pin) { PinMode::Output } else { PinMode::Input });
+        }
+        for (&source, &destination) in sources.iter().zip(destinations) {
+            pins.set(destination, pins.input(source));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    #[test]
+    fn test_74283() {
+        let content = "
+            .chipsets:
+            input a0
+            input a1
+            input a2
+            input a3
+            input b0
+            input b1
+            input b2
+            input b3
+            false c0
+            74283 adder
+            output s0
+            output s1
+            output s2
+            output s3
+            output s4
+            .links:
+            a0:1 adder:5
+            a1:1 adder:3
+            a2:1 adder:14
+            a3:1 adder:12
+            b0:1 adder:6
+            b1:1 adder:2
+            b2:1 adder:15
+            b3:1 adder:11
+            c0:1 adder:7
+            adder:4 s0:1
+            adder:1 s1:1
+            adder:13 s2:1
+            adder:10 s3:1
+            adder:9 s4:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        for (a, b) in [(0, 0), (9, 6), (15, 1), (15, 15)] {
+            circuit.set_bus("a", a).unwrap();
+            circuit.set_bus("b", b).unwrap();
+            circuit.simulate();
+            assert_eq!(circuit.get_bus("s"), Some(a + b), "{a} + {b}");
+        }
+    }
+
+    // Two 7485 cascaded as an 8-bit comparator: 'low' compares bits 0-3 and feeds 'high'.
+    fn comparator() -> Circuit {
+        let mut content =
+            String::from(".chipsets:\n7485 low\n7485 high\ntrue one\nfalse zero\noutput gt\noutput eq\noutput lt\n");
+        let (a_pins, b_pins) = ([10, 12, 13, 15], [9, 11, 14, 1]);
+
+        for bit in 0..8 {
+            content += &format!("input a{bit}\ninput b{bit}\n");
+        }
+        content += ".links:\nzero:1 low:4\none:1 low:3\nzero:1 low:2\nlow:5 high:4\nlow:6 high:3\nlow:7 high:2\n";
+        content += "high:5 gt:1\nhigh:6 eq:1\nhigh:7 lt:1\n";
+        for bit in 0..8 {
+            let chip = if bit < 4 { "low" } else { "high" };
+            content += &format!("a{bit}:1 {chip}:{}\nb{bit}:1 {chip}:{}\n", a_pins[bit % 4], b_pins[bit % 4]);
+        }
+
+        content.parse().unwrap()
+    }
+
+    #[test]
+    fn test_7485_cascade() {
+        let mut circuit = comparator();
+
+        for (a, b, expected) in [(0x42, 0x42, ["0", "1", "0"]), (0x43, 0x42, ["1", "0", "0"]), (0x42, 0x52, ["0", "0", "1"])] {
+            circuit.set_bus("a", a).unwrap();
+            circuit.set_bus("b", b).unwrap();
+            circuit.simulate();
+
+            let outputs = ["gt", "eq", "lt"].map(|name| circuit.get_output(name).unwrap());
+            assert_eq!(outputs, expected, "{a:#x} <=> {b:#x}");
+        }
+    }
+
+    #[test]
+    fn test_74138() {
+        let content = "
+            .chipsets:
+            input s0
+            input s1
+            input s2
+            input g1
+            false g2
+            74138 decoder
+            output y0
+            output y5
+            output y7
+            .links:
+            s0:1 decoder:1
+            s1:1 decoder:2
+            s2:1 decoder:3
+            g2:1 decoder:4
+            g2:1 decoder:5
+            g1:1 decoder:6
+            decoder:15 y0:1
+            decoder:10 y5:1
+            decoder:7 y7:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let mut outputs = |select: u64, enable: &str| {
+            circuit.set_bus("s", select).unwrap();
+            circuit.set_value("g1", enable).unwrap();
+            circuit.simulate();
+            ["y0", "y5", "y7"].map(|name| circuit.get_output(name).unwrap())
+        };
+
+        assert_eq!(outputs(0, "1"), ["0", "1", "1"]);
+        assert_eq!(outputs(5, "1"), ["1", "0", "1"]);
+        assert_eq!(outputs(7, "1"), ["1", "1", "0"]);
+        assert_eq!(outputs(7, "0"), ["1", "1", "1"]);
+    }
+
+    #[test]
+    fn test_74157() {
+        let content = "
+            .chipsets:
+            input s
+            input g
+            input a
+            input b
+            74157 mux
+            output y
+            .links:
+            s:1 mux:1
+            g:1 mux:15
+            a:1 mux:11
+            b:1 mux:10
+            mux:9 y:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let mut output = |s: &str, g: &str, a: &str, b: &str| {
+            for (name, value) in [("s", s), ("g", g), ("a", a), ("b", b)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            circuit.get_output("y").unwrap()
+        };
+
+        assert_eq!(output("0", "0", "1", "0"), "1");
+        assert_eq!(output("1", "0", "1", "0"), "0");
+        assert_eq!(output("U", "0", "1", "1"), "1");
+        assert_eq!(output("U", "0", "1", "0"), "U");
+        assert_eq!(output("0", "1", "1", "0"), "0");
+    }
+
+    #[test]
+    fn test_7474() {
+        let content = "
+            .chipsets:
+            input clock
+            input d
+            input preset
+            input clear
+            7474 flip_flop
+            output q
+            output q_bar
+            .links:
+            clock:1 flip_flop:11
+            d:1 flip_flop:12
+            preset:1 flip_flop:10
+            clear:1 flip_flop:13
+            flip_flop:9 q:1
+            flip_flop:8 q_bar:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let mut outputs = |clock: &str, d: &str, preset: &str, clear: &str| {
+            for (name, value) in [("clock", clock), ("d", d), ("preset", preset), ("clear", clear)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            ["q", "q_bar"].map(|name| circuit.get_output(name).unwrap())
+        };
+
+        assert_eq!(outputs("0", "1", "1", "1"), ["U", "U"]);
+        assert_eq!(outputs("1", "1", "1", "1"), ["1", "0"]);
+        assert_eq!(outputs("0", "0", "1", "1"), ["1", "0"]);
+        assert_eq!(outputs("1", "0", "1", "1"), ["0", "1"]);
+        assert_eq!(outputs("1", "0", "0", "1"), ["1", "0"]);
+        assert_eq!(outputs("0", "1", "1", "0"), ["0", "1"]);
+        assert_eq!(outputs("1", "1", "0", "0"), ["1", "1"]);
+    }
+
+    #[test]
+    fn test_74193() {
+        let content = "
+            .chipsets:
+            input up
+            input down
+            input clear
+            input load
+            input d0
+            input d1
+            input d2
+            input d3
+            74193 counter
+            output q0
+            output q1
+            output q2
+            output q3
+            output carry
+            output borrow
+            .links:
+            up:1 counter:5
+            down:1 counter:4
+            clear:1 counter:14
+            load:1 counter:11
+            d0:1 counter:15
+            d1:1 counter:1
+            d2:1 counter:10
+            d3:1 counter:9
+            counter:3 q0:1
+            counter:2 q1:1
+            counter:6 q2:1
+            counter:7 q3:1
+            counter:12 carry:1
+            counter:13 borrow:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        circuit.set_bus("d", 9).unwrap();
+        let mut step = |up: &str, down: &str, clear: &str, load: &str| {
+            for (name, value) in [("up", up), ("down", down), ("clear", clear), ("load", load)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            (circuit.get_bus("q"), circuit.get_output("carry").unwrap(), circuit.get_output("borrow").unwrap())
+        };
+
+        assert_eq!(step("1", "1", "1", "1"), (Some(0), "1".to_owned(), "1".to_owned()));
+        assert_eq!(step("1", "0", "0", "1"), (Some(0), "1".to_owned(), "0".to_owned()));
+        assert_eq!(step("1", "1", "0", "1"), (Some(15), "1".to_owned(), "1".to_owned()));
+        assert_eq!(step("0", "1", "0", "1"), (Some(15), "0".to_owned(), "1".to_owned()));
+        assert_eq!(step("1", "1", "0", "1"), (Some(0), "1".to_owned(), "1".to_owned()));
+        assert_eq!(step("1", "1", "0", "0"), (Some(9), "1".to_owned(), "1".to_owned()));
+        assert_eq!(step("1", "1", "0", "1"), (Some(9), "1".to_owned(), "1".to_owned()));
+        assert_eq!(step("0", "1", "0", "1"), (Some(9), "1".to_owned(), "1".to_owned()));
+        assert_eq!(step("1", "1", "0", "1"), (Some(10), "1".to_owned(), "1".to_owned()));
+    }
+
+    #[test]
+    fn test_74245() {
+        let content = "
+            .chipsets:
+            input dir
+            input oe
+            input a
+            input b
+            74245 transceiver
+            output out_a
+            output out_b
+            .links:
+            dir:1 transceiver:1
+            oe:1 transceiver:19
+            a:1 transceiver:2
+            b:1 transceiver:18
+            transceiver:2 out_a:1
+            transceiver:18 out_b:1
+        ";
+        let mut circuit: Circuit = content.parse().unwrap();
+        let mut outputs = |dir: &str, oe: &str, a: &str, b: &str| {
+            for (name, value) in [("dir", dir), ("oe", oe), ("a", a), ("b", b)] {
+                circuit.set_value(name, value).unwrap();
+            }
+            circuit.simulate();
+            ["out_a", "out_b"].map(|name| circuit.get_output(name).unwrap())
+        };
+
+        // A port driving its net only reads 0 from the transceiver.
+        assert_eq!(outputs("1", "0", "1", "0"), ["0", "1"]);
+        assert_eq!(outputs("0", "0", "0", "1"), ["1", "0"]);
+        assert_eq!(outputs("0", "0", "0", "0"), ["0", "0"]);
+        assert_eq!(outputs("1", "1", "1", "1"), ["0", "0"]);
+        assert_eq!(outputs("U", "0", "0", "0"), ["U", "U"]);
+    }
+
+    #[test]
+    fn test_7485_cascade_inputs() {
+        use super::compare;
+        use crate::components::tristate::Tristate::State;
+
+        // Datasheet truth table for A = B.
+        assert_eq!(compare(Some(3), Some(3), State(true), State(false), State(false)), (State(true), State(false), State(false)));
+        assert_eq!(compare(Some(3), Some(3), State(true), State(true), State(false)), (State(false), State(true), State(false)));
+        assert_eq!(compare(Some(3), Some(3), State(true), State(false), State(true)), (State(false), State(false), State(false)));
+        assert_eq!(compare(Some(3), Some(3), State(false), State(false), State(false)), (State(true), State(false), State(true)));
+    }
+}
@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::components::{tristate::Tristate, PinNumber};
+use crate::pin::PinContainer;
+
+/// Gate inside a composite component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternalGate {
+    /// Kind of gate, e.g. "NAND".
+    pub kind: &'static str,
+    /// Pins of the composite component read by the inputs of the gate.
+    pub inputs: Vec<PinNumber>,
+    /// Pin of the composite component driven by the gate.
+    pub output: PinNumber,
+}
+
+/// Values around an internal gate, as of the last tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateProbe {
+    pub inputs: Vec<Tristate>,
+    /// Value computed by the gate, whether it is forced or not.
+    pub output: Tristate,
+    pub forced: Option<Tristate>,
+}
+
+/// Component made of gates which can be inspected one by one, e.g. to show how a chip decomposes.
+pub trait Composite {
+    /// Internal gates, indexed from 0.
+    fn internal_gates(&self) -> Vec<InternalGate>;
+
+    /// `None` if there is no gate `index`.
+    fn probe_gate(&self, index: usize) -> Option<GateProbe>;
+
+    /// Forces the value driven by gate `index`, whatever the gate computes, or releases it with `None`.
+    /// Returns `false` if there is no gate `index`.
+    fn force_gate(&self, index: usize, value: Option<Tristate>) -> bool;
+}
+
+/// Values forced on the outputs of internal gates, by output pin.
+#[derive(Default)]
+pub(super) struct ForcedOutputs(RefCell<HashMap<PinNumber, Tristate>>);
+
+impl ForcedOutputs {
+    pub fn get(&self, pin: PinNumber) -> Option<Tristate> {
+        self.0.borrow().get(&pin).copied()
+    }
+}
+
+/// Composite component whose gates each drive one of its pins.
+pub(super) trait GatesComposite {
+    fn gates(&self) -> Vec<InternalGate>;
+
+    /// Value computed by gate `index`, which exists.
+    fn gate_output(&self, index: usize) -> Tristate;
+
+    fn pins(&self) -> &PinContainer;
+
+    fn forced_outputs(&self) -> &ForcedOutputs;
+}
+
+impl<T: GatesComposite> Composite for T {
+    fn internal_gates(&self) -> Vec<InternalGate> {
+        self.gates()
+    }
+
+    fn probe_gate(&self, index: usize) -> Option<GateProbe> {
+        let gate = self.gates().into_iter().nth(index)?;
+
+        Some(GateProbe {
+            inputs: gate.inputs.iter().map(|&pin| self.pins().compute_input(pin).unwrap()).collect(),
+            output: self.gate_output(index),
+            forced: self.forced_outputs().get(gate.output),
+        })
+    }
+
+    fn force_gate(&self, index: usize, value: Option<Tristate>) -> bool {
+        let Some(gate) = self.gates().into_iter().nth(index) else {
+            return false;
+        };
+        let mut forced = self.forced_outputs().0.borrow_mut();
+
+        match value {
+            Some(value) => forced.insert(gate.output, value),
+            None => forced.remove(&gate.output),
+        };
+        true
+    }
+}
@@ -1 +1,6 @@
+#[cfg(feature = "debug-internals")]
+mod internals;
 pub mod parallel_gates;
+
+#[cfg(feature = "debug-internals")]
+pub use internals::{Composite, GateProbe, InternalGate};
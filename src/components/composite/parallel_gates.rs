@@ -1,66 +1,153 @@
 use std::{
     collections::HashMap,
+    marker::PhantomData,
     rc::{Rc, Weak},
 };
 
 use crate::{
     components::{
         gates::{
-            one_input::{GateNOT, GateOneInput},
-            two_inputs::{GateAND, GateNAND, GateNOR, GateOR, GateTwoInputs, GateXOR},
+            n_inputs::{GateNInputs, GateNInputsAND, GateNInputsNAND, GateNInputsNOR, GateNInputsOR},
+            one_input::{GateBUF, GateNOT, GateOneInput},
+            two_inputs::{GateAND, GateNAND, GateNOR, GateOR, GateTwoInputs, GateXNOR, GateXOR},
         },
+        info::{ComponentInfo, PinDirection},
         tristate::Tristate,
         Component, InvalidPin, PinNumber, Tick,
     },
     pin::{PinContainer, PinSpecification},
 };
 
+#[cfg(feature = "debug-internals")]
+use super::internals::{Composite, ForcedOutputs, GatesComposite, InternalGate};
+
 /* Final Components Declaration */
 pub type Component4001 = ParallelGatesTwoInputs<GateNOR>;
+pub type Component4002 = ParallelGatesNInputs<GateNInputsNOR<4>, DualFourInputsPinout>;
 pub type Component4011 = ParallelGatesTwoInputs<GateNAND>;
+pub type Component4012 = ParallelGatesNInputs<GateNInputsNAND<4>, DualFourInputsPinout>;
 pub type Component4030 = ParallelGatesTwoInputs<GateXOR>;
+pub type Component4049 = ParallelGatesOneInput<GateNOT, HexBufferPinout>;
+pub type Component4050 = ParallelGatesOneInput<GateBUF, HexBufferPinout>;
+pub type Component4068 = ParallelGatesNInputs<GateNInputsNAND<8>, EightInputsPinout>;
 pub type Component4069 = ParallelGatesOneInput<GateNOT>;
+pub type Component4072 = ParallelGatesNInputs<GateNInputsOR<4>, DualFourInputsPinout>;
 pub type Component4071 = ParallelGatesTwoInputs<GateOR>;
+pub type Component4077 = ParallelGatesTwoInputs<GateXNOR>;
+pub type Component4078 = ParallelGatesNInputs<GateNInputsNOR<8>, EightInputsPinout>;
 pub type Component4081 = ParallelGatesTwoInputs<GateAND>;
+pub type Component4082 = ParallelGatesNInputs<GateNInputsAND<4>, DualFourInputsPinout>;
+pub type Component7400 = ParallelGatesTwoInputs<GateNAND, TtlPinout>;
+pub type Component7404 = ParallelGatesOneInput<GateNOT, TtlPinout>;
+pub type Component7408 = ParallelGatesTwoInputs<GateAND, TtlPinout>;
+pub type Component7432 = ParallelGatesTwoInputs<GateOR, TtlPinout>;
+pub type Component7486 = ParallelGatesTwoInputs<GateXOR, TtlPinout>;
 /* ---------------------------- */
 
+/// Pin layout of a 14-pin gate package, which differs between the CD4000 and 74 series.
+pub trait GatesPinout: 'static {
+    const POWER: [(PinNumber, &'static str); 2];
+    /// `(left input, right input, output)` of each 2-input gate.
+    const TWO_INPUTS_GATES: [(PinNumber, PinNumber, PinNumber); 4];
+}
+
+/// CD4000 series layout.
+pub struct CmosPinout;
+
+impl GatesPinout for CmosPinout {
+    const POWER: [(PinNumber, &'static str); 2] = [(7, "VSS"), (14, "VDD")];
+    const TWO_INPUTS_GATES: [(PinNumber, PinNumber, PinNumber); 4] = [(1, 2, 3), (5, 6, 4), (8, 9, 10), (12, 13, 11)];
+}
+
+/// 74 series layout.
+pub struct TtlPinout;
+
+impl GatesPinout for TtlPinout {
+    const POWER: [(PinNumber, &'static str); 2] = [(7, "GND"), (14, "VCC")];
+    const TWO_INPUTS_GATES: [(PinNumber, PinNumber, PinNumber); 4] = [(1, 2, 3), (4, 5, 6), (9, 10, 8), (12, 13, 11)];
+}
+
+/// Pin layout of a package of six single input gates.
+pub trait OneInputPinout: 'static {
+    const NB_PINS: usize = 14;
+    const POWER: [(PinNumber, &'static str); 2];
+    /// `(input, output)` of each gate, the same in the CD4000 and 74 series 14-pin packages.
+    const GATES: [(PinNumber, PinNumber); 6] = [(1, 2), (3, 4), (5, 6), (9, 8), (11, 10), (13, 12)];
+}
+
+impl OneInputPinout for CmosPinout {
+    const POWER: [(PinNumber, &'static str); 2] = <Self as GatesPinout>::POWER;
+}
+
+impl OneInputPinout for TtlPinout {
+    const POWER: [(PinNumber, &'static str); 2] = <Self as GatesPinout>::POWER;
+}
+
+/// 16-pin hex buffer package, pins 13 and 16 being not connected (CD4049 and CD4050 layout).
+pub struct HexBufferPinout;
+
+impl OneInputPinout for HexBufferPinout {
+    const NB_PINS: usize = 16;
+    const POWER: [(PinNumber, &'static str); 2] = [(8, "VSS"), (1, "VDD")];
+    const GATES: [(PinNumber, PinNumber); 6] = [(3, 2), (5, 4), (7, 6), (9, 10), (11, 12), (14, 15)];
+}
+
+/// Pin layout of a package of gates with more than 2 inputs.
+pub trait NInputsPinout: 'static {
+    /// `(inputs, output)` of each gate.
+    const GATES: &'static [(&'static [PinNumber], PinNumber)];
+    /// Pin driven by the inverted output of each gate, for packages with complementary outputs.
+    const COMPLEMENT_OUTPUTS: &'static [PinNumber] = &[];
+}
+
+/// Single 8-input gate with its complementary output on pin 1 (CD4068 and CD4078 layout).
+pub struct EightInputsPinout;
+
+impl NInputsPinout for EightInputsPinout {
+    const GATES: &'static [(&'static [PinNumber], PinNumber)] = &[(&[2, 3, 4, 5, 9, 10, 11, 12], 13)];
+    const COMPLEMENT_OUTPUTS: &'static [PinNumber] = &[1];
+}
+
+/// Two 4-input gates (CD4002, CD4012, CD4072 and CD4082 layout).
+pub struct DualFourInputsPinout;
+
+impl NInputsPinout for DualFourInputsPinout {
+    const GATES: &'static [(&'static [PinNumber], PinNumber)] = &[(&[2, 3, 4, 5], 1), (&[9, 10, 11, 12], 13)];
+}
+
 /* -----------
 GATE ONE INPUT
 ------------*/
 
-pub struct ParallelGatesOneInput<G: GateOneInput + 'static> {
+pub struct ParallelGatesOneInput<G: GateOneInput + 'static, P: OneInputPinout = CmosPinout> {
     pins: Rc<PinContainer>,
     components: [Rc<G>; 6],
+    #[cfg(feature = "debug-internals")]
+    forced: ForcedOutputs,
+    _pinout: PhantomData<P>,
 }
 
-impl<G> ParallelGatesOneInput<G>
+impl<G, P> ParallelGatesOneInput<G, P>
 where
-    G: GateOneInput + Default + 'static,
+    G: GateOneInput + 'static,
+    P: OneInputPinout,
 {
-    const INPUT_1: PinNumber = 1;
-    const OUTPUT_1: PinNumber = 2;
-    const INPUT_2: PinNumber = 3;
-    const OUTPUT_2: PinNumber = 4;
-    const INPUT_3: PinNumber = 5;
-    const OUTPUT_3: PinNumber = 6;
-    const OUTPUT_4: PinNumber = 8;
-    const INPUT_4: PinNumber = 9;
-    const OUTPUT_5: PinNumber = 10;
-    const INPUT_5: PinNumber = 11;
-    const OUTPUT_6: PinNumber = 12;
-    const INPUT_6: PinNumber = 13;
-
-    const PER_GATES: [(PinNumber, PinNumber); 6] = [
-        (Self::INPUT_1, Self::OUTPUT_1),
-        (Self::INPUT_2, Self::OUTPUT_2),
-        (Self::INPUT_3, Self::OUTPUT_3),
-        (Self::INPUT_4, Self::OUTPUT_4),
-        (Self::INPUT_5, Self::OUTPUT_5),
-        (Self::INPUT_6, Self::OUTPUT_6),
-    ];
+    pub const PER_GATES: [(PinNumber, PinNumber); 6] = P::GATES;
+}
 
+impl<G, P> ParallelGatesOneInput<G, P>
+where
+    G: GateOneInput + Default + 'static,
+    P: OneInputPinout,
+{
     pub fn new() -> Self {
-        let this = Self { pins: Rc::new(PinContainer::new(14, Self::build_pins_spec())), components: Default::default() };
+        let this = Self {
+            pins: Rc::new(PinContainer::new(P::NB_PINS, Self::build_pins_spec())),
+            components: Default::default(),
+            #[cfg(feature = "debug-internals")]
+            forced: Default::default(),
+            _pinout: PhantomData,
+        };
 
         debug_assert_eq!(this.components.len(), Self::PER_GATES.len());
 
@@ -74,6 +161,23 @@ where
         this
     }
 
+    pub fn info(name: &str) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, &format!("Six {} gates", G::NAME), P::NB_PINS);
+
+        for (pin, pin_name) in P::POWER {
+            info = info.with_pin(pin, pin_name, PinDirection::Unused);
+        }
+
+        for (idx, (input_pin, output_pin)) in Self::PER_GATES.into_iter().enumerate() {
+            info = info
+                .with_pin(input_pin, &format!("in_{}", idx + 1), PinDirection::Input)
+                .with_pin(output_pin, &format!("out_{}", idx + 1), PinDirection::Output)
+                .with_dependencies(output_pin, &[input_pin]);
+        }
+
+        info
+    }
+
     fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
         let mut spec: HashMap<PinNumber, PinSpecification> = Default::default();
 
@@ -88,16 +192,41 @@ where
     }
 }
 
-impl<G> Component for ParallelGatesOneInput<G>
+impl<G, P> Default for ParallelGatesOneInput<G, P>
+where
+    G: GateOneInput + Default + 'static,
+    P: OneInputPinout,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G, P> Component for ParallelGatesOneInput<G, P>
 where
     G: GateOneInput + 'static,
+    P: OneInputPinout,
 {
     fn simulate(&self, tick: Tick) {
         self.pins.simulate_no_manual_outputs(tick);
     }
 
     fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
-        self.pins.compute_for_external(pin)
+        let value = self.pins.compute_for_external(pin)?;
+
+        #[cfg(feature = "debug-internals")]
+        let value = self.forced.get(pin).unwrap_or(value);
+        Ok(value)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    #[cfg(feature = "debug-internals")]
+    fn as_composite(&self) -> Option<&dyn Composite> {
+        Some(self)
     }
 
     fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
@@ -105,41 +234,56 @@ where
     }
 }
 
+#[cfg(feature = "debug-internals")]
+impl<G, P> GatesComposite for ParallelGatesOneInput<G, P>
+where
+    G: GateOneInput + 'static,
+    P: OneInputPinout,
+{
+    fn gates(&self) -> Vec<InternalGate> {
+        Self::PER_GATES.map(|(input, output)| InternalGate { kind: G::NAME, inputs: vec![input], output }).into()
+    }
+
+    fn gate_output(&self, index: usize) -> Tristate {
+        self.components[index].compute(G::OUTPUT).unwrap()
+    }
+
+    fn pins(&self) -> &PinContainer {
+        &self.pins
+    }
+
+    fn forced_outputs(&self) -> &ForcedOutputs {
+        &self.forced
+    }
+}
+
 /* ------------
 GATE TWO INPUTS
 -------------*/
 
-pub struct ParallelGatesTwoInputs<G: GateTwoInputs + 'static> {
+pub struct ParallelGatesTwoInputs<G: GateTwoInputs + 'static, P: GatesPinout = CmosPinout> {
     pins: Rc<PinContainer>,
     components: [Rc<G>; 4],
+    #[cfg(feature = "debug-internals")]
+    forced: ForcedOutputs,
+    _pinout: PhantomData<P>,
 }
 
-impl<G> ParallelGatesTwoInputs<G>
+impl<G, P> ParallelGatesTwoInputs<G, P>
 where
     G: GateTwoInputs + Default + 'static,
+    P: GatesPinout,
 {
-    const INPUT_1_LEFT: PinNumber = 1;
-    const INPUT_1_RIGHT: PinNumber = 2;
-    const OUTPUT_1: PinNumber = 3;
-    const OUTPUT_2: PinNumber = 4;
-    const INPUT_2_LEFT: PinNumber = 5;
-    const INPUT_2_RIGHT: PinNumber = 6;
-    const INPUT_3_LEFT: PinNumber = 8;
-    const INPUT_3_RIGHT: PinNumber = 9;
-    const OUTPUT_3: PinNumber = 10;
-    const OUTPUT_4: PinNumber = 11;
-    const INPUT_4_LEFT: PinNumber = 12;
-    const INPUT_4_RIGHT: PinNumber = 13;
-
-    const PER_GATES: [(PinNumber, PinNumber, PinNumber); 4] = [
-        (Self::INPUT_1_LEFT, Self::INPUT_1_RIGHT, Self::OUTPUT_1),
-        (Self::INPUT_2_LEFT, Self::INPUT_2_RIGHT, Self::OUTPUT_2),
-        (Self::INPUT_3_LEFT, Self::INPUT_3_RIGHT, Self::OUTPUT_3),
-        (Self::INPUT_4_LEFT, Self::INPUT_4_RIGHT, Self::OUTPUT_4),
-    ];
+    pub const PER_GATES: [(PinNumber, PinNumber, PinNumber); 4] = P::TWO_INPUTS_GATES;
 
     pub fn new() -> Self {
-        let this = Self { pins: Rc::new(PinContainer::new(14, Self::build_pins_spec())), components: Default::default() };
+        let this = Self {
+            pins: Rc::new(PinContainer::new(14, Self::build_pins_spec())),
+            components: Default::default(),
+            #[cfg(feature = "debug-internals")]
+            forced: Default::default(),
+            _pinout: PhantomData,
+        };
 
         debug_assert_eq!(this.components.len(), Self::PER_GATES.len());
 
@@ -154,6 +298,24 @@ where
         this
     }
 
+    pub fn info(name: &str) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, &format!("Four 2-input {} gates", G::NAME), 14);
+
+        for (pin, pin_name) in P::POWER {
+            info = info.with_pin(pin, pin_name, PinDirection::Unused);
+        }
+
+        for (idx, (input_left_pin, input_right_pin, output_pin)) in Self::PER_GATES.into_iter().enumerate() {
+            info = info
+                .with_pin(input_left_pin, &format!("in_{}a", idx + 1), PinDirection::Input)
+                .with_pin(input_right_pin, &format!("in_{}b", idx + 1), PinDirection::Input)
+                .with_pin(output_pin, &format!("out_{}", idx + 1), PinDirection::Output)
+                .with_dependencies(output_pin, &[input_left_pin, input_right_pin]);
+        }
+
+        info
+    }
+
     fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
         let mut spec: HashMap<PinNumber, PinSpecification> = Default::default();
 
@@ -169,19 +331,421 @@ where
     }
 }
 
-impl<G> Component for ParallelGatesTwoInputs<G>
+impl<G, P> Default for ParallelGatesTwoInputs<G, P>
+where
+    G: GateTwoInputs + Default + 'static,
+    P: GatesPinout,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G, P> Component for ParallelGatesTwoInputs<G, P>
 where
     G: GateTwoInputs + 'static,
+    P: GatesPinout,
 {
     fn simulate(&self, tick: Tick) {
         self.pins.simulate_no_manual_outputs(tick);
     }
 
     fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
-        self.pins.compute_for_external(pin)
+        let value = self.pins.compute_for_external(pin)?;
+
+        #[cfg(feature = "debug-internals")]
+        let value = self.forced.get(pin).unwrap_or(value);
+        Ok(value)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    #[cfg(feature = "debug-internals")]
+    fn as_composite(&self) -> Option<&dyn Composite> {
+        Some(self)
     }
 
     fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
         self.pins.set_link_to_external_component(pin, other_component, other_pin)
     }
 }
+
+#[cfg(feature = "debug-internals")]
+impl<G, P> GatesComposite for ParallelGatesTwoInputs<G, P>
+where
+    G: GateTwoInputs + 'static,
+    P: GatesPinout,
+{
+    fn gates(&self) -> Vec<InternalGate> {
+        P::TWO_INPUTS_GATES
+            .map(|(input_left, input_right, output)| InternalGate {
+                kind: G::NAME,
+                inputs: vec![input_left, input_right],
+                output,
+            })
+            .into()
+    }
+
+    fn gate_output(&self, index: usize) -> Tristate {
+        self.components[index].compute(G::OUTPUT).unwrap()
+    }
+
+    fn pins(&self) -> &PinContainer {
+        &self.pins
+    }
+
+    fn forced_outputs(&self) -> &ForcedOutputs {
+        &self.forced
+    }
+}
+
+/* ------------
+GATE N INPUTS
+-------------*/
+
+pub struct ParallelGatesNInputs<G: GateNInputs + 'static, P: NInputsPinout> {
+    pins: Rc<PinContainer>,
+    components: Vec<Rc<G>>,
+    complements: Vec<Rc<G::Complement>>,
+    #[cfg(feature = "debug-internals")]
+    forced: ForcedOutputs,
+    _pinout: PhantomData<P>,
+}
+
+impl<G, P> ParallelGatesNInputs<G, P>
+where
+    G: GateNInputs + Default + 'static,
+    P: NInputsPinout,
+{
+    pub const PER_GATES: &'static [(&'static [PinNumber], PinNumber)] = P::GATES;
+
+    pub fn new() -> Self {
+        let this = Self {
+            pins: Rc::new(PinContainer::new(14, Self::build_pins_spec())),
+            components: Self::PER_GATES.iter().map(|_| Rc::new(G::default())).collect(),
+            complements: P::COMPLEMENT_OUTPUTS.iter().map(|_| Rc::new(G::Complement::default())).collect(),
+            #[cfg(feature = "debug-internals")]
+            forced: Default::default(),
+            _pinout: PhantomData,
+        };
+
+        for (idx, (input_pins, output_pin)) in Self::PER_GATES.iter().enumerate() {
+            let component = Rc::downgrade(&this.components[idx]);
+
+            debug_assert_eq!(input_pins.len(), G::NB_INPUTS);
+            for (gate_input, &input_pin) in input_pins.iter().enumerate() {
+                this.pins.link_internal_component(input_pin, component.clone(), gate_input + 1);
+            }
+            this.pins.link_internal_component(*output_pin, component.clone(), G::OUTPUT);
+        }
+
+        for (idx, &output_pin) in P::COMPLEMENT_OUTPUTS.iter().enumerate() {
+            let component = Rc::downgrade(&this.complements[idx]);
+
+            for (gate_input, &input_pin) in Self::PER_GATES[idx].0.iter().enumerate() {
+                this.pins.link_internal_component(input_pin, component.clone(), gate_input + 1);
+            }
+            this.pins.link_internal_component(output_pin, component, G::Complement::OUTPUT);
+        }
+
+        this
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        let count = match Self::PER_GATES.len() {
+            1 => "One".to_owned(),
+            2 => "Two".to_owned(),
+            count => count.to_string(),
+        };
+        let gates = if Self::PER_GATES.len() > 1 { "gates" } else { "gate" };
+        let mut info = ComponentInfo::new(name, &format!("{count} {}-input {} {gates}", G::NB_INPUTS, G::NAME), 14);
+
+        for (pin, pin_name) in <CmosPinout as GatesPinout>::POWER {
+            info = info.with_pin(pin, pin_name, PinDirection::Unused);
+        }
+
+        for (idx, (input_pins, output_pin)) in Self::PER_GATES.iter().enumerate() {
+            for (gate_input, &input_pin) in input_pins.iter().enumerate() {
+                info = info.with_pin(
+                    input_pin,
+                    &format!("in_{}{}", idx + 1, (b'a' + gate_input as u8) as char),
+                    PinDirection::Input,
+                );
+            }
+            info = info
+                .with_pin(*output_pin, &format!("out_{}", idx + 1), PinDirection::Output)
+                .with_dependencies(*output_pin, input_pins);
+        }
+
+        for (idx, &output_pin) in P::COMPLEMENT_OUTPUTS.iter().enumerate() {
+            info = info
+                .with_pin(output_pin, &format!("out_{}'", idx + 1), PinDirection::Output)
+                .with_dependencies(output_pin, Self::PER_GATES[idx].0);
+        }
+
+        info
+    }
+
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        let mut spec: HashMap<PinNumber, PinSpecification> = Default::default();
+
+        for &(input_pins, output_pin) in Self::PER_GATES {
+            spec.extend(input_pins.iter().map(|&input_pin| (input_pin, PinSpecification::UnidirectionalInput())));
+            spec.insert(output_pin, PinSpecification::UnidirectionalOutput());
+        }
+        spec.extend(P::COMPLEMENT_OUTPUTS.iter().map(|&output_pin| (output_pin, PinSpecification::UnidirectionalOutput())));
+
+        spec
+    }
+}
+
+impl<G, P> Default for ParallelGatesNInputs<G, P>
+where
+    G: GateNInputs + Default + 'static,
+    P: NInputsPinout,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G, P> Component for ParallelGatesNInputs<G, P>
+where
+    G: GateNInputs + 'static,
+    P: NInputsPinout,
+{
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate_no_manual_outputs(tick);
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        let value = self.pins.compute_for_external(pin)?;
+
+        #[cfg(feature = "debug-internals")]
+        let value = self.forced.get(pin).unwrap_or(value);
+        Ok(value)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    #[cfg(feature = "debug-internals")]
+    fn as_composite(&self) -> Option<&dyn Composite> {
+        Some(self)
+    }
+
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+}
+
+#[cfg(feature = "debug-internals")]
+impl<G, P> GatesComposite for ParallelGatesNInputs<G, P>
+where
+    G: GateNInputs + 'static,
+    P: NInputsPinout,
+{
+    fn gates(&self) -> Vec<InternalGate> {
+        let gates = P::GATES.iter().map(|&(inputs, output)| InternalGate { kind: G::NAME, inputs: inputs.to_vec(), output });
+        let complements = P::COMPLEMENT_OUTPUTS.iter().zip(P::GATES).map(|(&output, &(inputs, _))| InternalGate {
+            kind: <G::Complement as GateNInputs>::NAME,
+            inputs: inputs.to_vec(),
+            output,
+        });
+
+        gates.chain(complements).collect()
+    }
+
+    fn gate_output(&self, index: usize) -> Tristate {
+        match index.checked_sub(self.components.len()) {
+            None => self.components[index].compute(G::OUTPUT).unwrap(),
+            Some(index) => self.complements[index].compute(G::Complement::OUTPUT).unwrap(),
+        }
+    }
+
+    fn pins(&self) -> &PinContainer {
+        &self.pins
+    }
+
+    fn forced_outputs(&self) -> &ForcedOutputs {
+        &self.forced
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    #[test]
+    fn test_ttl_gates() {
+        // Third gate of each package: inputs 9 and 10, output 8.
+        for (component_type, expected) in [
+            ("7400", ["1", "1", "1", "0"]),
+            ("7408", ["0", "0", "0", "1"]),
+            ("7432", ["0", "1", "1", "1"]),
+            ("7486", ["0", "1", "1", "0"]),
+        ] {
+            let content = format!(".chipsets:\ninput a\ninput b\n{component_type} gate\noutput out\n.links:\na:1 gate:9\nb:1 gate:10\ngate:8 out:1\n");
+            let mut circuit: Circuit = content.parse().unwrap();
+
+            for (idx, (a, b)) in [("0", "0"), ("0", "1"), ("1", "0"), ("1", "1")].into_iter().enumerate() {
+                circuit.set_value("a", a).unwrap();
+                circuit.set_value("b", b).unwrap();
+                circuit.simulate();
+                assert_eq!(circuit.get_output("out").unwrap(), expected[idx], "{component_type}: {a} {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_4077() {
+        // Second gate of the CMOS package: inputs 5 and 6, output 4.
+        let mut circuit: Circuit =
+            ".chipsets:\ninput a\ninput b\n4077 gate\noutput out\n.links:\na:1 gate:5\nb:1 gate:6\ngate:4 out:1\n"
+                .parse()
+                .unwrap();
+
+        for (a, b, expected) in
+            [("0", "0", "1"), ("0", "1", "0"), ("1", "0", "0"), ("1", "1", "1"), ("U", "0", "U"), ("1", "U", "U")]
+        {
+            circuit.set_value("a", a).unwrap();
+            circuit.set_value("b", b).unwrap();
+            circuit.simulate();
+            assert_eq!(circuit.get_output("out").unwrap(), expected, "{a} {b}");
+        }
+    }
+
+    #[test]
+    fn test_4068() {
+        let inputs = [2, 3, 4, 5, 9, 10, 11, 12];
+        let mut content = ".chipsets:\n4068 gate\noutput out\noutput and\n".to_owned();
+        for pin in inputs {
+            content += &format!("input in{pin}\n");
+        }
+        content += ".links:\ngate:13 out:1\ngate:1 and:1\n";
+        for pin in inputs {
+            content += &format!("in{pin}:1 gate:{pin}\n");
+        }
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        for pin in inputs {
+            circuit.set_value(&format!("in{pin}"), "1").unwrap();
+        }
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "0");
+        assert_eq!(circuit.get_output("and").unwrap(), "1");
+
+        for pin in inputs {
+            circuit.set_value(&format!("in{pin}"), "0").unwrap();
+            circuit.simulate();
+            assert_eq!(circuit.get_output("out").unwrap(), "1", "in{pin} low");
+            assert_eq!(circuit.get_output("and").unwrap(), "0", "in{pin} low");
+            circuit.set_value(&format!("in{pin}"), "1").unwrap();
+        }
+
+        circuit.set_value("in9", "U").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "U");
+        assert_eq!(circuit.get_output("and").unwrap(), "U");
+    }
+
+    #[test]
+    fn test_4078() {
+        let inputs = [2, 3, 4, 5, 9, 10, 11, 12];
+        let mut content = ".chipsets:\n4078 gate\noutput out\noutput or\n".to_owned();
+        for pin in inputs {
+            content += &format!("input in{pin}\n");
+        }
+        content += ".links:\ngate:13 out:1\ngate:1 or:1\n";
+        for pin in inputs {
+            content += &format!("in{pin}:1 gate:{pin}\n");
+        }
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        for pin in inputs {
+            circuit.set_value(&format!("in{pin}"), "0").unwrap();
+        }
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "1");
+        assert_eq!(circuit.get_output("or").unwrap(), "0");
+
+        for pin in inputs {
+            circuit.set_value(&format!("in{pin}"), "1").unwrap();
+            circuit.simulate();
+            assert_eq!(circuit.get_output("out").unwrap(), "0", "in{pin} high");
+            assert_eq!(circuit.get_output("or").unwrap(), "1", "in{pin} high");
+            circuit.set_value(&format!("in{pin}"), "0").unwrap();
+        }
+
+        circuit.set_value("in9", "U").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "U");
+        assert_eq!(circuit.get_output("or").unwrap(), "U");
+    }
+
+    #[test]
+    fn test_dual_four_inputs_gates() {
+        let inputs = [2, 3, 4, 5, 9, 10, 11, 12];
+
+        // Outputs with every input low, with a single input of the first gate high, then with every input high.
+        for (component_type, all_low, one_high, all_high) in
+            [("4002", "1", "0", "0"), ("4012", "1", "1", "0"), ("4072", "0", "1", "1"), ("4082", "0", "0", "1")]
+        {
+            let mut content = format!(".chipsets:\n{component_type} gate\noutput out1\noutput out2\n");
+            for pin in inputs {
+                content += &format!("input in{pin}\n");
+            }
+            content += ".links:\ngate:1 out1:1\ngate:13 out2:1\n";
+            for pin in inputs {
+                content += &format!("in{pin}:1 gate:{pin}\n");
+            }
+            let mut circuit: Circuit = content.parse().unwrap();
+            let mut simulate_with = |values: [&str; 8]| {
+                for (pin, value) in inputs.into_iter().zip(values) {
+                    circuit.set_value(&format!("in{pin}"), value).unwrap();
+                }
+                circuit.simulate();
+                (circuit.get_output("out1").unwrap(), circuit.get_output("out2").unwrap())
+            };
+
+            assert_eq!(simulate_with(["0"; 8]), (all_low.to_owned(), all_low.to_owned()), "{component_type}");
+            assert_eq!(
+                simulate_with(["0", "1", "0", "0", "0", "0", "0", "0"]),
+                (one_high.to_owned(), all_low.to_owned()),
+                "{component_type}"
+            );
+            assert_eq!(simulate_with(["1"; 8]), (all_high.to_owned(), all_high.to_owned()), "{component_type}");
+        }
+    }
+
+    #[test]
+    fn test_hex_buffers() {
+        // Last gate of the 16-pin package: input 14, output 15.
+        for (component_type, expected) in [("4049", ["1", "0", "U"]), ("4050", ["0", "1", "U"])] {
+            let content =
+                format!(".chipsets:\ninput a\n{component_type} buffer\noutput out\n.links:\na:1 buffer:14\nbuffer:15 out:1\n");
+            let mut circuit: Circuit = content.parse().unwrap();
+
+            for (idx, a) in ["0", "1", "U"].into_iter().enumerate() {
+                circuit.set_value("a", a).unwrap();
+                circuit.simulate();
+                assert_eq!(circuit.get_output("out").unwrap(), expected[idx], "{component_type}: {a}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_7404() {
+        let mut circuit: Circuit =
+            ".chipsets:\ninput a\n7404 not\noutput out\n.links:\na:1 not:13\nnot:12 out:1\n".parse().unwrap();
+
+        circuit.set_value("a", "1").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("out").unwrap(), "0");
+    }
+}
@@ -0,0 +1,94 @@
+use std::{cell::RefCell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, InvalidPin, Monitor, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Records the ticks at which its input differs from the value given by `expect`.
+///
+/// The input is not checked at tick 0, the initial state of the circuit.
+pub struct AssertComponent {
+    pins: PinContainer,
+    expected: Tristate,
+    failures: RefCell<Vec<(Tick, Tristate)>>,
+}
+
+impl AssertComponent {
+    pub const INPUT: PinNumber = 1;
+
+    /// `expect`: `0`, `1` (the default) or `U`.
+    pub const PARAMETERS: &'static [&'static str] = &["expect"];
+
+    pub fn new(expected: Tristate) -> Self {
+        Self { pins: PinContainer::new(1, Self::build_pins_spec()), expected, failures: Default::default() }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(params.parse("expect")?.unwrap_or(Tristate::State(true))))
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        ComponentInfo::new(name, "Records the ticks at which its input differs from the expected value", 1)
+            .with_sequential(true)
+            .with_pin(Self::INPUT, "in", PinDirection::Input)
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        HashMap::from([(Self::INPUT, PinSpecification::UnidirectionalInput())])
+    }
+}
+
+impl Component for AssertComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |_| {
+            if tick == Tick::ZERO {
+                return;
+            }
+
+            let value = self.pins.compute_input(Self::INPUT).unwrap();
+            if value != self.expected {
+                self.failures.borrow_mut().push((tick, value));
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_monitor(&self) -> Option<&dyn Monitor> {
+        Some(self)
+    }
+}
+
+impl Monitor for AssertComponent {
+    fn expected(&self) -> Tristate {
+        self.expected
+    }
+
+    fn failures(&self) -> Vec<(Tick, Tristate)> {
+        self.failures.borrow().clone()
+    }
+}
+
+impl Default for AssertComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Tristate::State(true))
+    }
+}
@@ -0,0 +1,110 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    fs, io,
+    path::Path,
+    rc::Weak,
+};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        tristate::Tristate,
+        Component, FileDevice, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Exposes the next byte of its file at each rising edge of its clock.
+///
+/// Data pins are undefined until the first byte is read, and once the end of the file is reached.
+pub struct FileInputComponent {
+    pins: PinContainer,
+    bytes: RefCell<VecDeque<u8>>,
+    previous_clock: Cell<Tristate>,
+}
+
+impl FileInputComponent {
+    /// Pins of bits 0 to 7 of the byte.
+    pub const DATA: [PinNumber; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+    pub const CLOCK: PinNumber = 9;
+    /// `1` once every byte has been read.
+    pub const END: PinNumber = 10;
+
+    pub fn new() -> Self {
+        Self {
+            pins: PinContainer::new(10, Self::build_pins_spec()),
+            bytes: Default::default(),
+            previous_clock: Default::default(),
+        }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, "Reads a file byte by byte on its clock", 10).with_sequential(true);
+
+        for (bit, pin) in Self::DATA.into_iter().enumerate() {
+            info = info.with_pin(pin, &format!("d{bit}"), PinDirection::Output);
+        }
+        info.with_clock_pin(Self::CLOCK, "clock").with_pin(Self::END, "end", PinDirection::Output)
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        Self::DATA
+            .into_iter()
+            .chain([Self::END])
+            .map(|pin| (pin, PinSpecification::UnidirectionalOutput()))
+            .chain([(Self::CLOCK, PinSpecification::UnidirectionalInput())])
+            .collect()
+    }
+}
+
+impl Component for FileInputComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let clock = self.pins.compute_input(Self::CLOCK).unwrap();
+
+            if self.previous_clock.replace(clock) == false.into() && clock == true.into() {
+                let byte = self.bytes.borrow_mut().pop_front();
+
+                for (bit, pin) in Self::DATA.iter().enumerate() {
+                    let value = byte.map_or(Tristate::Undefined, |byte| Tristate::State((byte >> bit) & 1 == 1));
+
+                    outputs.get(pin).unwrap().set(value);
+                }
+            }
+            outputs.get(&Self::END).unwrap().set(self.bytes.borrow().is_empty().into());
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_file_device(&self) -> Option<&dyn FileDevice> {
+        Some(self)
+    }
+}
+
+impl FileDevice for FileInputComponent {
+    /// Replaces the bytes still to be read by the content of `path`.
+    fn attach(&self, path: &Path) -> io::Result<()> {
+        *self.bytes.borrow_mut() = fs::read(path)?.into();
+        Ok(())
+    }
+}
+
+impl Default for FileInputComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
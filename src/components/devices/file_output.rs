@@ -0,0 +1,107 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    rc::Weak,
+};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        tristate::Tristate,
+        Component, FileDevice, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Writes the byte on its data pins to its file at each rising edge of its clock.
+///
+/// Bytes with undefined bits are skipped, as well as every byte if no file is attached.
+pub struct FileOutputComponent {
+    pins: PinContainer,
+    file: RefCell<Option<File>>,
+    previous_clock: Cell<Tristate>,
+}
+
+impl FileOutputComponent {
+    /// Pins of bits 0 to 7 of the byte.
+    pub const DATA: [PinNumber; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+    pub const CLOCK: PinNumber = 9;
+
+    pub fn new() -> Self {
+        Self { pins: PinContainer::new(9, Self::build_pins_spec()), file: Default::default(), previous_clock: Default::default() }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, "Writes the bytes latched on its clock to a file", 9).with_sequential(true);
+
+        for (bit, pin) in Self::DATA.into_iter().enumerate() {
+            info = info.with_pin(pin, &format!("d{bit}"), PinDirection::Input);
+        }
+        info.with_clock_pin(Self::CLOCK, "clock")
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        Self::DATA.into_iter().chain([Self::CLOCK]).map(|pin| (pin, PinSpecification::UnidirectionalInput())).collect()
+    }
+
+    fn read_byte(&self) -> Option<u8> {
+        Self::DATA.iter().enumerate().try_fold(0, |byte, (bit, &pin)| match self.pins.compute_input(pin).unwrap() {
+            Tristate::State(value) => Some(byte | (u8::from(value) << bit)),
+            Tristate::Undefined => None,
+        })
+    }
+}
+
+impl Component for FileOutputComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |_| {
+            let clock = self.pins.compute_input(Self::CLOCK).unwrap();
+
+            if self.previous_clock.replace(clock) == false.into() && clock == true.into() {
+                let mut file = self.file.borrow_mut();
+
+                if let (Some(handle), Some(byte)) = (file.as_mut(), self.read_byte()) {
+                    // A write error cannot be reported from a tick: the file is detached instead.
+                    if handle.write_all(&[byte]).is_err() {
+                        *file = None;
+                    }
+                }
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_file_device(&self) -> Option<&dyn FileDevice> {
+        Some(self)
+    }
+}
+
+impl FileDevice for FileOutputComponent {
+    /// Creates (or truncates) `path`, where the following bytes are written.
+    fn attach(&self, path: &Path) -> io::Result<()> {
+        *self.file.borrow_mut() = Some(File::create(path)?);
+        Ok(())
+    }
+}
+
+impl Default for FileOutputComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
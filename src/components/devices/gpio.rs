@@ -0,0 +1,181 @@
+use crate::{
+    components::{
+        chips::{Chip, ChipComponent, ChipPins, ClockEdge},
+        tristate::Tristate,
+        PinNumber,
+    },
+    pin::PinMode,
+};
+
+/* Final Components Declaration */
+pub type GpioComponent = ChipComponent<Gpio>;
+/* ---------------------------- */
+
+/// Memory-mapped 8-bit I/O port for toy CPUs, selected by its active low `CS`.
+///
+/// The data bus is latched into the output port at the rising edge of `WR`. While `RD` is low,
+/// the data bus is driven with the input port when `A0` = 0, or with the output port latch when `A0` = 1.
+pub struct Gpio;
+
+#[derive(Debug, Default)]
+pub struct GpioState {
+    latch: Option<u64>,
+    write: ClockEdge,
+}
+
+impl Gpio {
+    pub const DATA: [PinNumber; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+    pub const ADDRESS: PinNumber = 9;
+    pub const CHIP_SELECT: PinNumber = 10;
+    pub const READ: PinNumber = 11;
+    pub const WRITE: PinNumber = 12;
+    pub const OUTPUT_PORT: [PinNumber; 8] = [13, 14, 15, 16, 17, 18, 19, 20];
+    pub const INPUT_PORT: [PinNumber; 8] = [21, 22, 23, 24, 25, 26, 27, 28];
+}
+
+impl Chip for Gpio {
+    type State = GpioState;
+
+    const DESCRIPTION: &'static str = "Memory-mapped I/O port";
+    const NB_PINS: usize = 28;
+    const INPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::ADDRESS, "A0"),
+        (Self::CHIP_SELECT, "CS"),
+        (Self::READ, "RD"),
+        (Self::INPUT_PORT[0], "in0"),
+        (Self::INPUT_PORT[1], "in1"),
+        (Self::INPUT_PORT[2], "in2"),
+        (Self::INPUT_PORT[3], "in3"),
+        (Self::INPUT_PORT[4], "in4"),
+        (Self::INPUT_PORT[5], "in5"),
+        (Self::INPUT_PORT[6], "in6"),
+        (Self::INPUT_PORT[7], "in7"),
+    ];
+    const OUTPUTS: &'static [(PinNumber, &'static str)] = &[
+        (Self::OUTPUT_PORT[0], "out0"),
+        (Self::OUTPUT_PORT[1], "out1"),
+        (Self::OUTPUT_PORT[2], "out2"),
+        (Self::OUTPUT_PORT[3], "out3"),
+        (Self::OUTPUT_PORT[4], "out4"),
+        (Self::OUTPUT_PORT[5], "out5"),
+        (Self::OUTPUT_PORT[6], "out6"),
+        (Self::OUTPUT_PORT[7], "out7"),
+    ];
+    const BIDIRECTIONAL: &'static [(PinNumber, &'static str)] = &[
+        (Self::DATA[0], "d0"),
+        (Self::DATA[1], "d1"),
+        (Self::DATA[2], "d2"),
+        (Self::DATA[3], "d3"),
+        (Self::DATA[4], "d4"),
+        (Self::DATA[5], "d5"),
+        (Self::DATA[6], "d6"),
+        (Self::DATA[7], "d7"),
+    ];
+    const CLOCKS: &'static [(PinNumber, &'static str)] = &[(Self::WRITE, "WR")];
+    const POWER: &'static [(PinNumber, &'static str)] = &[];
+
+    fn evaluate(state: &mut GpioState, pins: &ChipPins<'_>) {
+        let selected = pins.input(Self::CHIP_SELECT) == Tristate::State(false);
+        let reading = selected && pins.input(Self::READ) == Tristate::State(false);
+        let mode = if reading { PinMode::Output } else { PinMode::Input };
+
+        for pin in Self::DATA {
+            pins.switch_mode(pin, mode);
+        }
+
+        if state.write.rising(pins.input(Self::WRITE)) && selected {
+            state.latch = pins.read_bits(&Self::DATA);
+        }
+        if reading {
+            let value = match pins.input(Self::ADDRESS) {
+                Tristate::State(false) => pins.read_bits(&Self::INPUT_PORT),
+                Tristate::State(true) => state.latch,
+                Tristate::Undefined => None,
+            };
+            pins.write_bits(&Self::DATA, value);
+        }
+        pins.write_bits(&Self::OUTPUT_PORT, state.latch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a0
+        input cs
+        input rd
+        input wr
+        input d0
+        input d1
+        input in0
+        input in1
+        false zero
+        gpio port
+        output bus0
+        output bus1
+        output out0
+        output out1
+        .links:
+        a0:1 port:9
+        cs:1 port:10
+        rd:1 port:11
+        wr:1 port:12
+        d0:1 port:1
+        d1:1 port:2
+        in0:1 port:21
+        in1:1 port:22
+        zero:1 port:3
+        zero:1 port:4
+        zero:1 port:5
+        zero:1 port:6
+        zero:1 port:7
+        zero:1 port:8
+        zero:1 port:23
+        zero:1 port:24
+        zero:1 port:25
+        zero:1 port:26
+        zero:1 port:27
+        zero:1 port:28
+        port:1 bus0:1
+        port:2 bus1:1
+        port:13 out0:1
+        port:14 out1:1
+    ";
+
+    fn step(circuit: &mut Circuit, values: &[(&str, &str)]) {
+        for (name, value) in values {
+            circuit.set_value(name, value).unwrap();
+        }
+        circuit.simulate();
+    }
+
+    #[test]
+    fn test_write_then_read() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        step(&mut circuit, &[("a0", "0"), ("cs", "0"), ("rd", "1"), ("wr", "0"), ("d0", "1"), ("d1", "0")]);
+        assert_eq!(circuit.get_bus("out"), None);
+        step(&mut circuit, &[("wr", "1")]);
+        assert_eq!(circuit.get_bus("out"), Some(0b01));
+        assert_eq!(circuit.get_bus("bus"), Some(0));
+
+        // Reads of the input port, then of the output latch.
+        step(&mut circuit, &[("d0", "0"), ("in0", "0"), ("in1", "1"), ("rd", "0")]);
+        assert_eq!(circuit.get_bus("bus"), Some(0b10));
+        step(&mut circuit, &[("a0", "1")]);
+        assert_eq!(circuit.get_bus("bus"), Some(0b01));
+    }
+
+    #[test]
+    fn test_not_selected() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        step(&mut circuit, &[("a0", "0"), ("cs", "1"), ("rd", "0"), ("wr", "0"), ("d0", "1"), ("d1", "1")]);
+        step(&mut circuit, &[("wr", "1"), ("in0", "1"), ("in1", "1")]);
+        assert_eq!(circuit.get_bus("out"), None);
+        assert_eq!(circuit.get_bus("bus"), Some(0));
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    rc::Weak,
+};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        tristate::Tristate,
+        Component, InvalidPin, Keyboard, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Exposes the bits of the last key taken from its queue.
+///
+/// `ready` is `1` during the tick a key is taken, and keys are taken at most every other tick
+/// so that each key gives a rising edge on `ready`.
+pub struct KeyboardComponent {
+    pins: PinContainer,
+    pending_keys: RefCell<VecDeque<u8>>,
+    last_key: Cell<Option<u8>>,
+}
+
+impl KeyboardComponent {
+    /// Pins of bits 0 to 7 of the key.
+    pub const DATA: [PinNumber; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+    /// `1` during the tick a new key is taken from the queue.
+    pub const READY: PinNumber = 9;
+
+    pub fn new() -> Self {
+        Self {
+            pins: PinContainer::new(9, Self::build_pins_spec()),
+            pending_keys: Default::default(),
+            last_key: Default::default(),
+        }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, "Keyboard exposing the bits of the last key pressed", 9).with_sequential(true);
+
+        for (bit, pin) in Self::DATA.into_iter().enumerate() {
+            info = info.with_pin(pin, &format!("d{bit}"), PinDirection::Output);
+        }
+        info.with_pin(Self::READY, "ready", PinDirection::Output)
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        Self::DATA.into_iter().chain([Self::READY]).map(|pin| (pin, PinSpecification::UnidirectionalOutput())).collect()
+    }
+}
+
+impl Component for KeyboardComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let ready = outputs.get(&Self::READY).unwrap();
+            let key = match ready.get() {
+                Tristate::State(true) => None,
+                _ => self.pending_keys.borrow_mut().pop_front(),
+            };
+
+            ready.set(key.is_some().into());
+            if key.is_some() {
+                self.last_key.set(key);
+            }
+            if let Some(key) = self.last_key.get() {
+                for (bit, pin) in Self::DATA.iter().enumerate() {
+                    outputs.get(pin).unwrap().set(Tristate::State((key >> bit) & 1 == 1));
+                }
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_keyboard(&self) -> Option<&dyn Keyboard> {
+        Some(self)
+    }
+}
+
+impl Keyboard for KeyboardComponent {
+    fn push_key(&self, key: u8) {
+        self.pending_keys.borrow_mut().push_back(key);
+    }
+}
+
+impl Default for KeyboardComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyboardComponent;
+    use crate::components::{tristate::Tristate, Component, Keyboard, Tick};
+
+    fn key(keyboard: &KeyboardComponent) -> Option<u8> {
+        KeyboardComponent::DATA.iter().enumerate().try_fold(0, |key, (bit, &pin)| match keyboard.compute(pin).unwrap() {
+            Tristate::State(value) => Some(key | (u8::from(value) << bit)),
+            Tristate::Undefined => None,
+        })
+    }
+
+    #[test]
+    fn test_one_key_every_other_tick() {
+        let keyboard = KeyboardComponent::new();
+
+        keyboard.simulate(Tick::new(1));
+        assert_eq!(key(&keyboard), None);
+        assert_eq!(keyboard.compute(KeyboardComponent::READY).unwrap(), Tristate::State(false));
+
+        keyboard.push_key(b'h');
+        keyboard.push_key(b'i');
+        keyboard.simulate(Tick::new(2));
+        assert_eq!(key(&keyboard), Some(b'h'));
+        assert_eq!(keyboard.compute(KeyboardComponent::READY).unwrap(), Tristate::State(true));
+        keyboard.simulate(Tick::new(3));
+        assert_eq!(key(&keyboard), Some(b'h'));
+        assert_eq!(keyboard.compute(KeyboardComponent::READY).unwrap(), Tristate::State(false));
+        keyboard.simulate(Tick::new(4));
+        assert_eq!(key(&keyboard), Some(b'i'));
+        assert_eq!(keyboard.compute(KeyboardComponent::READY).unwrap(), Tristate::State(true));
+
+        // The last key stays exposed.
+        keyboard.simulate(Tick::new(5));
+        keyboard.simulate(Tick::new(6));
+        assert_eq!(key(&keyboard), Some(b'i'));
+        assert_eq!(keyboard.compute(KeyboardComponent::READY).unwrap(), Tristate::State(false));
+    }
+}
@@ -0,0 +1,316 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    rc::Weak,
+};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, FileDevice, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// How the logger writes the byte on its data pins.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum LogFormat {
+    /// The byte itself, skipped if a bit is undefined.
+    #[default]
+    Char,
+    /// Bits 7 to 0 as `0`, `1` or `U`, followed by a newline.
+    Bits,
+}
+
+/// Appends the byte on its data pins to its sink at each rising edge of its clock while INHIBIT is low,
+/// as in the original NanoTekSpice.
+///
+/// The sink is the file given by the `file` parameter, opened in append mode; nothing is written without one.
+pub struct LoggerComponent {
+    pins: PinContainer,
+    format: LogFormat,
+    sink: RefCell<Option<Box<dyn Write>>>,
+    previous_clock: Cell<Tristate>,
+}
+
+impl LoggerComponent {
+    /// Pins of bits 0 to 7 of the byte.
+    pub const DATA: [PinNumber; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+    pub const CLOCK: PinNumber = 9;
+    pub const INHIBIT: PinNumber = 10;
+
+    /// `file`: path of the log, `format`: `char` (the default) or `bits`.
+    pub const PARAMETERS: &'static [&'static str] = &["file", "format"];
+
+    pub fn new(format: LogFormat) -> Self {
+        Self {
+            pins: PinContainer::new(10, Self::build_pins_spec()),
+            format,
+            sink: Default::default(),
+            previous_clock: Default::default(),
+        }
+    }
+
+    /// Logger writing to `sink` instead of a file.
+    pub fn with_sink(format: LogFormat, sink: Box<dyn Write>) -> Self {
+        let logger = Self::new(format);
+
+        *logger.sink.borrow_mut() = Some(sink);
+        logger
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        let format = match params.get("format") {
+            None | Some("char") => LogFormat::Char,
+            Some("bits") => LogFormat::Bits,
+            Some(format) => return Err(CreateError::InvalidValue { key: "format".to_owned(), value: format.to_owned() }),
+        };
+
+        match params.path("file") {
+            Some(path) => match Self::open(&path) {
+                Ok(file) => Ok(Self::with_sink(format, Box::new(file))),
+                Err(error) => Err(CreateError::File { path, message: error.to_string() }),
+            },
+            None => Ok(Self::new(format)),
+        }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, "Logs the bytes latched on its clock to a file", 10).with_sequential(true);
+
+        for (bit, pin) in Self::DATA.into_iter().enumerate() {
+            info = info.with_pin(pin, &format!("d{bit}"), PinDirection::Input);
+        }
+        info.with_clock_pin(Self::CLOCK, "clock").with_pin(Self::INHIBIT, "inhibit", PinDirection::Input)
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        Self::DATA
+            .into_iter()
+            .chain([Self::CLOCK, Self::INHIBIT])
+            .map(|pin| (pin, PinSpecification::UnidirectionalInput()))
+            .collect()
+    }
+
+    fn open(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    /// Text written for the byte on the data pins, `None` if it is skipped.
+    fn entry(&self) -> Option<Vec<u8>> {
+        let bits = Self::DATA.map(|pin| self.pins.compute_input(pin).unwrap());
+
+        match self.format {
+            LogFormat::Char => bits
+                .iter()
+                .enumerate()
+                .try_fold(0, |byte, (bit, value)| match value {
+                    Tristate::State(value) => Some(byte | (u8::from(*value) << bit)),
+                    Tristate::Undefined => None,
+                })
+                .map(|byte| vec![byte]),
+            LogFormat::Bits => Some(format!("{}\n", bits.iter().rev().map(ToString::to_string).collect::<String>()).into_bytes()),
+        }
+    }
+}
+
+impl Component for LoggerComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |_| {
+            let clock = self.pins.compute_input(Self::CLOCK).unwrap();
+            let rising = self.previous_clock.replace(clock) == false.into() && clock == true.into();
+
+            if rising && self.pins.compute_input(Self::INHIBIT).unwrap() == false.into() {
+                let mut sink = self.sink.borrow_mut();
+
+                if let (Some(writer), Some(entry)) = (sink.as_mut(), self.entry()) {
+                    // A write error cannot be reported from a tick: the sink is detached instead.
+                    if writer.write_all(&entry).and_then(|_| writer.flush()).is_err() {
+                        *sink = None;
+                    }
+                }
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_file_device(&self) -> Option<&dyn FileDevice> {
+        Some(self)
+    }
+}
+
+impl FileDevice for LoggerComponent {
+    /// Opens `path` in append mode, where the following entries are written.
+    fn attach(&self, path: &Path) -> io::Result<()> {
+        *self.sink.borrow_mut() = Some(Box::new(Self::open(path)?));
+        Ok(())
+    }
+}
+
+impl Default for LoggerComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(LogFormat::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, io, rc::Rc};
+
+    use super::{LogFormat, LoggerComponent};
+    use crate::circuit::Circuit;
+    use crate::components::registry::ComponentRegistry;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input clock
+        input inhibit
+        input d0
+        input d1
+        input d2
+        input d3
+        input d4
+        input d5
+        input d6
+        input d7
+        logger log
+        .links:
+        clock:1 log:9
+        inhibit:1 log:10
+        d0:1 log:1
+        d1:1 log:2
+        d2:1 log:3
+        d3:1 log:4
+        d4:1 log:5
+        d5:1 log:6
+        d6:1 log:7
+        d7:1 log:8
+    ";
+
+    /// Sink whose content stays readable once it is given to the logger.
+    #[derive(Default, Clone)]
+    struct SharedSink(Rc<RefCell<Vec<u8>>>);
+
+    impl io::Write for SharedSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn build_circuit(format: LogFormat, sink: &SharedSink) -> Circuit {
+        let mut registry = ComponentRegistry::with_default_components();
+        let sink = sink.clone();
+
+        registry.register("logger", move || Box::new(LoggerComponent::with_sink(format, Box::new(sink.clone()))));
+        let circuit = Circuit::parse_with_factory(CONTENT, registry).unwrap();
+        circuit.set_value("inhibit", "0").unwrap();
+        circuit
+    }
+
+    /// Latches each byte on a clock cycle.
+    fn run(circuit: &mut Circuit, bytes: &[u64]) {
+        for &byte in bytes {
+            circuit.set_bus("d", byte).unwrap();
+            for clock in ["0", "1"] {
+                circuit.set_value("clock", clock).unwrap();
+                circuit.simulate();
+            }
+        }
+    }
+
+    #[test]
+    fn test_char_format() {
+        let sink = SharedSink::default();
+        let mut circuit = build_circuit(LogFormat::Char, &sink);
+
+        run(&mut circuit, &[b'h' as u64, b'i' as u64]);
+        circuit.set_value("inhibit", "1").unwrap();
+        run(&mut circuit, &[b'!' as u64]);
+        circuit.set_value("inhibit", "0").unwrap();
+        run(&mut circuit, &[b'\n' as u64]);
+        circuit.set_value("d7", "U").unwrap();
+        circuit.set_value("clock", "0").unwrap();
+        circuit.simulate();
+        circuit.set_value("clock", "1").unwrap();
+        circuit.simulate();
+        // Held high: no other rising edge.
+        circuit.simulate();
+
+        assert_eq!(sink.0.borrow().as_slice(), b"hi\n");
+    }
+
+    #[test]
+    fn test_bits_format() {
+        let sink = SharedSink::default();
+        let mut circuit = build_circuit(LogFormat::Bits, &sink);
+
+        run(&mut circuit, &[0b1010_0101]);
+        circuit.set_value("d0", "U").unwrap();
+        circuit.set_value("clock", "0").unwrap();
+        circuit.simulate();
+        circuit.set_value("clock", "1").unwrap();
+        circuit.simulate();
+
+        assert_eq!(String::from_utf8(sink.0.borrow().clone()).unwrap(), "10100101\n1010010U\n");
+    }
+
+    #[test]
+    fn test_file_parameter() {
+        let path = std::env::temp_dir().join("nts_logger.log");
+        std::fs::write(&path, "> ").unwrap();
+        // Logs 'a' (0x61) on each rising edge.
+        let content = "
+            .chipsets:
+            input clock
+            false low
+            true high
+            logger log file={path}
+            .links:
+            clock:1 log:9
+            low:1 log:10
+            high:1 log:1
+            low:1 log:2
+            low:1 log:3
+            low:1 log:4
+            low:1 log:5
+            high:1 log:6
+            high:1 log:7
+            low:1 log:8
+        "
+        .replace("{path}", path.to_str().unwrap());
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        for clock in ["0", "1", "0", "1"] {
+            circuit.set_value("clock", clock).unwrap();
+            circuit.simulate();
+        }
+
+        // Appended to the existing content.
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "> aa");
+        assert!(content.replace(path.to_str().unwrap(), "/nonexistent/nts.log").parse::<Circuit>().is_err());
+        assert!(content.replace("logger log file", "logger log format=hex file").parse::<Circuit>().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
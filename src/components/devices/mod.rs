@@ -0,0 +1,9 @@
+pub mod assert;
+pub mod file_input;
+pub mod file_output;
+pub mod gpio;
+pub mod keyboard;
+pub mod logger;
+pub mod player;
+pub mod seven_segment;
+pub mod terminal;
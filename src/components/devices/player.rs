@@ -0,0 +1,239 @@
+use std::{collections::HashMap, fmt, rc::Weak, str::FromStr};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingError {
+    pub line: usize,
+    pub kind: RecordingErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordingErrorKind {
+    MissingHeader,
+    DuplicateColumn { name: String },
+    FieldCount { expected: usize, got: usize },
+    InvalidValue { column: String, value: String },
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for RecordingErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "Missing header row"),
+            Self::DuplicateColumn { name } => write!(f, "Duplicate column \"{name}\""),
+            Self::FieldCount { expected, got } => write!(f, "Expected {expected} fields, got {got}"),
+            Self::InvalidValue { column, value } => write!(f, "Invalid value \"{value}\" for \"{column}\""),
+        }
+    }
+}
+
+/// Table of signal values, one row per tick.
+///
+/// The header row names the signals and each following row gives their values, `0`, `1` or `U`,
+/// an empty cell meaning `U`. Blank lines and lines starting with `#` are skipped.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Recording {
+    pub names: Vec<String>,
+    pub rows: Vec<Vec<Tristate>>,
+}
+
+impl FromStr for Recording {
+    type Err = RecordingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'));
+
+        let (header_line, header) = lines.next().ok_or(RecordingError { line: 1, kind: RecordingErrorKind::MissingHeader })?;
+        let mut names: Vec<String> = Vec::new();
+        for name in header.split(',').map(str::trim) {
+            if names.iter().any(|other| other == name) {
+                return Err(RecordingError {
+                    line: header_line,
+                    kind: RecordingErrorKind::DuplicateColumn { name: name.to_owned() },
+                });
+            }
+            names.push(name.to_owned());
+        }
+
+        let rows = lines
+            .map(|(line, content)| {
+                let fields: Vec<&str> = content.split(',').map(str::trim).collect();
+                let error = |kind: RecordingErrorKind| RecordingError { line, kind };
+
+                if fields.len() != names.len() {
+                    return Err(error(RecordingErrorKind::FieldCount { expected: names.len(), got: fields.len() }));
+                }
+                names
+                    .iter()
+                    .zip(fields)
+                    .map(|(column, value)| match value {
+                        "" => Ok(Tristate::Undefined),
+                        value => value.parse().map_err(|_| {
+                            error(RecordingErrorKind::InvalidValue { column: column.clone(), value: value.to_owned() })
+                        }),
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { names, rows })
+    }
+}
+
+/// Drives one output per signal of its recording, in column order, with the values of its n-th row at tick n.
+/// Once the rows are exhausted, the outputs keep the values of the last one.
+///
+/// The recording is read from the file given by the `file` parameter; without one, the player has no pins.
+pub struct PlayerComponent {
+    pins: PinContainer,
+    rows: Vec<Vec<Tristate>>,
+}
+
+impl PlayerComponent {
+    /// `file`: path of the recording.
+    pub const PARAMETERS: &'static [&'static str] = &["file"];
+
+    pub fn new(recording: Recording) -> Self {
+        let nb_pins = recording.names.len();
+
+        Self { pins: PinContainer::new(nb_pins, Self::build_pins_spec(nb_pins)), rows: recording.rows }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(Self::recording_from_params(params)?))
+    }
+
+    /// Recording read from the file given by `file`, empty if it is not set.
+    pub fn recording_from_params(params: &Params) -> Result<Recording, CreateError> {
+        let Some(content) = params.read_file("file")? else {
+            return Ok(Recording::default());
+        };
+        let path = params.path("file").unwrap();
+
+        String::from_utf8(content)
+            .map_err(|error| error.to_string())
+            .and_then(|content| content.parse().map_err(|error: RecordingError| error.to_string()))
+            .map_err(|message| CreateError::File { path, message })
+    }
+
+    pub fn info(name: &str, signals: &[String]) -> ComponentInfo {
+        let mut info =
+            ComponentInfo::new(name, "Drives its outputs with the rows of a recording file, one per tick", signals.len())
+                .with_sequential(true);
+
+        for (pin, signal) in (1..).zip(signals) {
+            info = info.with_pin(pin, signal, PinDirection::Output);
+        }
+        info
+    }
+
+    #[inline]
+    fn build_pins_spec(nb_pins: usize) -> HashMap<PinNumber, PinSpecification> {
+        (1..=nb_pins).map(|pin| (pin, PinSpecification::UnidirectionalOutput())).collect()
+    }
+}
+
+impl Component for PlayerComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            // Tick 0 is the initial state, before the first row.
+            let Some(row) = tick.get().checked_sub(1).and_then(|index| self.rows.get(index as usize)) else {
+                return;
+            };
+
+            for (pin, &value) in (1..).zip(row) {
+                outputs.get(&pin).unwrap().set(value);
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+impl Default for PlayerComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Recording::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Recording, RecordingError, RecordingErrorKind};
+    use crate::circuit::Circuit;
+    use crate::components::tristate::Tristate;
+
+    #[test]
+    fn test_parse() {
+        let recording: Recording = "a, b\n# comment\n0, 1\n\n1,\n".parse().unwrap();
+
+        assert_eq!(recording.names, vec!["a", "b"]);
+        assert_eq!(
+            recording.rows,
+            vec![vec![Tristate::State(false), Tristate::State(true)], vec![Tristate::State(true), Tristate::Undefined]]
+        );
+        assert_eq!("".parse::<Recording>(), Err(RecordingError { line: 1, kind: RecordingErrorKind::MissingHeader }));
+        assert_eq!("a, a".parse::<Recording>().unwrap_err().to_string(), "line 1: Duplicate column \"a\"");
+        assert_eq!("a, b\n0\n".parse::<Recording>().unwrap_err().to_string(), "line 2: Expected 2 fields, got 1");
+        assert_eq!("a\n\n2\n".parse::<Recording>().unwrap_err().to_string(), "line 3: Invalid value \"2\" for \"a\"");
+    }
+
+    #[test]
+    fn test_replay() {
+        let path = std::env::temp_dir().join("nts_player.csv");
+        std::fs::write(&path, "a, b\n0, 0\n0, 1\n1, 0\n1, 1\n").unwrap();
+        let content = "
+            .chipsets:
+            player stimulus file={path}
+            4081 gate
+            output out
+            .links:
+            stimulus:1 gate:1
+            stimulus:2 gate:2
+            gate:3 out:1
+        "
+        .replace("{path}", path.to_str().unwrap());
+        let mut circuit: Circuit = content.parse().unwrap();
+
+        let outputs: String = (0..6)
+            .map(|_| {
+                circuit.simulate();
+                circuit.get_output("out").unwrap()
+            })
+            .collect();
+
+        // The last row is held.
+        assert_eq!(outputs, "000111");
+        std::fs::write(&path, "a, b\n0, x\n").unwrap();
+        assert!(content.parse::<Circuit>().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
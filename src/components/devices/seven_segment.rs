@@ -0,0 +1,150 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        chips::display::DIGIT_SEGMENTS,
+        info::{ComponentInfo, PinDirection},
+        tristate::Tristate,
+        Component, InvalidPin, Output, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Seven-segment display, an output whose value is high while a segment is lit.
+///
+/// It is rendered as the digit it shows, the 6 and the 9 being recognised with or without their tail.
+/// Any other pattern, a blank display included, is rendered as the raw states of segments `a` to `g`.
+pub struct SevenSegmentComponent {
+    pins: PinContainer,
+    segments: Cell<[Tristate; 7]>,
+}
+
+impl SevenSegmentComponent {
+    /// Segments `a` to `g`.
+    pub const SEGMENTS: [PinNumber; 7] = [1, 2, 3, 4, 5, 6, 7];
+    const NAMES: [&'static str; 7] = ["a", "b", "c", "d", "e", "f", "g"];
+    /// Tailed 6 and 9, which the 4511 does not draw.
+    const TAILED_DIGITS: [(u8, char); 2] = [(0x7D, '6'), (0x6F, '9')];
+
+    pub fn new() -> Self {
+        Self { pins: PinContainer::new(7, Self::build_pins_spec()), segments: Default::default() }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        Self::SEGMENTS
+            .into_iter()
+            .zip(Self::NAMES)
+            .fold(ComponentInfo::new(name, "Seven-segment display", 7), |info, (pin, name)| {
+                info.with_pin(pin, name, PinDirection::Input)
+            })
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        Self::SEGMENTS.into_iter().map(|pin| (pin, PinSpecification::UnidirectionalInput())).collect()
+    }
+
+    /// Lit segments as a bit mask, `None` if a segment is undefined.
+    fn pattern(&self) -> Option<u8> {
+        self.segments.get().into_iter().enumerate().try_fold(0, |pattern, (bit, segment)| match segment {
+            Tristate::State(true) => Some(pattern | (1 << bit)),
+            Tristate::State(false) => Some(pattern),
+            Tristate::Undefined => None,
+        })
+    }
+}
+
+impl Component for SevenSegmentComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |_| {
+            self.segments.set(Self::SEGMENTS.map(|pin| self.pins.compute_input(pin).unwrap()));
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_output(&self) -> Option<&dyn Output> {
+        Some(self)
+    }
+}
+
+impl Output for SevenSegmentComponent {
+    fn get_value(&self) -> Tristate {
+        self.segments.get().into_iter().fold(Tristate::State(false), |lit, segment| lit | segment)
+    }
+
+    fn render(&self) -> String {
+        let pattern = self.pattern();
+        let digit = DIGIT_SEGMENTS
+            .iter()
+            .position(|&segments| Some(segments) == pattern)
+            .and_then(|digit| char::from_digit(digit as u32, 10))
+            .or_else(|| Self::TAILED_DIGITS.iter().find(|(segments, _)| Some(*segments) == pattern).map(|(_, digit)| *digit));
+
+        match digit {
+            Some(digit) => digit.to_string(),
+            None => self.segments.get().iter().map(Tristate::to_string).collect(),
+        }
+    }
+}
+
+impl Default for SevenSegmentComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        input c
+        false low
+        7seg display
+        .links:
+        a:1 display:1
+        b:1 display:2
+        c:1 display:3
+        low:1 display:4
+        low:1 display:5
+        low:1 display:6
+        low:1 display:7
+    ";
+
+    fn show(circuit: &mut Circuit, a: &str, b: &str, c: &str) -> (String, String) {
+        circuit.set_value("a", a).unwrap();
+        circuit.set_value("b", b).unwrap();
+        circuit.set_value("c", c).unwrap();
+        circuit.simulate();
+
+        let rendered = circuit.to_string().lines().last().unwrap().trim().to_owned();
+        (rendered, circuit.get_output("display").unwrap())
+    }
+
+    #[test]
+    fn test_render() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+
+        assert!(circuit.to_string().ends_with("output(s):\n  display: UUU0000\n"));
+        assert_eq!(show(&mut circuit, "1", "1", "1"), ("display: 7".to_owned(), "1".to_owned()));
+        assert_eq!(show(&mut circuit, "0", "1", "1"), ("display: 1".to_owned(), "1".to_owned()));
+        assert_eq!(show(&mut circuit, "1", "1", "0"), ("display: 1100000".to_owned(), "1".to_owned()));
+        assert_eq!(show(&mut circuit, "0", "0", "0"), ("display: 0000000".to_owned(), "0".to_owned()));
+        assert_eq!(show(&mut circuit, "0", "U", "0"), ("display: 0U00000".to_owned(), "U".to_owned()));
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Weak,
+};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Terminal, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Appends the byte on its data pins to its text at each rising edge of its clock.
+/// Bytes with undefined bits are ignored.
+pub struct TerminalComponent {
+    pins: PinContainer,
+    bytes: RefCell<Vec<u8>>,
+    previous_clock: Cell<Tristate>,
+}
+
+impl TerminalComponent {
+    /// Pins of bits 0 to 7 of the byte.
+    pub const DATA: [PinNumber; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+    pub const CLOCK: PinNumber = 9;
+
+    pub fn new() -> Self {
+        Self {
+            pins: PinContainer::new(9, Self::build_pins_spec()),
+            bytes: Default::default(),
+            previous_clock: Default::default(),
+        }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, "Terminal printing the bytes latched on its clock", 9).with_sequential(true);
+
+        for (bit, pin) in Self::DATA.into_iter().enumerate() {
+            info = info.with_pin(pin, &format!("d{bit}"), PinDirection::Input);
+        }
+        info.with_clock_pin(Self::CLOCK, "clock")
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        Self::DATA.into_iter().chain([Self::CLOCK]).map(|pin| (pin, PinSpecification::UnidirectionalInput())).collect()
+    }
+
+    fn read_byte(&self) -> Option<u8> {
+        Self::DATA.iter().enumerate().try_fold(0, |byte, (bit, &pin)| match self.pins.compute_input(pin).unwrap() {
+            Tristate::State(value) => Some(byte | (u8::from(value) << bit)),
+            Tristate::Undefined => None,
+        })
+    }
+}
+
+impl Component for TerminalComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |_| {
+            let clock = self.pins.compute_input(Self::CLOCK).unwrap();
+
+            if self.previous_clock.replace(clock) == false.into() && clock == true.into() {
+                if let Some(byte) = self.read_byte() {
+                    self.bytes.borrow_mut().push(byte);
+                }
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_terminal(&self) -> Option<&dyn Terminal> {
+        Some(self)
+    }
+}
+
+impl Terminal for TerminalComponent {
+    fn bytes(&self) -> Vec<u8> {
+        self.bytes.borrow().clone()
+    }
+}
+
+impl Default for TerminalComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
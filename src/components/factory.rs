@@ -1,45 +1,420 @@
+use super::alu::AluComponent;
+use super::bus::bus_input::BusInputComponent;
+use super::bus::bus_output::BusOutputComponent;
+use super::bus::merger::MergerComponent;
+use super::bus::splitter::SplitterComponent;
+use super::chips::arithmetic::{Component4008, Component4560};
+use super::chips::decoders::Component4555;
+use super::chips::display::Component4511;
+use super::chips::memory::{Component2716, Component4801};
+use super::chips::selectors::{ComponentDemux2, ComponentDemux4, ComponentMux2, ComponentMux4};
+use super::chips::sequential::{
+    Component4006, Component40103, Component4013, Component4014, Component4015, Component4027, Component4029, Component4043,
+    Component4044, Component4060, Component4510, Component4516,
+};
+use super::chips::ttl::{
+    Component74138, Component74157, Component74193, Component74245, Component74283, Component7474, Component7485,
+};
 use super::composite::parallel_gates::{
-    Component4001, Component4011, Component4030, Component4069, Component4071, Component4081,
+    Component4001, Component4002, Component4011, Component4012, Component4030, Component4049, Component4050, Component4068,
+    Component4069, Component4071, Component4072, Component4077, Component4078, Component4081, Component4082, Component7400,
+    Component7404, Component7408, Component7432, Component7486,
 };
+use super::devices::assert::AssertComponent;
+use super::devices::file_input::FileInputComponent;
+use super::devices::file_output::FileOutputComponent;
+use super::devices::gpio::GpioComponent;
+use super::devices::keyboard::KeyboardComponent;
+use super::devices::logger::LoggerComponent;
+use super::devices::player::PlayerComponent;
+use super::devices::seven_segment::SevenSegmentComponent;
+use super::devices::terminal::TerminalComponent;
+use super::gates::n_inputs::{VariadicGate, VariadicOperation};
+use super::image::MemoryImage;
+use super::lfsr::{LfsrComponent, LfsrDefinition};
+use super::lut::{LutComponent, LutDefinition};
+use super::params::{CreateError, Params};
+use super::single_pin::button_component::ButtonComponent;
 use super::single_pin::clock_component::ClockComponent;
 use super::single_pin::const_component::{FalseComponent, TrueComponent};
 use super::single_pin::input_component::InputComponent;
 use super::single_pin::output_component::OutputComponent;
-use super::{types::ComponentType, Component};
+use super::timers::divider::DividerComponent;
+use super::timers::monostable::Component4538;
+use super::timers::multivibrator::Component4047;
+use super::timers::oneshot::OneShotComponent;
+use super::timers::timer555::{Timer555Component, Timer555Mode};
+use super::{bus, info::ComponentInfo, types::ComponentType, Component};
 
 pub trait ComponentFactory {
     type Type;
 
-    fn create_component(&self, component_type: Self::Type) -> Box<dyn Component>;
+    fn parse_type(&self, name: &str) -> Option<Self::Type>;
+
+    /// Creates a component configured by the parameters of its chipset line.
+    fn create_component(&self, component_type: Self::Type, params: &Params) -> Result<Box<dyn Component>, CreateError>;
+
+    fn component_info(&self, _component_type: &Self::Type) -> Option<ComponentInfo> {
+        None
+    }
+
+    /// Info of a component created with `params`, for types whose pinout depends on them.
+    fn component_info_with_params(&self, component_type: &Self::Type, _params: &Params) -> Option<ComponentInfo> {
+        self.component_info(component_type)
+    }
+}
+
+impl<F: ComponentFactory + ?Sized> ComponentFactory for &F {
+    type Type = F::Type;
+
+    fn parse_type(&self, name: &str) -> Option<Self::Type> {
+        (**self).parse_type(name)
+    }
+
+    fn create_component(&self, component_type: Self::Type, params: &Params) -> Result<Box<dyn Component>, CreateError> {
+        (**self).create_component(component_type, params)
+    }
+
+    fn component_info(&self, component_type: &Self::Type) -> Option<ComponentInfo> {
+        (**self).component_info(component_type)
+    }
+
+    fn component_info_with_params(&self, component_type: &Self::Type, params: &Params) -> Option<ComponentInfo> {
+        (**self).component_info_with_params(component_type, params)
+    }
 }
 
 pub struct DefaultComponentFactory;
 
+impl DefaultComponentFactory {
+    /// Parameters accepted by each component type.
+    pub fn parameters(component_type: ComponentType) -> &'static [&'static str] {
+        match component_type {
+            ComponentType::BusInput | ComponentType::BusOutput | ComponentType::Splitter | ComponentType::Merger => {
+                bus::PARAMETERS
+            }
+            ComponentType::Clock => ClockComponent::PARAMETERS,
+            ComponentType::Logger => LoggerComponent::PARAMETERS,
+            ComponentType::Player => PlayerComponent::PARAMETERS,
+            ComponentType::Assert => AssertComponent::PARAMETERS,
+            ComponentType::OneShot => OneShotComponent::PARAMETERS,
+            ComponentType::Timer555 => Timer555Mode::PARAMETERS,
+            ComponentType::Divider => DividerComponent::PARAMETERS,
+            ComponentType::Lfsr => LfsrDefinition::PARAMETERS,
+            ComponentType::And | ComponentType::Or | ComponentType::Xor => VariadicGate::PARAMETERS,
+            ComponentType::Alu => AluComponent::PARAMETERS,
+            ComponentType::Lut => LutDefinition::PARAMETERS,
+            ComponentType::C2716 | ComponentType::C4801 => MemoryImage::PARAMETERS,
+            ComponentType::C4047 => Component4047::PARAMETERS,
+            ComponentType::C4538 => Component4538::PARAMETERS,
+            _ => &[],
+        }
+    }
+}
+
 impl ComponentFactory for DefaultComponentFactory {
     type Type = ComponentType;
 
-    fn create_component(&self, component_type: ComponentType) -> Box<dyn Component> {
-        match component_type {
+    fn parse_type(&self, name: &str) -> Option<ComponentType> {
+        name.parse().ok()
+    }
+
+    fn create_component(&self, component_type: ComponentType, params: &Params) -> Result<Box<dyn Component>, CreateError> {
+        params.check_keys(Self::parameters(component_type))?;
+
+        Ok(match component_type {
             ComponentType::Input => Box::new(InputComponent::new()),
             ComponentType::Output => Box::new(OutputComponent::new()),
-            ComponentType::Clock => Box::new(ClockComponent::new()),
+            ComponentType::Clock => Box::new(ClockComponent::from_params(params)?),
+            ComponentType::Button => Box::new(ButtonComponent::new()),
             ComponentType::True => Box::new(TrueComponent::new()),
             ComponentType::False => Box::new(FalseComponent::new()),
+            ComponentType::BusInput => Box::new(BusInputComponent::from_params(params)?),
+            ComponentType::BusOutput => Box::new(BusOutputComponent::from_params(params)?),
+            ComponentType::Splitter => Box::new(SplitterComponent::from_params(params)?),
+            ComponentType::Merger => Box::new(MergerComponent::from_params(params)?),
+            ComponentType::Keyboard => Box::new(KeyboardComponent::new()),
+            ComponentType::Terminal => Box::new(TerminalComponent::new()),
+            ComponentType::FileIn => Box::new(FileInputComponent::new()),
+            ComponentType::FileOut => Box::new(FileOutputComponent::new()),
+            ComponentType::Gpio => Box::new(GpioComponent::new()),
+            ComponentType::SevenSegment => Box::new(SevenSegmentComponent::new()),
+            ComponentType::Logger => Box::new(LoggerComponent::from_params(params)?),
+            ComponentType::Player => Box::new(PlayerComponent::from_params(params)?),
+            ComponentType::Assert => Box::new(AssertComponent::from_params(params)?),
+            ComponentType::OneShot => Box::new(OneShotComponent::from_params(params)?),
+            ComponentType::Timer555 => Box::new(Timer555Component::new(Timer555Mode::from_params(params)?)),
+            ComponentType::Divider => Box::new(DividerComponent::from_params(params)?),
+            ComponentType::Lfsr => Box::new(LfsrComponent::new(LfsrDefinition::from_params(params)?)),
+            ComponentType::And => Box::new(VariadicGate::from_params(VariadicOperation::And, params)?),
+            ComponentType::Or => Box::new(VariadicGate::from_params(VariadicOperation::Or, params)?),
+            ComponentType::Xor => Box::new(VariadicGate::from_params(VariadicOperation::Xor, params)?),
+            ComponentType::Mux2 => Box::new(ComponentMux2::new()),
+            ComponentType::Mux4 => Box::new(ComponentMux4::new()),
+            ComponentType::Demux2 => Box::new(ComponentDemux2::new()),
+            ComponentType::Demux4 => Box::new(ComponentDemux4::new()),
+            ComponentType::Alu => Box::new(AluComponent::from_params(params)?),
+            ComponentType::Lut => Box::new(LutComponent::new(LutDefinition::from_params(params)?)),
+            ComponentType::C2716 => {
+                let rom = Component2716::new();
+
+                MemoryImage::load_from_params(&rom, params)?;
+                Box::new(rom)
+            }
             ComponentType::C4001 => Box::new(Component4001::new()),
+            ComponentType::C4002 => Box::new(Component4002::new()),
+            ComponentType::C4006 => Box::new(Component4006::new()),
+            ComponentType::C4008 => Box::new(Component4008::new()),
             ComponentType::C4011 => Box::new(Component4011::new()),
+            ComponentType::C4012 => Box::new(Component4012::new()),
+            ComponentType::C4013 => Box::new(Component4013::new()),
+            ComponentType::C4014 => Box::new(Component4014::new()),
+            ComponentType::C4015 => Box::new(Component4015::new()),
+            ComponentType::C4027 => Box::new(Component4027::new()),
+            ComponentType::C4029 => Box::new(Component4029::new()),
             ComponentType::C4030 => Box::new(Component4030::new()),
+            ComponentType::C4043 => Box::new(Component4043::new()),
+            ComponentType::C4044 => Box::new(Component4044::new()),
+            ComponentType::C4047 => Box::new(Component4047::from_params(params)?),
+            ComponentType::C4049 => Box::new(Component4049::new()),
+            ComponentType::C4050 => Box::new(Component4050::new()),
+            ComponentType::C4060 => Box::new(Component4060::new()),
+            ComponentType::C4068 => Box::new(Component4068::new()),
             ComponentType::C4069 => Box::new(Component4069::new()),
             ComponentType::C4071 => Box::new(Component4071::new()),
+            ComponentType::C4072 => Box::new(Component4072::new()),
+            ComponentType::C4077 => Box::new(Component4077::new()),
+            ComponentType::C4078 => Box::new(Component4078::new()),
             ComponentType::C4081 => Box::new(Component4081::new()),
+            ComponentType::C4082 => Box::new(Component4082::new()),
+            ComponentType::C4510 => Box::new(Component4510::new()),
+            ComponentType::C4511 => Box::new(Component4511::new()),
+            ComponentType::C4516 => Box::new(Component4516::new()),
+            ComponentType::C4538 => Box::new(Component4538::from_params(params)?),
+            ComponentType::C4555 => Box::new(Component4555::new()),
+            ComponentType::C4560 => Box::new(Component4560::new()),
+            ComponentType::C4801 => {
+                let ram = Component4801::new();
+
+                MemoryImage::load_from_params(&ram, params)?;
+                Box::new(ram)
+            }
+            ComponentType::C7400 => Box::new(Component7400::new()),
+            ComponentType::C7404 => Box::new(Component7404::new()),
+            ComponentType::C7408 => Box::new(Component7408::new()),
+            ComponentType::C7432 => Box::new(Component7432::new()),
+            ComponentType::C7485 => Box::new(Component7485::new()),
+            ComponentType::C7486 => Box::new(Component7486::new()),
+            ComponentType::C40103 => Box::new(Component40103::new()),
+            ComponentType::C7474 => Box::new(Component7474::new()),
+            ComponentType::C74138 => Box::new(Component74138::new()),
+            ComponentType::C74157 => Box::new(Component74157::new()),
+            ComponentType::C74193 => Box::new(Component74193::new()),
+            ComponentType::C74245 => Box::new(Component74245::new()),
+            ComponentType::C74283 => Box::new(Component74283::new()),
+        })
+    }
+
+    fn component_info(&self, component_type: &ComponentType) -> Option<ComponentInfo> {
+        let name = component_type.to_string();
+
+        Some(match component_type {
+            ComponentType::Input => InputComponent::info(&name),
+            ComponentType::Output => OutputComponent::info(&name),
+            ComponentType::Clock => ClockComponent::info(&name),
+            ComponentType::Button => ButtonComponent::info(&name),
+            ComponentType::True => TrueComponent::info(&name),
+            ComponentType::False => FalseComponent::info(&name),
+            ComponentType::BusInput => BusInputComponent::info(&name, bus::DEFAULT_WIDTH),
+            ComponentType::BusOutput => BusOutputComponent::info(&name, bus::DEFAULT_WIDTH),
+            ComponentType::Splitter => SplitterComponent::info(&name, bus::DEFAULT_WIDTH),
+            ComponentType::Merger => MergerComponent::info(&name, bus::DEFAULT_WIDTH),
+            ComponentType::Keyboard => KeyboardComponent::info(&name),
+            ComponentType::Terminal => TerminalComponent::info(&name),
+            ComponentType::FileIn => FileInputComponent::info(&name),
+            ComponentType::FileOut => FileOutputComponent::info(&name),
+            ComponentType::Gpio => GpioComponent::info(&name),
+            ComponentType::SevenSegment => SevenSegmentComponent::info(&name),
+            ComponentType::Logger => LoggerComponent::info(&name),
+            ComponentType::Player => PlayerComponent::info(&name, &[]),
+            ComponentType::Assert => AssertComponent::info(&name),
+            ComponentType::OneShot => OneShotComponent::info(&name),
+            ComponentType::Timer555 => Timer555Component::info(&name),
+            ComponentType::Divider => DividerComponent::info(&name),
+            ComponentType::Lfsr => LfsrDefinition::default().info(&name),
+            ComponentType::And => VariadicGate::info(&name, VariadicOperation::And, VariadicGate::DEFAULT_INPUTS),
+            ComponentType::Or => VariadicGate::info(&name, VariadicOperation::Or, VariadicGate::DEFAULT_INPUTS),
+            ComponentType::Xor => VariadicGate::info(&name, VariadicOperation::Xor, VariadicGate::DEFAULT_INPUTS),
+            ComponentType::Mux2 => ComponentMux2::info(&name),
+            ComponentType::Mux4 => ComponentMux4::info(&name),
+            ComponentType::Demux2 => ComponentDemux2::info(&name),
+            ComponentType::Demux4 => ComponentDemux4::info(&name),
+            ComponentType::Alu => AluComponent::info(&name, AluComponent::DEFAULT_WIDTH),
+            ComponentType::Lut => LutDefinition::default().info(&name),
+            ComponentType::C2716 => Component2716::info(&name),
+            ComponentType::C4001 => Component4001::info(&name),
+            ComponentType::C4002 => Component4002::info(&name),
+            ComponentType::C4006 => Component4006::info(&name),
+            ComponentType::C4008 => Component4008::info(&name),
+            ComponentType::C4011 => Component4011::info(&name),
+            ComponentType::C4012 => Component4012::info(&name),
+            ComponentType::C4013 => Component4013::info(&name),
+            ComponentType::C4014 => Component4014::info(&name),
+            ComponentType::C4015 => Component4015::info(&name),
+            ComponentType::C4027 => Component4027::info(&name),
+            ComponentType::C4029 => Component4029::info(&name),
+            ComponentType::C4030 => Component4030::info(&name),
+            ComponentType::C4043 => Component4043::info(&name),
+            ComponentType::C4044 => Component4044::info(&name),
+            ComponentType::C4047 => Component4047::info(&name),
+            ComponentType::C4049 => Component4049::info(&name),
+            ComponentType::C4050 => Component4050::info(&name),
+            ComponentType::C4060 => Component4060::info(&name),
+            ComponentType::C4068 => Component4068::info(&name),
+            ComponentType::C4069 => Component4069::info(&name),
+            ComponentType::C4071 => Component4071::info(&name),
+            ComponentType::C4072 => Component4072::info(&name),
+            ComponentType::C4077 => Component4077::info(&name),
+            ComponentType::C4078 => Component4078::info(&name),
+            ComponentType::C4081 => Component4081::info(&name),
+            ComponentType::C4082 => Component4082::info(&name),
+            ComponentType::C4510 => Component4510::info(&name),
+            ComponentType::C4511 => Component4511::info(&name),
+            ComponentType::C4516 => Component4516::info(&name),
+            ComponentType::C4538 => Component4538::info(&name),
+            ComponentType::C4555 => Component4555::info(&name),
+            ComponentType::C4560 => Component4560::info(&name),
+            ComponentType::C4801 => Component4801::info(&name),
+            ComponentType::C7400 => Component7400::info(&name),
+            ComponentType::C7404 => Component7404::info(&name),
+            ComponentType::C7408 => Component7408::info(&name),
+            ComponentType::C7432 => Component7432::info(&name),
+            ComponentType::C7485 => Component7485::info(&name),
+            ComponentType::C7486 => Component7486::info(&name),
+            ComponentType::C40103 => Component40103::info(&name),
+            ComponentType::C7474 => Component7474::info(&name),
+            ComponentType::C74138 => Component74138::info(&name),
+            ComponentType::C74157 => Component74157::info(&name),
+            ComponentType::C74193 => Component74193::info(&name),
+            ComponentType::C74245 => Component74245::info(&name),
+            ComponentType::C74283 => Component74283::info(&name),
+        })
+    }
+
+    fn component_info_with_params(&self, component_type: &ComponentType, params: &Params) -> Option<ComponentInfo> {
+        match component_type {
+            ComponentType::Lfsr => {
+                Some(LfsrDefinition::from_params(params).unwrap_or_default().info(&component_type.to_string()))
+            }
+            ComponentType::BusInput => {
+                let width = bus::width_from_params(params).unwrap_or(bus::DEFAULT_WIDTH);
+                Some(BusInputComponent::info(&component_type.to_string(), width))
+            }
+            ComponentType::BusOutput => {
+                let width = bus::width_from_params(params).unwrap_or(bus::DEFAULT_WIDTH);
+                Some(BusOutputComponent::info(&component_type.to_string(), width))
+            }
+            ComponentType::Splitter => {
+                let width = bus::width_from_params(params).unwrap_or(bus::DEFAULT_WIDTH);
+                Some(SplitterComponent::info(&component_type.to_string(), width))
+            }
+            ComponentType::Merger => {
+                let width = bus::width_from_params(params).unwrap_or(bus::DEFAULT_WIDTH);
+                Some(MergerComponent::info(&component_type.to_string(), width))
+            }
+            ComponentType::And | ComponentType::Or | ComponentType::Xor => {
+                let operation = match component_type {
+                    ComponentType::And => VariadicOperation::And,
+                    ComponentType::Or => VariadicOperation::Or,
+                    _ => VariadicOperation::Xor,
+                };
+                let nb_inputs = VariadicGate::inputs_from_params(params).unwrap_or(VariadicGate::DEFAULT_INPUTS);
+                Some(VariadicGate::info(&component_type.to_string(), operation, nb_inputs))
+            }
+            ComponentType::Alu => {
+                let width = AluComponent::width_from_params(params).unwrap_or(AluComponent::DEFAULT_WIDTH);
+                Some(AluComponent::info(&component_type.to_string(), width))
+            }
+            ComponentType::Lut => Some(LutDefinition::from_params(params).unwrap_or_default().info(&component_type.to_string())),
+            ComponentType::Player => {
+                let recording = PlayerComponent::recording_from_params(params).unwrap_or_default();
+                Some(PlayerComponent::info(&component_type.to_string(), &recording.names))
+            }
+            _ => self.component_info(component_type),
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::{ComponentFactory, CreateError, DefaultComponentFactory, Params};
+    use crate::components::{info::PinDirection, types::ComponentType};
+
+    #[test]
+    fn test_info_available_for_all_types() {
+        for component_type in ComponentType::ALL {
+            let info = DefaultComponentFactory.component_info(component_type).unwrap();
+
+            assert_eq!(info.name, component_type.to_string());
+            assert_eq!(info.pins().len(), info.nb_pins);
+        }
+    }
+
+    #[test]
+    fn test_info_pins_match_component() {
+        for component_type in ComponentType::ALL {
+            let info = DefaultComponentFactory.component_info(component_type).unwrap();
+            let component = DefaultComponentFactory.create_component(*component_type, &Params::new()).unwrap();
+
+            for pin in info.pins() {
+                assert!(component.compute(pin.number).is_ok(), "{component_type}: pin {}", pin.number);
+            }
+            assert!(component.compute(info.nb_pins + 1).is_err());
+        }
+    }
+
+    #[test]
+    fn test_info_7400_pinout() {
+        let info = DefaultComponentFactory.component_info(&ComponentType::C7400).unwrap();
+
+        assert_eq!(info.pins_with_direction(PinDirection::Output).map(|pin| pin.number).collect::<Vec<_>>(), vec![3, 6, 8, 11]);
+        assert_eq!(info.pin(7).unwrap().name, "GND");
+        assert_eq!(info.dependencies(8), vec![9, 10]);
+    }
+
+    #[test]
+    fn test_info_4001_pinout() {
+        let info = DefaultComponentFactory.component_info(&ComponentType::C4001).unwrap();
+
+        assert_eq!(info.nb_pins, 14);
+        assert!(!info.sequential);
+        assert_eq!(info.pins_with_direction(PinDirection::Input).count(), 8);
+        assert_eq!(info.pins_with_direction(PinDirection::Output).map(|pin| pin.number).collect::<Vec<_>>(), vec![3, 4, 10, 11]);
+    }
+
+    #[test]
+    fn test_create_with_params() {
+        let params = Params::new().with("width", "16");
+        let info = DefaultComponentFactory.component_info_with_params(&ComponentType::Lfsr, &params).unwrap();
+
+        assert_eq!(info.nb_pins, 17);
+        assert!(DefaultComponentFactory.create_component(ComponentType::Lfsr, &params).unwrap().compute(17).is_ok());
+        assert!(matches!(
+            DefaultComponentFactory.create_component(ComponentType::C4001, &params),
+            Err(CreateError::UnknownParameter { key }) if key == "width"
+        ));
+        assert!(matches!(
+            DefaultComponentFactory.create_component(ComponentType::C2716, &Params::new().with("file", "/nonexistent.bin")),
+            Err(CreateError::File { .. })
+        ));
+    }
+}
+
 #[cfg(test)]
 pub mod mock {
     use crate::components::dummy::DummyComponent;
 
-    use super::{Component, ComponentFactory};
+    use super::{Component, ComponentFactory, CreateError, Params};
 
     #[derive(Debug, PartialEq, Eq, Clone, Copy)]
     pub enum MockComponentType {
@@ -64,11 +439,21 @@ pub mod mock {
     impl ComponentFactory for MockComponentFactory {
         type Type = MockComponentType;
 
-        fn create_component(&self, component_type: MockComponentType) -> Box<dyn Component> {
-            match component_type {
+        fn parse_type(&self, name: &str) -> Option<MockComponentType> {
+            name.parse().ok()
+        }
+
+        fn create_component(
+            &self,
+            component_type: MockComponentType,
+            params: &Params,
+        ) -> Result<Box<dyn Component>, CreateError> {
+            params.check_keys(&[])?;
+
+            Ok(match component_type {
                 MockComponentType::OnePin => Box::new(DummyComponent::new(1)),
                 MockComponentType::TwelvePins => Box::new(DummyComponent::new(12)),
-            }
+            })
         }
     }
 }
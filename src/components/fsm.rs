@@ -0,0 +1,431 @@
+//! Finite state machines described in a small text format and simulated as sequential components.
+//!
+//! ```text
+//! inputs: go, stop
+//! outputs: busy
+//! initial: idle
+//!
+//! state idle: busy=0
+//! state run: busy=1
+//!
+//! idle -> run: go=1
+//! run -> idle: stop=1 go=0
+//! ```
+//!
+//! The generated component has the clock on pin 1, then one pin per input and one pin per output,
+//! in declaration order. Transitions are evaluated on the clock rising edge, the first one whose
+//! conditions all hold wins. Outputs only depend on the current state (Moore machine).
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    fmt,
+    rc::{Rc, Weak},
+    str::FromStr,
+};
+
+use crate::pin::{PinContainer, PinSpecification};
+
+use super::info::{ComponentInfo, PinDirection};
+use super::registry::ComponentRegistry;
+use super::{tristate::Tristate, Component, InvalidPin, PinNumber, Tick};
+
+#[derive(Debug, Clone)]
+pub struct ParseFsmError {
+    pub line: usize,
+    pub kind: FsmErrorKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum FsmErrorKind {
+    InvalidLine,
+    InvalidAssignment { value: String },
+    UnknownSignal { name: String },
+    UnknownState { name: String },
+    StateRedefinition { name: String },
+    SignalRedefinition { name: String },
+    MissingInitialState,
+}
+
+impl fmt::Display for ParseFsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for FsmErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLine => write!(f, "Expected a declaration, a state or a transition"),
+            Self::InvalidAssignment { value } => write!(f, "\"{value}\" must respect this form: name=0|1"),
+            Self::UnknownSignal { name } => write!(f, "Unknown signal \"{name}\""),
+            Self::UnknownState { name } => write!(f, "Unknown state \"{name}\""),
+            Self::StateRedefinition { name } => write!(f, "Redefinition of state \"{name}\""),
+            Self::SignalRedefinition { name } => write!(f, "Redefinition of signal \"{name}\""),
+            Self::MissingInitialState => write!(f, "No initial state declared"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Transition {
+    from: usize,
+    to: usize,
+    conditions: Vec<(usize, bool)>,
+}
+
+/// Parsed state machine, shared by every component instantiated from it.
+#[derive(Debug, Clone)]
+pub struct FsmDefinition {
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    states: Vec<String>,
+    state_outputs: Vec<Vec<bool>>,
+    transitions: Vec<Transition>,
+    initial: usize,
+}
+
+impl FsmDefinition {
+    pub const CLOCK: PinNumber = 1;
+
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    pub fn input_pin(&self, index: usize) -> PinNumber {
+        Self::CLOCK + 1 + index
+    }
+
+    pub fn output_pin(&self, index: usize) -> PinNumber {
+        Self::CLOCK + 1 + self.inputs.len() + index
+    }
+
+    pub fn nb_pins(&self) -> usize {
+        1 + self.inputs.len() + self.outputs.len()
+    }
+
+    pub fn info(&self, name: &str) -> ComponentInfo {
+        let description = format!("State machine with {} states", self.states.len());
+        let mut info =
+            ComponentInfo::new(name, &description, self.nb_pins()).with_sequential(true).with_clock_pin(Self::CLOCK, "clock");
+
+        for (idx, input) in self.inputs.iter().enumerate() {
+            info = info.with_pin(self.input_pin(idx), input, PinDirection::Input);
+        }
+        for (idx, output) in self.outputs.iter().enumerate() {
+            info = info.with_pin(self.output_pin(idx), output, PinDirection::Output);
+        }
+
+        info
+    }
+
+    /// Registers this machine as the component type `name`.
+    pub fn register(self, registry: &mut ComponentRegistry, name: &str) {
+        let definition = Rc::new(self);
+
+        registry.register_with_info(name, definition.info(name), move || Box::new(FsmComponent::new(definition.clone())));
+    }
+
+    fn next_state(&self, state: usize, inputs: &[Tristate]) -> usize {
+        self.transitions
+            .iter()
+            .filter(|transition| transition.from == state)
+            .find(|transition| transition.conditions.iter().all(|&(input, value)| inputs[input] == value.into()))
+            .map_or(state, |transition| transition.to)
+    }
+}
+
+impl FromStr for FsmDefinition {
+    type Err = ParseFsmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FsmParser::default().parse(s)
+    }
+}
+
+type Assignments<'a> = Vec<(&'a str, bool)>;
+
+#[derive(Default)]
+struct FsmParser<'a> {
+    inputs: Vec<&'a str>,
+    outputs: Vec<&'a str>,
+    states: Vec<(&'a str, Assignments<'a>, usize)>,
+    transitions: Vec<(&'a str, &'a str, Assignments<'a>, usize)>,
+    initial: Option<(&'a str, usize)>,
+}
+
+impl<'a> FsmParser<'a> {
+    fn parse(mut self, input: &'a str) -> Result<FsmDefinition, ParseFsmError> {
+        for (index, content) in input.lines().enumerate() {
+            let line = index + 1;
+            let content = if let Some(comment_idx) = content.find('#') { &content[..comment_idx] } else { content };
+            let content = content.trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            self.parse_line(line, content).map_err(|kind| ParseFsmError { line, kind })?;
+        }
+
+        self.compile()
+    }
+
+    fn parse_line(&mut self, line: usize, content: &'a str) -> Result<(), FsmErrorKind> {
+        let (head, tail) = content.split_once(':').ok_or(FsmErrorKind::InvalidLine)?;
+        let head = head.trim();
+
+        match head.split_whitespace().collect::<Vec<&str>>()[..] {
+            ["inputs"] => self.inputs.extend(Self::parse_names(tail)),
+            ["outputs"] => self.outputs.extend(Self::parse_names(tail)),
+            ["initial"] => match tail.split_whitespace().collect::<Vec<&str>>()[..] {
+                [state] => self.initial = Some((state, line)),
+                _ => return Err(FsmErrorKind::InvalidLine),
+            },
+            ["state", name] => self.states.push((name, Self::parse_assignments(tail)?, line)),
+            [from, "->", to] => self.transitions.push((from, to, Self::parse_assignments(tail)?, line)),
+            _ => return Err(FsmErrorKind::InvalidLine),
+        }
+
+        Ok(())
+    }
+
+    fn parse_names(content: &'a str) -> impl Iterator<Item = &'a str> {
+        content.split([',', ' ', '\t']).filter(|name| !name.is_empty())
+    }
+
+    fn parse_assignments(content: &'a str) -> Result<Assignments<'a>, FsmErrorKind> {
+        content
+            .split_whitespace()
+            .map(|assignment| match assignment.split_once('=') {
+                Some((name, "0")) if !name.is_empty() => Ok((name, false)),
+                Some((name, "1")) if !name.is_empty() => Ok((name, true)),
+                _ => Err(FsmErrorKind::InvalidAssignment { value: assignment.to_owned() }),
+            })
+            .collect()
+    }
+
+    fn compile(self) -> Result<FsmDefinition, ParseFsmError> {
+        let signal_index = |names: &[&str]| -> Result<HashMap<String, usize>, FsmErrorKind> {
+            let mut indexes: HashMap<String, usize> = HashMap::new();
+            for (idx, name) in names.iter().enumerate() {
+                if indexes.insert(name.to_string(), idx).is_some() {
+                    return Err(FsmErrorKind::SignalRedefinition { name: name.to_string() });
+                }
+            }
+            Ok(indexes)
+        };
+        let inputs = signal_index(&self.inputs).map_err(|kind| ParseFsmError { line: 0, kind })?;
+        let outputs = signal_index(&self.outputs).map_err(|kind| ParseFsmError { line: 0, kind })?;
+
+        let mut states: HashMap<&str, usize> = HashMap::new();
+        let mut state_outputs: Vec<Vec<bool>> = Vec::new();
+        for (name, assignments, line) in self.states.iter() {
+            if states.insert(name, states.len()).is_some() {
+                return Err(ParseFsmError { line: *line, kind: FsmErrorKind::StateRedefinition { name: name.to_string() } });
+            }
+
+            let mut values = vec![false; outputs.len()];
+            for (output, value) in assignments.iter() {
+                let idx = outputs.get(*output).ok_or_else(|| ParseFsmError {
+                    line: *line,
+                    kind: FsmErrorKind::UnknownSignal { name: output.to_string() },
+                })?;
+                values[*idx] = *value;
+            }
+            state_outputs.push(values);
+        }
+
+        let state_index = |name: &str, line: usize| -> Result<usize, ParseFsmError> {
+            states.get(name).copied().ok_or(ParseFsmError { line, kind: FsmErrorKind::UnknownState { name: name.to_owned() } })
+        };
+
+        let mut transitions: Vec<Transition> = Vec::new();
+        for (from, to, conditions, line) in self.transitions.iter() {
+            let conditions = conditions
+                .iter()
+                .map(|(input, value)| match inputs.get(*input) {
+                    Some(idx) => Ok((*idx, *value)),
+                    None => Err(ParseFsmError { line: *line, kind: FsmErrorKind::UnknownSignal { name: input.to_string() } }),
+                })
+                .collect::<Result<Vec<(usize, bool)>, ParseFsmError>>()?;
+
+            transitions.push(Transition { from: state_index(from, *line)?, to: state_index(to, *line)?, conditions });
+        }
+
+        let (initial, line) = self.initial.ok_or(ParseFsmError { line: 0, kind: FsmErrorKind::MissingInitialState })?;
+
+        Ok(FsmDefinition {
+            inputs: self.inputs.iter().map(|name| name.to_string()).collect(),
+            outputs: self.outputs.iter().map(|name| name.to_string()).collect(),
+            states: self.states.iter().map(|(name, _, _)| name.to_string()).collect(),
+            state_outputs,
+            transitions,
+            initial: state_index(initial, line)?,
+        })
+    }
+}
+
+pub struct FsmComponent {
+    pins: PinContainer,
+    definition: Rc<FsmDefinition>,
+    state: Cell<usize>,
+    previous_clock: Cell<Tristate>,
+}
+
+impl FsmComponent {
+    pub fn new(definition: Rc<FsmDefinition>) -> Self {
+        let mut spec: HashMap<PinNumber, PinSpecification> =
+            HashMap::from([(FsmDefinition::CLOCK, PinSpecification::UnidirectionalInput())]);
+
+        spec.extend((0..definition.inputs.len()).map(|idx| (definition.input_pin(idx), PinSpecification::UnidirectionalInput())));
+        spec.extend(
+            (0..definition.outputs.len()).map(|idx| (definition.output_pin(idx), PinSpecification::UnidirectionalOutput())),
+        );
+
+        Self {
+            pins: PinContainer::new(definition.nb_pins(), spec),
+            state: Cell::new(definition.initial),
+            definition,
+            previous_clock: Default::default(),
+        }
+    }
+
+    pub fn current_state(&self) -> &str {
+        &self.definition.states[self.state.get()]
+    }
+}
+
+impl Component for FsmComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let definition = &self.definition;
+            let clock = self.pins.compute_input(FsmDefinition::CLOCK).unwrap();
+
+            if self.previous_clock.replace(clock) == false.into() && clock == true.into() {
+                let inputs: Vec<Tristate> =
+                    (0..definition.inputs.len()).map(|idx| self.pins.compute_input(definition.input_pin(idx)).unwrap()).collect();
+
+                self.state.set(definition.next_state(self.state.get(), &inputs));
+            }
+
+            for (idx, &value) in definition.state_outputs[self.state.get()].iter().enumerate() {
+                outputs.get(&definition.output_pin(idx)).unwrap().set(value.into());
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FsmDefinition, FsmErrorKind, ParseFsmError};
+    use crate::circuit::Circuit;
+    use crate::components::registry::ComponentRegistry;
+
+    const TOGGLE: &str = "
+        # Two states toggled by 'go'
+        inputs: go
+        outputs: on, off
+        initial: idle
+
+        state idle: off=1
+        state run: on=1
+
+        idle -> run: go=1
+        run -> idle: go=1
+    ";
+
+    fn build_circuit() -> Circuit {
+        let mut registry = ComponentRegistry::default();
+        TOGGLE.parse::<FsmDefinition>().unwrap().register(&mut registry, "toggle");
+
+        let content = "
+            .chipsets:
+            clock clk
+            input go
+            toggle fsm
+            output on
+            output off
+            .links:
+            clk:1 fsm:1
+            go:1 fsm:2
+            fsm:3 on:1
+            fsm:4 off:1
+        ";
+
+        Circuit::parse_with_factory(content, registry).unwrap()
+    }
+
+    #[test]
+    fn test_parse_definition() {
+        let definition: FsmDefinition = TOGGLE.parse().unwrap();
+
+        assert_eq!(definition.inputs(), &["go"]);
+        assert_eq!(definition.outputs(), &["on", "off"]);
+        assert_eq!(definition.states(), &["idle", "run"]);
+        assert_eq!(definition.nb_pins(), 4);
+        assert_eq!(definition.info("toggle").pin(3).unwrap().name, "on");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        fn kind(content: &str) -> FsmErrorKind {
+            content.parse::<FsmDefinition>().unwrap_err().kind
+        }
+
+        assert!(matches!(kind("nonsense"), FsmErrorKind::InvalidLine));
+        assert!(matches!(kind("outputs: a\nstate s: a=2"), FsmErrorKind::InvalidAssignment { .. }));
+        assert!(matches!(kind("state s: a=1"), FsmErrorKind::UnknownSignal { .. }));
+        assert!(matches!(kind("state s:\ns -> t:"), FsmErrorKind::UnknownState { .. }));
+        assert!(matches!(kind("state s:\nstate s:"), FsmErrorKind::StateRedefinition { .. }));
+        assert!(matches!(kind("inputs: a, a\nstate s:\ninitial: s"), FsmErrorKind::SignalRedefinition { .. }));
+        assert!(matches!(kind("state s:"), FsmErrorKind::MissingInitialState));
+        assert!(matches!("\n\nfoo".parse::<FsmDefinition>(), Err(ParseFsmError { line: 3, .. })));
+    }
+
+    #[test]
+    fn test_transitions_on_rising_edge() {
+        let mut circuit = build_circuit();
+
+        circuit.set_value("clk", "0").unwrap();
+        circuit.set_value("go", "0").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("on").unwrap(), "0");
+        assert_eq!(circuit.get_output("off").unwrap(), "1");
+
+        // Rising edge, but 'go' is low: stays idle.
+        circuit.simulate();
+        assert_eq!(circuit.get_output("on").unwrap(), "0");
+
+        circuit.set_value("go", "1").unwrap();
+        circuit.simulate(); // falling edge
+        assert_eq!(circuit.get_output("on").unwrap(), "0");
+        circuit.simulate(); // rising edge
+        assert_eq!(circuit.get_output("on").unwrap(), "1");
+        assert_eq!(circuit.get_output("off").unwrap(), "0");
+
+        circuit.simulate(); // falling edge
+        circuit.simulate(); // rising edge
+        assert_eq!(circuit.get_output("on").unwrap(), "0");
+        assert_eq!(circuit.get_output("off").unwrap(), "1");
+    }
+}
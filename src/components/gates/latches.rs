@@ -0,0 +1,250 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Weak,
+};
+
+use crate::{
+    components::{
+        chips::{JKFlipFlop as JKState, RSLatch},
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+fn build_pins_spec(inputs: &[PinNumber], outputs: &[PinNumber]) -> HashMap<PinNumber, PinSpecification> {
+    let inputs = inputs.iter().map(|&pin| (pin, PinSpecification::UnidirectionalInput()));
+    let outputs = outputs.iter().map(|&pin| (pin, PinSpecification::UnidirectionalOutput()));
+
+    inputs.chain(outputs).collect()
+}
+
+macro_rules! latch_component_impl {
+    ($name:ident) => {
+        impl Component for $name {
+            fn set_link(
+                &self,
+                pin: PinNumber,
+                other_component: Weak<dyn Component>,
+                other_pin: PinNumber,
+            ) -> Result<(), InvalidPin> {
+                self.pins.set_link_to_external_component(pin, other_component, other_pin)
+            }
+
+            fn simulate(&self, tick: Tick) {
+                self.pins.simulate(tick, |outputs| {
+                    let (q, q_bar) = self.update();
+
+                    outputs.get(&Self::Q).unwrap().set(q);
+                    outputs.get(&Self::Q_BAR).unwrap().set(q_bar);
+                })
+            }
+
+            fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+                self.pins.compute_for_external(pin)
+            }
+
+            fn is_driving(&self, pin: PinNumber) -> bool {
+                self.pins.is_driving(pin).unwrap_or(false)
+            }
+        }
+
+        impl Default for $name {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+/// Level-sensitive D latch: Q follows D while ENABLE is high and holds its value while it is low.
+pub struct DLatch {
+    pins: PinContainer,
+    q: Cell<Tristate>,
+}
+
+impl DLatch {
+    pub const D: PinNumber = 1;
+    pub const ENABLE: PinNumber = 2;
+    pub const Q: PinNumber = 3;
+    pub const Q_BAR: PinNumber = 4;
+
+    pub fn new() -> Self {
+        Self {
+            pins: PinContainer::new(4, build_pins_spec(&[Self::D, Self::ENABLE], &[Self::Q, Self::Q_BAR])),
+            q: Default::default(),
+        }
+    }
+
+    fn update(&self) -> (Tristate, Tristate) {
+        let (data, enable) = (self.pins.compute_input(Self::D).unwrap(), self.pins.compute_input(Self::ENABLE).unwrap());
+        let q = match enable {
+            Tristate::State(true) => data,
+            Tristate::State(false) => self.q.get(),
+            // Known when loading D would not change Q.
+            Tristate::Undefined if data == self.q.get() => data,
+            Tristate::Undefined => Tristate::Undefined,
+        };
+
+        self.q.set(q);
+        (q, !q)
+    }
+}
+
+latch_component_impl!(DLatch);
+
+/// SR latch with active high inputs, built like a pair of cross-coupled NOR gates:
+/// Q is reset while R is high and Q' is low while S is high, so both are low while S and R are high together.
+pub struct SRLatch {
+    pins: PinContainer,
+    latch: RefCell<RSLatch>,
+}
+
+impl SRLatch {
+    pub const S: PinNumber = 1;
+    pub const R: PinNumber = 2;
+    pub const Q: PinNumber = 3;
+    pub const Q_BAR: PinNumber = 4;
+
+    pub fn new() -> Self {
+        Self {
+            pins: PinContainer::new(4, build_pins_spec(&[Self::S, Self::R], &[Self::Q, Self::Q_BAR])),
+            latch: Default::default(),
+        }
+    }
+
+    fn update(&self) -> (Tristate, Tristate) {
+        let (set, reset) = (self.pins.compute_input(Self::S).unwrap(), self.pins.compute_input(Self::R).unwrap());
+        let q = self.latch.borrow_mut().update(set, reset, false);
+
+        (q, !(set | q))
+    }
+}
+
+latch_component_impl!(SRLatch);
+
+/// JK flip-flop updated on the rising edges of CLOCK: holds with J = K = 0, resets with K alone,
+/// sets with J alone and toggles with both.
+pub struct JKFlipFlop {
+    pins: PinContainer,
+    flip_flop: RefCell<JKState>,
+}
+
+impl JKFlipFlop {
+    pub const J: PinNumber = 1;
+    pub const K: PinNumber = 2;
+    pub const CLOCK: PinNumber = 3;
+    pub const Q: PinNumber = 4;
+    pub const Q_BAR: PinNumber = 5;
+
+    pub fn new() -> Self {
+        Self {
+            pins: PinContainer::new(5, build_pins_spec(&[Self::J, Self::K, Self::CLOCK], &[Self::Q, Self::Q_BAR])),
+            flip_flop: Default::default(),
+        }
+    }
+
+    fn update(&self) -> (Tristate, Tristate) {
+        let [j, k, clock] = [Self::J, Self::K, Self::CLOCK].map(|pin| self.pins.compute_input(pin).unwrap());
+
+        self.flip_flop.borrow_mut().update(clock, j, k, false.into(), false.into())
+    }
+}
+
+latch_component_impl!(JKFlipFlop);
+
+#[cfg(test)]
+mod tests {
+    use super::{DLatch, JKFlipFlop, SRLatch};
+    use crate::circuit::Circuit;
+    use crate::components::registry::ComponentRegistry;
+
+    // 'a' and 'b' drive the first two inputs of each primitive.
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        input clock
+        dlatch d
+        srlatch sr
+        jk jk
+        output d_q
+        output sr_q
+        output sr_q_bar
+        output jk_q
+        .links:
+        a:1 d:1
+        b:1 d:2
+        a:1 sr:1
+        b:1 sr:2
+        a:1 jk:1
+        b:1 jk:2
+        clock:1 jk:3
+        d:3 d_q:1
+        sr:3 sr_q:1
+        sr:4 sr_q_bar:1
+        jk:4 jk_q:1
+    ";
+
+    fn build_circuit() -> Circuit {
+        let mut registry = ComponentRegistry::with_default_components();
+
+        registry.register("dlatch", || Box::new(DLatch::new()));
+        registry.register("srlatch", || Box::new(SRLatch::new()));
+        registry.register("jk", || Box::new(JKFlipFlop::new()));
+        Circuit::parse_with_factory(CONTENT, registry).unwrap()
+    }
+
+    fn run(circuit: &mut Circuit, a: &str, b: &str, clock: &str, output: &str) -> String {
+        circuit.set_value("a", a).unwrap();
+        circuit.set_value("b", b).unwrap();
+        circuit.set_value("clock", clock).unwrap();
+        circuit.simulate();
+        circuit.get_output(output).unwrap()
+    }
+
+    #[test]
+    fn test_d_latch() {
+        let mut circuit = build_circuit();
+        let mut q = |data: &str, enable: &str| run(&mut circuit, data, enable, "0", "d_q");
+
+        assert_eq!(q("1", "0"), "U");
+        assert_eq!(q("1", "1"), "1");
+        assert_eq!(q("0", "1"), "0");
+        assert_eq!(q("1", "0"), "0");
+        assert_eq!(q("0", "U"), "0");
+        assert_eq!(q("1", "U"), "U");
+    }
+
+    #[test]
+    fn test_sr_latch() {
+        let mut circuit = build_circuit();
+        let mut q = |set: &str, reset: &str| {
+            run(&mut circuit, set, reset, "0", "sr_q");
+            [circuit.get_output("sr_q").unwrap(), circuit.get_output("sr_q_bar").unwrap()].concat()
+        };
+
+        assert_eq!(q("0", "0"), "UU");
+        assert_eq!(q("1", "0"), "10");
+        assert_eq!(q("0", "0"), "10");
+        assert_eq!(q("0", "1"), "01");
+        assert_eq!(q("0", "0"), "01");
+        assert_eq!(q("1", "1"), "00");
+    }
+
+    #[test]
+    fn test_jk_flip_flop() {
+        let mut circuit = build_circuit();
+        let mut q = |j: &str, k: &str, clock: &str| run(&mut circuit, j, k, clock, "jk_q");
+
+        assert_eq!(q("1", "0", "0"), "U");
+        assert_eq!(q("1", "0", "1"), "1");
+        assert_eq!(q("1", "1", "0"), "1");
+        assert_eq!(q("1", "1", "1"), "0");
+        assert_eq!(q("1", "1", "1"), "0");
+        assert_eq!(q("0", "0", "0"), "0");
+        assert_eq!(q("1", "1", "1"), "1");
+    }
+}
@@ -1,2 +1,4 @@
+pub mod latches;
+pub mod n_inputs;
 pub mod one_input;
 pub mod two_inputs;
@@ -0,0 +1,251 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+pub trait GateNInputs: Component {
+    const NAME: &'static str;
+    /// Number of inputs, on pins 1 to `NB_INPUTS`.
+    const NB_INPUTS: usize;
+    const OUTPUT: PinNumber = Self::NB_INPUTS + 1;
+    /// Gate with the same inputs and the inverted output.
+    type Complement: GateNInputs + Default;
+}
+
+macro_rules! gate_n_inputs_impl {
+    ($name:ident, $gate_name:expr, $complement:ident, $operation:expr) => {
+        pub struct $name<const N: usize> {
+            pins: PinContainer,
+        }
+
+        impl<const N: usize> $name<N> {
+            pub fn new() -> Self {
+                Self { pins: PinContainer::new(N + 1, Self::build_pins_spec()) }
+            }
+
+            #[inline]
+            fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+                (1..=N)
+                    .map(|pin| (pin, PinSpecification::UnidirectionalInput()))
+                    .chain([(Self::OUTPUT, PinSpecification::UnidirectionalOutput())])
+                    .collect()
+            }
+        }
+
+        impl<const N: usize> Component for $name<N> {
+            fn set_link(
+                &self,
+                pin: PinNumber,
+                other_component: Weak<dyn Component>,
+                other_pin: PinNumber,
+            ) -> Result<(), InvalidPin> {
+                self.pins.set_link_to_external_component(pin, other_component, other_pin)
+            }
+
+            fn simulate(&self, tick: Tick) {
+                static OPERATION: fn(&[Tristate]) -> Tristate = $operation;
+
+                self.pins.simulate(tick, |output_cells| {
+                    let inputs: Vec<Tristate> = (1..=N).map(|pin| self.pins.compute_input(pin).unwrap()).collect();
+                    let output_cell: &Cell<Tristate> = output_cells.get(&Self::OUTPUT).unwrap();
+
+                    output_cell.set(OPERATION(&inputs));
+                })
+            }
+
+            fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+                self.pins.compute_for_external(pin)
+            }
+
+            fn is_driving(&self, pin: PinNumber) -> bool {
+                self.pins.is_driving(pin).unwrap_or(false)
+            }
+        }
+
+        impl<const N: usize> GateNInputs for $name<N> {
+            const NAME: &'static str = $gate_name;
+            const NB_INPUTS: usize = N;
+            type Complement = $complement<N>;
+        }
+
+        impl<const N: usize> Default for $name<N> {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+gate_n_inputs_impl!(GateNInputsAND, "AND", GateNInputsNAND, |inputs| inputs
+    .iter()
+    .fold(true.into(), |result, &input| result & input));
+
+gate_n_inputs_impl!(GateNInputsNAND, "NAND", GateNInputsAND, |inputs| !inputs
+    .iter()
+    .fold(true.into(), |result, &input| result & input));
+
+gate_n_inputs_impl!(GateNInputsOR, "OR", GateNInputsNOR, |inputs| inputs
+    .iter()
+    .fold(false.into(), |result, &input| result | input));
+
+gate_n_inputs_impl!(GateNInputsNOR, "NOR", GateNInputsOR, |inputs| !inputs
+    .iter()
+    .fold(false.into(), |result, &input| result | input));
+
+/// Operation of a `VariadicGate`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum VariadicOperation {
+    And,
+    Or,
+    /// High if an odd number of inputs are high.
+    Xor,
+}
+
+impl VariadicOperation {
+    fn apply(self, inputs: &[Tristate]) -> Tristate {
+        match self {
+            Self::And => inputs.iter().fold(true.into(), |result, &input| result & input),
+            Self::Or => inputs.iter().fold(false.into(), |result, &input| result | input),
+            Self::Xor => inputs.iter().fold(false.into(), |result, &input| result ^ input),
+        }
+    }
+}
+
+/// Gate whose number of inputs is chosen at runtime, on pins 1 to `nb_inputs`, its output being the next pin.
+pub struct VariadicGate {
+    pins: PinContainer,
+    operation: VariadicOperation,
+    nb_inputs: usize,
+}
+
+impl VariadicGate {
+    /// Number of inputs without an `inputs` parameter.
+    pub const DEFAULT_INPUTS: usize = 2;
+    pub const MAX_INPUTS: usize = 64;
+    /// `inputs`: number of inputs, from 1 to 64, 2 by default.
+    pub const PARAMETERS: &'static [&'static str] = &["inputs"];
+
+    pub fn new(operation: VariadicOperation, nb_inputs: usize) -> Self {
+        Self { pins: PinContainer::new(nb_inputs + 1, Self::build_pins_spec(nb_inputs)), operation, nb_inputs }
+    }
+
+    pub fn from_params(operation: VariadicOperation, params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(operation, Self::inputs_from_params(params)?))
+    }
+
+    /// Number of inputs given by the `inputs` parameter.
+    pub fn inputs_from_params(params: &Params) -> Result<usize, CreateError> {
+        match params.parse("inputs")? {
+            None => Ok(Self::DEFAULT_INPUTS),
+            Some(inputs) if (1..=Self::MAX_INPUTS).contains(&inputs) => Ok(inputs),
+            Some(inputs) => Err(CreateError::InvalidValue { key: "inputs".to_owned(), value: inputs.to_string() }),
+        }
+    }
+
+    pub fn info(name: &str, operation: VariadicOperation, nb_inputs: usize) -> ComponentInfo {
+        let description = match operation {
+            VariadicOperation::And => "AND gate with a configurable number of inputs",
+            VariadicOperation::Or => "OR gate with a configurable number of inputs",
+            VariadicOperation::Xor => "XOR gate with a configurable number of inputs",
+        };
+        let mut info = ComponentInfo::new(name, description, nb_inputs + 1);
+
+        for input in 0..nb_inputs {
+            info = info.with_pin(input + 1, &format!("in{input}"), PinDirection::Input);
+        }
+        info.with_pin(nb_inputs + 1, "out", PinDirection::Output)
+            .with_dependencies(nb_inputs + 1, &(1..=nb_inputs).collect::<Vec<PinNumber>>())
+    }
+
+    #[inline]
+    fn build_pins_spec(nb_inputs: usize) -> HashMap<PinNumber, PinSpecification> {
+        (1..=nb_inputs)
+            .map(|pin| (pin, PinSpecification::UnidirectionalInput()))
+            .chain([(nb_inputs + 1, PinSpecification::UnidirectionalOutput())])
+            .collect()
+    }
+}
+
+impl Component for VariadicGate {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |output_cells| {
+            let inputs: Vec<Tristate> = (1..=self.nb_inputs).map(|pin| self.pins.compute_input(pin).unwrap()).collect();
+            let output_cell: &Cell<Tristate> = output_cells.get(&(self.nb_inputs + 1)).unwrap();
+
+            output_cell.set(self.operation.apply(&inputs));
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        input c
+        and g1 inputs=3
+        or g2 inputs=3
+        xor g3 inputs=3
+        output and
+        output or
+        output xor
+        .links:
+        a:1 g1:1
+        b:1 g1:2
+        c:1 g1:3
+        a:1 g2:1
+        b:1 g2:2
+        c:1 g2:3
+        a:1 g3:1
+        b:1 g3:2
+        c:1 g3:3
+        g1:4 and:1
+        g2:4 or:1
+        g3:4 xor:1
+    ";
+
+    #[test]
+    fn test_variadic_gates() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        let mut outputs = |a: &str, b: &str, c: &str| {
+            circuit.set_value("a", a).unwrap();
+            circuit.set_value("b", b).unwrap();
+            circuit.set_value("c", c).unwrap();
+            circuit.simulate();
+            ["and", "or", "xor"].map(|name| circuit.get_output(name).unwrap()).concat()
+        };
+
+        assert_eq!(outputs("0", "0", "0"), "000");
+        assert_eq!(outputs("1", "0", "0"), "011");
+        assert_eq!(outputs("1", "1", "0"), "010");
+        assert_eq!(outputs("1", "1", "1"), "111");
+        assert_eq!(outputs("U", "1", "1"), "U1U");
+        assert_eq!(outputs("U", "0", "0"), "0UU");
+
+        assert!(CONTENT.replace("inputs=3", "inputs=0").parse::<Circuit>().is_err());
+        assert!(CONTENT.replace("g1:4", "g1:5").parse::<Circuit>().is_err());
+    }
+}
@@ -6,55 +6,76 @@ use crate::{
 };
 
 pub trait GateOneInput: Component {
+    const NAME: &'static str;
     const INPUT: PinNumber;
     const OUTPUT: PinNumber;
 }
 
-pub struct GateNOT {
-    pins: PinContainer,
-}
+macro_rules! gate_one_input_impl {
+    ($name:ident, $gate_name:expr, $operation:expr) => {
+        pub struct $name {
+            pins: PinContainer,
+        }
 
-impl GateNOT {
-    pub fn new() -> Self {
-        Self { pins: PinContainer::new(2, Self::build_pins_spec()) }
-    }
-
-    #[inline]
-    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
-        HashMap::from([
-            (Self::INPUT, PinSpecification::UnidirectionalInput()),
-            (Self::OUTPUT, PinSpecification::UnidirectionalOutput()),
-        ])
-    }
-}
+        impl $name {
+            pub fn new() -> Self {
+                Self { pins: PinContainer::new(2, Self::build_pins_spec()) }
+            }
 
-impl Component for GateNOT {
-    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
-        self.pins.set_link_to_external_component(pin, other_component, other_pin)
-    }
+            #[inline]
+            fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+                HashMap::from([
+                    (Self::INPUT, PinSpecification::UnidirectionalInput()),
+                    (Self::OUTPUT, PinSpecification::UnidirectionalOutput()),
+                ])
+            }
+        }
 
-    fn simulate(&self, tick: Tick) {
-        self.pins.simulate(tick, |output_cells| {
-            let input: Tristate = self.pins.compute_input(Self::INPUT).unwrap();
-            let output: &Cell<Tristate> = output_cells.get(&Self::OUTPUT).unwrap();
+        impl Component for $name {
+            fn set_link(
+                &self,
+                pin: PinNumber,
+                other_component: Weak<dyn Component>,
+                other_pin: PinNumber,
+            ) -> Result<(), InvalidPin> {
+                self.pins.set_link_to_external_component(pin, other_component, other_pin)
+            }
 
-            output.set(!input);
-        })
-    }
+            fn simulate(&self, tick: Tick) {
+                static OPERATION: fn(Tristate) -> Tristate = $operation;
 
-    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
-        self.pins.compute_for_external(pin)
-    }
-}
+                self.pins.simulate(tick, |output_cells| {
+                    let input: Tristate = self.pins.compute_input(Self::INPUT).unwrap();
+                    let output: &Cell<Tristate> = output_cells.get(&Self::OUTPUT).unwrap();
 
-impl GateOneInput for GateNOT {
-    const INPUT: PinNumber = 1;
-    const OUTPUT: PinNumber = 2;
-}
+                    output.set(OPERATION(input));
+                })
+            }
+
+            fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+                self.pins.compute_for_external(pin)
+            }
 
-impl Default for GateNOT {
-    #[inline]
-    fn default() -> Self {
-        Self::new()
-    }
+            fn is_driving(&self, pin: PinNumber) -> bool {
+                self.pins.is_driving(pin).unwrap_or(false)
+            }
+        }
+
+        impl GateOneInput for $name {
+            const NAME: &'static str = $gate_name;
+            const INPUT: PinNumber = 1;
+            const OUTPUT: PinNumber = 2;
+        }
+
+        impl Default for $name {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
 }
+
+gate_one_input_impl!(GateNOT, "NOT", |input| !input);
+
+gate_one_input_impl!(GateBUF, "BUF", |input| input);
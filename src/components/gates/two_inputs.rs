@@ -6,13 +6,14 @@ use crate::{
 };
 
 pub trait GateTwoInputs: Component {
+    const NAME: &'static str;
     const INPUT_LEFT: PinNumber;
     const INPUT_RIGHT: PinNumber;
     const OUTPUT: PinNumber;
 }
 
 macro_rules! gate_two_inputs_impl {
-    ($name:ident, $operation:expr) => {
+    ($name:ident, $gate_name:expr, $operation:expr) => {
         pub struct $name {
             pins: PinContainer,
         }
@@ -57,9 +58,14 @@ macro_rules! gate_two_inputs_impl {
             fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
                 self.pins.compute_for_external(pin)
             }
+
+            fn is_driving(&self, pin: PinNumber) -> bool {
+                self.pins.is_driving(pin).unwrap_or(false)
+            }
         }
 
         impl GateTwoInputs for $name {
+            const NAME: &'static str = $gate_name;
             const INPUT_LEFT: PinNumber = 1;
             const INPUT_RIGHT: PinNumber = 2;
             const OUTPUT: PinNumber = 3;
@@ -74,12 +80,14 @@ macro_rules! gate_two_inputs_impl {
     };
 }
 
-gate_two_inputs_impl!(GateAND, |left, right| left & right);
+gate_two_inputs_impl!(GateAND, "AND", |left, right| left & right);
+
+gate_two_inputs_impl!(GateOR, "OR", |left, right| left | right);
 
-gate_two_inputs_impl!(GateOR, |left, right| left | right);
+gate_two_inputs_impl!(GateXOR, "XOR", |left, right| left ^ right);
 
-gate_two_inputs_impl!(GateXOR, |left, right| left ^ right);
+gate_two_inputs_impl!(GateNAND, "NAND", |left, right| !(left & right));
 
-gate_two_inputs_impl!(GateNAND, |left, right| !(left & right));
+gate_two_inputs_impl!(GateNOR, "NOR", |left, right| !(left | right));
 
-gate_two_inputs_impl!(GateNOR, |left, right| !(left | right));
+gate_two_inputs_impl!(GateXNOR, "XNOR", |left, right| !(left ^ right));
@@ -0,0 +1,333 @@
+//! Images initializing memory components, read from or written to plain binary or Intel HEX files.
+//!
+//! Intel HEX files are made of records `:LLAAAATT<data>CC`, `LL` being the number of data bytes, `AAAA` the
+//! address of the first one, `TT` the record type and `CC` the checksum. Data (`00`), end of file (`01`),
+//! extended segment address (`02`) and extended linear address (`04`) records are supported; start address
+//! records (`03` and `05`) are ignored.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::params::{CreateError, Params};
+use super::Memory;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Binary,
+    IntelHex,
+}
+
+impl ImageFormat {
+    /// Intel HEX for the `.hex`, `.ihex` and `.ihx` extensions, binary otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if ["hex", "ihex", "ihx"].contains(&extension.to_ascii_lowercase().as_str()) => Self::IntelHex,
+            _ => Self::Binary,
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bin" | "binary" => Ok(Self::Binary),
+            "hex" | "ihex" => Ok(Self::IntelHex),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HexErrorKind {
+    MissingStartCode,
+    InvalidDigit,
+    /// The record holds `found` bytes, start code excluded, instead of the `expected` ones announced by its length.
+    Length {
+        expected: usize,
+        found: usize,
+    },
+    Checksum {
+        expected: u8,
+        found: u8,
+    },
+    InvalidAddressRecord,
+    UnsupportedRecord(u8),
+}
+
+impl fmt::Display for HexErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingStartCode => write!(f, "Records must start with ':'"),
+            Self::InvalidDigit => write!(f, "Records must be made of pairs of hexadecimal digits"),
+            Self::Length { expected, found } => write!(f, "Expected {expected} bytes in the record, got {found}"),
+            Self::Checksum { expected, found } => write!(f, "Invalid checksum {found:02X}, expected {expected:02X}"),
+            Self::InvalidAddressRecord => write!(f, "Address records must hold 2 bytes"),
+            Self::UnsupportedRecord(record) => write!(f, "Unsupported record type {record:02X}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageError {
+    /// Malformed Intel HEX record, `line` counted from 1.
+    Syntax {
+        line: usize,
+        kind: HexErrorKind,
+    },
+    MissingEndOfFile,
+    /// The image needs `end` bytes, the memory only holds `capacity`.
+    OutOfRange {
+        end: usize,
+        capacity: usize,
+    },
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax { line, kind } => write!(f, "line {line}: {kind}"),
+            Self::MissingEndOfFile => write!(f, "Missing end of file record"),
+            Self::OutOfRange { end, capacity } => {
+                write!(f, "The image goes up to address {:#X}, the memory holds {capacity} bytes", end - 1)
+            }
+        }
+    }
+}
+
+/// Blocks of bytes with their start address, in file order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryImage {
+    blocks: Vec<(usize, Vec<u8>)>,
+}
+
+impl MemoryImage {
+    /// `file`: path of the image, `format`: `bin` or `hex`, deduced from the extension of `file` by default.
+    pub const PARAMETERS: &'static [&'static str] = &["file", "format"];
+
+    /// Image of `bytes` from address 0.
+    pub fn binary(bytes: &[u8]) -> Self {
+        Self { blocks: vec![(0, bytes.to_vec())] }
+    }
+
+    /// Image of the defined bytes of `dump`, as returned by `Memory::dump()`.
+    pub fn from_dump(dump: &[Option<u8>]) -> Self {
+        let mut image = Self::default();
+
+        for (address, byte) in dump.iter().enumerate() {
+            if let Some(byte) = byte {
+                image.push(address, &[*byte]);
+            }
+        }
+        image
+    }
+
+    pub fn parse(content: &[u8], format: ImageFormat) -> Result<Self, ImageError> {
+        match format {
+            ImageFormat::Binary => Ok(Self::binary(content)),
+            ImageFormat::IntelHex => Self::parse_intel_hex(&String::from_utf8_lossy(content)),
+        }
+    }
+
+    pub fn parse_intel_hex(content: &str) -> Result<Self, ImageError> {
+        let mut image = Self::default();
+        let mut base = 0;
+
+        for (index, line) in content.lines().enumerate() {
+            let syntax_error = |kind| ImageError::Syntax { line: index + 1, kind };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let digits = line.strip_prefix(':').ok_or_else(|| syntax_error(HexErrorKind::MissingStartCode))?;
+            if digits.len() % 2 != 0 || !digits.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+                return Err(syntax_error(HexErrorKind::InvalidDigit));
+            }
+            let bytes: Vec<u8> =
+                (0..digits.len()).step_by(2).map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap()).collect();
+            let expected = 5 + usize::from(bytes.first().copied().unwrap_or_default());
+            if bytes.len() != expected {
+                return Err(syntax_error(HexErrorKind::Length { expected, found: bytes.len() }));
+            }
+
+            let (record, checksum) = bytes.split_at(bytes.len() - 1);
+            let expected = record.iter().fold(0u8, |sum, byte| sum.wrapping_sub(*byte));
+            if checksum[0] != expected {
+                return Err(syntax_error(HexErrorKind::Checksum { expected, found: checksum[0] }));
+            }
+
+            let address = usize::from(u16::from_be_bytes([record[1], record[2]]));
+            let data = &record[4..];
+            let address_record = || -> Result<usize, ImageError> {
+                match data {
+                    [high, low] => Ok(usize::from(u16::from_be_bytes([*high, *low]))),
+                    _ => Err(syntax_error(HexErrorKind::InvalidAddressRecord)),
+                }
+            };
+            match record[3] {
+                0x00 => image.push(base + address, data),
+                0x01 => return Ok(image),
+                0x02 => base = address_record()? << 4,
+                0x04 => base = address_record()? << 16,
+                0x03 | 0x05 => (),
+                record => return Err(syntax_error(HexErrorKind::UnsupportedRecord(record))),
+            }
+        }
+        Err(ImageError::MissingEndOfFile)
+    }
+
+    fn push(&mut self, address: usize, data: &[u8]) {
+        match self.blocks.last_mut() {
+            Some((start, bytes)) if *start + bytes.len() == address => bytes.extend_from_slice(data),
+            _ => self.blocks.push((address, data.to_vec())),
+        }
+    }
+
+    pub fn blocks(&self) -> impl Iterator<Item = (usize, &[u8])> {
+        self.blocks.iter().map(|(address, bytes)| (*address, bytes.as_slice()))
+    }
+
+    /// Address following the last byte of the image, 0 if it is empty.
+    pub fn end(&self) -> usize {
+        self.blocks().map(|(address, bytes)| address + bytes.len()).max().unwrap_or(0)
+    }
+
+    /// Content of a file holding the image. The binary format fills the gaps between blocks with zeros.
+    pub fn encode(&self, format: ImageFormat) -> Vec<u8> {
+        match format {
+            ImageFormat::Binary => {
+                let mut content = vec![0; self.end()];
+
+                for (address, bytes) in self.blocks() {
+                    content[address..address + bytes.len()].copy_from_slice(bytes);
+                }
+                content
+            }
+            ImageFormat::IntelHex => self.to_intel_hex().into_bytes(),
+        }
+    }
+
+    /// Intel HEX records of the image, with up to 16 data bytes each.
+    pub fn to_intel_hex(&self) -> String {
+        let mut content = String::new();
+        let mut base = 0;
+
+        for (start, bytes) in self.blocks() {
+            let mut offset = 0;
+
+            while offset < bytes.len() {
+                let address = start + offset;
+                if address >> 16 != base {
+                    base = address >> 16;
+                    Self::push_record(&mut content, 0, 0x04, &(base as u16).to_be_bytes());
+                }
+
+                let length = (bytes.len() - offset).min(16).min(0x10000 - (address & 0xFFFF));
+                Self::push_record(&mut content, address & 0xFFFF, 0x00, &bytes[offset..offset + length]);
+                offset += length;
+            }
+        }
+        Self::push_record(&mut content, 0, 0x01, &[]);
+        content
+    }
+
+    fn push_record(content: &mut String, address: usize, record_type: u8, data: &[u8]) {
+        let mut record = vec![data.len() as u8];
+        record.extend((address as u16).to_be_bytes());
+        record.push(record_type);
+        record.extend_from_slice(data);
+        record.push(record.iter().fold(0u8, |sum, byte| sum.wrapping_sub(*byte)));
+
+        content.push(':');
+        content.extend(record.iter().map(|byte| format!("{byte:02X}")));
+        content.push('\n');
+    }
+
+    /// Stores the image in `memory`, leaving it untouched if the image does not fit.
+    pub fn load_into(&self, memory: &dyn Memory) -> Result<(), ImageError> {
+        let (end, capacity) = (self.end(), memory.size());
+        if end > capacity {
+            return Err(ImageError::OutOfRange { end, capacity });
+        }
+
+        self.blocks().for_each(|(address, bytes)| memory.store(address, bytes));
+        Ok(())
+    }
+
+    /// Loads the image given by the `file` parameter, if any, into `memory`.
+    pub fn load_from_params(memory: &dyn Memory, params: &Params) -> Result<(), CreateError> {
+        let Some(content) = params.read_file("file")? else {
+            return Ok(());
+        };
+        let path = params.path("file").unwrap();
+        let format = params.parse("format")?.unwrap_or_else(|| ImageFormat::from_path(&path));
+
+        Self::parse(&content, format)
+            .and_then(|image| image.load_into(memory))
+            .map_err(|error| CreateError::File { path, message: error.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{HexErrorKind, ImageError, ImageFormat, MemoryImage};
+
+    #[test]
+    fn test_parse_intel_hex() {
+        let content = "
+            :0300300002337A1E
+            :02000004000AF0
+            :0200100001AB42
+            :0100120007E6
+            :00000001FF
+            :this line is ignored
+        ";
+        let image = MemoryImage::parse_intel_hex(content).unwrap();
+
+        assert_eq!(image.blocks().collect::<Vec<_>>(), vec![(0x30, &[0x02, 0x33, 0x7A][..]), (0xA0010, &[0x01, 0xAB, 0x07][..])]);
+        assert_eq!(image.end(), 0xA0013);
+        assert_eq!(MemoryImage::parse_intel_hex(":020000021000EC\n:01000000FF00\n:00000001FF\n").unwrap().end(), 0x10001);
+        assert_eq!(ImageFormat::from_path(Path::new("rom.HEX")), ImageFormat::IntelHex);
+        assert_eq!(ImageFormat::from_path(Path::new("rom.bin")), ImageFormat::Binary);
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut dump = vec![None; 0x10020];
+        dump[1] = Some(0xAA);
+        dump[2] = Some(0xBB);
+        dump[0xFFFF] = Some(1);
+        dump[0x10000..0x10012].fill(Some(2));
+        let image = MemoryImage::from_dump(&dump);
+
+        assert_eq!(image.blocks().map(|(address, bytes)| (address, bytes.len())).collect::<Vec<_>>(), vec![(1, 2), (0xFFFF, 19)]);
+        assert_eq!(image.encode(ImageFormat::Binary)[..4], [0, 0xAA, 0xBB, 0]);
+        assert_eq!(image.encode(ImageFormat::Binary).len(), 0x10012);
+        assert_eq!(
+            image.to_intel_hex().lines().take(4).collect::<Vec<_>>(),
+            vec![":02000100AABB98", ":01FFFF000100", ":020000040001F9", ":1000000002020202020202020202020202020202D0"]
+        );
+        assert_eq!(MemoryImage::parse(&image.encode(ImageFormat::IntelHex), ImageFormat::IntelHex), Ok(image));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |content: &str| MemoryImage::parse_intel_hex(content).unwrap_err();
+
+        assert_eq!(error("\n0100000000FF\n"), ImageError::Syntax { line: 2, kind: HexErrorKind::MissingStartCode });
+        assert_eq!(error(":0100000000F\n"), ImageError::Syntax { line: 1, kind: HexErrorKind::InvalidDigit });
+        assert_eq!(
+            error(":0200000000FE\n"),
+            ImageError::Syntax { line: 1, kind: HexErrorKind::Length { expected: 7, found: 6 } }
+        );
+        assert_eq!(error(":0100000001FF\n").to_string(), "line 1: Invalid checksum FF, expected FE");
+        assert_eq!(error(":0100000400FB\n"), ImageError::Syntax { line: 1, kind: HexErrorKind::InvalidAddressRecord });
+        assert_eq!(error(":00000006FA\n"), ImageError::Syntax { line: 1, kind: HexErrorKind::UnsupportedRecord(6) });
+        assert_eq!(error(":0100000000FF\n"), ImageError::MissingEndOfFile);
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+
+use super::PinNumber;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PinDirection {
+    Input,
+    Output,
+    Bidirectional,
+    Unused,
+}
+
+impl fmt::Display for PinDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Input => write!(f, "input"),
+            Self::Output => write!(f, "output"),
+            Self::Bidirectional => write!(f, "bidirectional"),
+            Self::Unused => write!(f, "unused"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PinInfo {
+    pub number: PinNumber,
+    pub name: String,
+    pub direction: PinDirection,
+    /// The pin is the clock input of a sequential component.
+    pub clock: bool,
+    /// Inputs an output combinationally depends on, when narrower than every input of the component.
+    pub depends_on: Vec<PinNumber>,
+    /// Maximum number of inputs an output can drive, if limited.
+    pub max_fan_out: Option<usize>,
+}
+
+/// Static description of a component type: its pinout and behavior.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ComponentInfo {
+    pub name: String,
+    pub description: String,
+    pub nb_pins: usize,
+    pub sequential: bool,
+    pins: Vec<PinInfo>,
+}
+
+impl ComponentInfo {
+    pub fn new(name: &str, description: &str, nb_pins: usize) -> Self {
+        let pins = (1..(nb_pins + 1))
+            .map(|number| PinInfo {
+                number,
+                name: String::new(),
+                direction: PinDirection::Unused,
+                clock: false,
+                depends_on: Vec::new(),
+                max_fan_out: None,
+            })
+            .collect();
+
+        Self { name: name.to_owned(), description: description.to_owned(), nb_pins, sequential: false, pins }
+    }
+
+    pub fn with_pin(mut self, number: PinNumber, name: &str, direction: PinDirection) -> Self {
+        let pin = self.pins.get_mut(number.wrapping_sub(1)).expect("Pin number out of range");
+
+        pin.name = name.to_owned();
+        pin.direction = direction;
+        self
+    }
+
+    /// Declares `number` as a clock input.
+    pub fn with_clock_pin(mut self, number: PinNumber, name: &str) -> Self {
+        self = self.with_pin(number, name, PinDirection::Input);
+        self.pins[number - 1].clock = true;
+        self
+    }
+
+    /// Declares that output `number` only depends on `inputs`.
+    pub fn with_dependencies(mut self, number: PinNumber, inputs: &[PinNumber]) -> Self {
+        let pin = self.pins.get_mut(number.wrapping_sub(1)).expect("Pin number out of range");
+
+        pin.depends_on = inputs.to_vec();
+        self
+    }
+
+    /// Declares that output `number` can drive at most `max` inputs.
+    pub fn with_fan_out(mut self, number: PinNumber, max: usize) -> Self {
+        let pin = self.pins.get_mut(number.wrapping_sub(1)).expect("Pin number out of range");
+
+        pin.max_fan_out = Some(max);
+        self
+    }
+
+    pub fn with_sequential(mut self, sequential: bool) -> Self {
+        self.sequential = sequential;
+        self
+    }
+
+    pub fn pin(&self, number: PinNumber) -> Option<&PinInfo> {
+        self.pins.get(number.wrapping_sub(1))
+    }
+
+    pub fn pins(&self) -> &[PinInfo] {
+        &self.pins
+    }
+
+    /// Inputs the value of output `number` depends on.
+    pub fn dependencies(&self, number: PinNumber) -> Vec<PinNumber> {
+        match self.pin(number) {
+            Some(pin) if !pin.depends_on.is_empty() => pin.depends_on.clone(),
+            Some(_) => self
+                .pins
+                .iter()
+                .filter(|pin| matches!(pin.direction, PinDirection::Input | PinDirection::Bidirectional))
+                .map(|pin| pin.number)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn clock_pins(&self) -> impl Iterator<Item = &PinInfo> {
+        self.pins.iter().filter(|pin| pin.clock)
+    }
+
+    pub fn pins_with_direction(&self, direction: PinDirection) -> impl Iterator<Item = &PinInfo> {
+        self.pins.iter().filter(move |pin| pin.direction == direction)
+    }
+}
+
+impl fmt::Display for ComponentInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.name, self.description)?;
+        writeln!(f, "  {}", if self.sequential { "sequential" } else { "combinational" })?;
+        for pin in self.pins.iter().filter(|pin| pin.direction != PinDirection::Unused) {
+            writeln!(f, "  pin {:>2}: {} ({})", pin.number, pin.name, pin.direction)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentInfo, PinDirection};
+
+    #[test]
+    fn test_unspecified_pins_are_unused() {
+        let info = ComponentInfo::new("test", "Test component", 3).with_pin(2, "in", PinDirection::Input);
+
+        assert_eq!(info.pins().len(), 3);
+        assert_eq!(info.pin(1).unwrap().direction, PinDirection::Unused);
+        assert_eq!(info.pin(2).unwrap().direction, PinDirection::Input);
+        assert_eq!(info.pin(2).unwrap().name, "in");
+        assert!(info.pin(0).is_none());
+        assert!(info.pin(4).is_none());
+    }
+
+    #[test]
+    fn test_clock_pin() {
+        let info = ComponentInfo::new("test", "Test component", 2).with_clock_pin(1, "clk").with_pin(2, "d", PinDirection::Input);
+
+        assert_eq!(info.pin(1).unwrap().direction, PinDirection::Input);
+        assert!(info.pin(1).unwrap().clock);
+        assert!(!info.pin(2).unwrap().clock);
+        assert_eq!(info.clock_pins().map(|pin| pin.number).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let info = ComponentInfo::new("test", "Test component", 4)
+            .with_pin(1, "a", PinDirection::Input)
+            .with_pin(2, "b", PinDirection::Input)
+            .with_pin(3, "x", PinDirection::Output)
+            .with_pin(4, "y", PinDirection::Output)
+            .with_dependencies(4, &[2]);
+
+        assert_eq!(info.dependencies(3), vec![1, 2]);
+        assert_eq!(info.dependencies(4), vec![2]);
+        assert!(info.dependencies(5).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pin_out_of_range() {
+        let _ = ComponentInfo::new("test", "Test component", 1).with_pin(2, "in", PinDirection::Input);
+    }
+
+    #[test]
+    fn test_display() {
+        let info = ComponentInfo::new("buf", "Buffer", 3)
+            .with_pin(1, "in", PinDirection::Input)
+            .with_pin(2, "out", PinDirection::Output)
+            .with_sequential(true);
+
+        assert_eq!(info.to_string(), "buf: Buffer\n  sequential\n  pin  1: in (input)\n  pin  2: out (output)\n");
+    }
+}
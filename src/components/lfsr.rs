@@ -0,0 +1,297 @@
+//! Linear feedback shift registers, producing a pseudo-random sequence on their outputs.
+//!
+//! At each rising edge of its clock, the register shifts towards its most significant bit and
+//! loads the XOR of its tapped bits into bit 0. The register starts at 1, the all-zero state being a dead end.
+//!
+//! The generated component has its clock on pin 1, then one output pin per bit, from the least significant.
+
+use std::{cell::Cell, collections::HashMap, fmt, rc::Weak};
+
+use crate::pin::{PinContainer, PinSpecification};
+
+use super::info::{ComponentInfo, PinDirection};
+use super::params::{CreateError, Params};
+use super::registry::ComponentRegistry;
+use super::{tristate::Tristate, Component, InvalidPin, PinNumber, Tick};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LfsrError {
+    InvalidWidth(u32),
+    /// Taps must be non-empty, within the register, and include its most significant bit.
+    InvalidTaps(u64),
+}
+
+impl fmt::Display for LfsrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidWidth(width) => write!(f, "Invalid width {width}, must be between 2 and 64"),
+            Self::InvalidTaps(taps) => write!(f, "Invalid taps {taps:#x}"),
+        }
+    }
+}
+
+/// Width and feedback taps (bit `n` set for bit `n` of the register) of an LFSR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LfsrDefinition {
+    width: u32,
+    taps: u64,
+}
+
+impl LfsrDefinition {
+    pub const CLOCK: PinNumber = 1;
+    /// `width`: number of bits, 8 by default, `taps`: feedback taps, in hexadecimal with a `0x` prefix
+    /// or in decimal, maximal-length ones by default.
+    pub const PARAMETERS: &'static [&'static str] = &["width", "taps"];
+
+    /// Taps giving a maximal-length sequence, indexed by `width - 2`.
+    const MAXIMAL_TAPS: [&'static [u32]; 31] = [
+        &[2, 1],
+        &[3, 2],
+        &[4, 3],
+        &[5, 3],
+        &[6, 5],
+        &[7, 6],
+        &[8, 6, 5, 4],
+        &[9, 5],
+        &[10, 7],
+        &[11, 9],
+        &[12, 6, 4, 1],
+        &[13, 4, 3, 1],
+        &[14, 5, 3, 1],
+        &[15, 14],
+        &[16, 15, 13, 4],
+        &[17, 14],
+        &[18, 11],
+        &[19, 6, 2, 1],
+        &[20, 17],
+        &[21, 19],
+        &[22, 21],
+        &[23, 18],
+        &[24, 23, 22, 17],
+        &[25, 22],
+        &[26, 6, 2, 1],
+        &[27, 5, 2, 1],
+        &[28, 25],
+        &[29, 27],
+        &[30, 6, 4, 1],
+        &[31, 28],
+        &[32, 22, 2, 1],
+    ];
+
+    pub fn new(width: u32, taps: u64) -> Result<Self, LfsrError> {
+        if !(2..=64).contains(&width) {
+            return Err(LfsrError::InvalidWidth(width));
+        }
+        if taps >> (width - 1) != 1 {
+            return Err(LfsrError::InvalidTaps(taps));
+        }
+
+        Ok(Self { width, taps })
+    }
+
+    /// Register of `width` bits going through its `2^width - 1` non-zero states, up to 32 bits.
+    pub fn maximal(width: u32) -> Result<Self, LfsrError> {
+        let stages = width.checked_sub(2).and_then(|index| Self::MAXIMAL_TAPS.get(index as usize));
+        let stages = stages.ok_or(LfsrError::InvalidWidth(width))?;
+
+        Self::new(width, stages.iter().fold(0, |taps, stage| taps | (1 << (stage - 1))))
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn taps(&self) -> u64 {
+        self.taps
+    }
+
+    pub fn output_pin(&self, bit: u32) -> PinNumber {
+        Self::CLOCK + 1 + bit as PinNumber
+    }
+
+    pub fn nb_pins(&self) -> usize {
+        1 + self.width as usize
+    }
+
+    /// Value following `state`.
+    pub fn next(&self, state: u64) -> u64 {
+        let feedback = u64::from((state & self.taps).count_ones() % 2);
+        let mask = u64::MAX >> (64 - self.width);
+
+        ((state << 1) | feedback) & mask
+    }
+
+    pub fn info(&self, name: &str) -> ComponentInfo {
+        let description = format!("{}-bit linear feedback shift register", self.width);
+        let mut info =
+            ComponentInfo::new(name, &description, self.nb_pins()).with_sequential(true).with_clock_pin(Self::CLOCK, "clock");
+
+        for bit in 0..self.width {
+            info = info.with_pin(self.output_pin(bit), &format!("q{bit}"), PinDirection::Output);
+        }
+
+        info
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        let width: u32 = params.parse("width")?.unwrap_or(Self::default().width);
+        let invalid = |key: &str| CreateError::InvalidValue { key: key.to_owned(), value: params.get(key).unwrap().to_owned() };
+
+        match params.get("taps") {
+            Some(taps) => {
+                let taps = match taps.strip_prefix("0x") {
+                    Some(hexadecimal) => u64::from_str_radix(hexadecimal, 16),
+                    None => taps.parse(),
+                };
+                let taps = taps.map_err(|_| invalid("taps"))?;
+
+                Self::new(width, taps).map_err(|error| match error {
+                    LfsrError::InvalidWidth(_) => invalid("width"),
+                    LfsrError::InvalidTaps(_) => invalid("taps"),
+                })
+            }
+            None => Self::maximal(width).map_err(|_| invalid("width")),
+        }
+    }
+
+    /// Registers this LFSR as the component type `name`.
+    pub fn register(self, registry: &mut ComponentRegistry, name: &str) {
+        registry.register_with_info(name, self.info(name), move || Box::new(LfsrComponent::new(self)));
+    }
+}
+
+impl Default for LfsrDefinition {
+    /// Maximal-length 8-bit register, used by the `lfsr` component type.
+    fn default() -> Self {
+        Self::maximal(8).unwrap()
+    }
+}
+
+pub struct LfsrComponent {
+    pins: PinContainer,
+    definition: LfsrDefinition,
+    state: Cell<u64>,
+    previous_clock: Cell<Tristate>,
+}
+
+impl LfsrComponent {
+    pub fn new(definition: LfsrDefinition) -> Self {
+        let mut spec: HashMap<PinNumber, PinSpecification> =
+            HashMap::from([(LfsrDefinition::CLOCK, PinSpecification::UnidirectionalInput())]);
+
+        spec.extend((0..definition.width).map(|bit| (definition.output_pin(bit), PinSpecification::UnidirectionalOutput())));
+
+        Self {
+            pins: PinContainer::new(definition.nb_pins(), spec),
+            definition,
+            state: Cell::new(1),
+            previous_clock: Default::default(),
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.state.get()
+    }
+}
+
+impl Default for LfsrComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(LfsrDefinition::default())
+    }
+}
+
+impl Component for LfsrComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let definition = &self.definition;
+            let clock = self.pins.compute_input(LfsrDefinition::CLOCK).unwrap();
+
+            if self.previous_clock.replace(clock) == false.into() && clock == true.into() {
+                self.state.set(definition.next(self.state.get()));
+            }
+
+            for bit in 0..definition.width {
+                outputs.get(&definition.output_pin(bit)).unwrap().set(((self.state.get() >> bit) & 1 == 1).into());
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LfsrDefinition, LfsrError};
+    use crate::circuit::Circuit;
+    use crate::components::registry::ComponentRegistry;
+
+    #[test]
+    fn test_maximal_length() {
+        for width in 2..=16 {
+            let definition = LfsrDefinition::maximal(width).unwrap();
+            let period = std::iter::successors(Some(definition.next(1)), |&state| Some(definition.next(state)))
+                .position(|state| state == 1)
+                .unwrap()
+                + 1;
+
+            assert_eq!(period, (1 << width) - 1, "width {width}");
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(LfsrDefinition::new(1, 1), Err(LfsrError::InvalidWidth(1)));
+        assert_eq!(LfsrDefinition::new(65, 1), Err(LfsrError::InvalidWidth(65)));
+        assert_eq!(LfsrDefinition::new(4, 0b0011), Err(LfsrError::InvalidTaps(0b0011)));
+        assert_eq!(LfsrDefinition::new(4, 0b11000), Err(LfsrError::InvalidTaps(0b11000)));
+        assert_eq!(LfsrDefinition::maximal(33), Err(LfsrError::InvalidWidth(33)));
+        assert!(LfsrDefinition::new(64, 1 << 63).is_ok());
+    }
+
+    #[test]
+    fn test_component() {
+        let content = "
+            .chipsets:
+            clock clk
+            lfsr3 random
+            output q0
+            output q1
+            output q2
+            .links:
+            clk:1 random:1
+            random:2 q0:1
+            random:3 q1:1
+            random:4 q2:1
+        ";
+        let mut registry = ComponentRegistry::with_default_components();
+        let definition = LfsrDefinition::maximal(3).unwrap();
+        definition.register(&mut registry, "lfsr3");
+
+        let mut circuit = Circuit::parse_with_factory(content, registry).unwrap();
+        circuit.set_value("clk", "0").unwrap();
+
+        let mut values: Vec<u64> = Vec::new();
+        for _ in 0..7 {
+            circuit.simulate();
+            circuit.simulate();
+            values.push(circuit.get_bus("q").unwrap());
+        }
+
+        let mut expected: Vec<u64> =
+            std::iter::successors(Some(definition.next(1)), |&state| Some(definition.next(state))).take(7).collect();
+        assert_eq!(values, expected);
+        expected.sort();
+        assert_eq!(expected, vec![1, 2, 3, 4, 5, 6, 7]);
+    }
+}
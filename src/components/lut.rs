@@ -0,0 +1,287 @@
+//! Lookup tables: combinational components whose outputs are read from a truth table.
+//!
+//! The table is a hexadecimal number, optionally prefixed by `0x`, holding the outputs for each value of
+//! the inputs: with `M` outputs, bits `i * M` to `i * M + M - 1` are the outputs when the inputs read `i`,
+//! input 0 being the least significant bit. Missing leading digits are zeros, so that a 2-input AND is `8`
+//! and a 2-input XOR is `6`.
+//!
+//! The generated component has one pin per input, then one pin per output.
+
+use std::{collections::HashMap, fmt, rc::Weak};
+
+use crate::pin::{PinContainer, PinSpecification};
+
+use super::info::{ComponentInfo, PinDirection};
+use super::params::{CreateError, Params};
+use super::registry::ComponentRegistry;
+use super::{tristate::Tristate, Component, InvalidPin, PinNumber, Tick};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LutError {
+    InvalidInputs(usize),
+    InvalidOutputs(usize),
+    InvalidDigit(char),
+    /// The table has more significant bits than `2^inputs * outputs`.
+    TableTooLarge {
+        capacity: usize,
+    },
+}
+
+impl fmt::Display for LutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInputs(inputs) => write!(f, "Invalid number of inputs {inputs}, must be between 1 and 16"),
+            Self::InvalidOutputs(outputs) => write!(f, "Invalid number of outputs {outputs}, must be between 1 and 64"),
+            Self::InvalidDigit(digit) => write!(f, "Invalid hexadecimal digit '{digit}'"),
+            Self::TableTooLarge { capacity } => write!(f, "The table does not fit in {capacity} bits"),
+        }
+    }
+}
+
+/// Number of inputs and outputs, and outputs for each value of the inputs, of a lookup table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LutDefinition {
+    inputs: usize,
+    outputs: usize,
+    entries: Vec<u64>,
+}
+
+impl LutDefinition {
+    pub const MAX_INPUTS: usize = 16;
+    pub const MAX_OUTPUTS: usize = 64;
+    /// `inputs`: number of inputs, 2 by default, `outputs`: number of outputs, 1 by default,
+    /// `table`: the truth table, all zeros by default, `file`: file holding the table when `table` is not set.
+    pub const PARAMETERS: &'static [&'static str] = &["inputs", "outputs", "table", "file"];
+
+    pub fn new(inputs: usize, outputs: usize, table: &str) -> Result<Self, LutError> {
+        if !(1..=Self::MAX_INPUTS).contains(&inputs) {
+            return Err(LutError::InvalidInputs(inputs));
+        }
+        if !(1..=Self::MAX_OUTPUTS).contains(&outputs) {
+            return Err(LutError::InvalidOutputs(outputs));
+        }
+
+        let capacity = (1 << inputs) * outputs;
+        let digits = table.strip_prefix("0x").unwrap_or(table).trim_start_matches('0');
+        let mut bits: Vec<bool> = Vec::with_capacity(capacity);
+        for digit in digits.chars().rev() {
+            let nibble = digit.to_digit(16).ok_or(LutError::InvalidDigit(digit))?;
+
+            bits.extend((0..4).map(|bit| (nibble >> bit) & 1 == 1));
+        }
+        while bits.last() == Some(&false) {
+            bits.pop();
+        }
+        if bits.len() > capacity {
+            return Err(LutError::TableTooLarge { capacity });
+        }
+        bits.resize(capacity, false);
+
+        let entries =
+            bits.chunks(outputs).map(|entry| entry.iter().rev().fold(0, |value, &bit| (value << 1) | u64::from(bit))).collect();
+        Ok(Self { inputs, outputs, entries })
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+
+    pub fn output_pin(&self, output: usize) -> PinNumber {
+        self.inputs + 1 + output
+    }
+
+    pub fn nb_pins(&self) -> usize {
+        self.inputs + self.outputs
+    }
+
+    /// Outputs for `inputs`, each of them being undefined only if the undefined inputs can change it.
+    pub fn lookup(&self, inputs: &[Tristate]) -> Vec<Tristate> {
+        let (known, value) = inputs.iter().enumerate().fold((0, 0), |(known, value), (bit, input)| match input {
+            Tristate::State(state) => (known | (1 << bit), value | (usize::from(*state) << bit)),
+            Tristate::Undefined => (known, value),
+        });
+        let (ones, zeros) = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| index & known == value)
+            .fold((u64::MAX, u64::MAX), |(ones, zeros), (_, &entry)| (ones & entry, zeros & !entry));
+
+        (0..self.outputs)
+            .map(|output| match ((ones >> output) & 1, (zeros >> output) & 1) {
+                (1, _) => Tristate::State(true),
+                (_, 1) => Tristate::State(false),
+                _ => Tristate::Undefined,
+            })
+            .collect()
+    }
+
+    pub fn info(&self, name: &str) -> ComponentInfo {
+        let description = format!("{}-input, {}-output lookup table", self.inputs, self.outputs);
+        let mut info = ComponentInfo::new(name, &description, self.nb_pins());
+
+        for input in 0..self.inputs {
+            info = info.with_pin(input + 1, &format!("in{input}"), PinDirection::Input);
+        }
+        for output in 0..self.outputs {
+            info = info.with_pin(self.output_pin(output), &format!("out{output}"), PinDirection::Output);
+        }
+        info
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        let inputs = params.parse("inputs")?.unwrap_or(2);
+        let outputs = params.parse("outputs")?.unwrap_or(1);
+        let table = match (params.get("table"), params.read_file("file")?) {
+            (Some(table), _) => table.to_owned(),
+            (None, Some(content)) => String::from_utf8_lossy(&content).split_whitespace().collect(),
+            (None, None) => String::new(),
+        };
+
+        Self::new(inputs, outputs, &table).map_err(|error| {
+            let key = match error {
+                LutError::InvalidInputs(_) => "inputs",
+                LutError::InvalidOutputs(_) => "outputs",
+                LutError::InvalidDigit(_) | LutError::TableTooLarge { .. } => {
+                    if let (None, Some(path)) = (params.get("table"), params.path("file")) {
+                        return CreateError::File { path, message: error.to_string() };
+                    }
+                    "table"
+                }
+            };
+            CreateError::InvalidValue { key: key.to_owned(), value: params.get(key).unwrap_or_default().to_owned() }
+        })
+    }
+
+    /// Registers this lookup table as the component type `name`.
+    pub fn register(self, registry: &mut ComponentRegistry, name: &str) {
+        registry.register_with_info(name, self.info(name), move || Box::new(LutComponent::new(self.clone())));
+    }
+}
+
+impl Default for LutDefinition {
+    /// 2-input, 1-output table always giving 0, used by the `lut` component type without parameters.
+    fn default() -> Self {
+        Self::new(2, 1, "").unwrap()
+    }
+}
+
+pub struct LutComponent {
+    pins: PinContainer,
+    definition: LutDefinition,
+}
+
+impl LutComponent {
+    pub fn new(definition: LutDefinition) -> Self {
+        let inputs = (1..=definition.inputs).map(|pin| (pin, PinSpecification::UnidirectionalInput()));
+        let outputs =
+            (0..definition.outputs).map(|output| (definition.output_pin(output), PinSpecification::UnidirectionalOutput()));
+        let spec: HashMap<PinNumber, PinSpecification> = inputs.chain(outputs).collect();
+
+        Self { pins: PinContainer::new(definition.nb_pins(), spec), definition }
+    }
+}
+
+impl Default for LutComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(LutDefinition::default())
+    }
+}
+
+impl Component for LutComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let definition = &self.definition;
+            let inputs: Vec<Tristate> = (1..=definition.inputs).map(|pin| self.pins.compute_input(pin).unwrap()).collect();
+
+            for (output, value) in definition.lookup(&inputs).into_iter().enumerate() {
+                outputs.get(&definition.output_pin(output)).unwrap().set(value);
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LutDefinition, LutError};
+    use crate::circuit::Circuit;
+    use crate::components::tristate::Tristate;
+
+    #[test]
+    fn test_lookup() {
+        let (zero, one, undefined) = (Tristate::State(false), Tristate::State(true), Tristate::Undefined);
+        let and = LutDefinition::new(2, 1, "8").unwrap();
+        // Half adder: sum on output 0, carry on output 1.
+        let half_adder = LutDefinition::new(2, 2, "0x94").unwrap();
+
+        assert_eq!(and.lookup(&[one, one]), vec![one]);
+        assert_eq!(and.lookup(&[one, zero]), vec![zero]);
+        assert_eq!(and.lookup(&[zero, undefined]), vec![zero]);
+        assert_eq!(and.lookup(&[one, undefined]), vec![undefined]);
+        assert_eq!(half_adder.lookup(&[one, zero]), vec![one, zero]);
+        assert_eq!(half_adder.lookup(&[one, one]), vec![zero, one]);
+        assert_eq!(half_adder.lookup(&[zero, undefined]), vec![undefined, zero]);
+
+        assert_eq!(LutDefinition::new(0, 1, ""), Err(LutError::InvalidInputs(0)));
+        assert_eq!(LutDefinition::new(2, 65, ""), Err(LutError::InvalidOutputs(65)));
+        assert_eq!(LutDefinition::new(2, 1, "1g"), Err(LutError::InvalidDigit('g')));
+        assert_eq!(LutDefinition::new(2, 1, "0x010"), Err(LutError::TableTooLarge { capacity: 4 }));
+        assert_eq!(LutDefinition::new(2, 1, "000f"), LutDefinition::new(2, 1, "f"));
+    }
+
+    #[test]
+    fn test_lut_component() {
+        let path = std::env::temp_dir().join("nts_lut.hex");
+        std::fs::write(&path, "e8\n").unwrap();
+        // 3-input majority, from the table parameter then from a file.
+        let content = "
+            .chipsets:
+            input a
+            input b
+            input c
+            lut majority inputs=3 table=0xe8
+            output out
+            .links:
+            a:1 majority:1
+            b:1 majority:2
+            c:1 majority:3
+            majority:4 out:1
+        ";
+        let file_content = content.replace("table=0xe8", &format!("file={}", path.to_str().unwrap()));
+
+        for content in [content, file_content.as_str()] {
+            let mut circuit: Circuit = content.parse().unwrap();
+            let outputs: String = [(1, 1, 0), (1, 0, 0), (0, 1, 1), (0, 0, 1)]
+                .into_iter()
+                .map(|(a, b, c)| {
+                    circuit.set_value("a", &a.to_string()).unwrap();
+                    circuit.set_value("b", &b.to_string()).unwrap();
+                    circuit.set_value("c", &c.to_string()).unwrap();
+                    circuit.simulate();
+                    circuit.get_output("out").unwrap()
+                })
+                .collect();
+
+            assert_eq!(outputs, "1010");
+        }
+        assert!(content.replace("0xe8", "0x1e8").parse::<Circuit>().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,18 +1,75 @@
+pub mod alu;
 pub mod factory;
+pub mod fsm;
+pub mod image;
+pub mod info;
+pub mod lfsr;
+pub mod lut;
+pub mod params;
+pub mod pla;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+pub mod registry;
 pub mod tristate;
 pub mod types;
 
 /* Components implementations */
+pub mod bus;
+pub mod chips;
 pub mod composite;
+pub mod devices;
 pub mod gates;
 pub mod single_pin;
+pub mod timers;
 /* -------------------------- */
 
+use std::fmt;
+use std::io;
+use std::path::Path;
 use std::rc::Weak;
 
-pub type Tick = usize;
 pub type PinNumber = usize;
 
+/// Index of a simulation step, counted from 0 when the circuit is built.
+/// 64-bit wide whatever the target, so that long sessions do not run out of ticks on 32-bit platforms.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Tick(u64);
+
+impl Tick {
+    pub const ZERO: Self = Self(0);
+    pub const MAX: Self = Self(u64::MAX);
+
+    pub const fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Following tick, `None` on overflow.
+    pub fn checked_next(self) -> Option<Self> {
+        self.0.checked_add(1).map(Self)
+    }
+
+    /// Number of ticks from `earlier` to `self`, `None` if `earlier` comes after.
+    pub fn checked_since(self, earlier: Self) -> Option<u64> {
+        self.0.checked_sub(earlier.0)
+    }
+}
+
+impl From<u64> for Tick {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for Tick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct InvalidPin(pub PinNumber);
 
@@ -21,21 +78,139 @@ pub trait Component {
     fn compute(&self, pin: PinNumber) -> Result<tristate::Tristate, InvalidPin>;
     fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin>;
 
+    /// Whether `pin` drives the pins linked to it. Input pins, and bidirectional pins reading, do not:
+    /// an input pin only reads its drivers, whose disagreements are settled by the `ConflictPolicy` of the circuit.
+    fn is_driving(&self, _pin: PinNumber) -> bool {
+        true
+    }
+
+    /// Bits carried by `pin`, bit 0 first, if it is a bus pin linking several bits at once. As for `compute()`,
+    /// the component is simulated for the tick beforehand.
+    fn compute_bus(&self, _pin: PinNumber) -> Option<Vec<tristate::Tristate>> {
+        None
+    }
+
     fn as_input(&self) -> Option<&dyn Input> {
         None
     }
     fn as_output(&self) -> Option<&dyn Output> {
         None
     }
+    fn as_bus_input(&self) -> Option<&dyn BusInput> {
+        None
+    }
+    fn as_bus_output(&self) -> Option<&dyn BusOutput> {
+        None
+    }
+    fn as_keyboard(&self) -> Option<&dyn Keyboard> {
+        None
+    }
+    fn as_terminal(&self) -> Option<&dyn Terminal> {
+        None
+    }
+    fn as_file_device(&self) -> Option<&dyn FileDevice> {
+        None
+    }
+    fn as_memory(&self) -> Option<&dyn Memory> {
+        None
+    }
+    fn as_monitor(&self) -> Option<&dyn Monitor> {
+        None
+    }
+    #[cfg(feature = "debug-internals")]
+    fn as_composite(&self) -> Option<&dyn composite::Composite> {
+        None
+    }
 }
 
 pub trait Input {
     fn get_current_state(&self) -> tristate::Tristate;
     fn set_state_for_next_tick(&self, state: tristate::Tristate);
+
+    /// Whether the input toggles by itself at each tick, a value set only replacing the next toggle.
+    fn is_clock(&self) -> bool {
+        false
+    }
+
+    /// Makes a clock follow `waveform` from the next tick, ignored by the other inputs.
+    fn set_waveform(&self, _waveform: single_pin::clock_component::ClockWaveform) {}
 }
 
 pub trait Output {
     fn get_value(&self) -> tristate::Tristate;
+
+    /// Text shown for the output when the circuit is displayed.
+    fn render(&self) -> String {
+        self.get_value().to_string()
+    }
+}
+
+/// Input of several bits set at once, bit 0 being the least significant.
+pub trait BusInput {
+    fn width(&self) -> usize;
+
+    /// `None` if one of the bits is undefined.
+    fn get_bus_value(&self) -> Option<u64>;
+
+    /// Sets the bits for the next tick, the ones beyond `width()` being ignored.
+    fn set_bus_value(&self, value: u64);
+}
+
+/// Output of several bits read at once, bit 0 being the least significant.
+pub trait BusOutput {
+    fn width(&self) -> usize;
+
+    /// `None` if one of the bits is undefined.
+    fn get_bus_value(&self) -> Option<u64>;
+}
+
+pub trait Keyboard {
+    /// Queues a key, exposed by the keyboard at a following tick.
+    fn push_key(&self, key: u8);
+}
+
+pub trait FileDevice {
+    /// Attaches the file the component reads from or writes to.
+    fn attach(&self, path: &Path) -> io::Result<()>;
+}
+
+pub trait Memory {
+    /// Number of addressable bytes.
+    fn size(&self) -> usize;
+
+    /// Byte stored at `address`, `None` if it is undefined or out of range.
+    fn read(&self, address: usize) -> Option<u8>;
+
+    /// Every byte stored, from address 0, `None` for the undefined ones.
+    fn dump(&self) -> Vec<Option<u8>> {
+        (0..self.size()).map(|address| self.read(address)).collect()
+    }
+
+    /// Stores `bytes` from `address`, ignoring the ones beyond `size()`.
+    fn store(&self, address: usize, bytes: &[u8]);
+
+    /// Stores `bytes` from address 0, ignoring the ones beyond `size()`.
+    fn load(&self, bytes: &[u8]) {
+        self.store(0, bytes);
+    }
+}
+
+/// Checks a condition on its inputs at each tick.
+pub trait Monitor {
+    fn expected(&self) -> tristate::Tristate;
+
+    /// Ticks at which the condition did not hold, with the value read instead, in tick order.
+    fn failures(&self) -> Vec<(Tick, tristate::Tristate)>;
+}
+
+pub trait Terminal {
+    /// Bytes received so far.
+    fn bytes(&self) -> Vec<u8>;
+
+    /// Bytes received so far, decoded as UTF-8.
+    fn text(&self) -> String {
+        String::from_utf8_lossy(&self.bytes()).into_owned()
+    }
 }
 
 #[cfg(test)]
@@ -65,6 +240,10 @@ pub mod dummy {
             self.pins.compute_for_external(pin)
         }
 
+        fn is_driving(&self, pin: PinNumber) -> bool {
+            self.pins.is_driving(pin).unwrap_or(false)
+        }
+
         fn set_link(
             &self,
             pin: PinNumber,
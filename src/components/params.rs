@@ -0,0 +1,145 @@
+//! Parameters given to a component on its chipset line: `type name key=value ...`.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CreateError {
+    UnknownParameter { key: String },
+    MissingParameter { key: String },
+    InvalidValue { key: String, value: String },
+    File { path: PathBuf, message: String },
+}
+
+impl fmt::Display for CreateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownParameter { key } => write!(f, "Unknown parameter \"{key}\""),
+            Self::MissingParameter { key } => write!(f, "Missing parameter \"{key}\""),
+            Self::InvalidValue { key, value } => write!(f, "Invalid value \"{value}\" for parameter \"{key}\""),
+            Self::File { path, message } => write!(f, "{}: {message}", path.display()),
+        }
+    }
+}
+
+/// Key/value parameters, in declaration order, with the directory file references are relative to.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(String, String)>,
+    base_path: Option<PathBuf>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `key`, replacing its previous value.
+    pub fn with(mut self, key: &str, value: &str) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    pub fn with_base_path<P: AsRef<Path>>(mut self, base_path: P) -> Self {
+        self.base_path = Some(base_path.as_ref().to_owned());
+        self
+    }
+
+    /// Sets `key`, returning its previous value.
+    pub fn insert(&mut self, key: &str, value: &str) -> Option<String> {
+        match self.values.iter_mut().find(|(other, _)| other == key) {
+            Some((_, previous)) => Some(std::mem::replace(previous, value.to_owned())),
+            None => {
+                self.values.push((key.to_owned(), value.to_owned()));
+                None
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.iter().find(|(other, _)| *other == key).map(|(_, value)| value)
+    }
+
+    pub fn base_path(&self) -> Option<&Path> {
+        self.base_path.as_deref()
+    }
+
+    /// Parses the value of `key`, `None` if it is not set.
+    pub fn parse<T: FromStr>(&self, key: &str) -> Result<Option<T>, CreateError> {
+        self.get(key)
+            .map(|value| value.parse().map_err(|_| CreateError::InvalidValue { key: key.to_owned(), value: value.to_owned() }))
+            .transpose()
+    }
+
+    /// Parses the value of `key`, which must be set.
+    pub fn require<T: FromStr>(&self, key: &str) -> Result<T, CreateError> {
+        self.parse(key)?.ok_or_else(|| CreateError::MissingParameter { key: key.to_owned() })
+    }
+
+    /// Path given by `key`, joined to the base path when relative.
+    pub fn path(&self, key: &str) -> Option<PathBuf> {
+        let path = Path::new(self.get(key)?);
+
+        Some(match self.base_path() {
+            Some(base_path) => base_path.join(path),
+            None => path.to_owned(),
+        })
+    }
+
+    /// Reads the file given by `key`, `None` if it is not set.
+    pub fn read_file(&self, key: &str) -> Result<Option<Vec<u8>>, CreateError> {
+        self.path(key)
+            .map(|path| std::fs::read(&path).map_err(|error| CreateError::File { path, message: error.to_string() }))
+            .transpose()
+    }
+
+    /// Fails on the first key not in `known`.
+    pub fn check_keys(&self, known: &[&str]) -> Result<(), CreateError> {
+        match self.iter().find(|(key, _)| !known.contains(key)) {
+            Some((key, _)) => Err(CreateError::UnknownParameter { key: key.to_owned() }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{CreateError, Params};
+
+    #[test]
+    fn test_parse() {
+        let params = Params::new().with("width", "8").with("mode", "x").with("width", "4");
+
+        assert_eq!(params.iter().collect::<Vec<_>>(), vec![("width", "4"), ("mode", "x")]);
+        assert_eq!(params.parse::<u64>("width"), Ok(Some(4)));
+        assert_eq!(params.parse::<u64>("taps"), Ok(None));
+        assert_eq!(params.parse::<u64>("mode"), Err(CreateError::InvalidValue { key: "mode".to_owned(), value: "x".to_owned() }));
+        assert_eq!(params.require::<u64>("taps"), Err(CreateError::MissingParameter { key: "taps".to_owned() }));
+        assert_eq!(params.check_keys(&["mode", "width"]), Ok(()));
+        assert_eq!(params.check_keys(&["width"]).unwrap_err().to_string(), "Unknown parameter \"mode\"");
+    }
+
+    #[test]
+    fn test_path() {
+        let params = Params::new().with("file", "rom.bin").with("absolute", "/rom.bin");
+
+        assert_eq!(params.path("file").unwrap(), Path::new("rom.bin"));
+
+        let params = params.with_base_path("circuits");
+        assert_eq!(params.path("file").unwrap(), Path::new("circuits/rom.bin"));
+        assert_eq!(params.path("absolute").unwrap(), Path::new("/rom.bin"));
+        assert_eq!(params.path("other"), None);
+        assert!(matches!(params.read_file("file"), Err(CreateError::File { .. })));
+    }
+}
@@ -0,0 +1,440 @@
+//! Programmable logic arrays: an AND plane of product terms feeding an OR plane, configured at load time.
+//!
+//! The text format follows the espresso `.pla` layout:
+//!
+//! ```text
+//! .i 3
+//! .o 2
+//! # AND plane  OR plane
+//! 11-          10
+//! 0-1          01
+//! ```
+//!
+//! Each product line has one character per input (`1` true literal, `0` complemented literal,
+//! `-` not connected) and one character per output (`1` if the product term drives it).
+//!
+//! The generated component has one pin per input, then one pin per output.
+
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::Path,
+    rc::{Rc, Weak},
+    str::FromStr,
+};
+
+use crate::pin::{PinContainer, PinSpecification};
+
+use super::info::{ComponentInfo, PinDirection};
+use super::registry::ComponentRegistry;
+use super::{tristate::Tristate, Component, InvalidPin, PinNumber, Tick};
+
+#[derive(Debug, Clone)]
+pub struct ParsePlaError {
+    pub line: usize,
+    pub kind: PlaErrorKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum PlaErrorKind {
+    InvalidLine,
+    InvalidSize { value: String },
+    MissingSize,
+    InvalidProduct { expected: (usize, usize) },
+    InvalidFuse { value: char },
+    InvalidBitstringLength { expected: usize, got: usize },
+}
+
+#[derive(Debug)]
+pub enum LoadPlaError {
+    Io(io::Error),
+    Parse(ParsePlaError),
+}
+
+impl fmt::Display for ParsePlaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for PlaErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLine => write!(f, "Expected a size declaration or a product term"),
+            Self::InvalidSize { value } => write!(f, "Invalid size \"{value}\""),
+            Self::MissingSize => write!(f, "Number of inputs and outputs must be declared before product terms"),
+            Self::InvalidProduct { expected: (inputs, outputs) } => {
+                write!(f, "Product term must have {inputs} input and {outputs} output characters")
+            }
+            Self::InvalidFuse { value } => write!(f, "Invalid fuse '{value}'"),
+            Self::InvalidBitstringLength { expected, got } => {
+                write!(f, "Bitstring must have a multiple of {expected} fuses, got {got}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for LoadPlaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Parse(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Literal {
+    True,
+    Complement,
+    /// Both literals of the input are connected: the product is false whenever the input is defined.
+    Both,
+    Unused,
+}
+
+#[derive(Debug, Clone)]
+struct ProductTerm {
+    literals: Vec<Literal>,
+    outputs: Vec<bool>,
+}
+
+impl ProductTerm {
+    fn evaluate(&self, inputs: &[Tristate]) -> Tristate {
+        self.literals.iter().zip(inputs).fold(true.into(), |acc, (literal, &input)| match literal {
+            Literal::True => acc & input,
+            Literal::Complement => acc & !input,
+            Literal::Both => acc & input & !input,
+            Literal::Unused => acc,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PlaDefinition {
+    nb_inputs: usize,
+    nb_outputs: usize,
+    products: Vec<ProductTerm>,
+}
+
+impl PlaDefinition {
+    /// Builds a definition from a raw fuse map.
+    ///
+    /// Each product term takes `2 * nb_inputs` AND-plane fuses (true then complemented literal of each input)
+    /// followed by `nb_outputs` OR-plane fuses. A blown fuse is `0`, an intact one is `1`. Whitespace is ignored.
+    pub fn from_bitstring(nb_inputs: usize, nb_outputs: usize, bitstring: &str) -> Result<Self, PlaErrorKind> {
+        let fuses: Vec<bool> = bitstring
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _ => Err(PlaErrorKind::InvalidFuse { value: c }),
+            })
+            .collect::<Result<_, _>>()?;
+        let product_size = 2 * nb_inputs + nb_outputs;
+
+        if product_size == 0 || !fuses.len().is_multiple_of(product_size) {
+            return Err(PlaErrorKind::InvalidBitstringLength { expected: product_size, got: fuses.len() });
+        }
+
+        let products = fuses
+            .chunks(product_size)
+            .map(|fuses| {
+                let (and_plane, or_plane) = fuses.split_at(2 * nb_inputs);
+                let literals = and_plane
+                    .chunks(2)
+                    .map(|pair| match (pair[0], pair[1]) {
+                        (true, false) => Literal::True,
+                        (false, true) => Literal::Complement,
+                        (true, true) => Literal::Both,
+                        (false, false) => Literal::Unused,
+                    })
+                    .collect();
+
+                ProductTerm { literals, outputs: or_plane.to_vec() }
+            })
+            .collect();
+
+        Ok(Self { nb_inputs, nb_outputs, products })
+    }
+
+    /// Reads a definition in the text format from a file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LoadPlaError> {
+        fs::read_to_string(path).map_err(LoadPlaError::Io)?.parse().map_err(LoadPlaError::Parse)
+    }
+
+    pub fn nb_inputs(&self) -> usize {
+        self.nb_inputs
+    }
+
+    pub fn nb_outputs(&self) -> usize {
+        self.nb_outputs
+    }
+
+    pub fn nb_products(&self) -> usize {
+        self.products.len()
+    }
+
+    pub fn input_pin(&self, index: usize) -> PinNumber {
+        1 + index
+    }
+
+    pub fn output_pin(&self, index: usize) -> PinNumber {
+        1 + self.nb_inputs + index
+    }
+
+    pub fn nb_pins(&self) -> usize {
+        self.nb_inputs + self.nb_outputs
+    }
+
+    pub fn info(&self, name: &str) -> ComponentInfo {
+        let description = format!("Programmable logic array with {} product terms", self.products.len());
+        let mut info = ComponentInfo::new(name, &description, self.nb_pins());
+
+        for idx in 0..self.nb_inputs {
+            info = info.with_pin(self.input_pin(idx), &format!("I{idx}"), PinDirection::Input);
+        }
+        for idx in 0..self.nb_outputs {
+            info = info.with_pin(self.output_pin(idx), &format!("O{idx}"), PinDirection::Output);
+        }
+
+        info
+    }
+
+    /// Registers this array as the component type `name`.
+    pub fn register(self, registry: &mut ComponentRegistry, name: &str) {
+        let definition = Rc::new(self);
+
+        registry.register_with_info(name, definition.info(name), move || Box::new(PlaComponent::new(definition.clone())));
+    }
+
+    /// Values of every output for the given input values.
+    pub fn evaluate(&self, inputs: &[Tristate]) -> Vec<Tristate> {
+        let products: Vec<Tristate> = self.products.iter().map(|product| product.evaluate(inputs)).collect();
+
+        (0..self.nb_outputs)
+            .map(|output| {
+                self.products
+                    .iter()
+                    .zip(products.iter())
+                    .filter(|(product, _)| product.outputs[output])
+                    .fold(false.into(), |acc, (_, &value)| acc | value)
+            })
+            .collect()
+    }
+}
+
+impl FromStr for PlaDefinition {
+    type Err = ParsePlaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut nb_inputs: Option<usize> = None;
+        let mut nb_outputs: Option<usize> = None;
+        let mut products: Vec<ProductTerm> = Vec::new();
+
+        for (index, content) in s.lines().enumerate() {
+            let line = index + 1;
+            let error = |kind: PlaErrorKind| ParsePlaError { line, kind };
+            let content = content.split('#').next().unwrap().trim();
+
+            if content.is_empty() {
+                continue;
+            }
+
+            let words: Vec<&str> = content.split_whitespace().collect();
+            match words.as_slice() {
+                [".i", value] | [".o", value] => {
+                    let size = value.parse().map_err(|_| error(PlaErrorKind::InvalidSize { value: value.to_string() }))?;
+
+                    if words[0] == ".i" {
+                        nb_inputs = Some(size);
+                    } else {
+                        nb_outputs = Some(size);
+                    }
+                }
+                [".e"] => break,
+                [and_plane, or_plane] if !and_plane.starts_with('.') => {
+                    let (Some(nb_inputs), Some(nb_outputs)) = (nb_inputs, nb_outputs) else {
+                        return Err(error(PlaErrorKind::MissingSize));
+                    };
+                    if and_plane.chars().count() != nb_inputs || or_plane.chars().count() != nb_outputs {
+                        return Err(error(PlaErrorKind::InvalidProduct { expected: (nb_inputs, nb_outputs) }));
+                    }
+
+                    let literals = and_plane
+                        .chars()
+                        .map(|c| match c {
+                            '1' => Ok(Literal::True),
+                            '0' => Ok(Literal::Complement),
+                            '-' => Ok(Literal::Unused),
+                            _ => Err(error(PlaErrorKind::InvalidFuse { value: c })),
+                        })
+                        .collect::<Result<_, _>>()?;
+                    let outputs = or_plane
+                        .chars()
+                        .map(|c| match c {
+                            '1' => Ok(true),
+                            '0' | '-' => Ok(false),
+                            _ => Err(error(PlaErrorKind::InvalidFuse { value: c })),
+                        })
+                        .collect::<Result<_, _>>()?;
+
+                    products.push(ProductTerm { literals, outputs });
+                }
+                _ => return Err(error(PlaErrorKind::InvalidLine)),
+            }
+        }
+
+        match (nb_inputs, nb_outputs) {
+            (Some(nb_inputs), Some(nb_outputs)) => Ok(Self { nb_inputs, nb_outputs, products }),
+            _ => Err(ParsePlaError { line: s.lines().count(), kind: PlaErrorKind::MissingSize }),
+        }
+    }
+}
+
+pub struct PlaComponent {
+    pins: PinContainer,
+    definition: Rc<PlaDefinition>,
+}
+
+impl PlaComponent {
+    pub fn new(definition: Rc<PlaDefinition>) -> Self {
+        let mut spec: HashMap<PinNumber, PinSpecification> = HashMap::new();
+
+        spec.extend((0..definition.nb_inputs).map(|idx| (definition.input_pin(idx), PinSpecification::UnidirectionalInput())));
+        spec.extend((0..definition.nb_outputs).map(|idx| (definition.output_pin(idx), PinSpecification::UnidirectionalOutput())));
+
+        Self { pins: PinContainer::new(definition.nb_pins(), spec), definition }
+    }
+}
+
+impl Component for PlaComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let definition = &self.definition;
+            let inputs: Vec<Tristate> =
+                (0..definition.nb_inputs).map(|idx| self.pins.compute_input(definition.input_pin(idx)).unwrap()).collect();
+
+            for (idx, value) in definition.evaluate(&inputs).into_iter().enumerate() {
+                outputs.get(&definition.output_pin(idx)).unwrap().set(value);
+            }
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParsePlaError, PlaDefinition, PlaErrorKind};
+    use crate::circuit::Circuit;
+    use crate::components::registry::ComponentRegistry;
+    use crate::components::tristate::Tristate;
+
+    // O0 = I0 & I1, O1 = I0 ^ I1
+    const HALF_ADDER: &str = "
+        .i 2
+        .o 2
+        11 10
+        10 01
+        01 01
+        .e
+    ";
+
+    #[test]
+    fn test_parse_definition() {
+        let definition: PlaDefinition = HALF_ADDER.parse().unwrap();
+
+        assert_eq!(definition.nb_inputs(), 2);
+        assert_eq!(definition.nb_outputs(), 2);
+        assert_eq!(definition.nb_products(), 3);
+        assert_eq!(definition.info("ha").pin(3).unwrap().name, "O0");
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let definition: PlaDefinition = HALF_ADDER.parse().unwrap();
+        let (t, f, u) = (Tristate::State(true), Tristate::State(false), Tristate::Undefined);
+
+        assert_eq!(definition.evaluate(&[f, f]), vec![f, f]);
+        assert_eq!(definition.evaluate(&[t, f]), vec![f, t]);
+        assert_eq!(definition.evaluate(&[t, t]), vec![t, f]);
+        assert_eq!(definition.evaluate(&[f, u]), vec![f, u]);
+    }
+
+    #[test]
+    fn test_from_bitstring() {
+        // Products: I0 & !I1 -> O0, I1 -> O0
+        let definition = PlaDefinition::from_bitstring(2, 1, "10 01 1\n00 10 1").unwrap();
+        let (t, f) = (Tristate::State(true), Tristate::State(false));
+
+        assert_eq!(definition.nb_products(), 2);
+        assert_eq!(definition.evaluate(&[f, f]), vec![f]);
+        assert_eq!(definition.evaluate(&[t, f]), vec![t]);
+        assert_eq!(definition.evaluate(&[f, t]), vec![t]);
+
+        // Both literals of I0 connected: the product never fires.
+        let definition = PlaDefinition::from_bitstring(1, 1, "111").unwrap();
+        assert_eq!(definition.evaluate(&[t]), vec![f]);
+
+        assert!(matches!(PlaDefinition::from_bitstring(2, 1, "1001"), Err(PlaErrorKind::InvalidBitstringLength { .. })));
+        assert!(matches!(PlaDefinition::from_bitstring(1, 1, "1x1"), Err(PlaErrorKind::InvalidFuse { value: 'x' })));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        fn kind(content: &str) -> PlaErrorKind {
+            content.parse::<PlaDefinition>().unwrap_err().kind
+        }
+
+        assert!(matches!(kind(".i 1\n.o 1\n1 1 1"), PlaErrorKind::InvalidLine));
+        assert!(matches!(kind(".i x"), PlaErrorKind::InvalidSize { .. }));
+        assert!(matches!(kind("1 1"), PlaErrorKind::MissingSize));
+        assert!(matches!(kind(".i 1"), PlaErrorKind::MissingSize));
+        assert!(matches!(kind(".i 2\n.o 1\n1 1"), PlaErrorKind::InvalidProduct { expected: (2, 1) }));
+        assert!(matches!(kind(".i 1\n.o 1\n? 1"), PlaErrorKind::InvalidFuse { value: '?' }));
+        assert!(matches!(".i 1\n.o 1\nfoo".parse::<PlaDefinition>(), Err(ParsePlaError { line: 3, .. })));
+    }
+
+    #[test]
+    fn test_in_circuit() {
+        let mut registry = ComponentRegistry::default();
+        HALF_ADDER.parse::<PlaDefinition>().unwrap().register(&mut registry, "half_adder");
+
+        let content = "
+            .chipsets:
+            input a
+            input b
+            half_adder ha
+            output carry
+            output sum
+            .links:
+            a:1 ha:1
+            b:1 ha:2
+            ha:3 carry:1
+            ha:4 sum:1
+        ";
+        let mut circuit = Circuit::parse_with_factory(content, registry).unwrap();
+
+        circuit.set_value("a", "1").unwrap();
+        circuit.set_value("b", "1").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("carry").unwrap(), "1");
+        assert_eq!(circuit.get_output("sum").unwrap(), "0");
+
+        circuit.set_value("b", "0").unwrap();
+        circuit.simulate();
+        assert_eq!(circuit.get_output("carry").unwrap(), "0");
+        assert_eq!(circuit.get_output("sum").unwrap(), "1");
+    }
+}
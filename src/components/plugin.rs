@@ -0,0 +1,207 @@
+use std::{ffi::OsStr, fmt, rc::Rc, rc::Weak};
+
+use libloading::{Library, Symbol};
+
+use super::info::ComponentInfo;
+use super::params::{CreateError, Params};
+use super::registry::{ComponentConstructor, ComponentInfoProvider, ComponentRegistry};
+use super::{
+    tristate::Tristate, BusInput, BusOutput, Component, FileDevice, Input, InvalidPin, Keyboard, Memory, Monitor, Output,
+    PinNumber, Terminal, Tick,
+};
+
+/// Bumped whenever the plugin entry point or the `Component` trait changes.
+pub const PLUGIN_API_VERSION: u32 = 18;
+
+pub type PluginRegisterFn = fn(&mut ComponentRegistry);
+
+const VERSION_SYMBOL: &[u8] = b"NANOTEKSPICE_PLUGIN_API_VERSION\0";
+const REGISTER_SYMBOL: &[u8] = b"nanotekspice_plugin_register\0";
+
+/// Declares the entry point of a component plugin library (a `cdylib` built with the same toolchain).
+///
+/// ```ignore
+/// fn register(registry: &mut ComponentRegistry) {
+///     registry.register("grader", || Box::new(GraderComponent::new()));
+/// }
+///
+/// nanotekspice::export_plugin!(register);
+/// ```
+#[macro_export]
+macro_rules! export_plugin {
+    ($register:path) => {
+        #[no_mangle]
+        pub static NANOTEKSPICE_PLUGIN_API_VERSION: u32 = $crate::components::plugin::PLUGIN_API_VERSION;
+
+        #[no_mangle]
+        pub fn nanotekspice_plugin_register(registry: &mut $crate::components::registry::ComponentRegistry) {
+            let register: $crate::components::plugin::PluginRegisterFn = $register;
+
+            register(registry)
+        }
+    };
+}
+
+#[derive(Debug)]
+pub enum PluginError {
+    Load(libloading::Error),
+    IncompatibleVersion { found: u32 },
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Load(err) => write!(f, "Cannot load plugin: {err}"),
+            Self::IncompatibleVersion { found } => {
+                write!(f, "Plugin API version {found} is not supported (expected {PLUGIN_API_VERSION})")
+            }
+        }
+    }
+}
+
+impl From<libloading::Error> for PluginError {
+    fn from(value: libloading::Error) -> Self {
+        Self::Load(value)
+    }
+}
+
+impl ComponentRegistry {
+    /// Loads a plugin library and registers every component type it declares.
+    /// Returns the names of the newly registered types.
+    ///
+    /// # Safety
+    ///
+    /// The library runs arbitrary code on load and must have been built by `export_plugin!`
+    /// against this exact crate version and toolchain.
+    pub unsafe fn load_plugin<P: AsRef<OsStr>>(&mut self, path: P) -> Result<Vec<String>, PluginError> {
+        let library = Rc::new(Library::new(path)?);
+
+        let version: Symbol<*const u32> = library.get(VERSION_SYMBOL)?;
+        let version = **version;
+        if version != PLUGIN_API_VERSION {
+            return Err(PluginError::IncompatibleVersion { found: version });
+        }
+
+        let register: Symbol<PluginRegisterFn> = library.get(REGISTER_SYMBOL)?;
+        let mut plugin_registry = ComponentRegistry::new();
+        register(&mut plugin_registry);
+
+        let names: Vec<String> = plugin_registry.names().into_iter().map(str::to_owned).collect();
+        for name in names.iter() {
+            let (constructor, info) = plugin_registry.take(name).unwrap();
+            let plugin = Rc::new(PluginConstructor { constructor, info, library: library.clone() });
+            let info_plugin = plugin.clone();
+
+            self.register_with_info_provider(
+                name,
+                move |params| info_plugin.component_info(params),
+                move |params| Ok(Box::new(plugin.create_component(params)?) as Box<dyn Component>),
+            );
+        }
+
+        Ok(names)
+    }
+}
+
+/* Field order matters: plugin code must be dropped before the library is unloaded. */
+
+struct PluginConstructor {
+    constructor: ComponentConstructor,
+    info: Option<ComponentInfoProvider>,
+    library: Rc<Library>,
+}
+
+impl PluginConstructor {
+    fn component_info(&self, params: &Params) -> Option<ComponentInfo> {
+        self.info.as_ref().and_then(|info| info(params))
+    }
+
+    fn create_component(&self, params: &Params) -> Result<PluginComponent, CreateError> {
+        let constructor = &self.constructor;
+
+        Ok(PluginComponent { inner: constructor(params)?, _library: self.library.clone() })
+    }
+}
+
+struct PluginComponent {
+    inner: Box<dyn Component>,
+    _library: Rc<Library>,
+}
+
+impl Component for PluginComponent {
+    fn simulate(&self, tick: Tick) {
+        self.inner.simulate(tick)
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.inner.compute(pin)
+    }
+
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.inner.set_link(pin, other_component, other_pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.inner.is_driving(pin)
+    }
+
+    fn compute_bus(&self, pin: PinNumber) -> Option<Vec<Tristate>> {
+        self.inner.compute_bus(pin)
+    }
+
+    fn as_input(&self) -> Option<&dyn Input> {
+        self.inner.as_input()
+    }
+
+    fn as_output(&self) -> Option<&dyn Output> {
+        self.inner.as_output()
+    }
+
+    fn as_bus_input(&self) -> Option<&dyn BusInput> {
+        self.inner.as_bus_input()
+    }
+
+    fn as_bus_output(&self) -> Option<&dyn BusOutput> {
+        self.inner.as_bus_output()
+    }
+
+    fn as_keyboard(&self) -> Option<&dyn Keyboard> {
+        self.inner.as_keyboard()
+    }
+
+    fn as_terminal(&self) -> Option<&dyn Terminal> {
+        self.inner.as_terminal()
+    }
+
+    fn as_file_device(&self) -> Option<&dyn FileDevice> {
+        self.inner.as_file_device()
+    }
+
+    fn as_memory(&self) -> Option<&dyn Memory> {
+        self.inner.as_memory()
+    }
+
+    fn as_monitor(&self) -> Option<&dyn Monitor> {
+        self.inner.as_monitor()
+    }
+
+    #[cfg(feature = "debug-internals")]
+    fn as_composite(&self) -> Option<&dyn super::composite::Composite> {
+        self.inner.as_composite()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentRegistry, PluginError};
+
+    #[test]
+    fn test_load_missing_library() {
+        let mut registry = ComponentRegistry::new();
+
+        let result = unsafe { registry.load_plugin("/nonexistent/libplugin.so") };
+
+        assert!(matches!(result, Err(PluginError::Load(_))));
+        assert!(registry.names().is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use super::factory::{ComponentFactory, DefaultComponentFactory};
+use super::info::ComponentInfo;
+use super::params::{CreateError, Params};
+use super::types::ComponentType;
+use super::Component;
+
+pub type ComponentConstructor = Box<dyn Fn(&Params) -> Result<Box<dyn Component>, CreateError>>;
+/// Info of the components created with the given parameters.
+pub type ComponentInfoProvider = Box<dyn Fn(&Params) -> Option<ComponentInfo>>;
+
+/// Runtime `name -> constructor` table, usable anywhere a `ComponentFactory` is expected.
+pub struct ComponentRegistry {
+    constructors: HashMap<String, ComponentConstructor>,
+    infos: HashMap<String, ComponentInfoProvider>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Self { constructors: HashMap::new(), infos: HashMap::new() }
+    }
+
+    pub fn with_default_components() -> Self {
+        let mut registry = Self::new();
+
+        for &component_type in ComponentType::ALL {
+            registry.register_with_info_provider(
+                &component_type.to_string(),
+                move |params| DefaultComponentFactory.component_info_with_params(&component_type, params),
+                move |params| DefaultComponentFactory.create_component(component_type, params),
+            );
+        }
+
+        registry
+    }
+
+    /// Registers a new component type, replacing any constructor already registered under `name`.
+    /// Its components take no parameters.
+    pub fn register<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn() -> Box<dyn Component> + 'static,
+    {
+        self.register_with_params(name, None, Self::without_params(constructor));
+    }
+
+    pub fn register_with_info<F>(&mut self, name: &str, info: ComponentInfo, constructor: F)
+    where
+        F: Fn() -> Box<dyn Component> + 'static,
+    {
+        self.register_with_params(name, Some(info), Self::without_params(constructor));
+    }
+
+    /// Registers a component type whose constructor reads the parameters of the chipset line.
+    pub fn register_with_params<F>(&mut self, name: &str, info: Option<ComponentInfo>, constructor: F)
+    where
+        F: Fn(&Params) -> Result<Box<dyn Component>, CreateError> + 'static,
+    {
+        self.constructors.insert(name.to_owned(), Box::new(constructor));
+        match info {
+            Some(info) => self.infos.insert(name.to_owned(), Box::new(move |_| Some(info.clone()))),
+            None => self.infos.remove(name),
+        };
+    }
+
+    /// Registers a component type whose pinout depends on the parameters of the chipset line.
+    pub fn register_with_info_provider<I, F>(&mut self, name: &str, info: I, constructor: F)
+    where
+        I: Fn(&Params) -> Option<ComponentInfo> + 'static,
+        F: Fn(&Params) -> Result<Box<dyn Component>, CreateError> + 'static,
+    {
+        self.constructors.insert(name.to_owned(), Box::new(constructor));
+        self.infos.insert(name.to_owned(), Box::new(info));
+    }
+
+    fn without_params<F>(constructor: F) -> impl Fn(&Params) -> Result<Box<dyn Component>, CreateError>
+    where
+        F: Fn() -> Box<dyn Component> + 'static,
+    {
+        move |params| {
+            params.check_keys(&[])?;
+            Ok(constructor())
+        }
+    }
+
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.infos.remove(name);
+        self.constructors.remove(name).is_some()
+    }
+
+    #[cfg(feature = "plugins")]
+    pub(crate) fn take(&mut self, name: &str) -> Option<(ComponentConstructor, Option<ComponentInfoProvider>)> {
+        Some((self.constructors.remove(name)?, self.infos.remove(name)))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.constructors.contains_key(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.constructors.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+}
+
+impl Default for ComponentRegistry {
+    #[inline]
+    fn default() -> Self {
+        Self::with_default_components()
+    }
+}
+
+impl ComponentFactory for ComponentRegistry {
+    type Type = String;
+
+    fn parse_type(&self, name: &str) -> Option<String> {
+        self.contains(name).then(|| name.to_owned())
+    }
+
+    fn create_component(&self, component_type: String, params: &Params) -> Result<Box<dyn Component>, CreateError> {
+        let constructor = self.constructors.get(&component_type).expect("Unregistered component type");
+
+        constructor(params)
+    }
+
+    fn component_info(&self, component_type: &String) -> Option<ComponentInfo> {
+        self.component_info_with_params(component_type, &Params::new())
+    }
+
+    fn component_info_with_params(&self, component_type: &String, params: &Params) -> Option<ComponentInfo> {
+        self.infos.get(component_type).and_then(|info| info(params))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentFactory, ComponentInfo, ComponentRegistry, CreateError, Params};
+    use crate::circuit::Circuit;
+    use crate::components::{dummy::DummyComponent, types::ComponentType};
+
+    #[test]
+    fn test_default_registry_knows_all_types() {
+        let registry = ComponentRegistry::default();
+
+        for component_type in ComponentType::ALL {
+            assert!(registry.contains(&component_type.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_empty_registry() {
+        let registry = ComponentRegistry::new();
+
+        assert!(registry.names().is_empty());
+        assert_eq!(registry.parse_type("input"), None);
+    }
+
+    #[test]
+    fn test_register_custom_component() {
+        let mut registry = ComponentRegistry::new();
+        registry.register("dummy", || Box::new(DummyComponent::new(3)));
+
+        assert_eq!(registry.names(), vec!["dummy"]);
+
+        let component = registry.create_component(registry.parse_type("dummy").unwrap(), &Params::new()).unwrap();
+
+        assert!(component.compute(3).is_ok());
+        assert!(component.compute(4).is_err());
+        assert!(matches!(
+            registry.create_component("dummy".to_owned(), &Params::new().with("width", "2")),
+            Err(CreateError::UnknownParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_component_info() {
+        let mut registry = ComponentRegistry::default();
+
+        assert_eq!(registry.component_info(&"4001".to_owned()).unwrap().nb_pins, 14);
+
+        registry.register("4001", || Box::new(DummyComponent::new(3)));
+        assert!(registry.component_info(&"4001".to_owned()).is_none());
+
+        registry.register_with_info("4001", ComponentInfo::new("4001", "Dummy", 3), || Box::new(DummyComponent::new(3)));
+        assert_eq!(registry.component_info(&"4001".to_owned()).unwrap().nb_pins, 3);
+    }
+
+    #[test]
+    fn test_component_info_with_params() {
+        let content = ".chipsets:\nand g inputs=4\nbusinput bus width=3\n";
+        let registry = Circuit::parse_with_factory(content, ComponentRegistry::default()).unwrap();
+        let default: Circuit = content.parse().unwrap();
+        let info = |circuit: &Circuit, name: &str| circuit.netlist().component(name).unwrap().info.clone().unwrap();
+
+        assert_eq!(info(&registry, "g").dependencies(5), info(&default, "g").dependencies(5));
+        assert_eq!(info(&registry, "g").dependencies(5), vec![1, 2, 3, 4]);
+        assert_eq!(info(&registry, "bus").nb_pins, info(&default, "bus").nb_pins);
+    }
+
+    #[test]
+    fn test_unregister_component() {
+        let mut registry = ComponentRegistry::default();
+
+        assert!(registry.unregister("clock"));
+        assert!(!registry.unregister("clock"));
+        assert_eq!(registry.parse_type("clock"), None);
+    }
+}
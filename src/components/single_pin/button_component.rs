@@ -0,0 +1,81 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        tristate::Tristate,
+        Component, Input, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Momentary push-button: a value set from outside the circuit is held for the next tick only,
+/// the button being released (0) at the other ticks.
+pub struct ButtonComponent {
+    pins: PinContainer,
+    value_for_next_tick: Cell<Option<Tristate>>,
+}
+
+impl ButtonComponent {
+    const OUTPUT: PinNumber = 1;
+
+    pub fn new() -> Self {
+        Self { pins: PinContainer::new(1, Self::build_pins_spec()), value_for_next_tick: Default::default() }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        ComponentInfo::new(name, "Input holding a value set from outside the circuit for a single tick", 1).with_pin(
+            Self::OUTPUT,
+            "out",
+            PinDirection::Output,
+        )
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        HashMap::from([(Self::OUTPUT, PinSpecification::UnidirectionalOutput())])
+    }
+}
+
+impl Component for ButtonComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let output = outputs.get(&Self::OUTPUT).unwrap();
+
+            output.set(self.value_for_next_tick.replace(None).unwrap_or(Tristate::State(false)));
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
+    fn as_input(&self) -> Option<&dyn Input> {
+        Some(self)
+    }
+}
+
+impl Input for ButtonComponent {
+    fn get_current_state(&self) -> Tristate {
+        self.compute(Self::OUTPUT).unwrap()
+    }
+
+    fn set_state_for_next_tick(&self, state: Tristate) {
+        self.value_for_next_tick.set(Some(state));
+    }
+}
+
+impl Default for ButtonComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
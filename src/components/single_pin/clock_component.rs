@@ -1,20 +1,97 @@
 use std::{cell::Cell, collections::HashMap, rc::Weak};
 
 use crate::{
-    components::{tristate::Tristate, Component, Input, InvalidPin, PinNumber, Tick},
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, Input, InvalidPin, PinNumber, Tick,
+    },
     pin::{PinContainer, PinSpecification},
 };
 
+/// Periodic signal generated by a clock from the tick number rather than by toggling its last value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockWaveform {
+    period: u64,
+    high_ticks: u64,
+    phase: u64,
+}
+
+impl ClockWaveform {
+    pub const DEFAULT_PERIOD: u64 = 2;
+    pub const DEFAULT_DUTY: u64 = 50;
+
+    /// High for `duty` percent of each `period` ticks, the first rising edge being delayed by `phase` ticks.
+    /// `None` if `period` is zero or `duty` is over 100.
+    pub fn new(period: u64, duty: u64, phase: u64) -> Option<Self> {
+        if period == 0 || duty > 100 {
+            return None;
+        }
+
+        Some(Self { period, high_ticks: (period as u128 * duty as u128 / 100) as u64, phase: phase % period })
+    }
+
+    /// State of the clock at `tick`, tick 1 being the first rising edge without phase
+    /// and tick 0, the initial state of the circuit, the end of the previous cycle.
+    pub fn state_at(&self, tick: Tick) -> bool {
+        let offset = self.period - 1 - self.phase;
+        let position = ((tick.get() % self.period) as u128 + offset as u128) % self.period as u128;
+
+        position < self.high_ticks as u128
+    }
+}
+
 pub struct ClockComponent {
     pins: PinContainer,
     value_for_next_tick: Cell<Option<Tristate>>,
+    waveform: Cell<Option<ClockWaveform>>,
 }
 
 impl ClockComponent {
     const OUTPUT: PinNumber = 1;
 
+    /// `period`: length of a cycle in ticks, `duty`: percentage of the cycle the clock is high,
+    /// `phase`: delay of the first rising edge in ticks. Without any of them, the clock toggles at each tick.
+    pub const PARAMETERS: &'static [&'static str] = &["period", "duty", "phase"];
+
     pub fn new() -> Self {
-        Self { pins: PinContainer::new(1, Self::build_pins_spec()), value_for_next_tick: Default::default() }
+        Self {
+            pins: PinContainer::new(1, Self::build_pins_spec()),
+            value_for_next_tick: Default::default(),
+            waveform: Default::default(),
+        }
+    }
+
+    pub fn with_waveform(waveform: ClockWaveform) -> Self {
+        let clock = Self::new();
+
+        clock.waveform.set(Some(waveform));
+        clock
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        if params.is_empty() {
+            return Ok(Self::new());
+        }
+
+        let period = params.parse("period")?.unwrap_or(ClockWaveform::DEFAULT_PERIOD);
+        let duty = params.parse("duty")?.unwrap_or(ClockWaveform::DEFAULT_DUTY);
+        let phase = params.parse("phase")?.unwrap_or(0);
+
+        match ClockWaveform::new(period, duty, phase) {
+            Some(waveform) => Ok(Self::with_waveform(waveform)),
+            None if period == 0 => Err(CreateError::InvalidValue { key: "period".to_owned(), value: period.to_string() }),
+            None => Err(CreateError::InvalidValue { key: "duty".to_owned(), value: duty.to_string() }),
+        }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        ComponentInfo::new(name, "Input inverting its value at each tick, or following its waveform", 1).with_pin(
+            Self::OUTPUT,
+            "out",
+            PinDirection::Output,
+        )
     }
 
     #[inline]
@@ -34,6 +111,8 @@ impl Component for ClockComponent {
 
             if let Some(state) = self.value_for_next_tick.replace(None) {
                 output.set(state);
+            } else if let Some(waveform) = self.waveform.get() {
+                output.set(waveform.state_at(tick).into());
             } else {
                 output.set(!output.get());
             }
@@ -44,6 +123,10 @@ impl Component for ClockComponent {
         self.pins.compute_for_external(pin)
     }
 
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
     fn as_input(&self) -> Option<&dyn Input> {
         Some(self)
     }
@@ -57,4 +140,19 @@ impl Input for ClockComponent {
     fn set_state_for_next_tick(&self, state: Tristate) {
         self.value_for_next_tick.set(Some(state));
     }
+
+    fn is_clock(&self) -> bool {
+        true
+    }
+
+    fn set_waveform(&self, waveform: ClockWaveform) {
+        self.waveform.set(Some(waveform));
+    }
+}
+
+impl Default for ClockComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -1,6 +1,10 @@
 use std::rc::Weak;
 
-use crate::components::{tristate::Tristate, Component, InvalidPin, PinNumber, Tick};
+use crate::components::{
+    info::{ComponentInfo, PinDirection},
+    tristate::Tristate,
+    Component, InvalidPin, PinNumber, Tick,
+};
 
 pub struct ConstStateComponent<const STATE: bool>;
 
@@ -13,6 +17,12 @@ impl<const STATE: bool> ConstStateComponent<STATE> {
     pub fn new() -> Self {
         Self {}
     }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        let description = if STATE { "Always outputs 1" } else { "Always outputs 0" };
+
+        ComponentInfo::new(name, description, 1).with_pin(Self::OUTPUT, "out", PinDirection::Output)
+    }
 }
 
 impl<const STATE: bool> Component for ConstStateComponent<STATE> {
@@ -32,3 +42,10 @@ impl<const STATE: bool> Component for ConstStateComponent<STATE> {
         }
     }
 }
+
+impl<const STATE: bool> Default for ConstStateComponent<STATE> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,7 +1,11 @@
 use std::{cell::Cell, collections::HashMap, rc::Weak};
 
 use crate::{
-    components::{tristate::Tristate, Component, Input, InvalidPin, PinNumber, Tick},
+    components::{
+        info::{ComponentInfo, PinDirection},
+        tristate::Tristate,
+        Component, Input, InvalidPin, PinNumber, Tick,
+    },
     pin::{PinContainer, PinSpecification},
 };
 
@@ -17,6 +21,14 @@ impl InputComponent {
         Self { pins: PinContainer::new(1, Self::build_pins_spec()), value_for_next_tick: Default::default() }
     }
 
+    pub fn info(name: &str) -> ComponentInfo {
+        ComponentInfo::new(name, "Input whose value is set from outside the circuit", 1).with_pin(
+            Self::OUTPUT,
+            "out",
+            PinDirection::Output,
+        )
+    }
+
     #[inline]
     fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
         HashMap::from([(Self::OUTPUT, PinSpecification::UnidirectionalOutput())])
@@ -42,6 +54,10 @@ impl Component for InputComponent {
         self.pins.compute_for_external(pin)
     }
 
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
     fn as_input(&self) -> Option<&dyn Input> {
         Some(self)
     }
@@ -56,3 +72,10 @@ impl Input for InputComponent {
         self.value_for_next_tick.set(Some(state));
     }
 }
+
+impl Default for InputComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,4 @@
+pub mod button_component;
 pub mod clock_component;
 pub mod const_component;
 pub mod input_component;
@@ -1,7 +1,11 @@
 use std::{cell::Cell, collections::HashMap, rc::Weak};
 
 use crate::{
-    components::{tristate::Tristate, Component, InvalidPin, Output, PinNumber, Tick},
+    components::{
+        info::{ComponentInfo, PinDirection},
+        tristate::Tristate,
+        Component, InvalidPin, Output, PinNumber, Tick,
+    },
     pin::{PinContainer, PinSpecification},
 };
 
@@ -17,6 +21,14 @@ impl OutputComponent {
         Self { pins: PinContainer::new(1, Self::build_pins_spec()), result: Default::default() }
     }
 
+    pub fn info(name: &str) -> ComponentInfo {
+        ComponentInfo::new(name, "Output whose value is read from outside the circuit", 1).with_pin(
+            Self::INPUT,
+            "in",
+            PinDirection::Input,
+        )
+    }
+
     #[inline]
     fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
         HashMap::from([(Self::INPUT, PinSpecification::UnidirectionalInput())])
@@ -40,6 +52,10 @@ impl Component for OutputComponent {
         self.pins.compute_for_external(pin)
     }
 
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+
     fn as_output(&self) -> Option<&dyn Output> {
         Some(self)
     }
@@ -50,3 +66,10 @@ impl Output for OutputComponent {
         self.result.get()
     }
 }
+
+impl Default for OutputComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,160 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Frequency divider: its output, low at first, toggles once every `ratio` rising edges of its clock,
+/// so that its period is `2 * ratio` clock periods. A high reset clears the count and the output.
+pub struct DividerComponent {
+    pins: PinContainer,
+    ratio: u64,
+    /// Rising edges since the last toggle.
+    count: Cell<u64>,
+    state: Cell<bool>,
+    previous_clock: Cell<Tristate>,
+}
+
+impl DividerComponent {
+    pub const CLOCK: PinNumber = 1;
+    pub const RESET: PinNumber = 2;
+    pub const OUTPUT: PinNumber = 3;
+
+    /// Ratio of the `divider` component type.
+    pub const DEFAULT_RATIO: u64 = 2;
+    /// `ratio`: rising edges of the clock between two toggles of the output, at least 1.
+    pub const PARAMETERS: &'static [&'static str] = &["ratio"];
+
+    pub fn new(ratio: u64) -> Self {
+        Self {
+            pins: PinContainer::new(3, Self::build_pins_spec()),
+            ratio: ratio.max(1),
+            count: Default::default(),
+            state: Default::default(),
+            previous_clock: Default::default(),
+        }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        match params.parse("ratio")?.unwrap_or(Self::DEFAULT_RATIO) {
+            0 => Err(CreateError::InvalidValue { key: "ratio".to_owned(), value: "0".to_owned() }),
+            ratio => Ok(Self::new(ratio)),
+        }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        ComponentInfo::new(name, "Frequency divider toggling its output every N rising edges", 3)
+            .with_sequential(true)
+            .with_clock_pin(Self::CLOCK, "clock")
+            .with_pin(Self::RESET, "reset", PinDirection::Input)
+            .with_pin(Self::OUTPUT, "out", PinDirection::Output)
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        HashMap::from([
+            (Self::CLOCK, PinSpecification::UnidirectionalInput()),
+            (Self::RESET, PinSpecification::UnidirectionalInput()),
+            (Self::OUTPUT, PinSpecification::UnidirectionalOutput()),
+        ])
+    }
+}
+
+impl Component for DividerComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let clock = self.pins.compute_input(Self::CLOCK).unwrap();
+            let rising = self.previous_clock.replace(clock) == false.into() && clock == true.into();
+
+            let output = match self.pins.compute_input(Self::RESET).unwrap() {
+                Tristate::State(true) => {
+                    self.count.set(0);
+                    self.state.set(false);
+                    Tristate::State(false)
+                }
+                Tristate::State(false) => {
+                    if rising {
+                        self.count.set(self.count.get() + 1);
+                        if self.count.get() == self.ratio {
+                            self.count.set(0);
+                            self.state.set(!self.state.get());
+                        }
+                    }
+                    Tristate::State(self.state.get())
+                }
+                Tristate::Undefined => Tristate::Undefined,
+            };
+
+            outputs.get(&Self::OUTPUT).unwrap().set(output);
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+impl Default for DividerComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_RATIO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input clock
+        input reset
+        divider div ratio=3
+        output out
+        .links:
+        clock:1 div:1
+        reset:1 div:2
+        div:3 out:1
+    ";
+
+    fn run(circuit: &mut Circuit, clocks: &str) -> String {
+        clocks
+            .chars()
+            .map(|clock| {
+                circuit.set_value("clock", &clock.to_string()).unwrap();
+                circuit.simulate();
+                circuit.get_output("out").unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_divider() {
+        let mut circuit: Circuit = CONTENT.parse().unwrap();
+        circuit.set_value("reset", "0").unwrap();
+
+        // Toggles on the 3rd and 6th rising edges.
+        assert_eq!(run(&mut circuit, "010101010101"), "000001111110");
+        assert_eq!(run(&mut circuit, "0110"), "0000");
+        circuit.set_value("reset", "1").unwrap();
+        assert_eq!(run(&mut circuit, "01"), "00");
+        circuit.set_value("reset", "0").unwrap();
+        assert_eq!(run(&mut circuit, "010101"), "000001");
+
+        assert!(CONTENT.replace("ratio=3", "ratio=0").parse::<Circuit>().is_err());
+    }
+}
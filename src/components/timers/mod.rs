@@ -0,0 +1,7 @@
+//! Components whose outputs follow a duration counted in ticks or clock edges.
+
+pub mod divider;
+pub mod monostable;
+pub mod multivibrator;
+pub mod oneshot;
+pub mod timer555;
@@ -0,0 +1,260 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Pins of a section of the 4538.
+struct Section {
+    reset: PinNumber,
+    trigger_plus: PinNumber,
+    trigger_minus: PinNumber,
+    q: PinNumber,
+    q_bar: PinNumber,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SectionState {
+    /// Remaining ticks of the pulse.
+    counter: u64,
+    previous_plus: Tristate,
+    previous_minus: Tristate,
+}
+
+/// 4538 dual precision monostable, the pulse width of both sections given in ticks by `width`.
+///
+/// A rising edge on +TR while -TR is high, or a falling edge on -TR while +TR is low, sets Q for `width`
+/// ticks, starting from the tick of the edge. In the retriggerable mode, an edge during the pulse restarts
+/// it; in the non-retriggerable mode, it is ignored. A low CD' clears Q and inhibits the triggers.
+pub struct Component4538 {
+    pins: PinContainer,
+    width: u64,
+    retriggerable: bool,
+    sections: Cell<[SectionState; 2]>,
+}
+
+impl Component4538 {
+    const SECTIONS: [Section; 2] = [
+        Section { reset: 3, trigger_plus: 4, trigger_minus: 5, q: 6, q_bar: 7 },
+        Section { reset: 13, trigger_plus: 12, trigger_minus: 11, q: 10, q_bar: 9 },
+    ];
+
+    /// Pulse width of the `4538` component type.
+    pub const DEFAULT_WIDTH: u64 = 4;
+    /// `width`: pulse width in ticks, `mode`: `retriggerable` (the default) or `non-retriggerable`.
+    pub const PARAMETERS: &'static [&'static str] = &["width", "mode"];
+
+    pub fn new(width: u64, retriggerable: bool) -> Self {
+        Self {
+            pins: PinContainer::new(16, Self::build_pins_spec()),
+            width: width.max(1),
+            retriggerable,
+            sections: Default::default(),
+        }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        let retriggerable = match params.get("mode") {
+            None | Some("retriggerable") => true,
+            Some("non-retriggerable") => false,
+            Some(mode) => return Err(CreateError::InvalidValue { key: "mode".to_owned(), value: mode.to_owned() }),
+        };
+
+        Ok(Self::new(params.parse("width")?.unwrap_or(Self::DEFAULT_WIDTH), retriggerable))
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        let mut info = ComponentInfo::new(name, "Dual precision monostable multivibrator", 16)
+            .with_sequential(true)
+            .with_pin(1, "CX1", PinDirection::Unused)
+            .with_pin(2, "RX1CX1", PinDirection::Unused)
+            .with_pin(8, "VSS", PinDirection::Unused)
+            .with_pin(14, "RX2CX2", PinDirection::Unused)
+            .with_pin(15, "CX2", PinDirection::Unused)
+            .with_pin(16, "VDD", PinDirection::Unused);
+
+        for (index, section) in Self::SECTIONS.iter().enumerate() {
+            let number = index + 1;
+
+            info = info
+                .with_pin(section.reset, &format!("CD{number}'"), PinDirection::Input)
+                .with_clock_pin(section.trigger_plus, &format!("+TR{number}"))
+                .with_clock_pin(section.trigger_minus, &format!("-TR{number}"))
+                .with_pin(section.q, &format!("Q{number}"), PinDirection::Output)
+                .with_pin(section.q_bar, &format!("Q{number}'"), PinDirection::Output);
+        }
+
+        info
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        Self::SECTIONS
+            .iter()
+            .flat_map(|section| {
+                [
+                    (section.reset, PinSpecification::UnidirectionalInput()),
+                    (section.trigger_plus, PinSpecification::UnidirectionalInput()),
+                    (section.trigger_minus, PinSpecification::UnidirectionalInput()),
+                    (section.q, PinSpecification::UnidirectionalOutput()),
+                    (section.q_bar, PinSpecification::UnidirectionalOutput()),
+                ]
+            })
+            .collect()
+    }
+
+    /// Advances the pulse of a section, returning Q.
+    fn pulse(&self, state: &mut SectionState, reset: Tristate, plus: Tristate, minus: Tristate) -> Tristate {
+        let triggered = (state.previous_plus == false.into() && plus == true.into() && minus == true.into())
+            || (state.previous_minus == true.into() && minus == false.into() && plus == false.into());
+
+        state.previous_plus = plus;
+        state.previous_minus = minus;
+
+        match reset {
+            Tristate::State(false) => {
+                state.counter = 0;
+                return Tristate::State(false);
+            }
+            Tristate::Undefined => return Tristate::Undefined,
+            Tristate::State(true) => (),
+        }
+
+        if triggered && (self.retriggerable || state.counter == 0) {
+            state.counter = self.width;
+        }
+
+        let q = state.counter > 0;
+        state.counter = state.counter.saturating_sub(1);
+        Tristate::State(q)
+    }
+}
+
+impl Component for Component4538 {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let input = |pin| self.pins.compute_input(pin).unwrap();
+            let mut states = self.sections.get();
+
+            for (section, state) in Self::SECTIONS.iter().zip(states.iter_mut()) {
+                let q = self.pulse(state, input(section.reset), input(section.trigger_plus), input(section.trigger_minus));
+
+                outputs.get(&section.q).unwrap().set(q);
+                outputs.get(&section.q_bar).unwrap().set(!q);
+            }
+
+            self.sections.set(states);
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+impl Default for Component4538 {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_WIDTH, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input reset
+        input plus
+        input minus
+        4538 mv {params}
+        output q
+        output nq
+        output q2
+        .links:
+        reset:1 mv:3
+        plus:1 mv:4
+        minus:1 mv:5
+        mv:6 q:1
+        mv:7 nq:1
+        reset:1 mv:13
+        plus:1 mv:12
+        minus:1 mv:11
+        mv:10 q2:1
+    ";
+
+    fn build_circuit(params: &str) -> Circuit {
+        let circuit: Circuit = CONTENT.replace("{params}", params).parse().unwrap();
+
+        for (input, value) in [("reset", "1"), ("plus", "0"), ("minus", "1")] {
+            circuit.set_value(input, value).unwrap();
+        }
+        circuit
+    }
+
+    fn run(circuit: &mut Circuit, input: &str, values: &str) -> String {
+        values
+            .chars()
+            .map(|value| {
+                circuit.set_value(input, &value.to_string()).unwrap();
+                circuit.simulate();
+                circuit.get_output("q").unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_triggers() {
+        let mut circuit = build_circuit("width=3");
+
+        assert_eq!(run(&mut circuit, "plus", "0111111"), "0111000");
+        assert_eq!(circuit.get_output("nq").unwrap(), "1");
+        assert_eq!(circuit.get_output("q2").unwrap(), "0");
+        // -TR fires on its falling edge while +TR is low.
+        assert_eq!(run(&mut circuit, "plus", "0"), "0");
+        assert_eq!(run(&mut circuit, "minus", "000011"), "111000");
+        // Neither a rising edge on +TR while -TR is low, nor a falling edge on -TR while +TR is high.
+        circuit.set_value("minus", "0").unwrap();
+        assert_eq!(run(&mut circuit, "plus", "1"), "0");
+        assert_eq!(run(&mut circuit, "minus", "10"), "00");
+    }
+
+    #[test]
+    fn test_modes() {
+        let mut circuit = build_circuit("width=3");
+
+        assert_eq!(run(&mut circuit, "plus", "01010000"), "01111100");
+
+        let mut circuit = build_circuit("width=3 mode=non-retriggerable");
+
+        assert_eq!(run(&mut circuit, "plus", "01010101"), "01110111");
+        assert!(CONTENT.replace("{params}", "mode=once").parse::<Circuit>().is_err());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut circuit = build_circuit("width=5");
+
+        assert_eq!(run(&mut circuit, "plus", "01"), "01");
+        assert_eq!(run(&mut circuit, "reset", "0U1"), "0U0");
+        // Triggers are inhibited while CD' is low.
+        circuit.set_value("reset", "0").unwrap();
+        assert_eq!(run(&mut circuit, "plus", "01"), "00");
+        assert_eq!(run(&mut circuit, "reset", "11"), "00");
+    }
+}
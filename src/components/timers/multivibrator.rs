@@ -0,0 +1,292 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+fn is_rising(previous: Tristate, current: Tristate) -> bool {
+    previous == false.into() && current == true.into()
+}
+
+/// 4047 multivibrator, its RC time constant given in ticks by `width`.
+///
+/// In monostable mode, a rising edge on +TRIGGER while -TRIGGER is low, or a falling edge on -TRIGGER
+/// while +TRIGGER is high, sets Q for `width` ticks, starting from the tick of the edge. A rising edge on
+/// RETRIGGER restarts the pulse. In astable mode (ASTABLE high or ASTABLE' low), OSC OUT is high then low
+/// for `width` ticks each and Q toggles on each of its rising edges. A high EXTERNAL RESET clears Q.
+pub struct Component4047 {
+    pins: PinContainer,
+    width: u64,
+    /// Remaining ticks of the monostable pulse, or ticks spent in the oscillator cycle.
+    counter: Cell<u64>,
+    q: Cell<bool>,
+    astable: Cell<bool>,
+    previous_triggers: Cell<[Tristate; 3]>,
+}
+
+impl Component4047 {
+    pub const ASTABLE_BAR: PinNumber = 4;
+    pub const ASTABLE: PinNumber = 5;
+    pub const TRIGGER_MINUS: PinNumber = 6;
+    pub const TRIGGER_PLUS: PinNumber = 8;
+    pub const RESET: PinNumber = 9;
+    pub const Q: PinNumber = 10;
+    pub const Q_BAR: PinNumber = 11;
+    pub const RETRIGGER: PinNumber = 12;
+    pub const OSC_OUT: PinNumber = 13;
+
+    /// Time constant of the `4047` component type.
+    pub const DEFAULT_WIDTH: u64 = 4;
+    /// `width`: monostable pulse width and half period of the oscillator, in ticks.
+    pub const PARAMETERS: &'static [&'static str] = &["width"];
+
+    pub fn new(width: u64) -> Self {
+        Self {
+            pins: PinContainer::new(14, Self::build_pins_spec()),
+            width: width.max(1),
+            counter: Default::default(),
+            q: Default::default(),
+            astable: Default::default(),
+            previous_triggers: Default::default(),
+        }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(params.parse("width")?.unwrap_or(Self::DEFAULT_WIDTH)))
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        ComponentInfo::new(name, "Monostable/astable multivibrator", 14)
+            .with_sequential(true)
+            .with_pin(1, "C", PinDirection::Unused)
+            .with_pin(2, "R", PinDirection::Unused)
+            .with_pin(3, "RC", PinDirection::Unused)
+            .with_pin(Self::ASTABLE_BAR, "ASTABLE'", PinDirection::Input)
+            .with_pin(Self::ASTABLE, "ASTABLE", PinDirection::Input)
+            .with_clock_pin(Self::TRIGGER_MINUS, "-TRIGGER")
+            .with_pin(7, "VSS", PinDirection::Unused)
+            .with_clock_pin(Self::TRIGGER_PLUS, "+TRIGGER")
+            .with_pin(Self::RESET, "EXTERNAL RESET", PinDirection::Input)
+            .with_pin(Self::Q, "Q", PinDirection::Output)
+            .with_pin(Self::Q_BAR, "Q'", PinDirection::Output)
+            .with_clock_pin(Self::RETRIGGER, "RETRIGGER")
+            .with_pin(Self::OSC_OUT, "OSC OUT", PinDirection::Output)
+            .with_pin(14, "VDD", PinDirection::Unused)
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        let inputs = [Self::ASTABLE, Self::ASTABLE_BAR, Self::TRIGGER_MINUS, Self::TRIGGER_PLUS, Self::RESET, Self::RETRIGGER];
+        let outputs = [Self::Q, Self::Q_BAR, Self::OSC_OUT];
+
+        inputs
+            .into_iter()
+            .map(|pin| (pin, PinSpecification::UnidirectionalInput()))
+            .chain(outputs.into_iter().map(|pin| (pin, PinSpecification::UnidirectionalOutput())))
+            .collect()
+    }
+
+    /// Advances the oscillator, returning OSC OUT.
+    fn oscillate(&self) -> bool {
+        let counter = self.counter.get();
+
+        if counter == 0 {
+            self.q.set(!self.q.get());
+        }
+        self.counter.set((counter + 1) % (2 * self.width));
+        counter < self.width
+    }
+
+    /// Advances the monostable pulse, returning Q.
+    fn pulse(&self, [minus, plus, retrigger]: [Tristate; 3]) -> bool {
+        let [previous_minus, previous_plus, previous_retrigger] = self.previous_triggers.get();
+        let triggered = (is_rising(previous_plus, plus) && minus == false.into())
+            || (is_rising(!previous_minus, !minus) && plus == true.into());
+
+        if is_rising(previous_retrigger, retrigger) || (triggered && self.counter.get() == 0) {
+            self.counter.set(self.width);
+        }
+
+        let q = self.counter.get() > 0;
+        self.counter.set(self.counter.get().saturating_sub(1));
+        q
+    }
+}
+
+impl Component for Component4047 {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let input = |pin| self.pins.compute_input(pin).unwrap();
+            let triggers = [input(Self::TRIGGER_MINUS), input(Self::TRIGGER_PLUS), input(Self::RETRIGGER)];
+            let astable = input(Self::ASTABLE) | !input(Self::ASTABLE_BAR);
+
+            // Switching modes restarts from a cleared state.
+            if let Tristate::State(astable) = astable {
+                if self.astable.replace(astable) != astable {
+                    self.counter.set(0);
+                    self.q.set(false);
+                }
+            }
+
+            let (q, osc) = match (input(Self::RESET), astable) {
+                (Tristate::State(true), _) => {
+                    self.counter.set(0);
+                    self.q.set(false);
+                    (Tristate::State(false), Tristate::State(false))
+                }
+                (Tristate::State(false), Tristate::State(true)) => {
+                    let osc = self.oscillate();
+                    (Tristate::State(self.q.get()), Tristate::State(osc))
+                }
+                (Tristate::State(false), Tristate::State(false)) => {
+                    (Tristate::State(self.pulse(triggers)), Tristate::State(false))
+                }
+                _ => (Tristate::Undefined, Tristate::Undefined),
+            };
+
+            self.previous_triggers.set(triggers);
+            outputs.get(&Self::Q).unwrap().set(q);
+            outputs.get(&Self::Q_BAR).unwrap().set(!q);
+            outputs.get(&Self::OSC_OUT).unwrap().set(osc);
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+impl Default for Component4047 {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_WIDTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuit::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input astable
+        input minus
+        input plus
+        input reset
+        input retrigger
+        true high
+        4047 mv width=3
+        output q
+        output osc
+        .links:
+        high:1 mv:4
+        astable:1 mv:5
+        minus:1 mv:6
+        plus:1 mv:8
+        reset:1 mv:9
+        retrigger:1 mv:12
+        mv:10 q:1
+        mv:13 osc:1
+    ";
+
+    fn build_circuit() -> Circuit {
+        let circuit: Circuit = CONTENT.parse().unwrap();
+
+        for input in ["astable", "minus", "plus", "reset", "retrigger"] {
+            circuit.set_value(input, "0").unwrap();
+        }
+        circuit
+    }
+
+    fn run(circuit: &mut Circuit, input: &str, values: &str) -> String {
+        values
+            .chars()
+            .map(|value| {
+                circuit.set_value(input, &value.to_string()).unwrap();
+                circuit.simulate();
+                circuit.get_output("q").unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_monostable() {
+        let mut circuit = build_circuit();
+
+        assert_eq!(run(&mut circuit, "plus", "0111111"), "0111000");
+        // -TRIGGER fires on its falling edge while +TRIGGER is high.
+        circuit.set_value("minus", "1").unwrap();
+        assert_eq!(run(&mut circuit, "plus", "1"), "0");
+        assert_eq!(run(&mut circuit, "minus", "00000"), "11100");
+    }
+
+    #[test]
+    fn test_datasheet_monostable_wiring() {
+        // ASTABLE' to VDD and ASTABLE to VSS, as in the monostable applications of the datasheet.
+        let mut circuit: Circuit = "
+            .chipsets:
+            true vdd
+            false vss
+            input plus
+            4047 mv width=2
+            output q
+            output osc
+            .links:
+            vdd:1 mv:4
+            vss:1 mv:5
+            vss:1 mv:6
+            vss:1 mv:9
+            vss:1 mv:12
+            plus:1 mv:8
+            mv:10 q:1
+            mv:13 osc:1
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(run(&mut circuit, "plus", "001111"), "001100");
+        assert_eq!(circuit.get_output("osc").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_retrigger_and_reset() {
+        let mut circuit = build_circuit();
+
+        assert_eq!(run(&mut circuit, "plus", "01"), "01");
+        assert_eq!(run(&mut circuit, "retrigger", "01000"), "11110");
+        assert_eq!(run(&mut circuit, "plus", "01"), "01");
+        assert_eq!(run(&mut circuit, "reset", "100"), "000");
+    }
+
+    #[test]
+    fn test_astable() {
+        let mut circuit = build_circuit();
+        let mut osc = String::new();
+
+        circuit.set_value("astable", "1").unwrap();
+        let q: String = (0..12)
+            .map(|_| {
+                circuit.simulate();
+                osc.push_str(&circuit.get_output("osc").unwrap());
+                circuit.get_output("q").unwrap()
+            })
+            .collect();
+
+        assert_eq!(osc, "111000111000");
+        assert_eq!(q, "111111000000");
+    }
+}
@@ -0,0 +1,173 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        registry::ComponentRegistry,
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Retriggerable monostable: a rising edge on its trigger sets its output for `width` ticks,
+/// starting from the tick of the edge. A high reset ends the pulse immediately.
+pub struct OneShotComponent {
+    pins: PinContainer,
+    width: u64,
+    remaining: Cell<u64>,
+    previous_trigger: Cell<Tristate>,
+}
+
+impl OneShotComponent {
+    pub const TRIGGER: PinNumber = 1;
+    pub const RESET: PinNumber = 2;
+    pub const Q: PinNumber = 3;
+    pub const Q_BAR: PinNumber = 4;
+
+    /// Pulse width of the `oneshot` component type.
+    pub const DEFAULT_WIDTH: u64 = 4;
+    /// `width`: pulse width in ticks.
+    pub const PARAMETERS: &'static [&'static str] = &["width"];
+
+    pub fn new(width: u64) -> Self {
+        Self {
+            pins: PinContainer::new(4, Self::build_pins_spec()),
+            width,
+            remaining: Default::default(),
+            previous_trigger: Default::default(),
+        }
+    }
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        Ok(Self::new(params.parse("width")?.unwrap_or(Self::DEFAULT_WIDTH)))
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        ComponentInfo::new(name, "Monostable setting its output for a fixed number of ticks", 4)
+            .with_sequential(true)
+            .with_clock_pin(Self::TRIGGER, "trigger")
+            .with_pin(Self::RESET, "reset", PinDirection::Input)
+            .with_pin(Self::Q, "Q", PinDirection::Output)
+            .with_pin(Self::Q_BAR, "Q'", PinDirection::Output)
+    }
+
+    /// Registers a monostable with a pulse of `width` ticks as the component type `name`.
+    pub fn register(registry: &mut ComponentRegistry, name: &str, width: u64) {
+        registry.register_with_info(name, Self::info(name), move || Box::new(Self::new(width)));
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        HashMap::from([
+            (Self::TRIGGER, PinSpecification::UnidirectionalInput()),
+            (Self::RESET, PinSpecification::UnidirectionalInput()),
+            (Self::Q, PinSpecification::UnidirectionalOutput()),
+            (Self::Q_BAR, PinSpecification::UnidirectionalOutput()),
+        ])
+    }
+}
+
+impl Component for OneShotComponent {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let trigger = self.pins.compute_input(Self::TRIGGER).unwrap();
+
+            if self.previous_trigger.replace(trigger) == false.into() && trigger == true.into() {
+                self.remaining.set(self.width);
+            }
+
+            let output = match self.pins.compute_input(Self::RESET).unwrap() {
+                Tristate::State(true) => {
+                    self.remaining.set(0);
+                    Tristate::State(false)
+                }
+                Tristate::State(false) => Tristate::State(self.remaining.get() > 0),
+                Tristate::Undefined => Tristate::Undefined,
+            };
+            self.remaining.set(self.remaining.get().saturating_sub(1));
+
+            outputs.get(&Self::Q).unwrap().set(output);
+            outputs.get(&Self::Q_BAR).unwrap().set(!output);
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+impl Default for OneShotComponent {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_WIDTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OneShotComponent;
+    use crate::circuit::Circuit;
+    use crate::components::registry::ComponentRegistry;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input trigger
+        input reset
+        pulse stretcher
+        output q
+        .links:
+        trigger:1 stretcher:1
+        reset:1 stretcher:2
+        stretcher:3 q:1
+    ";
+
+    fn build_circuit(width: u64) -> Circuit {
+        let mut registry = ComponentRegistry::with_default_components();
+
+        OneShotComponent::register(&mut registry, "pulse", width);
+        let circuit = Circuit::parse_with_factory(CONTENT, registry).unwrap();
+        circuit.set_value("trigger", "0").unwrap();
+        circuit.set_value("reset", "0").unwrap();
+        circuit
+    }
+
+    fn run(circuit: &mut Circuit, triggers: &str) -> String {
+        triggers
+            .chars()
+            .map(|trigger| {
+                circuit.set_value("trigger", &trigger.to_string()).unwrap();
+                circuit.simulate();
+                circuit.get_output("q").unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pulse_width() {
+        let mut circuit = build_circuit(3);
+
+        assert_eq!(run(&mut circuit, "0111111"), "0111000");
+        assert_eq!(run(&mut circuit, "0101000"), "0111110");
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut circuit = build_circuit(3);
+
+        assert_eq!(run(&mut circuit, "01"), "01");
+        circuit.set_value("reset", "1").unwrap();
+        assert_eq!(run(&mut circuit, "1"), "0");
+        circuit.set_value("reset", "0").unwrap();
+        assert_eq!(run(&mut circuit, "11"), "00");
+    }
+}
@@ -0,0 +1,218 @@
+use std::{cell::Cell, collections::HashMap, rc::Weak};
+
+use crate::{
+    components::{
+        info::{ComponentInfo, PinDirection},
+        params::{CreateError, Params},
+        registry::ComponentRegistry,
+        tristate::Tristate,
+        Component, InvalidPin, PinNumber, Tick,
+    },
+    pin::{PinContainer, PinSpecification},
+};
+
+/// Wiring of a 555 timer, fixing the durations its external resistors and capacitor would give.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Timer555Mode {
+    /// Free running oscillator, high for `high` ticks then low for `low` ticks.
+    Astable { high: u64, low: u64 },
+    /// A falling edge on the trigger sets the output for `width` ticks. Not retriggerable.
+    Monostable { width: u64 },
+}
+
+impl Timer555Mode {
+    /// `mode`: `astable` (the default) or `monostable`, `high` and `low`: durations of the astable cycle,
+    /// `width`: duration of the monostable pulse. Durations are in ticks, 1 by default.
+    pub const PARAMETERS: &'static [&'static str] = &["mode", "high", "low", "width"];
+
+    pub fn from_params(params: &Params) -> Result<Self, CreateError> {
+        let unknown = |key: &str| match params.get(key) {
+            Some(_) => Err(CreateError::UnknownParameter { key: key.to_owned() }),
+            None => Ok(()),
+        };
+
+        match params.get("mode") {
+            None | Some("astable") => {
+                unknown("width")?;
+                Ok(Self::Astable { high: params.parse("high")?.unwrap_or(1), low: params.parse("low")?.unwrap_or(1) })
+            }
+            Some("monostable") => {
+                unknown("high")?;
+                unknown("low")?;
+                Ok(Self::Monostable { width: params.parse("width")?.unwrap_or(1) })
+            }
+            Some(mode) => Err(CreateError::InvalidValue { key: "mode".to_owned(), value: mode.to_owned() }),
+        }
+    }
+}
+
+/// Behavioral 555 timer with its DIP-8 pinout. Only the trigger, output and reset pins are simulated,
+/// the active low reset forcing the output to 0.
+pub struct Timer555Component {
+    pins: PinContainer,
+    mode: Timer555Mode,
+    /// Ticks spent in the astable cycle, or remaining ticks of the monostable pulse.
+    counter: Cell<u64>,
+    previous_trigger: Cell<Tristate>,
+}
+
+impl Timer555Component {
+    pub const TRIGGER: PinNumber = 2;
+    pub const OUTPUT: PinNumber = 3;
+    pub const RESET: PinNumber = 4;
+
+    /// Mode of the `timer555` component type.
+    pub const DEFAULT_MODE: Timer555Mode = Timer555Mode::Astable { high: 1, low: 1 };
+
+    pub fn new(mode: Timer555Mode) -> Self {
+        Self {
+            pins: PinContainer::new(8, Self::build_pins_spec()),
+            mode,
+            counter: Default::default(),
+            previous_trigger: Default::default(),
+        }
+    }
+
+    pub fn info(name: &str) -> ComponentInfo {
+        ComponentInfo::new(name, "555 timer, astable or monostable", 8)
+            .with_sequential(true)
+            .with_pin(1, "GND", PinDirection::Unused)
+            .with_clock_pin(Self::TRIGGER, "TRIG")
+            .with_pin(Self::OUTPUT, "OUT", PinDirection::Output)
+            .with_pin(Self::RESET, "RESET", PinDirection::Input)
+            .with_pin(5, "CTRL", PinDirection::Unused)
+            .with_pin(6, "THR", PinDirection::Unused)
+            .with_pin(7, "DIS", PinDirection::Unused)
+            .with_pin(8, "VCC", PinDirection::Unused)
+    }
+
+    /// Registers a timer wired in `mode` as the component type `name`.
+    pub fn register(registry: &mut ComponentRegistry, name: &str, mode: Timer555Mode) {
+        registry.register_with_info(name, Self::info(name), move || Box::new(Self::new(mode)));
+    }
+
+    #[inline]
+    fn build_pins_spec() -> HashMap<PinNumber, PinSpecification> {
+        HashMap::from([
+            (Self::TRIGGER, PinSpecification::UnidirectionalInput()),
+            (Self::OUTPUT, PinSpecification::UnidirectionalOutput()),
+            (Self::RESET, PinSpecification::UnidirectionalInput()),
+        ])
+    }
+
+    fn next_output(&self, trigger: Tristate) -> bool {
+        let counter = self.counter.get();
+
+        match self.mode {
+            Timer555Mode::Astable { high, low } => {
+                self.counter.set((counter + 1) % (high + low).max(1));
+                counter < high
+            }
+            Timer555Mode::Monostable { width } => {
+                let falling = self.previous_trigger.get() == true.into() && trigger == false.into();
+                let remaining = if counter == 0 && falling { width } else { counter };
+
+                self.counter.set(remaining.saturating_sub(1));
+                remaining > 0
+            }
+        }
+    }
+}
+
+impl Component for Timer555Component {
+    fn set_link(&self, pin: PinNumber, other_component: Weak<dyn Component>, other_pin: PinNumber) -> Result<(), InvalidPin> {
+        self.pins.set_link_to_external_component(pin, other_component, other_pin)
+    }
+
+    fn simulate(&self, tick: Tick) {
+        self.pins.simulate(tick, |outputs| {
+            let trigger = self.pins.compute_input(Self::TRIGGER).unwrap();
+            let output = match self.pins.compute_input(Self::RESET).unwrap() {
+                Tristate::State(true) => Tristate::State(self.next_output(trigger)),
+                Tristate::State(false) => {
+                    self.counter.set(0);
+                    Tristate::State(false)
+                }
+                Tristate::Undefined => Tristate::Undefined,
+            };
+
+            self.previous_trigger.set(trigger);
+            outputs.get(&Self::OUTPUT).unwrap().set(output);
+        })
+    }
+
+    fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
+        self.pins.compute_for_external(pin)
+    }
+
+    fn is_driving(&self, pin: PinNumber) -> bool {
+        self.pins.is_driving(pin).unwrap_or(false)
+    }
+}
+
+impl Default for Timer555Component {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MODE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Timer555Component, Timer555Mode};
+    use crate::circuit::Circuit;
+    use crate::components::registry::ComponentRegistry;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input trigger
+        input reset
+        timer t
+        output out
+        .links:
+        trigger:1 t:2
+        reset:1 t:4
+        t:3 out:1
+    ";
+
+    fn build_circuit(mode: Timer555Mode) -> Circuit {
+        let mut registry = ComponentRegistry::with_default_components();
+
+        Timer555Component::register(&mut registry, "timer", mode);
+        let circuit = Circuit::parse_with_factory(CONTENT, registry).unwrap();
+        circuit.set_value("trigger", "1").unwrap();
+        circuit.set_value("reset", "1").unwrap();
+        circuit
+    }
+
+    fn run(circuit: &mut Circuit, triggers: &str) -> String {
+        triggers
+            .chars()
+            .map(|trigger| {
+                circuit.set_value("trigger", &trigger.to_string()).unwrap();
+                circuit.simulate();
+                circuit.get_output("out").unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_astable() {
+        let mut circuit = build_circuit(Timer555Mode::Astable { high: 3, low: 2 });
+
+        assert_eq!(run(&mut circuit, "1111111111"), "1110011100");
+        circuit.set_value("reset", "0").unwrap();
+        assert_eq!(run(&mut circuit, "11"), "00");
+        circuit.set_value("reset", "1").unwrap();
+        assert_eq!(run(&mut circuit, "1111"), "1110");
+    }
+
+    #[test]
+    fn test_monostable() {
+        let mut circuit = build_circuit(Timer555Mode::Monostable { width: 3 });
+
+        assert_eq!(run(&mut circuit, "110000"), "001110");
+        // Not retriggerable while the pulse lasts.
+        assert_eq!(run(&mut circuit, "101011"), "011100");
+    }
+}
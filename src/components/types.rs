@@ -11,15 +11,171 @@ pub enum ComponentType {
     Input,
     Output,
     Clock,
+    Button,
     True,
     False,
+    BusInput,
+    BusOutput,
+    Splitter,
+    Merger,
+    /* Devices */
+    Keyboard,
+    Terminal,
+    FileIn,
+    FileOut,
+    Gpio,
+    SevenSegment,
+    Logger,
+    Player,
+    Assert,
+    /* Timers */
+    OneShot,
+    Timer555,
+    Divider,
+    /* Generators */
+    Lfsr,
+    /* Generic logic */
+    And,
+    Or,
+    Xor,
+    Mux2,
+    Mux4,
+    Demux2,
+    Demux4,
+    Alu,
+    Lut,
     /* Gates */
-    C4001, // NOR
-    C4011, // NAND
-    C4030, // XOR
-    C4069, // NOT
-    C4071, // OR
-    C4081, // AND
+    C2716,  // 2Kx8 EPROM
+    C4001,  // NOR
+    C4002,  // dual 4-input NOR
+    C4006,  // 18-stage shift register
+    C4008,  // 4-bit adder
+    C4011,  // NAND
+    C4012,  // dual 4-input NAND
+    C4013,  // dual D flip-flop
+    C4014,  // 8-stage parallel-in/serial-out shift register
+    C4015,  // dual 4-stage shift register
+    C4027,  // dual JK flip-flop
+    C4029,  // presettable binary/BCD up/down counter
+    C4030,  // XOR
+    C4043,  // quad NOR RS latch
+    C4044,  // quad NAND RS latch
+    C4047,  // monostable/astable multivibrator
+    C4049,  // hex inverting buffer
+    C4050,  // hex buffer
+    C4060,  // 14-stage ripple counter with oscillator
+    C4068,  // 8-input NAND/AND
+    C4069,  // NOT
+    C4071,  // OR
+    C4072,  // dual 4-input OR
+    C4077,  // XNOR
+    C4078,  // 8-input NOR/OR
+    C4081,  // AND
+    C4082,  // dual 4-input AND
+    C4510,  // presettable BCD up/down counter
+    C4511,  // BCD-to-7-segment decoder
+    C4516,  // presettable up/down counter
+    C4538,  // dual precision monostable
+    C4555,  // dual 1-of-4 decoder
+    C4560,  // BCD adder
+    C4801,  // 256x8 RAM
+    C7400,  // NAND
+    C7404,  // NOT
+    C7408,  // AND
+    C7432,  // OR
+    C7474,  // dual D flip-flop
+    C7485,  // 4-bit comparator
+    C7486,  // XOR
+    C40103, // presettable 8-bit down counter
+    C74138, // 3-to-8 decoder
+    C74157, // quad 2-to-1 multiplexer
+    C74193, // 4-bit up/down counter
+    C74245, // octal bus transceiver
+    C74283, // 4-bit adder
+}
+
+impl ComponentType {
+    pub const ALL: &'static [ComponentType] = &[
+        Self::Input,
+        Self::Output,
+        Self::Clock,
+        Self::Button,
+        Self::True,
+        Self::False,
+        Self::BusInput,
+        Self::BusOutput,
+        Self::Splitter,
+        Self::Merger,
+        Self::Keyboard,
+        Self::Terminal,
+        Self::FileIn,
+        Self::FileOut,
+        Self::Gpio,
+        Self::SevenSegment,
+        Self::Logger,
+        Self::Player,
+        Self::Assert,
+        Self::OneShot,
+        Self::Timer555,
+        Self::Divider,
+        Self::Lfsr,
+        Self::And,
+        Self::Or,
+        Self::Xor,
+        Self::Mux2,
+        Self::Mux4,
+        Self::Demux2,
+        Self::Demux4,
+        Self::Alu,
+        Self::Lut,
+        Self::C2716,
+        Self::C4001,
+        Self::C4002,
+        Self::C4006,
+        Self::C4008,
+        Self::C4011,
+        Self::C4012,
+        Self::C4013,
+        Self::C4014,
+        Self::C4015,
+        Self::C4027,
+        Self::C4029,
+        Self::C4030,
+        Self::C4043,
+        Self::C4044,
+        Self::C4047,
+        Self::C4049,
+        Self::C4050,
+        Self::C4060,
+        Self::C4068,
+        Self::C4069,
+        Self::C4071,
+        Self::C4072,
+        Self::C4077,
+        Self::C4078,
+        Self::C4081,
+        Self::C4082,
+        Self::C4510,
+        Self::C4511,
+        Self::C4516,
+        Self::C4538,
+        Self::C4555,
+        Self::C4560,
+        Self::C4801,
+        Self::C7400,
+        Self::C7404,
+        Self::C7408,
+        Self::C7432,
+        Self::C7474,
+        Self::C7485,
+        Self::C7486,
+        Self::C40103,
+        Self::C74138,
+        Self::C74157,
+        Self::C74193,
+        Self::C74245,
+        Self::C74283,
+    ];
 }
 
 impl FromStr for ComponentType {
@@ -30,14 +186,82 @@ impl FromStr for ComponentType {
             "input" => Ok(Self::Input),
             "output" => Ok(Self::Output),
             "clock" => Ok(Self::Clock),
+            "button" => Ok(Self::Button),
             "true" => Ok(Self::True),
             "false" => Ok(Self::False),
+            "businput" => Ok(Self::BusInput),
+            "busoutput" => Ok(Self::BusOutput),
+            "splitter" => Ok(Self::Splitter),
+            "merger" => Ok(Self::Merger),
+            "keyboard" => Ok(Self::Keyboard),
+            "terminal" => Ok(Self::Terminal),
+            "filein" => Ok(Self::FileIn),
+            "fileout" => Ok(Self::FileOut),
+            "gpio" => Ok(Self::Gpio),
+            "7seg" => Ok(Self::SevenSegment),
+            "logger" => Ok(Self::Logger),
+            "player" => Ok(Self::Player),
+            "assert" => Ok(Self::Assert),
+            "oneshot" => Ok(Self::OneShot),
+            "timer555" => Ok(Self::Timer555),
+            "divider" => Ok(Self::Divider),
+            "lfsr" => Ok(Self::Lfsr),
+            "and" => Ok(Self::And),
+            "or" => Ok(Self::Or),
+            "xor" => Ok(Self::Xor),
+            "mux2" => Ok(Self::Mux2),
+            "mux4" => Ok(Self::Mux4),
+            "demux2" => Ok(Self::Demux2),
+            "demux4" => Ok(Self::Demux4),
+            "alu" => Ok(Self::Alu),
+            "lut" => Ok(Self::Lut),
+            "2716" => Ok(Self::C2716),
             "4001" => Ok(Self::C4001),
+            "4002" => Ok(Self::C4002),
+            "4006" => Ok(Self::C4006),
+            "4008" => Ok(Self::C4008),
             "4011" => Ok(Self::C4011),
+            "4012" => Ok(Self::C4012),
+            "4013" => Ok(Self::C4013),
+            "4014" => Ok(Self::C4014),
+            "4015" => Ok(Self::C4015),
+            "4027" => Ok(Self::C4027),
+            "4029" => Ok(Self::C4029),
             "4030" => Ok(Self::C4030),
+            "4043" => Ok(Self::C4043),
+            "4044" => Ok(Self::C4044),
+            "4047" => Ok(Self::C4047),
+            "4049" => Ok(Self::C4049),
+            "4050" => Ok(Self::C4050),
+            "4060" => Ok(Self::C4060),
+            "4068" => Ok(Self::C4068),
             "4069" => Ok(Self::C4069),
             "4071" => Ok(Self::C4071),
+            "4072" => Ok(Self::C4072),
+            "4077" => Ok(Self::C4077),
+            "4078" => Ok(Self::C4078),
             "4081" => Ok(Self::C4081),
+            "4082" => Ok(Self::C4082),
+            "4510" => Ok(Self::C4510),
+            "4511" => Ok(Self::C4511),
+            "4516" => Ok(Self::C4516),
+            "4538" => Ok(Self::C4538),
+            "4555" => Ok(Self::C4555),
+            "4560" => Ok(Self::C4560),
+            "4801" => Ok(Self::C4801),
+            "7400" => Ok(Self::C7400),
+            "7404" => Ok(Self::C7404),
+            "7408" => Ok(Self::C7408),
+            "7432" => Ok(Self::C7432),
+            "7485" => Ok(Self::C7485),
+            "7486" => Ok(Self::C7486),
+            "40103" => Ok(Self::C40103),
+            "7474" => Ok(Self::C7474),
+            "74138" => Ok(Self::C74138),
+            "74157" => Ok(Self::C74157),
+            "74193" => Ok(Self::C74193),
+            "74245" => Ok(Self::C74245),
+            "74283" => Ok(Self::C74283),
             _ => Err(Self::Err::InvalidValue),
         }
     }
@@ -49,14 +273,82 @@ impl fmt::Display for ComponentType {
             Self::Input => write!(f, "input"),
             Self::Output => write!(f, "output"),
             Self::Clock => write!(f, "clock"),
+            Self::Button => write!(f, "button"),
             Self::True => write!(f, "true"),
             Self::False => write!(f, "false"),
+            Self::BusInput => write!(f, "businput"),
+            Self::BusOutput => write!(f, "busoutput"),
+            Self::Splitter => write!(f, "splitter"),
+            Self::Merger => write!(f, "merger"),
+            Self::Keyboard => write!(f, "keyboard"),
+            Self::Terminal => write!(f, "terminal"),
+            Self::FileIn => write!(f, "filein"),
+            Self::FileOut => write!(f, "fileout"),
+            Self::Gpio => write!(f, "gpio"),
+            Self::SevenSegment => write!(f, "7seg"),
+            Self::Logger => write!(f, "logger"),
+            Self::Player => write!(f, "player"),
+            Self::Assert => write!(f, "assert"),
+            Self::OneShot => write!(f, "oneshot"),
+            Self::Timer555 => write!(f, "timer555"),
+            Self::Divider => write!(f, "divider"),
+            Self::Lfsr => write!(f, "lfsr"),
+            Self::And => write!(f, "and"),
+            Self::Or => write!(f, "or"),
+            Self::Xor => write!(f, "xor"),
+            Self::Mux2 => write!(f, "mux2"),
+            Self::Mux4 => write!(f, "mux4"),
+            Self::Demux2 => write!(f, "demux2"),
+            Self::Demux4 => write!(f, "demux4"),
+            Self::Alu => write!(f, "alu"),
+            Self::Lut => write!(f, "lut"),
+            Self::C2716 => write!(f, "2716"),
             Self::C4001 => write!(f, "4001"),
+            Self::C4002 => write!(f, "4002"),
+            Self::C4006 => write!(f, "4006"),
+            Self::C4008 => write!(f, "4008"),
             Self::C4011 => write!(f, "4011"),
+            Self::C4012 => write!(f, "4012"),
+            Self::C4013 => write!(f, "4013"),
+            Self::C4014 => write!(f, "4014"),
+            Self::C4015 => write!(f, "4015"),
+            Self::C4027 => write!(f, "4027"),
+            Self::C4029 => write!(f, "4029"),
             Self::C4030 => write!(f, "4030"),
+            Self::C4043 => write!(f, "4043"),
+            Self::C4044 => write!(f, "4044"),
+            Self::C4047 => write!(f, "4047"),
+            Self::C4049 => write!(f, "4049"),
+            Self::C4050 => write!(f, "4050"),
+            Self::C4060 => write!(f, "4060"),
+            Self::C4068 => write!(f, "4068"),
             Self::C4069 => write!(f, "4069"),
             Self::C4071 => write!(f, "4071"),
+            Self::C4072 => write!(f, "4072"),
+            Self::C4077 => write!(f, "4077"),
+            Self::C4078 => write!(f, "4078"),
             Self::C4081 => write!(f, "4081"),
+            Self::C4082 => write!(f, "4082"),
+            Self::C4510 => write!(f, "4510"),
+            Self::C4511 => write!(f, "4511"),
+            Self::C4516 => write!(f, "4516"),
+            Self::C4538 => write!(f, "4538"),
+            Self::C4555 => write!(f, "4555"),
+            Self::C4560 => write!(f, "4560"),
+            Self::C4801 => write!(f, "4801"),
+            Self::C7400 => write!(f, "7400"),
+            Self::C7404 => write!(f, "7404"),
+            Self::C7408 => write!(f, "7408"),
+            Self::C7432 => write!(f, "7432"),
+            Self::C7485 => write!(f, "7485"),
+            Self::C7486 => write!(f, "7486"),
+            Self::C40103 => write!(f, "40103"),
+            Self::C7474 => write!(f, "7474"),
+            Self::C74138 => write!(f, "74138"),
+            Self::C74157 => write!(f, "74157"),
+            Self::C74193 => write!(f, "74193"),
+            Self::C74245 => write!(f, "74245"),
+            Self::C74283 => write!(f, "74283"),
         }
     }
 }
@@ -94,22 +386,165 @@ mod tests {
 
     tests_suite_for_type!(clock, "clock", Clock);
 
+    tests_suite_for_type!(button, "button", Button);
+
     tests_suite_for_type!(r#true, "true", True);
 
     tests_suite_for_type!(r#false, "false", False);
 
+    tests_suite_for_type!(businput, "businput", BusInput);
+
+    tests_suite_for_type!(busoutput, "busoutput", BusOutput);
+
+    tests_suite_for_type!(splitter, "splitter", Splitter);
+
+    tests_suite_for_type!(merger, "merger", Merger);
+
+    tests_suite_for_type!(keyboard, "keyboard", Keyboard);
+
+    tests_suite_for_type!(terminal, "terminal", Terminal);
+
+    tests_suite_for_type!(filein, "filein", FileIn);
+
+    tests_suite_for_type!(fileout, "fileout", FileOut);
+
+    tests_suite_for_type!(gpio, "gpio", Gpio);
+
+    tests_suite_for_type!(component_7seg, "7seg", SevenSegment);
+
+    tests_suite_for_type!(logger, "logger", Logger);
+
+    tests_suite_for_type!(player, "player", Player);
+
+    tests_suite_for_type!(assert, "assert", Assert);
+
+    tests_suite_for_type!(oneshot, "oneshot", OneShot);
+
+    tests_suite_for_type!(timer555, "timer555", Timer555);
+
+    tests_suite_for_type!(divider, "divider", Divider);
+
+    tests_suite_for_type!(lfsr, "lfsr", Lfsr);
+
+    tests_suite_for_type!(and, "and", And);
+
+    tests_suite_for_type!(or, "or", Or);
+
+    tests_suite_for_type!(xor, "xor", Xor);
+
+    tests_suite_for_type!(mux2, "mux2", Mux2);
+
+    tests_suite_for_type!(mux4, "mux4", Mux4);
+
+    tests_suite_for_type!(demux2, "demux2", Demux2);
+
+    tests_suite_for_type!(demux4, "demux4", Demux4);
+
+    tests_suite_for_type!(alu, "alu", Alu);
+
+    tests_suite_for_type!(lut, "lut", Lut);
+
+    tests_suite_for_type!(component_2716, "2716", C2716);
+
     tests_suite_for_type!(component_4001, "4001", C4001);
 
+    tests_suite_for_type!(component_4002, "4002", C4002);
+
+    tests_suite_for_type!(component_4006, "4006", C4006);
+
+    tests_suite_for_type!(component_4008, "4008", C4008);
+
     tests_suite_for_type!(component_4011, "4011", C4011);
 
+    tests_suite_for_type!(component_4012, "4012", C4012);
+
+    tests_suite_for_type!(component_4013, "4013", C4013);
+
+    tests_suite_for_type!(component_4014, "4014", C4014);
+
+    tests_suite_for_type!(component_4015, "4015", C4015);
+
+    tests_suite_for_type!(component_4027, "4027", C4027);
+
+    tests_suite_for_type!(component_4029, "4029", C4029);
+
     tests_suite_for_type!(component_4030, "4030", C4030);
 
+    tests_suite_for_type!(component_4043, "4043", C4043);
+
+    tests_suite_for_type!(component_4044, "4044", C4044);
+
+    tests_suite_for_type!(component_4047, "4047", C4047);
+
+    tests_suite_for_type!(component_4049, "4049", C4049);
+
+    tests_suite_for_type!(component_4050, "4050", C4050);
+
+    tests_suite_for_type!(component_4060, "4060", C4060);
+
+    tests_suite_for_type!(component_4068, "4068", C4068);
+
     tests_suite_for_type!(component_4069, "4069", C4069);
 
     tests_suite_for_type!(component_4071, "4071", C4071);
 
+    tests_suite_for_type!(component_4072, "4072", C4072);
+
+    tests_suite_for_type!(component_4077, "4077", C4077);
+
+    tests_suite_for_type!(component_4078, "4078", C4078);
+
     tests_suite_for_type!(component_4081, "4081", C4081);
 
+    tests_suite_for_type!(component_4082, "4082", C4082);
+
+    tests_suite_for_type!(component_4510, "4510", C4510);
+
+    tests_suite_for_type!(component_4511, "4511", C4511);
+
+    tests_suite_for_type!(component_4516, "4516", C4516);
+
+    tests_suite_for_type!(component_4538, "4538", C4538);
+
+    tests_suite_for_type!(component_4555, "4555", C4555);
+
+    tests_suite_for_type!(component_4560, "4560", C4560);
+
+    tests_suite_for_type!(component_4801, "4801", C4801);
+
+    tests_suite_for_type!(component_7400, "7400", C7400);
+
+    tests_suite_for_type!(component_7404, "7404", C7404);
+
+    tests_suite_for_type!(component_7408, "7408", C7408);
+
+    tests_suite_for_type!(component_7432, "7432", C7432);
+
+    tests_suite_for_type!(component_7485, "7485", C7485);
+
+    tests_suite_for_type!(component_7486, "7486", C7486);
+
+    tests_suite_for_type!(component_40103, "40103", C40103);
+
+    tests_suite_for_type!(component_7474, "7474", C7474);
+
+    tests_suite_for_type!(component_74138, "74138", C74138);
+
+    tests_suite_for_type!(component_74157, "74157", C74157);
+
+    tests_suite_for_type!(component_74193, "74193", C74193);
+
+    tests_suite_for_type!(component_74245, "74245", C74245);
+
+    tests_suite_for_type!(component_74283, "74283", C74283);
+
+    #[test]
+    fn test_all_types_round_trip() {
+        for component_type in ComponentType::ALL {
+            assert_eq!(component_type.to_string().parse::<ComponentType>().ok(), Some(*component_type));
+        }
+    }
+
     #[test]
     fn test_string_parse_unknown() {
         assert!(matches!("unknown".parse::<ComponentType>(), Err(ParseComponentTypeError::InvalidValue)));
@@ -1,5 +1,14 @@
 mod circuit;
-mod components;
-mod pin;
+pub mod components;
+pub mod pin;
+#[cfg(feature = "scripting")]
+pub mod script;
+pub mod shell;
 
 pub use circuit::*;
+
+#[cfg(feature = "derive")]
+extern crate self as nanotekspice;
+
+#[cfg(feature = "derive")]
+pub use nanotekspice_derive::Component;
@@ -1,5 +1,62 @@
-use nanotekspice;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
 
-fn main() {
-    println!("Hello, world!");
+use nanotekspice::shell::Shell;
+
+const EXIT_FAILURE: u8 = 84;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+
+    // Without --rc, the startup file of the home directory is read if there is one.
+    let (rc_path, path) = match args.as_slice() {
+        [_, path] => {
+            (std::env::var_os("HOME").map(|home| PathBuf::from(home).join(Shell::RC_FILE)).filter(|rc| rc.is_file()), path)
+        }
+        [_, flag, rc, path] if flag == "--rc" => (Some(PathBuf::from(rc)), path),
+        _ => {
+            eprintln!("Usage: {} [--rc <file>] <circuit.nts>", args.first().map_or("nanotekspice", String::as_str));
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) => {
+            eprintln!("{path}: {error}");
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    // File parameters of the circuit are relative to its directory.
+    let base_path = std::path::Path::new(path).parent().unwrap_or(std::path::Path::new(""));
+    let mut shell = match Shell::new_relative_to(content, base_path) {
+        Ok(shell) => shell,
+        Err(error) => {
+            eprintln!("{path}: {error}");
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    if let Some(rc_path) = rc_path {
+        let sourced = std::fs::read_to_string(&rc_path).and_then(|rc| {
+            let mut errors: Vec<u8> = Vec::new();
+
+            shell.source(&rc, &mut io::stdout(), &mut errors)?;
+            for line in String::from_utf8_lossy(&errors).lines() {
+                eprintln!("{}: {line}", rc_path.display());
+            }
+            Ok(())
+        });
+        if let Err(error) = sourced {
+            eprintln!("{}: {error}", rc_path.display());
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    }
+
+    match shell.run(io::stdin().lock(), &mut io::stdout(), &mut io::stderr()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::from(EXIT_FAILURE),
+    }
 }
@@ -126,7 +126,7 @@ impl PinContainer {
 
     pub fn simulate<F>(&self, tick: Tick, simulate_fn: F)
     where
-        F: FnOnce(&HashMap<PinNumber, &Cell<Tristate>>) -> (),
+        F: FnOnce(&HashMap<PinNumber, &Cell<Tristate>>),
     {
         if let PinContainerState::Available(current_tick) = self.state.get() {
             if current_tick == tick {
@@ -208,7 +208,7 @@ impl PinContainer {
             if let Some(ref component) = *internal_component_proxy {
                 component.clone()
             } else {
-                let component: Rc<dyn Component> = Rc::new(InternalComponentProxy::new(Rc::downgrade(&self)));
+                let component: Rc<dyn Component> = Rc::new(InternalComponentProxy::new(Rc::downgrade(self)));
 
                 *internal_component_proxy = Some(component.clone());
                 component
@@ -243,6 +243,11 @@ impl PinContainer {
         }
     }
 
+    /// Whether `pin` drives its links, rather than reading them.
+    pub fn is_driving(&self, pin: PinNumber) -> Result<bool, InvalidPin> {
+        Ok(self.get_pin(pin)?.is_driving())
+    }
+
     pub fn check(&self, pin: PinNumber) -> Result<(), InvalidPin> {
         self.get_pin_ref(pin).map(|_| ())
     }
@@ -258,8 +263,9 @@ impl PinContainer {
     }
 
     fn simulate_all_inputs(&self, tick: Tick) {
+        // Bidirectional pins are simulated on demand by compute_input(), once the component has chosen their mode.
         for (_, pin_ref) in self.all_pins.iter() {
-            if let Some(pin_ref) = pin_ref.as_input_pin() {
+            if let PinRef::UnidirectionalInput(pin_ref) = pin_ref {
                 pin_ref.simulate(tick);
             }
         }
@@ -344,8 +350,9 @@ impl Component for InternalComponentProxy {
         Ok(())
     }
 
-    fn simulate(&self, tick: Tick) {
-        self.container().simulate_all_inputs(tick)
+    fn simulate(&self, _tick: Tick) {
+        // Inputs are simulated on demand by compute(): simulating all of them here would
+        // re-enter pins still being computed when the container is linked to itself.
     }
 
     fn compute(&self, pin: PinNumber) -> Result<Tristate, InvalidPin> {
@@ -1,5 +1,6 @@
 mod container;
+#[allow(clippy::module_inception)]
 mod pin;
 
 pub use container::{PinContainer, PinSpecification};
-pub use pin::PinMode;
+pub use pin::{restore_counts, set_conflict_policy, take_dangling_links, take_driver_conflicts, ConflictPolicy, PinMode};
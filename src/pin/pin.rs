@@ -9,6 +9,8 @@ use crate::components::{tristate::Tristate, Component, PinNumber, Tick};
 
 pub trait Pin {
     fn compute_for_external(&self) -> Tristate;
+    /// Whether the value of the pin is driven to its links, input pins only reading theirs.
+    fn is_driving(&self) -> bool;
 }
 
 pub trait InputPin: Pin {
@@ -42,6 +44,10 @@ where
 
         compute_output_fn()
     }
+
+    fn is_driving(&self) -> bool {
+        true
+    }
 }
 
 pub struct UnidirectionalInputPin {
@@ -65,12 +71,39 @@ impl UnidirectionalInputPin {
 
         self.input_state.set(PinState::Computing(tick));
 
-        let mut state: Tristate = false.into();
+        let mut drivers: Vec<Tristate> = Vec::new();
+        let mut read_only: bool = false;
+        let mut dangling: usize = 0;
         for link in links.iter() {
-            state |= link.compute(tick);
+            match link.compute(tick) {
+                Some((value, true)) => drivers.push(value),
+                Some((_, false)) => read_only = true,
+                None => dangling += 1,
+            }
         }
+        drop(links);
+
+        if dangling > 0 {
+            // The pin is left as if these links never existed: an unlinked input reads undefined.
+            self.links.borrow_mut().retain(PinLink::is_alive);
+            DANGLING_LINKS.with(|count| count.set(count.get() + dangling));
+        }
+
+        // Without any driver, a net of pins which only read it reads 0.
+        let value = match drivers[..] {
+            [] if read_only => Tristate::State(false),
+            [] => Tristate::Undefined,
+            [value] => value,
+            _ => {
+                let (value, conflict) = CONFLICT_POLICY.with(Cell::get).resolve(&drivers);
+                if conflict {
+                    DRIVER_CONFLICTS.with(|count| count.set(count.get() + 1));
+                }
+                value
+            }
+        };
 
-        self.input_value.set(state);
+        self.input_value.set(value);
         self.input_state.set(PinState::Available(tick));
     }
 }
@@ -79,6 +112,10 @@ impl Pin for UnidirectionalInputPin {
     fn compute_for_external(&self) -> Tristate {
         false.into()
     }
+
+    fn is_driving(&self) -> bool {
+        false
+    }
 }
 
 impl InputPin for UnidirectionalInputPin {
@@ -153,6 +190,10 @@ where
             PinMode::Output => self.output_pin.compute_for_external(),
         }
     }
+
+    fn is_driving(&self) -> bool {
+        matches!(self.mode.get(), PinMode::Output)
+    }
 }
 
 impl<F> InputPin for BidirectionalPin<F>
@@ -160,7 +201,10 @@ where
     F: Fn() -> Tristate,
 {
     fn simulate(&self, tick: Tick) {
-        self.input_pin.simulate(tick);
+        // A pin driving its links does not read them, which would simulate the components reading it too early.
+        if let PinMode::Input = self.mode.get() {
+            self.input_pin.simulate(tick);
+        }
     }
 
     fn link_to(&self, component: Weak<dyn Component>, pin: PinNumber) {
@@ -175,8 +219,90 @@ where
     }
 }
 
+/// How an input pin reads the pins driving it when they disagree.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Undefined drivers are ignored, drivers disagreeing on a defined value read as undefined.
+    #[default]
+    PreferDefined,
+    /// Any disagreement, an undefined driver included, reads as undefined.
+    ForceUndefined,
+    /// Same as `ForceUndefined`, the tick failing with the number of conflicts.
+    Error,
+}
+
+impl ConflictPolicy {
+    /// Value read from `drivers`, and whether they disagree.
+    pub fn resolve(self, drivers: &[Tristate]) -> (Tristate, bool) {
+        let first = drivers.first().copied().unwrap_or_default();
+        let conflict = drivers.iter().any(|&value| value != first);
+        let defined = || drivers.iter().copied().filter(|&value| value != Tristate::Undefined);
+
+        let value = match self {
+            _ if !conflict => first,
+            Self::PreferDefined => match defined().next() {
+                Some(value) if defined().all(|other| other == value) => value,
+                _ => Tristate::Undefined,
+            },
+            Self::ForceUndefined | Self::Error => Tristate::Undefined,
+        };
+        (value, conflict)
+    }
+}
+
+impl std::fmt::Display for ConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PreferDefined => write!(f, "prefer-defined"),
+            Self::ForceUndefined => write!(f, "force-undefined"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl std::str::FromStr for ConflictPolicy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "prefer-defined" => Ok(Self::PreferDefined),
+            "force-undefined" => Ok(Self::ForceUndefined),
+            "error" => Ok(Self::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Number of links to dropped components pruned since the last call, on this thread.
+pub fn take_dangling_links() -> usize {
+    DANGLING_LINKS.with(|count| count.replace(0))
+}
+
+/// Sets the policy of the input pins computed on this thread, returning the previous one.
+pub fn set_conflict_policy(policy: ConflictPolicy) -> ConflictPolicy {
+    CONFLICT_POLICY.with(|current| current.replace(policy))
+}
+
+/// Number of input pins computed with disagreeing drivers since the last call, on this thread.
+pub fn take_driver_conflicts() -> usize {
+    DRIVER_CONFLICTS.with(|count| count.replace(0))
+}
+
+/// Adds back counts returned by `take_dangling_links()` and `take_driver_conflicts()`, e.g. the ones of an
+/// enclosing circuit taken aside while simulating a nested one.
+pub fn restore_counts(dangling_links: usize, driver_conflicts: usize) {
+    DANGLING_LINKS.with(|count| count.set(count.get() + dangling_links));
+    DRIVER_CONFLICTS.with(|count| count.set(count.get() + driver_conflicts));
+}
+
 /* Private helpers */
 
+thread_local! {
+    static DANGLING_LINKS: Cell<usize> = const { Cell::new(0) };
+    static CONFLICT_POLICY: Cell<ConflictPolicy> = const { Cell::new(ConflictPolicy::PreferDefined) };
+    static DRIVER_CONFLICTS: Cell<usize> = const { Cell::new(0) };
+}
+
 #[derive(Clone, Copy)]
 enum PinState {
     NeverComputed,
@@ -201,17 +327,23 @@ impl PinLink {
         Self { component, pin }
     }
 
-    pub fn compute(&self, tick: Tick) -> Tristate {
-        let component = self.component.upgrade().expect("Weak reference lost");
+    /// Value of the linked pin and whether it drives it, `None` if its component was dropped.
+    pub fn compute(&self, tick: Tick) -> Option<(Tristate, bool)> {
+        let component = self.component.upgrade()?;
 
         component.simulate(tick);
-        component.compute(self.pin).expect("Broken link to a pin of a component")
+        let value = component.compute(self.pin).expect("Broken link to a pin of a component");
+        Some((value, component.is_driving(self.pin)))
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.component.strong_count() > 0
     }
 }
 
 impl PartialEq for PinLink {
     fn eq(&self, other: &Self) -> bool {
-        self.component.upgrade().is_some() && self.component.ptr_eq(&other.component) && self.pin == other.pin
+        self.component.ptr_eq(&other.component) && self.pin == other.pin
     }
 }
 
@@ -237,4 +369,37 @@ mod tests {
             assert_eq!(pin.compute_for_external(), true.into());
         }
     }
+
+    mod test_input_pin {
+        use std::rc::Rc;
+
+        use super::*;
+        use crate::components::single_pin::const_component::TrueComponent;
+
+        #[test]
+        fn test_dropped_component_is_pruned() {
+            let kept: Rc<dyn Component> = Rc::new(TrueComponent::new());
+            let dropped: Rc<dyn Component> = Rc::new(TrueComponent::new());
+            let pin = UnidirectionalInputPin::new();
+
+            pin.link_to(Rc::downgrade(&kept), 1);
+            pin.link_to(Rc::downgrade(&dropped), 1);
+            take_dangling_links();
+            drop(dropped);
+
+            pin.simulate(Tick::new(1));
+            assert_eq!(pin.compute_input(), true.into());
+            assert_eq!(pin.links.borrow().len(), 1);
+            assert_eq!(take_dangling_links(), 1);
+
+            drop(kept);
+            pin.simulate(Tick::new(2));
+            assert_eq!(pin.compute_input(), Tristate::Undefined);
+            assert!(pin.links.borrow().is_empty());
+            assert_eq!(take_dangling_links(), 1);
+
+            pin.simulate(Tick::new(3));
+            assert_eq!(take_dangling_links(), 0);
+        }
+    }
 }
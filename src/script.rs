@@ -0,0 +1,154 @@
+//! Rhai scripts driving a circuit from the shell (`script path`, `eval code`).
+//!
+//! Functions available to scripts:
+//! - `set(name, value)`: sets an input for the next tick, `value` being `0`, `1` or a string such as `"U"`
+//! - `get(name)`: value of an input or output, `0` or `1`, `()` when undefined
+//! - `simulate()`, `simulate(n)`: runs one or `n` ticks
+//! - `tick()`: current tick
+//! - `assert(condition)`, `assert(condition, message)`: stops the script when `condition` is false
+//!
+//! `print` and `debug` write to the shell output.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::io::Write;
+use std::rc::Rc;
+
+use rhai::{Dynamic, Engine, EvalAltResult, INT};
+
+use crate::components::tristate::Tristate;
+use crate::shell::ShellError;
+use crate::Circuit;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptError(pub String);
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+fn set(circuit: &Circuit, name: &str, value: &str) -> ScriptResult<()> {
+    circuit.set_value(name, value).map_err(|error| ShellError::from(error).to_string().into())
+}
+
+fn get(circuit: &Circuit, name: &str) -> ScriptResult<Dynamic> {
+    let value = circuit.get_output(name).or_else(|| circuit.get_input(name));
+
+    match value.ok_or_else(|| format!("Unknown input or output \"{name}\""))?.parse() {
+        Ok(Tristate::State(state)) => Ok(Dynamic::from_int(state.into())),
+        _ => Ok(Dynamic::UNIT),
+    }
+}
+
+fn build_engine(circuit: &Rc<RefCell<Circuit>>, output: &Rc<RefCell<Vec<u8>>>) -> Engine {
+    let mut engine = Engine::new();
+
+    let (handle, printed) = (circuit.clone(), output.clone());
+    engine.on_print(move |text| writeln!(printed.borrow_mut(), "{text}").unwrap());
+    let printed = output.clone();
+    engine.on_debug(move |text, _, _| writeln!(printed.borrow_mut(), "{text}").unwrap());
+
+    engine.register_fn("set", move |name: &str, value: INT| set(&handle.borrow(), name, &value.to_string()));
+    let handle = circuit.clone();
+    engine.register_fn("set", move |name: &str, value: &str| set(&handle.borrow(), name, value));
+    let handle = circuit.clone();
+    engine.register_fn("get", move |name: &str| get(&handle.borrow(), name));
+    let handle = circuit.clone();
+    engine.register_fn("simulate", move || handle.borrow_mut().simulate());
+    let handle = circuit.clone();
+    engine.register_fn("simulate", move |ticks: INT| {
+        for _ in 0..ticks {
+            handle.borrow_mut().simulate();
+        }
+    });
+    let handle = circuit.clone();
+    engine.register_fn("tick", move || handle.borrow().current_tick().get() as INT);
+
+    let handle = circuit.clone();
+    let assert = move |condition: bool, message: &str| -> ScriptResult<()> {
+        match condition {
+            true => Ok(()),
+            false => Err(format!("Assertion failed at tick {}{message}", handle.borrow().current_tick().get()).into()),
+        }
+    };
+    let assert_with_message = assert.clone();
+    engine.register_fn("assert", move |condition: bool| assert(condition, ""));
+    engine.register_fn("assert", move |condition: bool, message: &str| assert_with_message(condition, &format!(": {message}")));
+
+    engine
+}
+
+/// Runs `script` on `circuit`, which is given back once the script is over, even if it failed.
+pub fn run(circuit: Circuit, script: &str, output: &mut dyn Write) -> (Circuit, Result<(), ScriptError>) {
+    let circuit = Rc::new(RefCell::new(circuit));
+    let printed = Rc::new(RefCell::new(Vec::new()));
+
+    let result = build_engine(&circuit, &printed).run(script);
+    let written = output.write_all(&printed.borrow());
+    let result = match (result, written) {
+        (Err(error), _) => Err(ScriptError(error.to_string())),
+        (Ok(()), Err(error)) => Err(ScriptError(error.to_string())),
+        (Ok(()), Ok(())) => Ok(()),
+    };
+
+    // The engine and its functions are gone: this is the last handle.
+    let circuit = Rc::try_unwrap(circuit).ok().expect("Circuit still borrowed by the script engine").into_inner();
+    (circuit, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::Circuit;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input in
+        4069 gate
+        output out
+        .links:
+        in:1 gate:1
+        gate:2 out:1
+    ";
+
+    fn run_script(script: &str) -> (Circuit, Result<String, String>) {
+        let mut output = Vec::new();
+        let (circuit, result) = run(CONTENT.parse().unwrap(), script, &mut output);
+
+        (circuit, result.map(|_| String::from_utf8(output).unwrap()).map_err(|error| error.to_string()))
+    }
+
+    #[test]
+    fn test_loop() {
+        let (circuit, result) = run_script(
+            r#"
+            if get("out") == () { print("undefined"); }
+            for t in 0..10 {
+                set("in", t % 2);
+                simulate();
+                assert(get("out") == 1 - t % 2, "inverted");
+            }
+            print(tick());
+            "#,
+        );
+
+        assert_eq!(result, Ok("undefined\n10\n".to_owned()));
+        assert_eq!(circuit.current_tick().get(), 10);
+        assert_eq!(circuit.get_output("out").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_errors() {
+        let (circuit, result) = run_script("set(\"in\", 1); simulate(3); assert(get(\"out\") == 1, \"expected high\");");
+
+        assert!(result.unwrap_err().contains("Assertion failed at tick 3: expected high"));
+        assert_eq!(circuit.current_tick().get(), 3);
+        assert!(run_script("get(\"nope\")").1.unwrap_err().contains("Unknown input or output \"nope\""));
+        assert!(run_script("set(\"out\", \"U\")").1.is_err());
+        assert!(run_script("for").1.is_err());
+    }
+}
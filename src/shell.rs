@@ -0,0 +1,1072 @@
+//! Interactive command interpreter driving a circuit.
+//!
+//! Commands:
+//! - `simulate`: runs one tick
+//! - `display`: prints the current tick, inputs and outputs
+//! - `name=value`: sets an input value for the next tick
+//! - `table [component]`: prints the truth table of the circuit, or of one combinational component
+//! - `help type`: describes a component type, e.g. `help 4013`: whether it is sequential and the name and direction
+//!   of its pins
+//! - `type keyboard text`: queues the characters of `text` on a keyboard
+//! - `attach component path`: attaches a file to a `filein` or `fileout` component
+//! - `load memory path`: loads a binary or Intel HEX (`.hex` extension) image into a memory component
+//! - `dump memory [path]`: prints the bytes of a memory component (`--` when undefined), or saves them to `path`
+//!   in the format of `load`
+//! - `open path as name`: loads another circuit under `name`, the first one being `main`
+//! - `use name`: makes `name` the active circuit
+//! - `circuits`: lists the loaded circuits, the active one marked with `*`
+//! - `save path`: saves the session (circuit sources, ticks and commands run) to `path`; component states are
+//!   not saved but rebuilt by replaying the commands, so a session which used files (`attach`, `load memory`,
+//!   `dump memory path`, `script`) cannot be saved
+//! - `load path`: restores a saved session, replaying its commands
+//! - `script path`, `eval code`: runs a Rhai script on the circuit (`scripting` feature), see `crate::script`
+//! - `waveform [name...]`: prints the values traced since `set trace on`, of every input and output by default
+//! - `alias name = command`: makes `name` stand for `command`, the words following it being appended;
+//!   `alias` lists the aliases and `unalias name` removes one
+//! - `set trace on|off`: records the inputs and outputs of every circuit after each `simulate`
+//! - `set display text|waveform`: format of `display`, the waveform one falling back to text while nothing
+//!   was traced; `set` lists the settings
+//! - `exit`: leaves the shell
+//!
+//! Commands apply to the active circuit, or to another one when prefixed by its name (`a.simulate`).
+//! Aliases and settings are usually defined by a startup file (see `Shell::source()`), and are kept when a
+//! session is loaded.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::components::factory::ComponentFactory;
+use crate::components::registry::ComponentRegistry;
+use crate::{
+    Circuit, DeviceError, History, HistoryError, ParseCircuitError, SetInputError, SimulationError, TruthTable, TruthTableError,
+};
+
+#[derive(Debug, Clone)]
+pub enum ShellError {
+    UnknownCommand(String),
+    UnknownType(String),
+    SetInput(String),
+    TruthTable(TruthTableError),
+    Device(String),
+    Simulation(SimulationError),
+    Workspace(String),
+    Session(String),
+    Script(String),
+    Settings(String),
+}
+
+impl fmt::Display for ShellError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCommand(command) => write!(f, "Unknown command \"{command}\""),
+            Self::UnknownType(component_type) => write!(f, "Unknown component type \"{component_type}\""),
+            Self::SetInput(message) => write!(f, "{message}"),
+            Self::TruthTable(error) => write!(f, "{error}"),
+            Self::Device(message) => write!(f, "{message}"),
+            Self::Simulation(error) => write!(f, "{error}"),
+            Self::Workspace(message) => write!(f, "{message}"),
+            Self::Session(message) => write!(f, "{message}"),
+            Self::Script(message) => write!(f, "{message}"),
+            Self::Settings(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl From<SetInputError<'_>> for ShellError {
+    fn from(value: SetInputError<'_>) -> Self {
+        Self::SetInput(match value {
+            SetInputError::UnknownName(name) => format!("Unknown component \"{name}\""),
+            SetInputError::NotAnInput(name) => format!("\"{name}\" is not an input"),
+            SetInputError::ValueParseError(value) => format!("Invalid value \"{value}\""),
+            SetInputError::NotAClock(name) => format!("\"{name}\" is not a clock"),
+        })
+    }
+}
+
+impl From<DeviceError<'_>> for ShellError {
+    fn from(value: DeviceError<'_>) -> Self {
+        Self::Device(value.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ShellStatus {
+    Continue,
+    Exit,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum DisplayFormat {
+    /// Current tick, inputs and outputs.
+    #[default]
+    Text,
+    /// Values traced so far.
+    Waveform,
+}
+
+impl fmt::Display for DisplayFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Waveform => write!(f, "waveform"),
+        }
+    }
+}
+
+/// Options changed with `set`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Settings {
+    pub trace: bool,
+    pub display: DisplayFormat,
+}
+
+struct LoadedCircuit {
+    name: String,
+    source: String,
+    /// Directory the file parameters of the source are relative to.
+    base_path: Option<PathBuf>,
+    /// Component types the source is parsed with.
+    registry: Rc<ComponentRegistry>,
+    circuit: Circuit,
+    /// Inputs and outputs recorded after each tick, while tracing is on.
+    trace: Option<History>,
+}
+
+pub struct Shell {
+    circuits: Vec<LoadedCircuit>,
+    active: usize,
+    /// Commands which succeeded or completed a tick, replayed when a saved session is loaded.
+    history: Vec<String>,
+    /// First command of the history which used a file, preventing the session from being saved.
+    file_command: Option<String>,
+    /// Component types of every circuit, described by `help`.
+    registry: Rc<ComponentRegistry>,
+    aliases: BTreeMap<String, String>,
+    settings: Settings,
+}
+
+impl Shell {
+    pub const PROMPT: &'static str = "> ";
+    /// Name of the circuit the shell is created with.
+    pub const MAIN: &'static str = "main";
+    /// Startup file read from the home directory, unless another one is given.
+    pub const RC_FILE: &'static str = ".ntsrc";
+    /// First line of the files written by `save`.
+    const SESSION_HEADER: &'static str = "nanotekspice session 1";
+
+    pub fn new(source: String) -> Result<Self, ParseCircuitError> {
+        Self::with_registry(source, None, ComponentRegistry::default())
+    }
+
+    /// Same as `new()`, the file parameters of `source` being relative to `base_path`.
+    pub fn new_relative_to<P: AsRef<Path>>(source: String, base_path: P) -> Result<Self, ParseCircuitError> {
+        Self::with_registry(source, Some(base_path.as_ref().to_owned()), ComponentRegistry::default())
+    }
+
+    /// Shell whose circuits, `source` and the ones opened later, are made of the component types of `registry`.
+    pub fn with_registry(
+        source: String,
+        base_path: Option<PathBuf>,
+        registry: ComponentRegistry,
+    ) -> Result<Self, ParseCircuitError> {
+        let registry = Rc::new(registry);
+        let main = LoadedCircuit::new(Self::MAIN, source, base_path, registry.clone())?;
+
+        Ok(Self::with_circuits(vec![main], registry))
+    }
+
+    fn with_circuits(circuits: Vec<LoadedCircuit>, registry: Rc<ComponentRegistry>) -> Self {
+        Self {
+            circuits,
+            active: 0,
+            history: Vec::new(),
+            file_command: None,
+            registry,
+            aliases: BTreeMap::new(),
+            settings: Settings::default(),
+        }
+    }
+
+    /// The active circuit.
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuits[self.active].circuit
+    }
+
+    pub fn circuit_named(&self, name: &str) -> Option<&Circuit> {
+        self.circuits.iter().find(|loaded| loaded.name == name).map(|loaded| &loaded.circuit)
+    }
+
+    pub fn active_name(&self) -> &str {
+        &self.circuits[self.active].name
+    }
+
+    /// Commands which succeeded or completed a tick so far, session commands excluded.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    pub fn aliases(&self) -> &BTreeMap<String, String> {
+        &self.aliases
+    }
+
+    pub fn settings(&self) -> Settings {
+        self.settings
+    }
+
+    /// Runs the commands of a startup file, one per line, blank lines and lines starting with `#` being skipped.
+    /// A failed command is reported to `errors` with its line, and does not stop the file.
+    pub fn source<W, E>(&mut self, content: &str, output: &mut W, errors: &mut E) -> io::Result<()>
+    where
+        W: Write,
+        E: Write,
+    {
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Err(error) = self.execute(line, output)? {
+                writeln!(errors, "line {}: {error}", index + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs one command line, writing its result to `output`.
+    pub fn execute<W: Write>(&mut self, line: &str, output: &mut W) -> io::Result<Result<ShellStatus, ShellError>> {
+        let line = line.trim();
+        let expanded = self.expand_alias(line);
+
+        match expanded.split_whitespace().collect::<Vec<_>>()[..] {
+            ["save", path] => return Ok(self.save_session(path)),
+            ["load", path] => return Ok(self.load_session(path)),
+            [] | ["exit"] => return self.execute_command(&expanded, output),
+            _ => (),
+        }
+
+        let ticks = self.ticks();
+        let result = self.execute_command(&expanded, output)?;
+        // A failed simulation has completed its tick anyway, which replaying must do too.
+        if result.is_ok() || self.ticks() != ticks {
+            if self.file_command.is_none() && self.uses_file(&expanded) {
+                self.file_command = Some(line.to_owned());
+            }
+            self.history.push(line.to_owned());
+        }
+        Ok(result)
+    }
+
+    /// Current tick of each circuit.
+    fn ticks(&self) -> Vec<u64> {
+        self.circuits.iter().map(|loaded| loaded.circuit.current_tick().get()).collect()
+    }
+
+    /// Whether `command`, aliases expanded, reads or writes a file other than a circuit source.
+    fn uses_file(&self, command: &str) -> bool {
+        let routed = command.split_once('.').filter(|(name, _)| self.position(name).is_some());
+        let command = routed.map_or(command, |(_, command)| command);
+
+        matches!(
+            command.split_whitespace().collect::<Vec<_>>()[..],
+            ["attach", _, _] | ["load", _, _] | ["dump", _, _] | ["script", ..]
+        )
+    }
+
+    /// `line` with its first word replaced if it is an alias. Aliases are not expanded again.
+    fn expand_alias(&self, line: &str) -> String {
+        let (first, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+
+        match self.aliases.get(first) {
+            Some(command) if rest.is_empty() => command.clone(),
+            Some(command) => format!("{command} {rest}"),
+            None => line.to_owned(),
+        }
+    }
+
+    fn alias(&mut self, definition: &str) -> Result<ShellStatus, ShellError> {
+        let invalid = || ShellError::Settings(format!("Invalid alias \"{definition}\", expected \"alias name = command\""));
+        let (name, command) = definition.split_once('=').ok_or_else(invalid)?;
+        let (name, command) = (name.trim(), command.trim());
+
+        if name.is_empty() || command.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '.') {
+            return Err(invalid());
+        }
+        self.aliases.insert(name.to_owned(), command.to_owned());
+        Ok(ShellStatus::Continue)
+    }
+
+    fn set(&mut self, option: &str, value: &str) -> Result<ShellStatus, ShellError> {
+        match (option, value) {
+            ("trace", "on" | "off") => {
+                self.settings.trace = value == "on";
+                for loaded in self.circuits.iter_mut() {
+                    loaded.enable_trace(self.settings.trace);
+                }
+            }
+            ("display", "text") => self.settings.display = DisplayFormat::Text,
+            ("display", "waveform") => self.settings.display = DisplayFormat::Waveform,
+            ("trace" | "display", _) => return Err(ShellError::Settings(format!("Invalid value \"{value}\" for \"{option}\""))),
+            _ => return Err(ShellError::Settings(format!("Unknown setting \"{option}\""))),
+        }
+        Ok(ShellStatus::Continue)
+    }
+
+    fn execute_command<W: Write>(&mut self, line: &str, output: &mut W) -> io::Result<Result<ShellStatus, ShellError>> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        if let Some(definition) = line.strip_prefix("alias ") {
+            return Ok(self.alias(definition));
+        }
+        match words[..] {
+            ["alias"] => {
+                for (name, command) in self.aliases.iter() {
+                    writeln!(output, "alias {name} = {command}")?;
+                }
+                return Ok(Ok(ShellStatus::Continue));
+            }
+            ["unalias", name] => {
+                return Ok(match self.aliases.remove(name) {
+                    Some(_) => Ok(ShellStatus::Continue),
+                    None => Err(ShellError::Settings(format!("Unknown alias \"{name}\""))),
+                })
+            }
+            ["set"] => {
+                writeln!(output, "trace {}", if self.settings.trace { "on" } else { "off" })?;
+                writeln!(output, "display {}", self.settings.display)?;
+                return Ok(Ok(ShellStatus::Continue));
+            }
+            ["set", option, value] => return Ok(self.set(option, value)),
+            _ => (),
+        }
+        match words[..] {
+            ["open", path, "as", name] => return Ok(self.open(path, name).map(|_| ShellStatus::Continue)),
+            ["use", name] => {
+                return Ok(match self.position(name) {
+                    Some(index) => {
+                        self.active = index;
+                        Ok(ShellStatus::Continue)
+                    }
+                    None => Err(ShellError::Workspace(format!("Unknown circuit \"{name}\""))),
+                })
+            }
+            ["circuits"] => {
+                for (index, loaded) in self.circuits.iter().enumerate() {
+                    writeln!(output, "{} {}", if index == self.active { '*' } else { ' ' }, loaded.name)?;
+                }
+                return Ok(Ok(ShellStatus::Continue));
+            }
+            ["help", component_type] => {
+                let info = self
+                    .registry
+                    .parse_type(component_type)
+                    .and_then(|component_type| self.registry.component_info(&component_type));
+
+                return match info {
+                    Some(info) => write!(output, "{info}").map(|_| Ok(ShellStatus::Continue)),
+                    None => Ok(Err(ShellError::UnknownType(component_type.to_owned()))),
+                };
+            }
+            _ => (),
+        }
+
+        let routed = line.split_once('.').and_then(|(name, command)| Some((self.position(name)?, command)));
+        let (index, line) = routed.unwrap_or((self.active, line));
+
+        #[cfg(feature = "scripting")]
+        if let Some(result) = self.run_script(index, line, output) {
+            return Ok(result);
+        }
+        self.circuits[index].execute(line, self.settings, output)
+    }
+
+    /// Runs the `script` and `eval` commands, `None` for other commands.
+    #[cfg(feature = "scripting")]
+    fn run_script<W: Write>(&mut self, index: usize, line: &str, output: &mut W) -> Option<Result<ShellStatus, ShellError>> {
+        let script = match line.split_once(' ') {
+            Some(("eval", code)) => code.to_owned(),
+            Some(("script", path)) => match std::fs::read_to_string(path.trim()) {
+                Ok(script) => script,
+                Err(error) => return Some(Err(ShellError::Script(format!("{}: {error}", path.trim())))),
+            },
+            _ => return None,
+        };
+
+        // The script engine needs to own the circuit while it runs.
+        let mut loaded = self.circuits.remove(index);
+        let (circuit, result) = crate::script::run(loaded.circuit, &script, output);
+        loaded.circuit = circuit;
+        self.circuits.insert(index, loaded);
+
+        Some(result.map(|_| ShellStatus::Continue).map_err(|error| ShellError::Script(error.to_string())))
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.circuits.iter().position(|loaded| loaded.name == name)
+    }
+
+    fn open(&mut self, path: &str, name: &str) -> Result<(), ShellError> {
+        if name.contains(['.', '=']) {
+            return Err(ShellError::Workspace(format!("Invalid circuit name \"{name}\"")));
+        }
+        if self.position(name).is_some() {
+            return Err(ShellError::Workspace(format!("Circuit \"{name}\" is already open")));
+        }
+
+        let source = std::fs::read_to_string(path).map_err(|error| ShellError::Workspace(format!("{path}: {error}")))?;
+        let base_path = Path::new(path).parent().map(Path::to_owned);
+        let mut loaded = LoadedCircuit::new(name, source, base_path, self.registry.clone())
+            .map_err(|error| ShellError::Workspace(format!("{path}: {error}")))?;
+
+        loaded.enable_trace(self.settings.trace);
+        self.circuits.push(loaded);
+        Ok(())
+    }
+
+    /// Writes the sources of the loaded circuits, their ticks and the command history to `path`.
+    ///
+    /// Component states are not written: loading the session replays the commands on fresh circuits.
+    /// The files used by a session may have changed by then, so such a session is refused.
+    fn save_session(&self, path: &str) -> Result<ShellStatus, ShellError> {
+        if let Some(command) = self.file_command.as_ref() {
+            return Err(ShellError::Session(format!(
+                "Cannot save the session: \"{command}\" uses a file, which could not be replayed"
+            )));
+        }
+        let mut content = format!("{}\n", Self::SESSION_HEADER);
+
+        for loaded in self.circuits.iter() {
+            let base_path = loaded.base_path.as_ref().map(|base_path| format!(" {}", base_path.display())).unwrap_or_default();
+
+            content.push_str(&format!(
+                "circuit {} {} {}{base_path}\n",
+                loaded.name,
+                loaded.circuit.current_tick().get(),
+                loaded.source.lines().count()
+            ));
+            for line in loaded.source.lines() {
+                content.push_str(line);
+                content.push('\n');
+            }
+        }
+        for command in self.history.iter() {
+            content.push_str(&format!("command {command}\n"));
+        }
+
+        std::fs::write(path, content).map_err(|error| ShellError::Session(format!("{path}: {error}")))?;
+        Ok(ShellStatus::Continue)
+    }
+
+    fn load_session(&mut self, path: &str) -> Result<ShellStatus, ShellError> {
+        let content = std::fs::read_to_string(path).map_err(|error| ShellError::Session(format!("{path}: {error}")))?;
+
+        let mut shell = Self::from_session(&content, self.registry.clone())
+            .map_err(|error| ShellError::Session(format!("{path}: {error}")))?;
+
+        // Aliases and settings belong to the user rather than to the session.
+        for loaded in shell.circuits.iter_mut() {
+            loaded.enable_trace(self.settings.trace);
+        }
+        shell.aliases = std::mem::take(&mut self.aliases);
+        shell.settings = self.settings;
+        *self = shell;
+        Ok(ShellStatus::Continue)
+    }
+
+    /// Rebuilds the shell saved in `content` by `save`.
+    fn from_session(content: &str, registry: Rc<ComponentRegistry>) -> Result<Self, String> {
+        let mut lines = content.lines();
+        let mut circuits: Vec<(LoadedCircuit, u64)> = Vec::new();
+        let mut history: Vec<&str> = Vec::new();
+
+        if lines.next() != Some(Self::SESSION_HEADER) {
+            return Err("Not a session file".to_owned());
+        }
+        while let Some(line) = lines.next() {
+            if let Some(command) = line.strip_prefix("command ") {
+                history.push(command);
+                continue;
+            }
+
+            let invalid = || format!("Invalid session line \"{line}\"");
+            let mut words = line.splitn(5, ' ');
+            let (Some("circuit"), Some(name), Some(tick), Some(nb_lines)) =
+                (words.next(), words.next(), words.next(), words.next())
+            else {
+                return Err(invalid());
+            };
+            let (Ok(tick), Ok(nb_lines)) = (tick.parse::<u64>(), nb_lines.parse::<usize>()) else {
+                return Err(invalid());
+            };
+            let source: Vec<&str> = lines.by_ref().take(nb_lines).collect();
+            if source.len() < nb_lines {
+                return Err(format!("Truncated source of circuit \"{name}\""));
+            }
+
+            let base_path = words.next().map(PathBuf::from);
+            let loaded = LoadedCircuit::new(name, source.join("\n"), base_path, registry.clone())
+                .map_err(|error| format!("{name}: {error}"))?;
+            circuits.push((loaded, tick));
+        }
+        if circuits.is_empty() {
+            return Err("No circuit".to_owned());
+        }
+
+        let ticks: Vec<u64> = circuits.iter().map(|(_, tick)| *tick).collect();
+        let mut shell = Self::with_circuits(circuits.into_iter().map(|(loaded, _)| loaded).collect(), registry);
+
+        // The circuits opened during the session are already loaded.
+        for command in history {
+            if command.starts_with("open ") {
+                shell.history.push(command.to_owned());
+            } else if shell.uses_file(&shell.expand_alias(command)) {
+                return Err(format!("{command}: Commands using files cannot be replayed"));
+            } else {
+                let before = shell.ticks();
+
+                // Commands were saved when they failed after completing a tick, which they do again.
+                match shell.execute(command, &mut io::sink()).map_err(|error| error.to_string())? {
+                    Err(error) if shell.ticks() == before => return Err(format!("{command}: {error}")),
+                    _ => (),
+                }
+            }
+        }
+        for (loaded, tick) in shell.circuits.iter().zip(ticks) {
+            if loaded.circuit.current_tick().get() != tick {
+                return Err(format!(
+                    "Circuit \"{}\" replayed to tick {} instead of {tick}",
+                    loaded.name,
+                    loaded.circuit.current_tick().get()
+                ));
+            }
+        }
+
+        Ok(shell)
+    }
+
+    /// Reads commands from `input` until `exit` or end of input. Errors are written to `errors`.
+    pub fn run<R, W, E>(&mut self, input: R, output: &mut W, errors: &mut E) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+        E: Write,
+    {
+        let mut lines = input.lines();
+
+        loop {
+            write!(output, "{}", Self::PROMPT)?;
+            output.flush()?;
+
+            let Some(line) = lines.next() else {
+                return Ok(());
+            };
+            match self.execute(&line?, output)? {
+                Ok(ShellStatus::Continue) => (),
+                Ok(ShellStatus::Exit) => return Ok(()),
+                Err(error) => writeln!(errors, "{error}")?,
+            }
+        }
+    }
+}
+
+impl LoadedCircuit {
+    fn new(
+        name: &str,
+        source: String,
+        base_path: Option<PathBuf>,
+        registry: Rc<ComponentRegistry>,
+    ) -> Result<Self, ParseCircuitError> {
+        let circuit = Self::parse(&source, base_path.as_deref(), &registry)?;
+
+        Ok(Self { name: name.to_owned(), source, base_path, registry, circuit, trace: None })
+    }
+
+    /// Starts a trace from the current values, or drops the trace.
+    fn enable_trace(&mut self, enabled: bool) {
+        match (enabled, self.trace.is_some()) {
+            (true, false) => {
+                let mut trace = History::new();
+                trace.record(&self.circuit);
+                self.trace = Some(trace);
+            }
+            (false, true) => self.trace = None,
+            _ => (),
+        }
+    }
+
+    /// Waveform of `names`, every input and output if empty.
+    fn waveform(&self, names: &[&str]) -> Result<String, ShellError> {
+        let trace = self.trace.as_ref().ok_or_else(|| ShellError::Settings("Tracing is off, see \"set trace on\"".to_owned()))?;
+        let all: Vec<&str> = self.circuit.signals().map(|(name, _)| name).collect();
+
+        trace
+            .render_ascii(if names.is_empty() { &all } else { names })
+            .map_err(|HistoryError::UnknownSignal(name)| ShellError::Settings(format!("Unknown signal \"{name}\"")))
+    }
+
+    fn parse(source: &str, base_path: Option<&Path>, registry: &ComponentRegistry) -> Result<Circuit, ParseCircuitError> {
+        Circuit::parse_with_factory_relative_to(source, registry, base_path)
+    }
+
+    fn execute<W: Write>(
+        &mut self,
+        line: &str,
+        settings: Settings,
+        output: &mut W,
+    ) -> io::Result<Result<ShellStatus, ShellError>> {
+        let line = line.trim();
+        let mut words = line.split_whitespace();
+
+        if line.split_whitespace().next() == Some("waveform") {
+            let names: Vec<&str> = words.skip(1).collect();
+
+            return match self.waveform(&names) {
+                Ok(waveform) => write!(output, "{waveform}").map(|_| Ok(ShellStatus::Continue)),
+                Err(error) => Ok(Err(error)),
+            };
+        }
+
+        // The typed text is kept as is, spaces included.
+        if let Some((keyboard, text)) = line.strip_prefix("type ").and_then(|rest| rest.trim_start().split_once(' ')) {
+            return Ok(self.circuit.type_text(keyboard, text).map(|_| ShellStatus::Continue).map_err(ShellError::from));
+        }
+
+        match (words.next(), words.next(), words.next()) {
+            (None, _, _) => (),
+            (Some("exit"), None, _) => return Ok(Ok(ShellStatus::Exit)),
+            (Some("simulate"), None, _) => {
+                let result = self.circuit.try_simulate();
+
+                if let Some(trace) = self.trace.as_mut() {
+                    trace.record(&self.circuit);
+                }
+                if let Err(error) = result {
+                    return Ok(Err(ShellError::Simulation(error)));
+                }
+            }
+            (Some("display"), None, _) => match (settings.display, self.waveform(&[])) {
+                (DisplayFormat::Waveform, Ok(waveform)) => write!(output, "{waveform}")?,
+                _ => write!(output, "{}", self.circuit)?,
+            },
+            (Some("table"), component, None) => {
+                let table = match component {
+                    Some(name) => TruthTable::of_component(&self.circuit, name, &*self.registry),
+                    // The source has already been parsed successfully once.
+                    None => {
+                        TruthTable::of_circuit(|| Self::parse(&self.source, self.base_path.as_deref(), &self.registry).unwrap())
+                    }
+                };
+                match table {
+                    Ok(table) => write!(output, "{table}")?,
+                    Err(error) => return Ok(Err(ShellError::TruthTable(error))),
+                }
+            }
+            (Some("attach"), Some(name), Some(path)) if words.next().is_none() => {
+                if let Err(error) = self.circuit.attach_file(name, path) {
+                    return Ok(Err(error.into()));
+                }
+            }
+            (Some("load"), Some(name), Some(path)) if words.next().is_none() => {
+                if let Err(error) = self.circuit.load_memory(name, path) {
+                    return Ok(Err(error.into()));
+                }
+            }
+            (Some("dump"), Some(name), None) => match self.circuit.dump_memory(name) {
+                Ok(bytes) => {
+                    for (row, chunk) in bytes.chunks(16).enumerate() {
+                        let cells: Vec<String> =
+                            chunk.iter().map(|byte| byte.map_or_else(|| "--".to_owned(), |byte| format!("{byte:02X}"))).collect();
+
+                        writeln!(output, "{:04X}: {}", row * 16, cells.join(" "))?;
+                    }
+                }
+                Err(error) => return Ok(Err(error.into())),
+            },
+            (Some("dump"), Some(name), Some(path)) if words.next().is_none() => {
+                if let Err(error) = self.circuit.save_memory(name, path) {
+                    return Ok(Err(error.into()));
+                }
+            }
+            (Some(assignment), None, _) if assignment.contains('=') => {
+                let (name, value) = assignment.split_once('=').unwrap();
+
+                match self.circuit.set_value_checked(name, value) {
+                    Ok(Some(warning)) => writeln!(output, "{warning}")?,
+                    Ok(None) => (),
+                    Err(error) => return Ok(Err(error.into())),
+                }
+            }
+            _ => return Ok(Err(ShellError::UnknownCommand(line.to_owned()))),
+        }
+
+        Ok(Ok(ShellStatus::Continue))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::{Shell, ShellError, ShellStatus};
+    use crate::components::gates::one_input::GateBUF;
+    use crate::components::info::{ComponentInfo, PinDirection};
+    use crate::components::registry::ComponentRegistry;
+    use crate::pin::ConflictPolicy;
+
+    const CONTENT: &str = "
+        .chipsets:
+        input a
+        input b
+        4081 gate
+        output out
+        .links:
+        a:1 gate:1
+        b:1 gate:2
+        gate:3 out:1
+    ";
+
+    fn run(commands: &str) -> (String, String) {
+        let mut shell = Shell::new(CONTENT.to_owned()).unwrap();
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+
+        shell.run(commands.as_bytes(), &mut output, &mut errors).unwrap();
+        (String::from_utf8(output).unwrap(), String::from_utf8(errors).unwrap())
+    }
+
+    #[test]
+    fn test_simulate_and_display() {
+        let (output, errors) = run("a=1\nb=1\nsimulate\ndisplay\nexit\n");
+
+        assert_eq!(output, "> > > > tick: 1\ninput(s):\n  a: 1\n  b: 1\noutput(s):\n  out: 1\n> ");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let (_, errors) = run("foo\nc=1\na=2\ntable nope\n");
+
+        assert_eq!(errors, "Unknown command \"foo\"\nUnknown component \"c\"\nInvalid value \"2\"\nUnknown component \"nope\"\n");
+    }
+
+    #[test]
+    fn test_clock_warning() {
+        let mut shell = Shell::new(".chipsets:\nclock clk\noutput out\n.links:\nclk:1 out:1\n".to_owned()).unwrap();
+        let mut output = Vec::new();
+
+        shell.execute("clk=1", &mut output).unwrap().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "warning[clock-override]: 'clk' is a clock: setting it to 1 overrides its toggling\n"
+        );
+    }
+
+    #[test]
+    fn test_help() {
+        let (output, errors) = run("help 4013\nhelp 4081\nhelp nope\nmain.help 4013\n");
+
+        assert!(output.starts_with("> 4013: "));
+        assert!(output.contains("\n  sequential\n"));
+        assert!(output.contains("\n  pin  1: "));
+        assert!(
+            output.contains("\n  combinational\n  pin  1: in_1a (input)\n  pin  2: in_1b (input)\n  pin  3: out_1 (output)\n")
+        );
+        assert!(!output.contains("VDD"));
+        assert_eq!(errors, "Unknown component type \"nope\"\nUnknown command \"help 4013\"\n");
+    }
+
+    #[test]
+    fn test_registry_types() {
+        let mut registry = ComponentRegistry::default();
+        let info = ComponentInfo::new("buffer", "Non-inverting buffer", 2)
+            .with_pin(1, "in", PinDirection::Input)
+            .with_pin(2, "out", PinDirection::Output)
+            .with_dependencies(2, &[1]);
+        registry.register_with_info("buffer", info, || Box::new(GateBUF::new()));
+        let source = ".chipsets:\ninput a\nbuffer buf\noutput out\n.links:\na:1 buf:1\nbuf:2 out:1\n".to_owned();
+        let mut shell = Shell::with_registry(source, None, registry).unwrap();
+        let mut output = Vec::new();
+
+        shell.execute("help buffer", &mut output).unwrap().unwrap();
+        shell.execute("table buf", &mut output).unwrap().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "buffer: Non-inverting buffer\n  combinational\n  pin  1: in (input)\n  pin  2: out (output)\n1 | 2\n0 | 0\n1 | 1\n"
+        );
+    }
+
+    #[test]
+    fn test_type() {
+        let mut shell = Shell::new(".chipsets:\nkeyboard kbd\noutput ready\n.links:\nkbd:9 ready:1\n".to_owned()).unwrap();
+        let mut output = Vec::new();
+
+        shell.execute("type kbd a b", &mut output).unwrap().unwrap();
+        shell.execute("simulate", &mut output).unwrap().unwrap();
+        assert_eq!(shell.circuit().get_output("ready").unwrap(), "1");
+        assert!(matches!(shell.execute("type ready x", &mut output).unwrap(), Err(ShellError::Device(_))));
+        assert!(matches!(shell.execute("attach kbd file", &mut output).unwrap(), Err(ShellError::Device(_))));
+    }
+
+    #[test]
+    fn test_load() {
+        let mut shell = Shell::new(".chipsets:\n2716 rom\n".to_owned()).unwrap();
+        let path = std::env::temp_dir().join("nts_shell_load.bin");
+        let mut output = Vec::new();
+
+        std::fs::write(&path, [0x2A]).unwrap();
+        shell.execute(&format!("load rom {}", path.display()), &mut output).unwrap().unwrap();
+        assert_eq!(shell.circuit().read_memory("rom", 0).unwrap(), Some(0x2A));
+        assert!(matches!(shell.execute("load rom /nonexistent/image", &mut output).unwrap(), Err(ShellError::Device(_))));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_dump() {
+        let mut shell = Shell::new(".chipsets:\n4801 ram\n".to_owned()).unwrap();
+        let path = std::env::temp_dir().join("nts_shell_dump.hex");
+        let mut output = Vec::new();
+
+        shell.circuit().load_rom("ram", &[0x2A, 0x2B]).unwrap();
+        shell.execute("dump ram", &mut output).unwrap().unwrap();
+        shell.execute(&format!("dump ram {}", path.display()), &mut output).unwrap().unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 16);
+        assert!(output.starts_with("0000: 2A 2B -- -- -- -- -- -- -- -- -- -- -- -- -- --\n0010: --"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), ":020000002A2BA9\n:00000001FF\n");
+        assert!(matches!(shell.execute("dump nope", &mut Vec::new()).unwrap(), Err(ShellError::Device(_))));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_table_keeps_simulation_state() {
+        let mut shell = Shell::new(CONTENT.to_owned()).unwrap();
+        let mut output = Vec::new();
+
+        shell.execute("a=1", &mut output).unwrap().unwrap();
+        shell.execute("simulate", &mut output).unwrap().unwrap();
+        assert_eq!(shell.execute("table", &mut output).unwrap().unwrap(), ShellStatus::Continue);
+
+        assert_eq!(String::from_utf8(output).unwrap(), "a b | out\n0 0 |   0\n0 1 |   0\n1 0 |   0\n1 1 |   1\n");
+        assert_eq!(shell.circuit().current_tick().get(), 1);
+        assert_eq!(shell.circuit().get_input("a").unwrap(), "1");
+        assert_eq!(shell.circuit().get_output("out").unwrap(), "U");
+    }
+
+    #[test]
+    fn test_component_table() {
+        let mut shell = Shell::new(CONTENT.to_owned()).unwrap();
+        let mut output = Vec::new();
+
+        shell.execute("table gate", &mut output).unwrap().unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("1 2 5 6 8 9 12 13 | 3 4 10 11\n0 0 0 0 0 0  0  0 | 0 0  0  0\n"));
+        assert_eq!(output.lines().count(), 257);
+        assert!(matches!(shell.execute("table a b", &mut Vec::new()).unwrap(), Err(ShellError::UnknownCommand(_))));
+    }
+
+    #[test]
+    fn test_workspace() {
+        let mut shell = Shell::new(CONTENT.to_owned()).unwrap();
+        let path = std::env::temp_dir().join("nts_shell_workspace.nts");
+        let mut output = Vec::new();
+
+        std::fs::write(&path, ".chipsets:\ninput a\n4069 gate\noutput out\n.links:\na:1 gate:1\ngate:2 out:1\n").unwrap();
+        shell.execute(&format!("open {} as inv", path.display()), &mut output).unwrap().unwrap();
+        assert_eq!(shell.active_name(), "main");
+
+        shell.execute("inv.a=0", &mut output).unwrap().unwrap();
+        shell.execute("inv.simulate", &mut output).unwrap().unwrap();
+        assert_eq!(shell.circuit_named("inv").unwrap().get_output("out").unwrap(), "1");
+        assert_eq!(shell.circuit().current_tick().get(), 0);
+
+        shell.execute("use inv", &mut output).unwrap().unwrap();
+        shell.execute("circuits", &mut output).unwrap().unwrap();
+        shell.execute("main.display", &mut output).unwrap().unwrap();
+        assert_eq!(shell.circuit().current_tick().get(), 1);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "  main\n* inv\ntick: 0\ninput(s):\n  a: U\n  b: U\noutput(s):\n  out: U\n"
+        );
+
+        let mut errors = Vec::new();
+        let open = format!("open {} as inv", path.display());
+        for command in [open.as_str(), "open /nonexistent.nts as other", "open x as a.b", "use nope", "nope.simulate"] {
+            let error = shell.execute(command, &mut Vec::new()).unwrap().unwrap_err();
+            writeln!(errors, "{error}").unwrap();
+        }
+        let errors = String::from_utf8(errors).unwrap();
+        assert!(errors.starts_with("Circuit \"inv\" is already open\n/nonexistent.nts: "));
+        assert!(errors.ends_with("Invalid circuit name \"a.b\"\nUnknown circuit \"nope\"\nUnknown command \"nope.simulate\"\n"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_session() {
+        let mut shell = Shell::new(CONTENT.to_owned()).unwrap();
+        let path = std::env::temp_dir().join("nts_shell_session.nsim");
+        let inverter = std::env::temp_dir().join("nts_shell_session.nts");
+        let save = format!("save {}", path.display());
+        let mut output = Vec::new();
+
+        std::fs::write(&inverter, ".chipsets:\ninput a\n4069 gate\noutput out\n.links:\na:1 gate:1\ngate:2 out:1\n").unwrap();
+        let open = format!("open {} as inv", inverter.display());
+        for command in
+            ["a=1", "b=1", "simulate", "nope", open.as_str(), "use inv", "a=0", "simulate", "main.simulate", save.as_str()]
+        {
+            let _ = shell.execute(command, &mut output).unwrap();
+        }
+        // The session does not depend on the circuit files anymore.
+        std::fs::remove_file(&inverter).unwrap();
+
+        let mut restored = Shell::new(".chipsets:\ninput x\n".to_owned()).unwrap();
+        restored.execute(&format!("load {}", path.display()), &mut output).unwrap().unwrap();
+
+        assert_eq!(restored.history(), shell.history());
+        assert_eq!(restored.history().len(), 8);
+        assert_eq!(restored.active_name(), "inv");
+        assert_eq!(restored.circuit().get_output("out").unwrap(), "1");
+        let main = restored.circuit_named("main").unwrap();
+        assert_eq!(main.current_tick().get(), 2);
+        assert_eq!(main.get_output("out").unwrap(), "1");
+
+        std::fs::write(&path, "nanotekspice session 1\ncircuit main 3 9\n.chipsets:\ninput a\n").unwrap();
+        let error = restored.execute(&format!("load {}", path.display()), &mut output).unwrap().unwrap_err();
+        assert!(error.to_string().ends_with("Truncated source of circuit \"main\""));
+        std::fs::write(&path, "nanotekspice session 1\ncircuit main 3 2\n.chipsets:\ninput a\ncommand simulate\n").unwrap();
+        let error = restored.execute(&format!("load {}", path.display()), &mut output).unwrap().unwrap_err();
+        assert!(error.to_string().ends_with("Circuit \"main\" replayed to tick 1 instead of 3"));
+        assert_eq!(restored.active_name(), "inv");
+        std::fs::write(
+            &path,
+            "nanotekspice session 1\ncircuit main 0 2\n.chipsets:\ninput x\ncommand alias a = attach\ncommand a f in\n",
+        )
+        .unwrap();
+        let error = restored.execute(&format!("load {}", path.display()), &mut output).unwrap().unwrap_err();
+        assert!(error.to_string().ends_with("a f in: Commands using files cannot be replayed"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_session_after_failed_simulation() {
+        let mut shell =
+            Shell::new(".chipsets:\ninput a\ninput b\noutput out\n.links:\na:1 out:1\nb:1 out:1\n".to_owned()).unwrap();
+        let path = std::env::temp_dir().join("nts_shell_session_failed.nsim");
+        let mut output = Vec::new();
+
+        shell.circuits[0].circuit.set_conflict_policy(ConflictPolicy::Error);
+        for command in ["a=1", "b=0", "simulate", "a=0", "simulate"] {
+            let result = shell.execute(command, &mut output).unwrap();
+            assert_eq!(result.is_err(), shell.history().len() == 3, "{command}");
+        }
+        assert_eq!(shell.history(), ["a=1", "b=0", "simulate", "a=0", "simulate"]);
+        assert_eq!(shell.circuit().current_tick().get(), 2);
+        shell.execute(&format!("save {}", path.display()), &mut output).unwrap().unwrap();
+
+        let mut restored = Shell::new(".chipsets:\ninput x\n".to_owned()).unwrap();
+        restored.execute(&format!("load {}", path.display()), &mut output).unwrap().unwrap();
+        assert_eq!(restored.history(), shell.history());
+        assert_eq!(restored.circuit().current_tick().get(), 2);
+        assert_eq!(restored.circuit().get_output("out").unwrap(), "0");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_session_with_files() {
+        let mut shell = Shell::new(".chipsets:\n4801 ram\n".to_owned()).unwrap();
+        let path = std::env::temp_dir().join("nts_shell_session_files.nsim");
+        let dump = std::env::temp_dir().join("nts_shell_session_files.bin");
+        let save = format!("save {}", path.display());
+        let mut output = Vec::new();
+
+        shell.execute("dump ram", &mut output).unwrap().unwrap();
+        shell.execute(&save, &mut output).unwrap().unwrap();
+        shell.execute(&format!("main.dump ram {}", dump.display()), &mut output).unwrap().unwrap();
+        let error = shell.execute(&save, &mut output).unwrap().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("Cannot save the session: \"main.dump ram {}\" uses a file, which could not be replayed", dump.display())
+        );
+        std::fs::remove_file(dump).unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_aliases() {
+        let (output, errors) = run("alias high = a=1\nalias sim = simulate\nhigh\nb=1\nsim\nalias d = main.display\nd\nalias\nunalias d\nd\nalias x.y = z\nunalias d\n");
+
+        assert!(output.contains("output(s):\n  out: 1\n"));
+        assert!(output.contains("alias d = main.display\nalias high = a=1\nalias sim = simulate\n"));
+        assert_eq!(
+            errors,
+            "Unknown command \"d\"\nInvalid alias \"x.y = z\", expected \"alias name = command\"\nUnknown alias \"d\"\n"
+        );
+    }
+
+    #[test]
+    fn test_trace_settings() {
+        let (output, errors) = run("set trace on\nset display waveform\na=1\nb=1\nsimulate\nb=0\nsimulate\ndisplay\nwaveform out\nset\nset trace maybe\nset colors on\n");
+
+        assert!(output.contains("  a |xx‾‾‾‾\n  b |xx‾‾\\_\nout |xx‾‾\\_\n"));
+        assert!(output.contains("> out |xx‾‾\\_\n> trace on\ndisplay waveform\n"));
+        assert_eq!(errors, "Invalid value \"maybe\" for \"trace\"\nUnknown setting \"colors\"\n");
+
+        let (output, errors) = run("waveform\ndisplay\n");
+        assert!(output.contains("tick: 0"));
+        assert_eq!(errors, "Tracing is off, see \"set trace on\"\n");
+    }
+
+    #[test]
+    fn test_source() {
+        let mut shell = Shell::new(CONTENT.to_owned()).unwrap();
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        let rc = "# startup\nalias s = simulate\n\nset trace on\nset trace twice\n";
+
+        shell.source(rc, &mut output, &mut errors).unwrap();
+        assert_eq!(String::from_utf8(errors).unwrap(), "line 5: Invalid value \"twice\" for \"trace\"\n");
+        assert_eq!(shell.aliases().get("s").map(String::as_str), Some("simulate"));
+        assert!(shell.settings().trace);
+
+        // Aliases and settings survive loading a session.
+        let path = std::env::temp_dir().join("nts_shell_source.nsim");
+        shell.execute("s", &mut output).unwrap().unwrap();
+        shell.execute(&format!("save {}", path.display()), &mut output).unwrap().unwrap();
+        shell.execute(&format!("load {}", path.display()), &mut output).unwrap().unwrap();
+        assert_eq!(shell.circuit().current_tick().get(), 1);
+        assert!(shell.settings().trace);
+        assert!(shell.execute("s", &mut output).unwrap().is_ok());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn test_script() {
+        let mut shell = Shell::new(CONTENT.to_owned()).unwrap();
+        let path = std::env::temp_dir().join("nts_shell_script.rhai");
+        let mut output = Vec::new();
+
+        std::fs::write(&path, "set(\"a\", 1);\nfor b in 0..2 { set(\"b\", b); simulate(); print(get(\"out\")); }\n").unwrap();
+        shell.execute(&format!("script {}", path.display()), &mut output).unwrap().unwrap();
+        shell.execute("main.eval assert(tick() == 2)", &mut output).unwrap().unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "0\n1\n");
+        assert_eq!(shell.circuit().current_tick().get(), 2);
+
+        let error = shell.execute("eval assert(get(\"out\") == 0)", &mut Vec::new()).unwrap().unwrap_err();
+        assert!(matches!(error, ShellError::Script(_)));
+        assert!(matches!(shell.execute("script /nonexistent.rhai", &mut Vec::new()).unwrap(), Err(ShellError::Script(_))));
+        std::fs::remove_file(&path).unwrap();
+
+        // A script failing after a tick is replayed when the session is loaded, failing again.
+        let mut shell = Shell::new(CONTENT.to_owned()).unwrap();
+        let session = std::env::temp_dir().join("nts_shell_script.nsim");
+        assert!(shell.execute("eval simulate(); throw \"late\"", &mut Vec::new()).unwrap().is_err());
+        shell.execute(&format!("save {}", session.display()), &mut Vec::new()).unwrap().unwrap();
+        shell.execute(&format!("load {}", session.display()), &mut Vec::new()).unwrap().unwrap();
+        assert_eq!(shell.circuit().current_tick().get(), 1);
+        std::fs::remove_file(session).unwrap();
+    }
+}
@@ -1 +1,2 @@
 mod parser;
+mod registry;
@@ -1,13 +1,47 @@
 use std::fs::read_to_string;
+use std::path::Path;
 use test_generator::test_resources;
 
-use nanotekspice::{BuildErrorKind, Circuit, ParseCircuitError, SyntaxErrorKind};
+use nanotekspice::components::params::CreateError;
+use nanotekspice::components::tristate::Tristate;
+use nanotekspice::{
+    parse_instructions, BuildErrorKind, Circuit, CircuitBuilder, Endpoint, Instruction, ParseCircuitError, PinRef,
+    SyntaxErrorKind,
+};
 
 #[test_resources("tests/.nts/input_output.nts")]
 fn read_a_nts_file(resource: &str) {
     let content = read_to_string(resource).unwrap();
 
-    assert!(matches!(content.parse::<Circuit>(), Ok(_)))
+    assert!(content.parse::<Circuit>().is_ok())
+}
+
+#[test_resources("tests/.nts/nets.nts")]
+fn build_from_transformed_instructions(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+    let prefix = |name: &mut String| name.insert_str(0, "top.");
+    let mut instructions = parse_instructions(&content).unwrap();
+
+    for instruction in instructions.iter_mut() {
+        match instruction {
+            Instruction::AddComponent { name, .. } => prefix(name),
+            Instruction::LinkComponents { left, right } => {
+                for endpoint in [left, right] {
+                    if let Endpoint::Pin(name, _) = endpoint {
+                        prefix(name);
+                    }
+                }
+            }
+            Instruction::DeclareNet { pins, .. } => pins.iter_mut().for_each(|(name, _)| prefix(name)),
+        }
+    }
+    let mut circuit = CircuitBuilder::default().add_instructions(&instructions).unwrap().build().unwrap();
+
+    circuit.set_value("top.a", "1").unwrap();
+    circuit.set_value("top.b", "1").unwrap();
+    circuit.simulate();
+    assert_eq!(circuit.get_output("top.carry").unwrap(), "1");
+    assert_eq!(circuit.netlist().net("carry_out").unwrap()[0], PinRef::new("top.and", 3));
 }
 
 #[test_resources("tests/.nts/error/chipset_name_exists*.nts")]
@@ -30,6 +64,77 @@ fn chipset_syntax_error(resource: &str) {
     ))
 }
 
+#[test]
+fn chipset_parameters() {
+    let base_path = std::env::temp_dir();
+    std::fs::write(base_path.join("nts_parser_rom.bin"), [0x2A, 0x07]).unwrap();
+    let content = "
+        .chipsets:
+        2716 rom file=nts_parser_rom.bin
+        lfsr random width=16 taps=0xB400
+        timer555 timer mode=monostable width=3
+    ";
+    let circuit = Circuit::parse_relative_to(content, &base_path).unwrap();
+
+    assert_eq!(circuit.read_memory("rom", 1).unwrap(), Some(0x07));
+    assert_eq!(circuit.netlist().component("random").unwrap().info.as_ref().unwrap().nb_pins, 17);
+    assert!(matches!(
+        "\n.chipsets:\ntimer555 timer mode=astable width=3\n".parse::<Circuit>(),
+        Err(ParseCircuitError::Build { line: 3, kind: BuildErrorKind::ComponentCreation { .. } })
+    ));
+    std::fs::remove_file(base_path.join("nts_parser_rom.bin")).unwrap();
+}
+
+#[test_resources("tests/.nts/error/chipset_parameter_unknown.nts")]
+fn chipset_parameter_unknown(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+
+    assert!(matches!(
+        content.parse::<Circuit>(),
+        Err(ParseCircuitError::Build {
+            line: 2,
+            kind: BuildErrorKind::ComponentCreation { name: _, error: CreateError::UnknownParameter { key: _ } }
+        })
+    ))
+}
+
+#[test_resources("tests/.nts/error/chipset_parameter_file.nts")]
+fn chipset_parameter_file(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+    let base_path = Path::new(resource).parent().unwrap();
+
+    match Circuit::parse_relative_to(&content, base_path) {
+        Err(ParseCircuitError::Build {
+            line: 3,
+            kind: BuildErrorKind::ComponentCreation { name, error: CreateError::File { path, .. } },
+        }) => {
+            assert_eq!(name, "rom");
+            assert_eq!(path, base_path.join("missing.bin"));
+        }
+        _ => panic!("missing ROM image not reported"),
+    }
+}
+
+#[test_resources("tests/.nts/error/chipset_input_value_invalid*.nts")]
+fn chipset_input_value_invalid(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+
+    assert!(matches!(
+        content.parse::<Circuit>(),
+        Err(ParseCircuitError::Syntax { line: 2, kind: SyntaxErrorKind::InvalidInputValue { value: _ } })
+    ))
+}
+
+#[test_resources("tests/.nts/error/chipset_not_an_input.nts")]
+fn chipset_not_an_input(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+
+    assert!(matches!(
+        content.parse::<Circuit>(),
+        Err(ParseCircuitError::Build { line: 3, kind: BuildErrorKind::NotAnInput { name: _ } })
+    ))
+}
+
 #[test_resources("tests/.nts/error/chipset_type_unknown*.nts")]
 fn chipset_type_unknown(resource: &str) {
     let content = read_to_string(resource).unwrap();
@@ -40,6 +145,48 @@ fn chipset_type_unknown(resource: &str) {
     ))
 }
 
+#[test_resources("tests/.nts/input_sequence.nts")]
+fn input_values_sequence(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+    let mut circuit: Circuit = content.parse().unwrap();
+
+    assert_eq!(circuit.get_input("a").unwrap(), "U");
+    for (a, b, out) in [("0", "0", "0"), ("0", "1", "0"), ("1", "0", "0"), ("1", "1", "1")] {
+        circuit.simulate();
+
+        assert_eq!(circuit.get_input("a").unwrap(), a);
+        assert_eq!(circuit.get_input("b").unwrap(), b);
+        assert_eq!(circuit.get_output("out").unwrap(), out);
+    }
+
+    // Sequences are over: the last values are kept until changed by hand.
+    circuit.simulate();
+    assert_eq!(circuit.get_output("out").unwrap(), "1");
+    circuit.set_value("b", "0").unwrap();
+    circuit.simulate();
+    assert_eq!(circuit.get_output("out").unwrap(), "0");
+}
+
+#[test_resources("tests/.nts/input_sequence.nts")]
+#[test_resources("tests/.nts/true.nts")]
+fn embedded_checks(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+    let mut circuit: Circuit = content.parse().unwrap();
+
+    assert!(!circuit.embedded_checks().is_empty());
+    assert_eq!(circuit.run_embedded_checks(), vec![]);
+}
+
+#[test_resources("tests/.nts/error/check_syntax_error.nts")]
+fn check_syntax_error(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+
+    assert!(matches!(
+        content.parse::<Circuit>(),
+        Err(ParseCircuitError::Syntax { line: 2, kind: SyntaxErrorKind::InvalidCheckFormat })
+    ))
+}
+
 #[test_resources("tests/.nts/error/empty.nts")]
 #[test_resources("tests/.nts/error/no_instructions.nts")]
 fn empty(resource: &str) {
@@ -117,3 +264,39 @@ fn redeclaration(resource: &str) {
         Err(ParseCircuitError::Syntax { line: _, kind: SyntaxErrorKind::DeclarationDuplicate { declaration: _ } })
     ))
 }
+
+#[test_resources("tests/.nts/nets.nts")]
+fn nets_link_labelled_pins(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+    let mut circuit: Circuit = content.parse().unwrap();
+
+    circuit.set_value("a", "1").unwrap();
+    circuit.set_value("b", "1").unwrap();
+    circuit.simulate();
+
+    assert_eq!(circuit.get_output("sum").unwrap(), "0");
+    assert_eq!(circuit.get_output("carry").unwrap(), "1");
+    assert_eq!(circuit.get_net("carry_out"), Some(Tristate::State(true)));
+    assert_eq!(circuit.netlist().net_labels(), vec!["a_bus", "b_bus", "carry_out"]);
+    assert_eq!(circuit.netlist().net("carry_out").unwrap(), &[PinRef::new("and", 3), PinRef::new("carry", 1)]);
+}
+
+#[test_resources("tests/.nts/error/nets_syntax_error*.nts")]
+fn nets_syntax_error(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+
+    assert!(matches!(
+        content.parse::<Circuit>(),
+        Err(ParseCircuitError::Syntax { line: 6, kind: SyntaxErrorKind::InvalidNetFormat })
+    ))
+}
+
+#[test_resources("tests/.nts/error/nets_label_duplicate.nts")]
+fn nets_label_duplicate(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+
+    assert!(matches!(
+        content.parse::<Circuit>(),
+        Err(ParseCircuitError::Syntax { line: 7, kind: SyntaxErrorKind::NetLabelDuplicate { label: _ } })
+    ))
+}
@@ -0,0 +1,31 @@
+use std::fs::read_to_string;
+use test_generator::test_resources;
+
+use nanotekspice::components::{gates::one_input::GateNOT, registry::ComponentRegistry};
+use nanotekspice::{BuildErrorKind, Circuit, ParseCircuitError};
+
+#[test_resources("tests/.nts/custom_inverter.nts")]
+fn unregistered_type_is_unknown(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+
+    assert!(matches!(
+        content.parse::<Circuit>(),
+        Err(ParseCircuitError::Build { line: _, kind: BuildErrorKind::ComponentTypeUnknown { value: _ } })
+    ))
+}
+
+#[test_resources("tests/.nts/custom_inverter.nts")]
+fn registered_type_is_used_by_the_parser(resource: &str) {
+    let content = read_to_string(resource).unwrap();
+    let mut registry = ComponentRegistry::default();
+    registry.register("inverter", || Box::new(GateNOT::new()));
+
+    let mut circuit = Circuit::parse_with_factory(&content, registry).unwrap();
+
+    for (state, expected) in [("0", "1"), ("1", "0"), ("U", "U")] {
+        circuit.set_value("in", state).unwrap();
+        circuit.simulate();
+
+        assert_eq!(circuit.get_output("out").unwrap(), expected);
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "derive")]
+
+use std::{cell::Cell, collections::HashMap, rc::Rc};
+
+use nanotekspice::components::{
+    single_pin::input_component::InputComponent, tristate::Tristate, Component, Input, PinNumber, Tick,
+};
+use nanotekspice::pin::PinContainer;
+
+#[derive(nanotekspice::Component)]
+#[component(pins = 3, inputs(1, 2), outputs(3), simulate = update)]
+struct CustomAnd {
+    pins: PinContainer,
+}
+
+impl CustomAnd {
+    fn new() -> Self {
+        Self { pins: Self::build_pin_container() }
+    }
+
+    fn update(&self, outputs: &HashMap<PinNumber, &Cell<Tristate>>) {
+        let left = self.pins.compute_input(1).unwrap();
+        let right = self.pins.compute_input(2).unwrap();
+
+        outputs.get(&3).unwrap().set(left & right);
+    }
+}
+
+#[test]
+fn derived_component_computes_its_outputs() {
+    let left: Rc<InputComponent> = Rc::new(InputComponent::new());
+    let right: Rc<InputComponent> = Rc::new(InputComponent::new());
+    let gate: Rc<CustomAnd> = Rc::new(CustomAnd::new());
+
+    let left_dyn: Rc<dyn Component> = left.clone();
+    let right_dyn: Rc<dyn Component> = right.clone();
+    gate.set_link(1, Rc::downgrade(&left_dyn), 1).unwrap();
+    gate.set_link(2, Rc::downgrade(&right_dyn), 1).unwrap();
+
+    for (tick, (l, r, expected)) in [("0", "0", "0"), ("1", "0", "0"), ("0", "1", "0"), ("1", "1", "1")].into_iter().enumerate() {
+        left.set_state_for_next_tick(l.parse().unwrap());
+        right.set_state_for_next_tick(r.parse().unwrap());
+        gate.simulate(Tick::new(tick as u64 + 1));
+
+        assert_eq!(gate.compute(3).unwrap().to_string(), expected);
+    }
+}
+
+#[test]
+fn derived_component_rejects_unknown_pins() {
+    let gate = CustomAnd::new();
+
+    assert!(gate.compute(4).is_err());
+}
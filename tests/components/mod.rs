@@ -1,3 +1,4 @@
 mod clock_component;
 mod const_component;
+mod derive_component;
 mod input_output_components;